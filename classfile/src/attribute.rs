@@ -3,14 +3,20 @@ use alloc::{collections::BTreeMap, string::String, sync::Arc, vec::Vec};
 use nom::{
     bytes::complete::take,
     combinator::{flat_map, map, map_res},
+    error::ErrorKind,
     multi::length_count,
-    number::complete::{be_u16, be_u32},
+    number::complete::{be_u16, be_u32, u8},
     sequence::tuple,
-    IResult,
+    Parser,
 };
-use nom_derive::{NomBE, Parse};
 
-use crate::{constant_pool::ConstantPoolItem, opcode::Opcode, ValueConstant};
+use crate::{
+    constant_pool::{ConstantPoolItem, MethodHandleConstant},
+    error::{ClassFileErrorKind, ClassFileParseError, ParseResult},
+    interface::parse_interface,
+    opcode::Opcode,
+    ValueConstant,
+};
 
 pub struct CodeAttributeExceptionTable {
     pub start_pc: u16,
@@ -20,22 +26,21 @@ pub struct CodeAttributeExceptionTable {
 }
 
 impl CodeAttributeExceptionTable {
-    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> IResult<&'a [u8], Self> {
-        map(tuple((be_u16, be_u16, be_u16, be_u16)), |(start_pc, end_pc, handler_pc, catch_type)| {
-            let catch_type = if catch_type != 0 {
-                let index = constant_pool.get(&catch_type).unwrap().class_name_index();
-                Some(constant_pool.get(&index).unwrap().utf8())
-            } else {
-                None
-            };
-
-            Self {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
+        map(
+            tuple((
+                be_u16,
+                be_u16,
+                be_u16,
+                map_res(be_u16, |x| optional(x, |x| ConstantPoolItem::class_name(constant_pool, x))),
+            )),
+            |(start_pc, end_pc, handler_pc, catch_type)| Self {
                 start_pc,
                 end_pc,
                 handler_pc,
                 catch_type,
-            }
-        })(data)
+            },
+        )(data)
     }
 }
 
@@ -48,12 +53,12 @@ pub struct AttributeInfoCode {
 }
 
 impl AttributeInfoCode {
-    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> IResult<&'a [u8], Self> {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
         map(
             tuple((
                 be_u16,
                 be_u16,
-                map(flat_map(be_u32, take), |x: &[u8]| Self::parse_code(x, constant_pool)),
+                flat_map(be_u32, take).and_then(|x| Self::parse_code(x, constant_pool)),
                 length_count(be_u16, |x| CodeAttributeExceptionTable::parse(x, constant_pool)),
                 length_count(be_u16, |x| AttributeInfo::parse(x, constant_pool)),
             )),
@@ -67,31 +72,33 @@ impl AttributeInfoCode {
         )(data)
     }
 
-    fn parse_code(code: &[u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> BTreeMap<u32, Opcode> {
+    fn parse_code<'a>(code: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, BTreeMap<u32, Opcode>> {
         let mut result = BTreeMap::new();
 
         let mut data = code;
-        loop {
+        while !data.is_empty() {
             let offset = unsafe { data.as_ptr().offset_from(code.as_ptr()) } as usize;
-            if let Ok((remaining, opcode)) = Opcode::parse(data, offset, constant_pool) {
-                result.insert(offset as _, opcode);
+            let (remaining, opcode) = Opcode::parse(data, offset, constant_pool)?;
+            result.insert(offset as _, opcode);
 
-                data = remaining;
-            } else {
-                break;
-            }
+            data = remaining;
         }
 
-        result
+        Ok((data, result))
     }
 }
 
-#[derive(NomBE)]
 pub struct AttributeInfoLineNumberTableEntry {
     pub start_pc: u16,
     pub line_number: u16,
 }
 
+impl AttributeInfoLineNumberTableEntry {
+    pub fn parse(data: &[u8]) -> ParseResult<'_, Self> {
+        map(tuple((be_u16, be_u16)), |(start_pc, line_number)| Self { start_pc, line_number })(data)
+    }
+}
+
 pub struct LocalVariableTableEntry {
     pub start_pc: u16,
     pub length: u16,
@@ -101,13 +108,13 @@ pub struct LocalVariableTableEntry {
 }
 
 impl LocalVariableTableEntry {
-    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> IResult<&'a [u8], Self> {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
         map(
             tuple((
                 be_u16,
                 be_u16,
-                map(be_u16, |x| constant_pool.get(&x).unwrap().utf8()),
-                map(be_u16, |x| constant_pool.get(&x).unwrap().utf8()),
+                map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)),
+                map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)),
                 be_u16,
             )),
             |(start_pc, length, name, descriptor, index)| Self {
@@ -121,60 +128,254 @@ impl LocalVariableTableEntry {
     }
 }
 
+pub struct InnerClassEntry {
+    pub inner_class: Arc<String>,
+    pub outer_class: Option<Arc<String>>,
+    pub inner_name: Option<Arc<String>>, // None for anonymous classes
+    pub access_flags: u16,               // as declared in source, including PRIVATE, PROTECTED and STATIC
+}
+
+impl InnerClassEntry {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
+        map(
+            tuple((
+                map_res(be_u16, |x| ConstantPoolItem::class_name(constant_pool, x)),
+                map_res(be_u16, |x| optional(x, |x| ConstantPoolItem::class_name(constant_pool, x))),
+                map_res(be_u16, |x| optional(x, |x| ConstantPoolItem::utf8(constant_pool, x))),
+                be_u16,
+            )),
+            |(inner_class, outer_class, inner_name, access_flags)| Self {
+                inner_class,
+                outer_class,
+                inner_name,
+                access_flags,
+            },
+        )(data)
+    }
+}
+
+pub struct EnclosingMethod {
+    pub class: Arc<String>,
+    pub method: Option<(Arc<String>, Arc<String>)>, // name and descriptor, None if class is not enclosed by a method
+}
+
+impl EnclosingMethod {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
+        map(
+            tuple((
+                map_res(be_u16, |x| ConstantPoolItem::class_name(constant_pool, x)),
+                map_res(be_u16, |x| optional(x, |x| ConstantPoolItem::name_and_type(constant_pool, x))),
+            )),
+            |(class, method)| Self { class, method },
+        )(data)
+    }
+}
+
+pub struct Annotation {
+    pub type_descriptor: Arc<String>,
+    pub elements: Vec<(Arc<String>, ElementValue)>,
+}
+
+impl Annotation {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
+        map(
+            tuple((
+                map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)),
+                length_count(
+                    be_u16,
+                    tuple((map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)), |x| {
+                        ElementValue::parse(x, constant_pool)
+                    })),
+                ),
+            )),
+            |(type_descriptor, elements)| Self { type_descriptor, elements },
+        )(data)
+    }
+}
+
+pub enum ElementValue {
+    // tag is one of `BCDFIJSZs`, boolean, byte, char and short values are stored as integer
+    Constant(u8, ValueConstant),
+    Enum { type_descriptor: Arc<String>, name: Arc<String> },
+    Class(Arc<String>), // return descriptor, e.g. `Ljava/lang/Object;` or `V`
+    Annotation(Annotation),
+    Array(Vec<ElementValue>),
+}
+
+impl ElementValue {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
+        let (remaining, tag) = u8(data)?;
+
+        match tag {
+            b'B' | b'C' | b'D' | b'F' | b'I' | b'J' | b'S' | b'Z' | b's' => map_res(be_u16, |x| {
+                ValueConstant::from_constant_pool(constant_pool, x).map(|x| Self::Constant(tag, x))
+            })(remaining),
+            b'e' => map(
+                tuple((
+                    map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)),
+                    map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)),
+                )),
+                |(type_descriptor, name)| Self::Enum { type_descriptor, name },
+            )(remaining),
+            b'c' => map(map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)), Self::Class)(remaining),
+            b'@' => map(|x| Annotation::parse(x, constant_pool), Self::Annotation)(remaining),
+            b'[' => map(length_count(be_u16, |x| Self::parse(x, constant_pool)), Self::Array)(remaining),
+            _ => ClassFileParseError::failure(data, ClassFileErrorKind::Malformed(ErrorKind::Switch)),
+        }
+    }
+}
+
+pub struct BootstrapMethod {
+    pub method: MethodHandleConstant,
+    pub arguments: Vec<ValueConstant>,
+}
+
+impl BootstrapMethod {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
+        map(
+            tuple((
+                map_res(be_u16, |x| MethodHandleConstant::from_constant_pool(constant_pool, x)),
+                length_count(be_u16, map_res(be_u16, |x| ValueConstant::from_constant_pool(constant_pool, x))),
+            )),
+            |(method, arguments)| Self { method, arguments },
+        )(data)
+    }
+}
+
+pub struct MethodParameter {
+    pub name: Option<Arc<String>>,
+    pub access_flags: u16, // FINAL, SYNTHETIC or MANDATED(0x8000)
+}
+
+impl MethodParameter {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
+        map(
+            tuple((map_res(be_u16, |x| optional(x, |x| ConstantPoolItem::utf8(constant_pool, x))), be_u16)),
+            |(name, access_flags)| Self { name, access_flags },
+        )(data)
+    }
+}
+
+// constant pool index 0 means absent value
+fn optional<T, F>(index: u16, f: F) -> Result<Option<T>, ClassFileErrorKind>
+where
+    F: FnOnce(u16) -> Result<T, ClassFileErrorKind>,
+{
+    if index != 0 {
+        f(index).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 pub enum AttributeInfo {
     ConstantValue(ValueConstant),
     Code(AttributeInfoCode),
     StackMap(Vec<u8>),      // TODO Older variant of StackMapTable
     StackMapTable(Vec<u8>), // TODO
-    Exceptions(Vec<u8>),    // TODO
-    InnerClasses(Vec<u8>),  // TODO
-    Synthetic(Vec<u8>),     // TODO
+    Exceptions(Vec<Arc<String>>),
+    InnerClasses(Vec<InnerClassEntry>),
+    EnclosingMethod(EnclosingMethod),
+    Synthetic,
+    Deprecated,
+    Signature(Arc<String>),
     SourceFile(Arc<String>),
-    SourceDebugExtension,
+    SourceDebugExtension(String),
     LineNumberTable(Vec<AttributeInfoLineNumberTableEntry>),
     LocalVariableTable(Vec<LocalVariableTableEntry>),
-    MethodParameters(Vec<u8>), // TODO
-    NestMembers(Vec<u8>),      // TODO
-    NestHost(Vec<u8>),         // TODO
+    LocalVariableTypeTable(Vec<LocalVariableTableEntry>), // descriptor is generic signature
+    RuntimeVisibleAnnotations(Vec<Annotation>),
+    RuntimeInvisibleAnnotations(Vec<Annotation>),
+    RuntimeVisibleParameterAnnotations(Vec<Vec<Annotation>>),
+    RuntimeInvisibleParameterAnnotations(Vec<Vec<Annotation>>),
+    AnnotationDefault(ElementValue),
+    BootstrapMethods(Vec<BootstrapMethod>),
+    MethodParameters(Vec<MethodParameter>),
+    NestMembers(Vec<Arc<String>>),
+    NestHost(Arc<String>),
+    // attributes we don't parse, e.g. Module, Record or type annotations, and malformed debug or invisible attributes.
+    // jvm is required to ignore unknown attributes
+    Unknown { name: Arc<String>, data: Vec<u8> },
 }
 
 impl AttributeInfo {
-    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> IResult<&'a [u8], Self> {
-        map_res(
-            tuple((map(be_u16, |x| constant_pool.get(&x).unwrap().utf8()), flat_map(be_u32, take))),
-            |(name, info): (_, &[u8])| {
-                Ok::<_, nom::Err<_>>(match name.as_str() {
-                    "ConstantValue" => AttributeInfo::ConstantValue(Self::parse_constant_value(info, constant_pool)?.1),
-                    "Code" => AttributeInfo::Code(AttributeInfoCode::parse(info, constant_pool)?.1),
-                    "LineNumberTable" => AttributeInfo::LineNumberTable(length_count(be_u16, AttributeInfoLineNumberTableEntry::parse)(info)?.1),
-                    "SourceFile" => AttributeInfo::SourceFile(Self::parse_source_file(info, constant_pool)?.1),
-                    "LocalVariableTable" => AttributeInfo::LocalVariableTable(Self::parse_local_variable_table(info, constant_pool)?.1),
-                    "StackMap" => AttributeInfo::StackMap(info.to_vec()),
-                    "StackMapTable" => AttributeInfo::StackMapTable(info.to_vec()),
-                    "Exceptions" => AttributeInfo::Exceptions(info.to_vec()),
-                    "InnerClasses" => AttributeInfo::InnerClasses(info.to_vec()),
-                    "Synthetic" => AttributeInfo::Synthetic(info.to_vec()),
-                    "MethodParameters" => AttributeInfo::MethodParameters(info.to_vec()),
-                    "NestMembers" => AttributeInfo::NestMembers(info.to_vec()),
-                    "NestHost" => AttributeInfo::NestHost(info.to_vec()),
-                    _ => return Err(nom::Err::Error(nom::error_position!(info, nom::error::ErrorKind::Switch))),
-                })
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
+        let (remaining, (name, info)) = tuple((map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)), flat_map(be_u32, take)))(data)?;
+
+        let attribute = match name.as_str() {
+            "ConstantValue" => AttributeInfo::ConstantValue(Self::parse_constant_value(info, constant_pool)?.1),
+            "Code" => AttributeInfo::Code(AttributeInfoCode::parse(info, constant_pool)?.1),
+            "LineNumberTable" => AttributeInfo::LineNumberTable(length_count(be_u16, AttributeInfoLineNumberTableEntry::parse)(info)?.1),
+            "SourceFile" => AttributeInfo::SourceFile(Self::parse_source_file(info, constant_pool)?.1),
+            "LocalVariableTable" => AttributeInfo::LocalVariableTable(Self::parse_local_variable_table(info, constant_pool)?.1),
+            "SourceDebugExtension" => match String::from_utf8(info.to_vec()) {
+                Ok(x) => AttributeInfo::SourceDebugExtension(x),
+                Err(_) => AttributeInfo::Unknown { name, data: info.to_vec() },
             },
-        )(data)
+            "LocalVariableTypeTable" => Self::parse_or_unknown(name, info, AttributeInfo::LocalVariableTypeTable, |x| {
+                Self::parse_local_variable_table(x, constant_pool)
+            }),
+            "StackMap" => AttributeInfo::StackMap(info.to_vec()),
+            "StackMapTable" => AttributeInfo::StackMapTable(info.to_vec()),
+            "Exceptions" => AttributeInfo::Exceptions(length_count(be_u16, |x| parse_interface(x, constant_pool))(info)?.1),
+            "InnerClasses" => AttributeInfo::InnerClasses(length_count(be_u16, |x| InnerClassEntry::parse(x, constant_pool))(info)?.1),
+            "EnclosingMethod" => AttributeInfo::EnclosingMethod(EnclosingMethod::parse(info, constant_pool)?.1),
+            "Synthetic" => AttributeInfo::Synthetic,
+            "Deprecated" => AttributeInfo::Deprecated,
+            "Signature" => AttributeInfo::Signature(map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x))(info)?.1),
+            "RuntimeVisibleAnnotations" => AttributeInfo::RuntimeVisibleAnnotations(Self::parse_annotations(info, constant_pool)?.1),
+            "RuntimeInvisibleAnnotations" => Self::parse_or_unknown(name, info, AttributeInfo::RuntimeInvisibleAnnotations, |x| {
+                Self::parse_annotations(x, constant_pool)
+            }),
+            "RuntimeVisibleParameterAnnotations" => {
+                AttributeInfo::RuntimeVisibleParameterAnnotations(Self::parse_parameter_annotations(info, constant_pool)?.1)
+            }
+            "RuntimeInvisibleParameterAnnotations" => Self::parse_or_unknown(name, info, AttributeInfo::RuntimeInvisibleParameterAnnotations, |x| {
+                Self::parse_parameter_annotations(x, constant_pool)
+            }),
+            "AnnotationDefault" => AttributeInfo::AnnotationDefault(ElementValue::parse(info, constant_pool)?.1),
+            "BootstrapMethods" => AttributeInfo::BootstrapMethods(length_count(be_u16, |x| BootstrapMethod::parse(x, constant_pool))(info)?.1),
+            "MethodParameters" => AttributeInfo::MethodParameters(length_count(u8, |x| MethodParameter::parse(x, constant_pool))(info)?.1),
+            "NestMembers" => AttributeInfo::NestMembers(length_count(be_u16, |x| parse_interface(x, constant_pool))(info)?.1),
+            "NestHost" => AttributeInfo::NestHost(parse_interface(info, constant_pool)?.1),
+            _ => AttributeInfo::Unknown { name, data: info.to_vec() },
+        };
+
+        Ok((remaining, attribute))
+    }
+
+    // attributes only used by tools, which are kept unparsed if malformed as jvm doesn't check them
+    fn parse_or_unknown<'a, T, F, P>(name: Arc<String>, data: &'a [u8], f: F, parser: P) -> Self
+    where
+        F: FnOnce(T) -> Self,
+        P: FnOnce(&'a [u8]) -> ParseResult<'a, T>,
+    {
+        match parser(data) {
+            Ok(([], x)) => f(x),
+            _ => AttributeInfo::Unknown { name, data: data.to_vec() },
+        }
     }
 
-    fn parse_source_file<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> IResult<&'a [u8], Arc<String>> {
-        map(be_u16, |x| constant_pool.get(&x).unwrap().utf8())(data)
+    fn parse_source_file<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Arc<String>> {
+        map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x))(data)
     }
 
-    fn parse_constant_value<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> IResult<&'a [u8], ValueConstant> {
-        map(be_u16, |x| ValueConstant::from_constant_pool(constant_pool, x as _))(data)
+    fn parse_constant_value<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, ValueConstant> {
+        map_res(be_u16, |x| ValueConstant::from_constant_pool(constant_pool, x))(data)
     }
 
     fn parse_local_variable_table<'a>(
         data: &'a [u8],
         constant_pool: &BTreeMap<u16, ConstantPoolItem>,
-    ) -> IResult<&'a [u8], Vec<LocalVariableTableEntry>> {
+    ) -> ParseResult<'a, Vec<LocalVariableTableEntry>> {
         length_count(be_u16, |x| LocalVariableTableEntry::parse(x, constant_pool))(data)
     }
+
+    fn parse_annotations<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Vec<Annotation>> {
+        length_count(be_u16, |x| Annotation::parse(x, constant_pool))(data)
+    }
+
+    fn parse_parameter_annotations<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Vec<Vec<Annotation>>> {
+        length_count(u8, |x| Self::parse_annotations(x, constant_pool))(data)
+    }
 }
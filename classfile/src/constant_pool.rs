@@ -2,14 +2,17 @@ use alloc::{collections::BTreeMap, string::String, sync::Arc};
 
 use nom::{
     bytes::complete::take,
-    combinator::{flat_map, map},
+    combinator::{flat_map, map_res},
+    error::{ErrorKind, ParseError},
     number::complete::{be_u16, u8},
     IResult,
 };
 use nom_derive::{NomBE, Parse};
 
+use crate::error::{ClassFileErrorKind, ClassFileParseError, ParseResult};
+
 fn parse_utf8(data: &[u8]) -> IResult<&[u8], Arc<String>> {
-    map(flat_map(be_u16, take), |x: &[u8]| Arc::new(String::from_utf8(x.to_vec()).unwrap()))(data)
+    map_res(flat_map(be_u16, take), |x: &[u8]| String::from_utf8(x.to_vec()).map(Arc::new))(data)
 }
 
 #[derive(NomBE, Debug)]
@@ -37,16 +40,34 @@ pub enum ConstantPoolItem {
     InstanceMethodref { class_index: u16, name_and_type_index: u16 },
     #[nom(Selector = "12")]
     NameAndType { name_index: u16, descriptor_index: u16 },
+    #[nom(Selector = "15")]
+    MethodHandle { reference_kind: u8, reference_index: u16 },
+    #[nom(Selector = "16")]
+    MethodType { descriptor_index: u16 },
+    #[nom(Selector = "17")]
+    Dynamic {
+        bootstrap_method_attr_index: u16,
+        name_and_type_index: u16,
+    },
+    #[nom(Selector = "18")]
+    InvokeDynamic {
+        bootstrap_method_attr_index: u16,
+        name_and_type_index: u16,
+    },
+    #[nom(Selector = "19")]
+    Module { name_index: u16 },
+    #[nom(Selector = "20")]
+    Package { name_index: u16 },
 }
 
 impl ConstantPoolItem {
-    pub fn parse_all(data: &[u8]) -> IResult<&[u8], BTreeMap<u16, Self>> {
+    pub fn parse_all(data: &[u8]) -> ParseResult<'_, BTreeMap<u16, Self>> {
         let (remaining, count) = be_u16(data)?;
 
         let mut data = remaining;
         let mut result = BTreeMap::new();
         let mut i = 1;
-        loop {
+        while i < count {
             let (remaining, item) = Self::parse_with_tag(data)?;
             let is_double_entry = match &item {
                 Self::Long(_) | Self::Double(_) => {
@@ -62,46 +83,57 @@ impl ConstantPoolItem {
             if is_double_entry {
                 i += 1;
             }
-
-            if i >= count {
-                break;
-            }
         }
 
         Ok((data, result))
     }
 
-    pub fn parse_with_tag(data: &[u8]) -> IResult<&[u8], Self> {
-        flat_map(u8, |x| move |i| Self::parse(i, x))(data)
+    pub fn parse_with_tag(data: &[u8]) -> ParseResult<'_, Self> {
+        let (remaining, tag) = u8(data)?;
+
+        Self::parse(remaining, tag).map_err(|x| {
+            x.map(|e: nom::error::Error<&[u8]>| match e.code {
+                ErrorKind::Switch => ClassFileParseError::new(data, ClassFileErrorKind::UnknownConstantPoolTag(tag)),
+                ErrorKind::MapRes => ClassFileParseError::new(e.input, ClassFileErrorKind::InvalidUtf8),
+                _ => ClassFileParseError::from_error_kind(e.input, e.code),
+            })
+        })
     }
 
-    pub fn utf8(&self) -> Arc<String> {
-        if let ConstantPoolItem::Utf8(x) = self {
-            x.clone()
+    pub fn utf8(constant_pool: &BTreeMap<u16, Self>, index: u16) -> Result<Arc<String>, ClassFileErrorKind> {
+        if let ConstantPoolItem::Utf8(x) = Self::get(constant_pool, index)? {
+            Ok(x.clone())
         } else {
-            panic!("Invalid constant pool item");
+            Err(ClassFileErrorKind::UnexpectedConstantPoolItem { index, expected: "Utf8" })
         }
     }
 
-    pub fn class_name_index(&self) -> u16 {
-        if let ConstantPoolItem::Class { name_index } = self {
-            *name_index
+    pub fn class_name(constant_pool: &BTreeMap<u16, Self>, index: u16) -> Result<Arc<String>, ClassFileErrorKind> {
+        if let ConstantPoolItem::Class { name_index } = Self::get(constant_pool, index)? {
+            Self::utf8(constant_pool, *name_index)
         } else {
-            panic!("Invalid constant pool item");
+            Err(ClassFileErrorKind::UnexpectedConstantPoolItem { index, expected: "Class" })
         }
     }
 
-    pub fn name_and_type(&self) -> (u16, u16) {
+    pub fn name_and_type(constant_pool: &BTreeMap<u16, Self>, index: u16) -> Result<(Arc<String>, Arc<String>), ClassFileErrorKind> {
         if let ConstantPoolItem::NameAndType {
             name_index,
             descriptor_index,
-        } = self
+        } = Self::get(constant_pool, index)?
         {
-            (*name_index, *descriptor_index)
+            Ok((Self::utf8(constant_pool, *name_index)?, Self::utf8(constant_pool, *descriptor_index)?))
         } else {
-            panic!("Invalid constant pool item");
+            Err(ClassFileErrorKind::UnexpectedConstantPoolItem {
+                index,
+                expected: "NameAndType",
+            })
         }
     }
+
+    fn get(constant_pool: &BTreeMap<u16, Self>, index: u16) -> Result<&Self, ClassFileErrorKind> {
+        constant_pool.get(&index).ok_or(ClassFileErrorKind::InvalidConstantPoolIndex(index))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -114,36 +146,39 @@ pub enum ValueConstant {
     Class(Arc<String>),
     Method(ReferenceConstant),
     Field(ReferenceConstant),
+    MethodHandle(MethodHandleConstant),
+    MethodType(Arc<String>),
+    Dynamic(DynamicConstant),
 }
 
 impl ValueConstant {
-    pub fn from_constant_pool(constant_pool: &BTreeMap<u16, ConstantPoolItem>, index: u16) -> Self {
-        match &constant_pool.get(&index).unwrap() {
+    pub fn from_constant_pool(constant_pool: &BTreeMap<u16, ConstantPoolItem>, index: u16) -> Result<Self, ClassFileErrorKind> {
+        Ok(match ConstantPoolItem::get(constant_pool, index)? {
             ConstantPoolItem::Integer(x) => Self::Integer(*x),
             ConstantPoolItem::Float(x) => Self::Float(*x),
             ConstantPoolItem::Long(x) => Self::Long(*x),
             ConstantPoolItem::Double(x) => Self::Double(*x),
-            ConstantPoolItem::String { string_index } => Self::String(constant_pool.get(string_index).unwrap().utf8()),
-            ConstantPoolItem::Class { name_index } => Self::Class(constant_pool.get(name_index).unwrap().utf8()),
+            ConstantPoolItem::String { string_index } => Self::String(ConstantPoolItem::utf8(constant_pool, *string_index)?),
+            ConstantPoolItem::Class { name_index } => Self::Class(ConstantPoolItem::utf8(constant_pool, *name_index)?),
             ConstantPoolItem::Utf8(x) => Self::String(x.clone()),
             ConstantPoolItem::Methodref {
                 class_index,
                 name_and_type_index,
-            } => Self::Method(ReferenceConstant::from_reference_info(
-                constant_pool,
-                *class_index as _,
-                *name_and_type_index as _,
-            )),
+            } => Self::Method(ReferenceConstant::from_reference_info(constant_pool, *class_index, *name_and_type_index)?),
             ConstantPoolItem::Fieldref {
                 class_index,
                 name_and_type_index,
-            } => Self::Field(ReferenceConstant::from_reference_info(
-                constant_pool,
-                *class_index as _,
-                *name_and_type_index as _,
-            )),
-            _ => panic!("Invalid constant pool item {:?}", constant_pool.get(&index).unwrap()),
-        }
+            } => Self::Field(ReferenceConstant::from_reference_info(constant_pool, *class_index, *name_and_type_index)?),
+            ConstantPoolItem::MethodHandle { .. } => Self::MethodHandle(MethodHandleConstant::from_constant_pool(constant_pool, index)?),
+            ConstantPoolItem::MethodType { descriptor_index } => Self::MethodType(ConstantPoolItem::utf8(constant_pool, *descriptor_index)?),
+            ConstantPoolItem::Dynamic { .. } => Self::Dynamic(DynamicConstant::from_constant_pool(constant_pool, index)?),
+            _ => {
+                return Err(ClassFileErrorKind::UnexpectedConstantPoolItem {
+                    index,
+                    expected: "loadable constant",
+                })
+            }
+        })
     }
 
     pub fn as_class(&self) -> &str {
@@ -163,8 +198,8 @@ pub struct ReferenceConstant {
 }
 
 impl ReferenceConstant {
-    pub fn from_constant_pool(constant_pool: &BTreeMap<u16, ConstantPoolItem>, index: u16) -> Self {
-        match &constant_pool.get(&index).unwrap() {
+    pub fn from_constant_pool(constant_pool: &BTreeMap<u16, ConstantPoolItem>, index: u16) -> Result<Self, ClassFileErrorKind> {
+        match ConstantPoolItem::get(constant_pool, index)? {
             ConstantPoolItem::Fieldref {
                 class_index,
                 name_and_type_index,
@@ -177,22 +212,86 @@ impl ReferenceConstant {
                 class_index,
                 name_and_type_index,
             } => Self::from_reference_info(constant_pool, *class_index, *name_and_type_index),
-            _ => panic!("Invalid constant pool item {:?}", constant_pool.get(&index).unwrap()),
+            _ => Err(ClassFileErrorKind::UnexpectedConstantPoolItem {
+                index,
+                expected: "reference",
+            }),
         }
     }
 
-    pub fn from_reference_info(constant_pool: &BTreeMap<u16, ConstantPoolItem>, class_index: u16, name_and_type_index: u16) -> Self {
-        let class_name_index = constant_pool.get(&class_index).unwrap().class_name_index();
-        let class_name = constant_pool.get(&class_name_index).unwrap().utf8();
+    pub fn from_reference_info(
+        constant_pool: &BTreeMap<u16, ConstantPoolItem>,
+        class_index: u16,
+        name_and_type_index: u16,
+    ) -> Result<Self, ClassFileErrorKind> {
+        let class_name = ConstantPoolItem::class_name(constant_pool, class_index)?;
+        let (name, descriptor) = ConstantPoolItem::name_and_type(constant_pool, name_and_type_index)?;
 
-        let (name_index, descriptor_index) = constant_pool.get(&name_and_type_index).unwrap().name_and_type();
-        let name = constant_pool.get(&name_index).unwrap().utf8();
-        let descriptor = constant_pool.get(&descriptor_index).unwrap().utf8();
-
-        Self {
+        Ok(Self {
             class: class_name,
             name,
             descriptor,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MethodHandleConstant {
+    pub reference_kind: u8, // REF_getField(1) to REF_invokeInterface(9)
+    pub reference: ReferenceConstant,
+}
+
+impl MethodHandleConstant {
+    pub fn from_constant_pool(constant_pool: &BTreeMap<u16, ConstantPoolItem>, index: u16) -> Result<Self, ClassFileErrorKind> {
+        if let ConstantPoolItem::MethodHandle {
+            reference_kind,
+            reference_index,
+        } = ConstantPoolItem::get(constant_pool, index)?
+        {
+            Ok(Self {
+                reference_kind: *reference_kind,
+                reference: ReferenceConstant::from_constant_pool(constant_pool, *reference_index)?,
+            })
+        } else {
+            Err(ClassFileErrorKind::UnexpectedConstantPoolItem {
+                index,
+                expected: "MethodHandle",
+            })
+        }
+    }
+}
+
+// dynamically computed constant or call site, bootstrapped by method at `bootstrap_method_index` of BootstrapMethods attribute
+#[derive(Clone, Debug)]
+pub struct DynamicConstant {
+    pub bootstrap_method_index: u16,
+    pub name: Arc<String>,
+    pub descriptor: Arc<String>,
+}
+
+impl DynamicConstant {
+    pub fn from_constant_pool(constant_pool: &BTreeMap<u16, ConstantPoolItem>, index: u16) -> Result<Self, ClassFileErrorKind> {
+        match ConstantPoolItem::get(constant_pool, index)? {
+            ConstantPoolItem::Dynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            }
+            | ConstantPoolItem::InvokeDynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            } => {
+                let (name, descriptor) = ConstantPoolItem::name_and_type(constant_pool, *name_and_type_index)?;
+
+                Ok(Self {
+                    bootstrap_method_index: *bootstrap_method_attr_index,
+                    name,
+                    descriptor,
+                })
+            }
+            _ => Err(ClassFileErrorKind::UnexpectedConstantPoolItem {
+                index,
+                expected: "dynamic constant",
+            }),
         }
     }
 }
@@ -0,0 +1,102 @@
+use core::fmt::{self, Display, Formatter};
+
+use nom::{
+    error::{ErrorKind, FromExternalError, ParseError},
+    IResult,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClassFileErrorKind {
+    InvalidMagic(u32),
+    UnexpectedEnd,
+    TrailingData,
+    InvalidConstantPoolIndex(u16),
+    UnexpectedConstantPoolItem { index: u16, expected: &'static str },
+    UnknownConstantPoolTag(u8),
+    InvalidUtf8,
+    UnknownOpcode(u8),
+    Malformed(ErrorKind),
+}
+
+impl Display for ClassFileErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic(x) => write!(f, "Invalid magic 0x{:08x}", x),
+            Self::UnexpectedEnd => write!(f, "Unexpected end of data"),
+            Self::TrailingData => write!(f, "Trailing data after class"),
+            Self::InvalidConstantPoolIndex(x) => write!(f, "Invalid constant pool index #{}", x),
+            Self::UnexpectedConstantPoolItem { index, expected } => write!(f, "Expected {} at constant pool index #{}", expected, index),
+            Self::UnknownConstantPoolTag(x) => write!(f, "Unknown constant pool tag {}", x),
+            Self::InvalidUtf8 => write!(f, "Invalid utf8 string"),
+            Self::UnknownOpcode(x) => write!(f, "Unknown opcode 0x{:02x}", x),
+            Self::Malformed(x) => write!(f, "Malformed data ({:?})", x),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClassFileError {
+    pub offset: usize,
+    pub kind: ClassFileErrorKind,
+}
+
+impl ClassFileError {
+    pub(crate) fn from_nom(file: &[u8], err: nom::Err<ClassFileParseError<'_>>) -> Self {
+        match err {
+            nom::Err::Error(x) | nom::Err::Failure(x) => Self {
+                offset: x.input.as_ptr() as usize - file.as_ptr() as usize,
+                kind: x.kind,
+            },
+            nom::Err::Incomplete(_) => Self {
+                offset: file.len(),
+                kind: ClassFileErrorKind::UnexpectedEnd,
+            },
+        }
+    }
+}
+
+impl Display for ClassFileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.kind, self.offset)
+    }
+}
+
+// nom error type used by all parsers, input is kept to calculate offset from the start of the file
+#[derive(Debug)]
+pub struct ClassFileParseError<'a> {
+    pub input: &'a [u8],
+    pub kind: ClassFileErrorKind,
+}
+
+impl<'a> ClassFileParseError<'a> {
+    pub fn new(input: &'a [u8], kind: ClassFileErrorKind) -> Self {
+        Self { input, kind }
+    }
+
+    pub fn failure<T>(input: &'a [u8], kind: ClassFileErrorKind) -> ParseResult<'a, T> {
+        Err(nom::Err::Failure(Self::new(input, kind)))
+    }
+}
+
+impl<'a> ParseError<&'a [u8]> for ClassFileParseError<'a> {
+    fn from_error_kind(input: &'a [u8], kind: ErrorKind) -> Self {
+        let kind = match kind {
+            ErrorKind::Eof => ClassFileErrorKind::UnexpectedEnd,
+            x => ClassFileErrorKind::Malformed(x),
+        };
+
+        Self { input, kind }
+    }
+
+    fn append(_: &'a [u8], _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a> FromExternalError<&'a [u8], ClassFileErrorKind> for ClassFileParseError<'a> {
+    fn from_external_error(input: &'a [u8], _: ErrorKind, e: ClassFileErrorKind) -> Self {
+        Self { input, kind: e }
+    }
+}
+
+pub type ParseResult<'a, T> = IResult<&'a [u8], T, ClassFileParseError<'a>>;
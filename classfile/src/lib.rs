@@ -4,15 +4,17 @@ extern crate alloc;
 mod attribute;
 mod class;
 mod constant_pool;
+mod error;
 mod field;
 mod interface;
 mod method;
 mod opcode;
 
 pub use {
-    attribute::{AttributeInfo, AttributeInfoCode},
+    attribute::{Annotation, AttributeInfo, AttributeInfoCode, BootstrapMethod, ElementValue, EnclosingMethod, InnerClassEntry, MethodParameter},
     class::ClassInfo,
-    constant_pool::{ReferenceConstant, ValueConstant},
+    constant_pool::{DynamicConstant, MethodHandleConstant, ReferenceConstant, ValueConstant},
+    error::{ClassFileError, ClassFileErrorKind},
     field::FieldInfo,
     method::MethodInfo,
     opcode::Opcode,
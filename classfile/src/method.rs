@@ -1,10 +1,15 @@
 use alloc::{collections::BTreeMap, string::String, sync::Arc, vec::Vec};
 
-use nom::{combinator::map, multi::length_count, number::complete::be_u16, sequence::tuple, IResult};
+use nom::{
+    combinator::{map, map_res},
+    multi::length_count,
+    number::complete::be_u16,
+    sequence::tuple,
+};
 
 use java_constants::MethodAccessFlags;
 
-use crate::{attribute::AttributeInfo, constant_pool::ConstantPoolItem};
+use crate::{attribute::AttributeInfo, constant_pool::ConstantPoolItem, error::ParseResult};
 
 pub struct MethodInfo {
     pub access_flags: MethodAccessFlags,
@@ -14,16 +19,16 @@ pub struct MethodInfo {
 }
 
 impl MethodInfo {
-    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> IResult<&'a [u8], Self> {
+    pub fn parse<'a>(data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
         map(
             tuple((
                 be_u16,
-                map(be_u16, |x| constant_pool.get(&x).unwrap().utf8()),
-                map(be_u16, |x| constant_pool.get(&x).unwrap().utf8()),
+                map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)),
+                map_res(be_u16, |x| ConstantPoolItem::utf8(constant_pool, x)),
                 length_count(be_u16, |x| AttributeInfo::parse(x, constant_pool)),
             )),
             |(access_flags, name, descriptor, attributes)| Self {
-                access_flags: MethodAccessFlags::from_bits(access_flags).unwrap(),
+                access_flags: MethodAccessFlags::from_bits_truncate(access_flags),
                 name,
                 descriptor,
                 attributes,
@@ -2,14 +2,16 @@ use alloc::{collections::BTreeMap, vec::Vec};
 
 use nom::{
     bytes::complete::take,
-    combinator::{flat_map, map, success},
+    combinator::{flat_map, map, map_res, success},
     multi::count,
     number::complete::{be_i16, be_i32, be_u16, i8, u8},
     sequence::tuple,
-    IResult,
 };
 
-use crate::constant_pool::{ConstantPoolItem, ReferenceConstant, ValueConstant};
+use crate::{
+    constant_pool::{ConstantPoolItem, DynamicConstant, ReferenceConstant, ValueConstant},
+    error::{ClassFileErrorKind, ClassFileParseError, ParseResult},
+};
 
 #[derive(Clone, Debug)]
 pub enum Opcode {
@@ -105,7 +107,7 @@ pub enum Opcode {
     Imul,
     Ineg,
     Instanceof(ValueConstant),
-    Invokedynamic(ReferenceConstant),
+    Invokedynamic(DynamicConstant),
     Invokeinterface(ReferenceConstant, u8, u8),
     Invokespecial(ReferenceConstant),
     Invokestatic(ReferenceConstant),
@@ -168,11 +170,11 @@ pub enum Opcode {
 }
 
 impl Opcode {
-    pub fn parse<'a>(data: &'a [u8], offset: usize, constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> IResult<&'a [u8], Self> {
+    pub fn parse<'a>(data: &'a [u8], offset: usize, constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
         flat_map(u8, |x| move |i| Self::parse_opcode(x, offset, i, constant_pool))(data)
     }
 
-    fn parse_opcode<'a>(opcode: u8, offset: usize, data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> IResult<&'a [u8], Self> {
+    fn parse_opcode<'a>(opcode: u8, offset: usize, data: &'a [u8], constant_pool: &BTreeMap<u16, ConstantPoolItem>) -> ParseResult<'a, Self> {
         match opcode {
             0x32 => success(Opcode::Aaload)(data),
             0x53 => success(Opcode::Aastore)(data),
@@ -182,7 +184,9 @@ impl Opcode {
             0x2b => success(Opcode::Aload(1))(data),
             0x2c => success(Opcode::Aload(2))(data),
             0x2d => success(Opcode::Aload(3))(data),
-            0xbd => map(be_u16, |x| Opcode::Anewarray(ValueConstant::from_constant_pool(constant_pool, x as _)))(data),
+            0xbd => map_res(be_u16, |x| {
+                ConstantPoolItem::class_name(constant_pool, x).map(|x| Opcode::Anewarray(ValueConstant::Class(x)))
+            })(data),
             0xb0 => success(Opcode::Areturn)(data),
             0xbe => success(Opcode::Arraylength)(data),
             0x3a => map(u8, Opcode::Astore)(data),
@@ -256,9 +260,11 @@ impl Opcode {
             0x45 => success(Opcode::Fstore(2))(data),
             0x46 => success(Opcode::Fstore(3))(data),
             0x66 => success(Opcode::Fsub)(data),
-            0xb4 => map(be_u16, |x| Opcode::Getfield(ReferenceConstant::from_constant_pool(constant_pool, x as _)))(data),
-            0xb2 => map(be_u16, |x| {
-                Opcode::Getstatic(ReferenceConstant::from_constant_pool(constant_pool, x as _))
+            0xb4 => map_res(be_u16, |x| {
+                ReferenceConstant::from_constant_pool(constant_pool, x as _).map(Opcode::Getfield)
+            })(data),
+            0xb2 => map_res(be_u16, |x| {
+                ReferenceConstant::from_constant_pool(constant_pool, x as _).map(Opcode::Getstatic)
             })(data),
             0xa7 => map(be_i16, Opcode::Goto)(data),
             0xc8 => map(be_i32, Opcode::GotoW)(data),
@@ -304,21 +310,23 @@ impl Opcode {
             0x1d => success(Opcode::Iload(3))(data),
             0x68 => success(Opcode::Imul)(data),
             0x74 => success(Opcode::Ineg)(data),
-            0xc1 => map(be_u16, |x| Opcode::Instanceof(ValueConstant::from_constant_pool(constant_pool, x as _)))(data),
-            0xba => map(be_u16, |x| {
-                Opcode::Invokedynamic(ReferenceConstant::from_constant_pool(constant_pool, x as _))
+            0xc1 => map_res(be_u16, |x| {
+                ConstantPoolItem::class_name(constant_pool, x).map(|x| Opcode::Instanceof(ValueConstant::Class(x)))
             })(data),
-            0xb9 => map(be_u16, |x| {
-                Opcode::Invokeinterface(ReferenceConstant::from_constant_pool(constant_pool, x as _), 0, 0)
+            0xba => map_res(tuple((be_u16, be_u16)), |(x, _)| {
+                DynamicConstant::from_constant_pool(constant_pool, x).map(Opcode::Invokedynamic)
             })(data),
-            0xb7 => map(be_u16, |x| {
-                Opcode::Invokespecial(ReferenceConstant::from_constant_pool(constant_pool, x as _))
+            0xb9 => map_res(tuple((be_u16, u8, u8)), |(x, count, zero)| {
+                ReferenceConstant::from_constant_pool(constant_pool, x).map(|x| Opcode::Invokeinterface(x, count, zero))
             })(data),
-            0xb8 => map(be_u16, |x| {
-                Opcode::Invokestatic(ReferenceConstant::from_constant_pool(constant_pool, x as _))
+            0xb7 => map_res(be_u16, |x| {
+                ReferenceConstant::from_constant_pool(constant_pool, x as _).map(Opcode::Invokespecial)
             })(data),
-            0xb6 => map(be_u16, |x| {
-                Opcode::Invokevirtual(ReferenceConstant::from_constant_pool(constant_pool, x as _))
+            0xb8 => map_res(be_u16, |x| {
+                ReferenceConstant::from_constant_pool(constant_pool, x as _).map(Opcode::Invokestatic)
+            })(data),
+            0xb6 => map_res(be_u16, |x| {
+                ReferenceConstant::from_constant_pool(constant_pool, x as _).map(Opcode::Invokevirtual)
             })(data),
             0x80 => success(Opcode::Ior)(data),
             0x70 => success(Opcode::Irem)(data),
@@ -345,9 +353,9 @@ impl Opcode {
             0x94 => success(Opcode::Lcmp)(data),
             0x09 => success(Opcode::Lconst(0))(data),
             0x0a => success(Opcode::Lconst(1))(data),
-            0x12 => map(u8, |x| Opcode::Ldc(ValueConstant::from_constant_pool(constant_pool, x as _)))(data),
-            0x13 => map(be_u16, |x| Opcode::LdcW(ValueConstant::from_constant_pool(constant_pool, x as _)))(data),
-            0x14 => map(be_u16, |x| Opcode::Ldc2W(ValueConstant::from_constant_pool(constant_pool, x as _)))(data),
+            0x12 => map_res(u8, |x| ValueConstant::from_constant_pool(constant_pool, x as _).map(Opcode::Ldc))(data),
+            0x13 => map_res(be_u16, |x| ValueConstant::from_constant_pool(constant_pool, x as _).map(Opcode::LdcW))(data),
+            0x14 => map_res(be_u16, |x| ValueConstant::from_constant_pool(constant_pool, x as _).map(Opcode::Ldc2W))(data),
             0x6d => success(Opcode::Ldiv)(data),
             0x16 => map(u8, Opcode::Lload)(data),
             0x1e => success(Opcode::Lload(0))(data),
@@ -378,17 +386,21 @@ impl Opcode {
             0x83 => success(Opcode::Lxor)(data),
             0xc2 => success(Opcode::Monitorenter)(data),
             0xc3 => success(Opcode::Monitorexit)(data),
-            0xc5 => map(tuple((be_u16, u8)), |(index, dimensions)| {
-                Opcode::Multianewarray(ValueConstant::from_constant_pool(constant_pool, index as _), dimensions)
+            0xc5 => map_res(tuple((be_u16, u8)), |(index, dimensions)| {
+                ConstantPoolItem::class_name(constant_pool, index).map(|x| Opcode::Multianewarray(ValueConstant::Class(x), dimensions))
+            })(data),
+            0xbb => map_res(be_u16, |x| {
+                ConstantPoolItem::class_name(constant_pool, x).map(|x| Opcode::New(ValueConstant::Class(x)))
             })(data),
-            0xbb => map(be_u16, |x| Opcode::New(ValueConstant::from_constant_pool(constant_pool, x as _)))(data),
             0xbc => map(u8, Opcode::Newarray)(data),
             0x00 => success(Opcode::Nop)(data),
             0x57 => success(Opcode::Pop)(data),
             0x58 => success(Opcode::Pop2)(data),
-            0xb5 => map(be_u16, |x| Opcode::Putfield(ReferenceConstant::from_constant_pool(constant_pool, x as _)))(data),
-            0xb3 => map(be_u16, |x| {
-                Opcode::Putstatic(ReferenceConstant::from_constant_pool(constant_pool, x as _))
+            0xb5 => map_res(be_u16, |x| {
+                ReferenceConstant::from_constant_pool(constant_pool, x as _).map(Opcode::Putfield)
+            })(data),
+            0xb3 => map_res(be_u16, |x| {
+                ReferenceConstant::from_constant_pool(constant_pool, x as _).map(Opcode::Putstatic)
             })(data),
             0xa9 => map(u8, Opcode::Ret)(data),
             0xb1 => success(Opcode::Return)(data),
@@ -407,7 +419,7 @@ impl Opcode {
                 },
             )(data),
             0xc4 => success(Opcode::Wide)(data),
-            _ => panic!("Unknown opcode: {:02x}", opcode),
+            _ => ClassFileParseError::failure(data, ClassFileErrorKind::UnknownOpcode(opcode)),
         }
     }
 }
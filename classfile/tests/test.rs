@@ -1,6 +1,6 @@
 use java_constants::ClassAccessFlags;
 
-use classfile::{AttributeInfo, ClassInfo, Opcode, ValueConstant};
+use classfile::{AttributeInfo, ClassFileErrorKind, ClassInfo, ElementValue, Opcode, ValueConstant};
 
 #[test]
 fn test_hello() {
@@ -116,3 +116,171 @@ fn test_switch() {
             Opcode::Lookupswitch(default, pairs) if *default == 82 && *pairs == vec![(1, 41), (10, 52), (100, 63), (1000, 74)]));
     }
 }
+
+#[test]
+fn test_invalid_magic() {
+    let mut hello = include_bytes!("../../test_data/Hello.class").to_vec();
+    hello[0] = 0;
+
+    let error = ClassInfo::parse(&hello).err().unwrap();
+
+    assert_eq!(error.offset, 0);
+    assert_eq!(error.kind, ClassFileErrorKind::InvalidMagic(0x00FEBABE));
+}
+
+#[test]
+fn test_truncated() {
+    let hello = include_bytes!("../../test_data/Hello.class");
+
+    let error = ClassInfo::parse(&hello[..hello.len() - 4]).err().unwrap();
+
+    assert_eq!(error.kind, ClassFileErrorKind::UnexpectedEnd);
+}
+
+#[test]
+fn test_unknown_constant_pool_tag() {
+    let mut hello = include_bytes!("../../test_data/Hello.class").to_vec();
+    hello[10] = 2; // tag of first constant pool item
+
+    let error = ClassInfo::parse(&hello).err().unwrap();
+
+    assert_eq!(error.offset, 10);
+    assert_eq!(error.kind, ClassFileErrorKind::UnknownConstantPoolTag(2));
+}
+
+#[test]
+fn test_attributes() {
+    let attributes = include_bytes!("../../test_data/attributes/Attributes.class");
+
+    let class = ClassInfo::parse(attributes).unwrap();
+
+    assert!(
+        matches!(class.attributes.iter().find(|x| matches!(x, AttributeInfo::Signature(_))), Some(AttributeInfo::Signature(x)) if **x == "<T:Ljava/lang/Object;>Ljava/lang/Object;")
+    );
+    let Some(AttributeInfo::InnerClasses(inner_classes)) = class.attributes.iter().find(|x| matches!(x, AttributeInfo::InnerClasses(_))) else {
+        panic!("Expected InnerClasses attribute");
+    };
+    let inner = inner_classes.iter().find(|x| *x.inner_class == "Attributes$Inner").unwrap();
+    assert_eq!(inner.outer_class.as_deref().map(|x| x.as_str()), Some("Attributes"));
+    assert_eq!(inner.inner_name.as_deref().map(|x| x.as_str()), Some("Inner"));
+    let anonymous = inner_classes.iter().find(|x| *x.inner_class == "Attributes$1").unwrap();
+    assert!(anonymous.outer_class.is_none() && anonymous.inner_name.is_none());
+
+    let Some(AttributeInfo::BootstrapMethods(bootstrap_methods)) = class.attributes.iter().find(|x| matches!(x, AttributeInfo::BootstrapMethods(_)))
+    else {
+        panic!("Expected BootstrapMethods attribute");
+    };
+    assert_eq!(*bootstrap_methods[0].method.reference.name, "metafactory");
+    assert_eq!(bootstrap_methods[0].method.reference_kind, 6); // REF_invokeStatic
+    assert!(matches!(&bootstrap_methods[0].arguments[0], ValueConstant::MethodType(x) if **x == "()Ljava/lang/Object;"));
+    assert!(matches!(&bootstrap_methods[0].arguments[1], ValueConstant::MethodHandle(x) if x.reference.name.starts_with("lambda$")));
+
+    let lambda = class.methods.iter().find(|x| *x.name == "lambda").unwrap();
+    let AttributeInfo::Code(code) = &lambda.attributes[0] else {
+        panic!("Expected code attribute");
+    };
+    assert!(matches!(code.code.get(&0).unwrap(), Opcode::Invokedynamic(x) if *x.name == "get" && x.bootstrap_method_index == 0));
+    assert!(matches!(code.code.get(&5).unwrap(), Opcode::Areturn));
+
+    let generic = class.methods.iter().find(|x| *x.name == "generic").unwrap();
+    let find = |name: &str| {
+        generic
+            .attributes
+            .iter()
+            .find(|x| {
+                matches!(
+                    (name, x),
+                    ("Exceptions", AttributeInfo::Exceptions(_))
+                        | ("MethodParameters", AttributeInfo::MethodParameters(_))
+                        | ("Deprecated", AttributeInfo::Deprecated)
+                        | ("RuntimeVisibleAnnotations", AttributeInfo::RuntimeVisibleAnnotations(_))
+                        | ("RuntimeVisibleParameterAnnotations", AttributeInfo::RuntimeVisibleParameterAnnotations(_))
+                )
+            })
+            .unwrap()
+    };
+
+    assert!(matches!(find("Exceptions"), AttributeInfo::Exceptions(x) if *x[0] == "java/io/IOException"));
+    assert!(matches!(find("Deprecated"), AttributeInfo::Deprecated));
+
+    let AttributeInfo::MethodParameters(parameters) = find("MethodParameters") else {
+        unreachable!()
+    };
+    assert_eq!(parameters[0].name.as_deref().map(|x| x.as_str()), Some("first"));
+    assert_eq!(parameters[0].access_flags, 0x0010); // final
+    assert_eq!(parameters[1].name.as_deref().map(|x| x.as_str()), Some("second"));
+
+    let AttributeInfo::RuntimeVisibleAnnotations(annotations) = find("RuntimeVisibleAnnotations") else {
+        unreachable!()
+    };
+    let marker = annotations.iter().find(|x| *x.type_descriptor == "LAttributes$Marker;").unwrap();
+    assert!(matches!(&marker.elements[0], (name, ElementValue::Constant(b's', ValueConstant::String(x))) if **name == "value" && **x == "method"));
+    assert!(
+        matches!(&marker.elements[1], (name, ElementValue::Array(x)) if **name == "numbers" && matches!(x[1], ElementValue::Constant(b'I', ValueConstant::Integer(2))))
+    );
+    assert!(
+        matches!(&marker.elements[2], (_, ElementValue::Enum { type_descriptor, name }) if **type_descriptor == "Ljava/lang/annotation/RetentionPolicy;" && **name == "RUNTIME")
+    );
+
+    let AttributeInfo::RuntimeVisibleParameterAnnotations(parameter_annotations) = find("RuntimeVisibleParameterAnnotations") else {
+        unreachable!()
+    };
+    assert_eq!(parameter_annotations.len(), 2);
+    assert_eq!(*parameter_annotations[0][0].type_descriptor, "LAttributes$Marker;");
+    assert!(parameter_annotations[1].is_empty());
+
+    let anonymous = ClassInfo::parse(include_bytes!("../../test_data/attributes/Attributes$1.class")).unwrap();
+    assert!(anonymous.attributes.iter().any(
+        |x| matches!(x, AttributeInfo::EnclosingMethod(x) if *x.class == "Attributes" && x.method.as_ref().is_some_and(|(name, _)| **name == "anonymous"))
+    ));
+
+    let marker = ClassInfo::parse(include_bytes!("../../test_data/attributes/Attributes$Marker.class")).unwrap();
+    let value = marker.methods.iter().find(|x| *x.name == "value").unwrap();
+    assert!(
+        matches!(&value.attributes[0], AttributeInfo::AnnotationDefault(ElementValue::Constant(b's', ValueConstant::String(x))) if **x == "default")
+    );
+}
+
+#[test]
+fn test_unknown_attribute() {
+    let mut hello = include_bytes!("../../test_data/Hello.class").to_vec();
+    let offset = hello.windows(10).position(|x| x == b"SourceFile").unwrap();
+    hello[offset..offset + 10].copy_from_slice(b"SourceFilf");
+
+    let class = ClassInfo::parse(&hello).unwrap();
+
+    assert!(matches!(&class.attributes[0], AttributeInfo::Unknown { name, data } if **name == "SourceFilf" && data.len() == 2));
+}
+
+// renames SourceFile attribute of Hello.class, keeping its data of constant pool index
+fn hello_with_source_file_renamed(name: &str) -> Vec<u8> {
+    let hello = include_bytes!("../../test_data/Hello.class");
+    let offset = hello.windows(12).position(|x| x == b"\x00\x0aSourceFile").unwrap();
+
+    let mut result = hello[..offset].to_vec();
+    result.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    result.extend_from_slice(name.as_bytes());
+    result.extend_from_slice(&hello[offset + 12..]);
+
+    result
+}
+
+#[test]
+fn test_source_debug_extension() {
+    let class = ClassInfo::parse(&hello_with_source_file_renamed("SourceDebugExtension")).unwrap();
+
+    assert!(matches!(&class.attributes[0], AttributeInfo::SourceDebugExtension(x) if x.len() == 2));
+}
+
+#[test]
+fn test_malformed_optional_attribute() {
+    for name in [
+        "RuntimeInvisibleAnnotations",
+        "RuntimeInvisibleParameterAnnotations",
+        "LocalVariableTypeTable",
+    ] {
+        let class = ClassInfo::parse(&hello_with_source_file_renamed(name)).unwrap();
+
+        assert!(matches!(&class.attributes[0], AttributeInfo::Unknown { name: x, data } if **x == name && data.len() == 2));
+    }
+}
@@ -5,6 +5,25 @@ mod method;
 mod proto;
 
 pub use {
+    java_class_proto_macros::java_class,
     method::{MethodBody, TypeConverter},
     proto::{JavaClassProto, JavaFieldProto, JavaMethodProto},
 };
+
+// used by code generated by `#[java_class]`
+#[doc(hidden)]
+pub mod __private {
+    use alloc::format;
+
+    pub use alloc::{string::String, vec};
+    pub use java_constants::{FieldAccessFlags, MethodAccessFlags};
+    pub use jvm::{ClassInstanceRef, JavaValue, Jvm, Result};
+
+    pub fn method_descriptor(parameters: &[String], return_type: &str) -> String {
+        format!("({}){}", parameters.concat(), return_type)
+    }
+
+    pub fn array_descriptor(element_type: &str) -> String {
+        format!("[{}", element_type)
+    }
+}
@@ -1,7 +1,7 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String};
 use core::{future::Future, marker::PhantomData};
 
-use jvm::{ClassInstanceRef, JavaChar, JavaValue, Jvm};
+use jvm::{ClassInstanceRef, JavaChar, JavaError, JavaValue, Jvm, Result as JvmResult};
 
 macro_rules! __impl_fn_helper {
     ($($arg: ident),*) => {
@@ -14,12 +14,12 @@ macro_rules! __impl_fn_helper {
         {
             type Output = Fut;
             #[allow(unused_assignments, non_snake_case, unused_mut, unused_variables)]
-            fn do_call(&self, jvm: &'a Jvm, context: &'a mut C, args: Box<[JavaValue]>) -> Fut {
+            fn do_call(&self, jvm: &'a Jvm, context: &'a mut C, args: Box<[JavaValue]>) -> JvmResult<Fut> {
                 let mut args = alloc::vec::Vec::from(args).into_iter();
                 $(
-                    let $arg = $arg::to_rust(&jvm, args.next().unwrap());
+                    let $arg = $arg::to_rust(&jvm, args.next().ok_or_else(|| JavaError::FatalError("Not enough arguments".into()))?)?;
                 )*
-                self(jvm, context, $($arg),*)
+                Ok(self(jvm, context, $($arg),*))
             }
         }
     };
@@ -33,11 +33,12 @@ macro_rules! __impl_method_body {
         where
             F: for<'a> FnHelper<'a, C, E, R, ($($arg,)*)> + Sync + Send,
             C: ?Sized + Send,
+            E: From<JavaError>,
             R: TypeConverter<R> + Sync + Send,
             $($arg: Sync + Send),*
         {
             async fn call(&self, jvm: &Jvm, context: &mut C, args: Box<[JavaValue]>) -> Result<JavaValue, E> {
-                let result = self.0.do_call(jvm, context, args).await?;
+                let result = self.0.do_call(jvm, context, args)?.await?;
 
                 Ok(R::from_rust(&jvm, result))
             }
@@ -51,6 +52,7 @@ macro_rules! __impl_method_impl {
         where
             F: for<'a> FnHelper<'a, C, E, R, ($($arg,)*)> + 'static + Sync + Send,
             C: ?Sized + Send,
+            E: From<JavaError> + 'static,
             R: TypeConverter<R> + 'static + Sync + Send,
             $($arg: 'static + Sync + Send),*
         {
@@ -82,14 +84,16 @@ where
     C: ?Sized + 'a + Send,
 {
     type Output: Future<Output = Result<R, E>> + 'a + Send;
-    fn do_call(&self, jvm: &'a Jvm, context: &'a mut C, args: Box<[JavaValue]>) -> Self::Output;
+    fn do_call(&self, jvm: &'a Jvm, context: &'a mut C, args: Box<[JavaValue]>) -> JvmResult<Self::Output>;
 }
 
 struct MethodHolder<F, R, P>(pub F, PhantomData<(R, P)>);
 
 pub trait TypeConverter<T> {
-    fn to_rust(jvm: &Jvm, raw: JavaValue) -> T;
+    fn to_rust(jvm: &Jvm, raw: JavaValue) -> JvmResult<T>;
     fn from_rust(jvm: &Jvm, rust: T) -> JavaValue;
+    // java type descriptor of T, used by `#[java_class]` to build method descriptors
+    fn descriptor() -> String;
 }
 
 pub trait MethodImpl<F, C, R, E, P>
@@ -111,102 +115,145 @@ __generate!(P0, P1, P2, P3, P4, P5, P6, P7);
 __generate!(P0, P1, P2, P3, P4, P5, P6, P7, P8);
 
 impl TypeConverter<i8> for i8 {
-    fn to_rust(_: &Jvm, raw: JavaValue) -> i8 {
-        raw.into()
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<i8> {
+        raw.try_into()
     }
 
     fn from_rust(_: &Jvm, rust: i8) -> JavaValue {
         rust.into()
     }
+
+    fn descriptor() -> String {
+        "B".into()
+    }
 }
 
 impl TypeConverter<i16> for i16 {
-    fn to_rust(_: &Jvm, raw: JavaValue) -> i16 {
-        raw.into()
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<i16> {
+        raw.try_into()
     }
 
     fn from_rust(_: &Jvm, rust: i16) -> JavaValue {
         rust.into()
     }
+
+    fn descriptor() -> String {
+        "S".into()
+    }
 }
 
 impl TypeConverter<i32> for i32 {
-    fn to_rust(_: &Jvm, raw: JavaValue) -> i32 {
-        raw.into()
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<i32> {
+        raw.try_into()
     }
 
     fn from_rust(_: &Jvm, rust: i32) -> JavaValue {
         rust.into()
     }
+
+    fn descriptor() -> String {
+        "I".into()
+    }
 }
 
 impl TypeConverter<JavaChar> for JavaChar {
-    fn to_rust(_: &Jvm, raw: JavaValue) -> JavaChar {
-        raw.into()
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<JavaChar> {
+        raw.try_into()
     }
 
     fn from_rust(_: &Jvm, rust: JavaChar) -> JavaValue {
         rust.into()
     }
+
+    fn descriptor() -> String {
+        "C".into()
+    }
 }
 
 impl TypeConverter<i64> for i64 {
-    fn to_rust(_: &Jvm, raw: JavaValue) -> i64 {
-        raw.into()
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<i64> {
+        raw.try_into()
     }
 
     fn from_rust(_: &Jvm, rust: i64) -> JavaValue {
         rust.into()
     }
+
+    fn descriptor() -> String {
+        "J".into()
+    }
 }
 
 impl TypeConverter<bool> for bool {
-    fn to_rust(_: &Jvm, raw: JavaValue) -> bool {
-        raw.into()
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<bool> {
+        raw.try_into()
     }
 
     fn from_rust(_: &Jvm, rust: bool) -> JavaValue {
         rust.into()
     }
+
+    fn descriptor() -> String {
+        "Z".into()
+    }
 }
 
 impl TypeConverter<f32> for f32 {
-    fn to_rust(_: &Jvm, raw: JavaValue) -> f32 {
-        raw.into()
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<f32> {
+        raw.try_into()
     }
 
     fn from_rust(_: &Jvm, rust: f32) -> JavaValue {
         rust.into()
     }
+
+    fn descriptor() -> String {
+        "F".into()
+    }
 }
 
 impl TypeConverter<f64> for f64 {
-    fn to_rust(_: &Jvm, raw: JavaValue) -> f64 {
-        raw.into()
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<f64> {
+        raw.try_into()
     }
 
     fn from_rust(_: &Jvm, rust: f64) -> JavaValue {
         rust.into()
     }
+
+    fn descriptor() -> String {
+        "D".into()
+    }
 }
 
 impl TypeConverter<()> for () {
-    fn to_rust(_: &Jvm, _: JavaValue) {}
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<()> {
+        raw.try_into()
+    }
 
     fn from_rust(_: &Jvm, _: ()) -> JavaValue {
         JavaValue::Void
     }
+
+    fn descriptor() -> String {
+        "V".into()
+    }
 }
 
 impl<T> TypeConverter<ClassInstanceRef<T>> for ClassInstanceRef<T>
 where
     T: Sync + Send,
 {
-    fn to_rust(_: &Jvm, raw: JavaValue) -> Self {
-        Self::new(raw.into())
+    fn to_rust(_: &Jvm, raw: JavaValue) -> JvmResult<Self> {
+        raw.try_into()
     }
 
     fn from_rust(_: &Jvm, value: Self) -> JavaValue {
         value.instance.into()
     }
+
+    // class of the reference is known to its marker, which `#[java_class]` asks instead
+    fn descriptor() -> String {
+        "Ljava/lang/Object;".into()
+    }
 }
@@ -11,6 +11,7 @@ where
 {
     pub name: &'static str,
     pub parent_class: Option<&'static str>,
+    pub is_interface: bool,
     pub interfaces: Vec<&'static str>,
     pub methods: Vec<JavaMethodProto<C>>,
     pub fields: Vec<JavaFieldProto>,
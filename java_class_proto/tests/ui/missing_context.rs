@@ -0,0 +1,8 @@
+use java_class_proto::java_class;
+
+struct Foo;
+
+#[java_class("Foo")]
+impl Foo {}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use java_class_proto::java_class;
+use jvm::{Jvm, Result};
+
+struct Context;
+
+struct Foo;
+
+#[java_class("Foo")]
+impl Foo {
+    #[java_method]
+    pub async fn value(_: &Jvm, _: &Context, value: i32) -> Result<i32> {
+        Ok(value)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use java_class_proto::java_class;
+use jvm::Jvm;
+
+struct Context;
+
+struct Foo;
+
+#[java_class("Foo")]
+impl Foo {
+    #[java_method]
+    pub async fn value(_: &Jvm, _: &mut Context, value: i32) -> i32 {
+        value
+    }
+}
+
+fn main() {}
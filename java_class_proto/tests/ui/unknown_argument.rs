@@ -0,0 +1,10 @@
+use java_class_proto::java_class;
+
+struct Context;
+
+struct Foo;
+
+#[java_class("Foo", parent = "Bar", context = Context)]
+impl Foo {}
+
+fn main() {}
@@ -0,0 +1,525 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Error, Expr, FnArg, ForeignItemFn, GenericArgument, Ident, ImplItem, ItemImpl, LitStr, Pat, PathArguments, Result, ReturnType,
+    Signature, Token, Type, Visibility,
+};
+
+// generates `as_proto()` for an impl block of a java class marker, and `TypeConverter` of references to the marker.
+//
+// #[java_class("java/util/Foo", extends = "java/lang/Object", implements = ["java/lang/Runnable"])]
+// impl Foo {
+//     #[java_field]
+//     const count: i32;
+//
+//     #[java_method]
+//     pub async fn init(jvm: &Jvm, context: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> { .. }
+//
+//     #[java_method(name = "valueOf")]
+//     pub async fn value_of_integer(jvm: &Jvm, context: &mut RuntimeContext, value: i32) -> Result<ClassInstanceRef<Self>> { .. }
+// }
+//
+// method names are camel cased rust names, with `init` and `clinit` mapped to `<init>` and `<clinit>`.
+// methods without `this` parameter are static. descriptors are built from parameter and return types,
+// and can be given with `descriptor = ".."` for types without `TypeConverter`.
+// `pub` members are public in java, and others are package-private unless given with `flags = ..`.
+//
+// with `interface`, the marker is a java interface and methods declared without body are abstract:
+//
+// #[java_class("java/lang/Runnable", interface)]
+// impl Runnable {
+//     #[java_method]
+//     async fn run(jvm: &Jvm, context: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()>;
+// }
+#[proc_macro_attribute]
+pub fn java_class(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as ClassArgs);
+    let item = parse_macro_input!(item as ItemImpl);
+
+    match expand_class(args, item) {
+        Ok(x) => x.into(),
+        Err(x) => x.to_compile_error().into(),
+    }
+}
+
+struct ClassArgs {
+    name: LitStr,
+    parent_class: Option<LitStr>,
+    interfaces: Vec<LitStr>,
+    is_interface: bool,
+    context: Option<Type>,
+}
+
+impl Parse for ClassArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: LitStr = input.parse()?;
+        let mut parent_class = None;
+        let mut interfaces = Vec::new();
+        let mut is_interface = false;
+        let mut context = None;
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let key: Ident = input.parse()?;
+            if key == "interface" {
+                is_interface = true;
+                continue;
+            }
+            input.parse::<Token![=]>()?;
+
+            match key.to_string().as_str() {
+                "extends" => parent_class = Some(input.parse()?),
+                "implements" => {
+                    let content;
+                    bracketed!(content in input);
+                    interfaces = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?.into_iter().collect();
+                }
+                "context" => context = Some(input.parse()?),
+                _ => return Err(Error::new(key.span(), "expected `extends`, `implements`, `interface` or `context`")),
+            }
+        }
+
+        Ok(Self {
+            name,
+            parent_class,
+            interfaces,
+            is_interface,
+            context,
+        })
+    }
+}
+
+#[derive(Default)]
+struct MemberArgs {
+    name: Option<LitStr>,
+    descriptor: Option<LitStr>,
+    flags: Option<Expr>,
+}
+
+impl MemberArgs {
+    fn from_attribute(attr: &Attribute) -> Result<Self> {
+        let mut result = Self::default();
+        if !matches!(attr.meta, syn::Meta::List(_)) {
+            return Ok(result);
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                result.name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("descriptor") {
+                result.descriptor = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("flags") {
+                result.flags = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `name`, `descriptor` or `flags`"));
+            }
+
+            Ok(())
+        })?;
+
+        Ok(result)
+    }
+}
+
+// `#[java_field] pub const name: Type;`, which is not valid rust outside of macro input
+struct FieldDeclaration {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    ty: Type,
+}
+
+impl Parse for FieldDeclaration {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![const]>()?;
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        Ok(Self { attrs, vis, name, ty })
+    }
+}
+
+fn take_attribute(attrs: &mut Vec<Attribute>, name: &str) -> Option<Attribute> {
+    let index = attrs.iter().position(|x| x.path().is_ident(name))?;
+
+    Some(attrs.remove(index))
+}
+
+fn expand_class(args: ClassArgs, mut item: ItemImpl) -> Result<TokenStream2> {
+    let mut methods = Vec::new();
+    let mut fields = Vec::new();
+    let mut abstract_signatures = Vec::new();
+    let mut context = args.context;
+
+    let mut items = Vec::with_capacity(item.items.len());
+    for impl_item in item.items {
+        match impl_item {
+            ImplItem::Fn(mut x) => {
+                if let Some(attr) = take_attribute(&mut x.attrs, "java_method") {
+                    let (method, method_context) = expand_method(&MemberArgs::from_attribute(&attr)?, &x.vis, &x.sig, false, args.is_interface)?;
+                    methods.push(method);
+                    context.get_or_insert(method_context);
+                }
+                items.push(ImplItem::Fn(x));
+            }
+            ImplItem::Verbatim(x) => {
+                if let Ok(mut field) = syn::parse2::<FieldDeclaration>(x.clone()) {
+                    if let Some(attr) = take_attribute(&mut field.attrs, "java_field") {
+                        fields.push(expand_field(&MemberArgs::from_attribute(&attr)?, &field));
+                        continue;
+                    }
+                }
+
+                // abstract method, which is only declared in java
+                if let Ok(mut method) = syn::parse2::<ForeignItemFn>(x.clone()) {
+                    if let Some(attr) = take_attribute(&mut method.attrs, "java_method") {
+                        let (proto, method_context) =
+                            expand_method(&MemberArgs::from_attribute(&attr)?, &method.vis, &method.sig, true, args.is_interface)?;
+                        methods.push(proto);
+                        abstract_signatures.push(signature_type(&method.sig));
+                        context.get_or_insert(method_context);
+                        continue;
+                    }
+                }
+
+                items.push(ImplItem::Verbatim(x));
+            }
+            x => items.push(x),
+        }
+    }
+
+    let Some(context) = context else {
+        return Err(Error::new(
+            item.self_ty.span(),
+            "class without java methods should specify its context type with `context = ..`",
+        ));
+    };
+
+    let name = &args.name;
+    let parent_class = match &args.parent_class {
+        Some(x) => quote!(Some(#x)),
+        None if name.value() == "java/lang/Object" || args.is_interface => quote!(None),
+        None => quote!(Some("java/lang/Object")),
+    };
+    let interfaces = &args.interfaces;
+    let is_interface = args.is_interface;
+
+    items.push(syn::parse_quote! {
+        pub fn as_proto() -> ::java_class_proto::JavaClassProto<#context> {
+            // abstract methods are not emitted, so we check their declarations here
+            #(let _: ::core::option::Option<#abstract_signatures> = None;)*
+
+            ::java_class_proto::JavaClassProto {
+                name: #name,
+                parent_class: #parent_class,
+                is_interface: #is_interface,
+                interfaces: ::java_class_proto::__private::vec![#(#interfaces),*],
+                methods: ::java_class_proto::__private::vec![#(#methods),*],
+                fields: ::java_class_proto::__private::vec![#(#fields),*],
+            }
+        }
+    });
+    item.items = items;
+
+    let self_ty = &item.self_ty;
+    let descriptor = format!("L{};", name.value());
+
+    Ok(quote! {
+        #item
+
+        impl ::java_class_proto::TypeConverter<::java_class_proto::__private::ClassInstanceRef<#self_ty>> for #self_ty {
+            fn to_rust(
+                jvm: &::java_class_proto::__private::Jvm,
+                raw: ::java_class_proto::__private::JavaValue,
+            ) -> ::java_class_proto::__private::Result<::java_class_proto::__private::ClassInstanceRef<#self_ty>> {
+                <::java_class_proto::__private::ClassInstanceRef<#self_ty> as ::java_class_proto::TypeConverter<_>>::to_rust(jvm, raw)
+            }
+
+            fn from_rust(
+                jvm: &::java_class_proto::__private::Jvm,
+                rust: ::java_class_proto::__private::ClassInstanceRef<#self_ty>,
+            ) -> ::java_class_proto::__private::JavaValue {
+                <::java_class_proto::__private::ClassInstanceRef<#self_ty> as ::java_class_proto::TypeConverter<_>>::from_rust(jvm, rust)
+            }
+
+            fn descriptor() -> ::java_class_proto::__private::String {
+                #descriptor.into()
+            }
+        }
+    })
+}
+
+// returns method proto expression and context type of the method
+fn expand_method(args: &MemberArgs, vis: &Visibility, sig: &Signature, is_abstract: bool, is_interface: bool) -> Result<(TokenStream2, Type)> {
+    let inputs = sig.inputs.iter().collect::<Vec<_>>();
+
+    let context = match inputs.get(1) {
+        Some(FnArg::Typed(x)) => match &*x.ty {
+            Type::Reference(x) if x.mutability.is_some() => (*x.elem).clone(),
+            _ => return Err(Error::new(x.ty.span(), "expected `&mut` context")),
+        },
+        _ => {
+            return Err(Error::new(
+                sig.span(),
+                "java method should take `&Jvm` and `&mut` context as first parameters",
+            ))
+        }
+    };
+
+    let mut parameters = Vec::new();
+    let mut is_static = true;
+    for (i, input) in inputs.iter().enumerate().skip(2) {
+        let FnArg::Typed(x) = input else {
+            return Err(Error::new(input.span(), "java method can't take self"));
+        };
+        if i == 2 && matches!(&*x.pat, Pat::Ident(x) if x.ident == "this") {
+            is_static = false;
+            continue;
+        }
+        parameters.push(&*x.ty);
+    }
+
+    let name = match &args.name {
+        Some(x) => x.value(),
+        None => java_method_name(&sig.ident.to_string()),
+    };
+
+    let descriptor = match &args.descriptor {
+        Some(x) => quote!(#x),
+        None => {
+            let return_type = return_type(&sig.output)?;
+
+            let parameters = parameters.into_iter().map(type_descriptor);
+            let return_type = type_descriptor(return_type);
+
+            quote! {
+                &::java_class_proto::__private::method_descriptor(&[#(#parameters),*], &#return_type)
+            }
+        }
+    };
+
+    // `pub fn` and interface methods are public in java, others are package-private unless flags say otherwise
+    let flags = match &args.flags {
+        Some(x) => quote!(#x),
+        None => quote!(::java_class_proto::__private::MethodAccessFlags::empty()),
+    };
+    let flags = if matches!(vis, Visibility::Public(_)) || is_interface {
+        quote!(#flags | ::java_class_proto::__private::MethodAccessFlags::PUBLIC)
+    } else {
+        flags
+    };
+    let flags = if is_static {
+        quote!(#flags | ::java_class_proto::__private::MethodAccessFlags::STATIC)
+    } else {
+        flags
+    };
+
+    if is_abstract {
+        return Ok((
+            quote!(::java_class_proto::JavaMethodProto::new_abstract(
+                #name,
+                #descriptor,
+                #flags | ::java_class_proto::__private::MethodAccessFlags::ABSTRACT
+            )),
+            context,
+        ));
+    }
+
+    let ident = &sig.ident;
+
+    Ok((
+        quote!(::java_class_proto::JavaMethodProto::new(#name, #descriptor, Self::#ident, #flags)),
+        context,
+    ))
+}
+
+fn expand_field(args: &MemberArgs, field: &FieldDeclaration) -> TokenStream2 {
+    let name = match &args.name {
+        Some(x) => x.value(),
+        None => field.name.to_string(),
+    };
+
+    let ty = &field.ty;
+    let descriptor = match &args.descriptor {
+        Some(x) => quote!(#x),
+        None => {
+            let descriptor = type_descriptor(ty);
+
+            quote!(&#descriptor)
+        }
+    };
+
+    let flags = match &args.flags {
+        Some(x) => quote!(#x),
+        None => quote!(::java_class_proto::__private::FieldAccessFlags::empty()),
+    };
+    let flags = if matches!(field.vis, Visibility::Public(_)) {
+        quote!(#flags | ::java_class_proto::__private::FieldAccessFlags::PUBLIC)
+    } else {
+        flags
+    };
+
+    quote!(::java_class_proto::JavaFieldProto::new(#name, #descriptor, #flags))
+}
+
+// function pointer type of a method declaration
+fn signature_type(sig: &Signature) -> TokenStream2 {
+    let inputs = sig.inputs.iter().filter_map(|x| match x {
+        FnArg::Typed(x) => Some(&x.ty),
+        FnArg::Receiver(_) => None,
+    });
+    let output = &sig.output;
+
+    quote!(fn(#(#inputs),*) #output)
+}
+
+// descriptor expression of a rust type. references and arrays are described by their class marker or element type,
+// so that class markers only need `TypeConverter` of references to themselves
+fn type_descriptor(ty: &Type) -> TokenStream2 {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if let PathArguments::AngleBracketed(x) = &segment.arguments {
+                if let Some(GenericArgument::Type(inner)) = x.args.first() {
+                    if segment.ident == "ClassInstanceRef" {
+                        return type_descriptor(inner);
+                    }
+                    if segment.ident == "Array" {
+                        let inner = type_descriptor(inner);
+
+                        return quote!(::java_class_proto::__private::array_descriptor(&#inner));
+                    }
+                }
+            }
+        }
+    }
+
+    quote!(<#ty as ::java_class_proto::TypeConverter<_>>::descriptor())
+}
+
+// `Result<T>` to `T`
+fn return_type(output: &ReturnType) -> Result<&Type> {
+    if let ReturnType::Type(_, ty) = output {
+        if let Type::Path(path) = &**ty {
+            if let Some(PathArguments::AngleBracketed(x)) = path.path.segments.last().map(|x| &x.arguments) {
+                if let Some(GenericArgument::Type(x)) = x.args.first() {
+                    return Ok(x);
+                }
+            }
+        }
+    }
+
+    Err(Error::new(output.span(), "java method should return `Result<T>`"))
+}
+
+fn java_method_name(rust_name: &str) -> String {
+    match rust_name {
+        "init" => "<init>".into(),
+        "clinit" => "<clinit>".into(),
+        _ => {
+            let mut parts = rust_name.split('_').filter(|x| !x.is_empty());
+            let mut result = parts.next().unwrap_or_default().to_owned();
+            for part in parts {
+                let mut chars = part.chars();
+                result.extend(chars.next().map(|x| x.to_ascii_uppercase()));
+                result.push_str(chars.as_str());
+            }
+
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use quote::ToTokens;
+    use syn::ImplItemFn;
+
+    use super::{expand_method, java_method_name, ClassArgs, MemberArgs};
+
+    #[test]
+    fn test_java_method_name() {
+        assert_eq!(java_method_name("init"), "<init>");
+        assert_eq!(java_method_name("clinit"), "<clinit>");
+        assert_eq!(java_method_name("run"), "run");
+        assert_eq!(java_method_name("get_property"), "getProperty");
+        assert_eq!(java_method_name("to_char_array"), "toCharArray");
+        assert_eq!(java_method_name("value_of_2"), "valueOf2");
+        assert_eq!(java_method_name("_private_run"), "privateRun");
+    }
+
+    #[test]
+    fn test_class_args() {
+        let args = syn::parse_str::<ClassArgs>(r#""java/util/Foo""#).unwrap();
+        assert_eq!(args.name.value(), "java/util/Foo");
+        assert!(args.parent_class.is_none());
+        assert!(args.interfaces.is_empty());
+        assert!(!args.is_interface);
+        assert!(args.context.is_none());
+
+        let args = syn::parse_str::<ClassArgs>(
+            r#""java/util/Foo", extends = "java/util/Bar", implements = ["java/lang/Runnable", "java/io/Closeable"], context = Context,"#,
+        )
+        .unwrap();
+        assert_eq!(args.parent_class.unwrap().value(), "java/util/Bar");
+        assert_eq!(
+            args.interfaces.iter().map(|x| x.value()).collect::<Vec<_>>(),
+            ["java/lang/Runnable", "java/io/Closeable"]
+        );
+        assert_eq!(args.context.unwrap().to_token_stream().to_string(), "Context");
+
+        let args = syn::parse_str::<ClassArgs>(r#""java/lang/Runnable", interface"#).unwrap();
+        assert!(args.is_interface);
+    }
+
+    #[test]
+    fn test_class_args_invalid() {
+        assert!(syn::parse_str::<ClassArgs>("").is_err());
+        assert!(syn::parse_str::<ClassArgs>(r#""java/util/Foo", parent = "java/util/Bar""#).is_err());
+        assert!(syn::parse_str::<ClassArgs>(r#""java/util/Foo", implements = "java/lang/Runnable""#).is_err());
+        assert!(syn::parse_str::<ClassArgs>(r#""java/util/Foo" extends = "java/util/Bar""#).is_err());
+    }
+
+    #[test]
+    fn test_method_context() {
+        let method =
+            syn::parse_str::<ImplItemFn>("async fn run(jvm: &Jvm, context: &mut Context, this: ClassInstanceRef<Self>) -> Result<()> {}").unwrap();
+        let (_, context) = expand_method(&MemberArgs::default(), &method.vis, &method.sig, false, false).unwrap();
+
+        assert_eq!(context.to_token_stream().to_string(), "Context");
+    }
+
+    #[test]
+    fn test_method_invalid() {
+        let expand = |x: &str| {
+            let method = syn::parse_str::<ImplItemFn>(x).unwrap();
+
+            expand_method(&MemberArgs::default(), &method.vis, &method.sig, false, false).map(|_| ())
+        };
+
+        let error = expand("async fn run(jvm: &Jvm, context: &Context) -> Result<()> {}").unwrap_err();
+        assert_eq!(error.to_string(), "expected `&mut` context");
+
+        let error = expand("async fn run(jvm: &Jvm) -> Result<()> {}").unwrap_err();
+        assert_eq!(error.to_string(), "java method should take `&Jvm` and `&mut` context as first parameters");
+
+        let error = expand("async fn run(jvm: &Jvm, context: &mut Context) -> i32 {}").unwrap_err();
+        assert_eq!(error.to_string(), "java method should return `Result<T>`");
+
+        let error = expand("async fn run(jvm: &Jvm, context: &mut Context) {}").unwrap_err();
+        assert_eq!(error.to_string(), "java method should return `Result<T>`");
+    }
+}
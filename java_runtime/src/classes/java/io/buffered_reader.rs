@@ -1,6 +1,7 @@
 use alloc::{vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{Array, ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{
@@ -18,10 +19,11 @@ impl BufferedReader {
         RuntimeClassProto {
             name: "java/io/BufferedReader",
             parent_class: Some("java/io/Reader"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/Reader;)V", Self::init, Default::default()),
-                JavaMethodProto::new("readLine", "()Ljava/lang/String;", Self::read_line, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/Reader;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readLine", "()Ljava/lang/String;", Self::read_line, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![
                 JavaFieldProto::new("in", "Ljava/io/Reader;", Default::default()),
@@ -67,7 +69,12 @@ impl BufferedReader {
             jvm.put_field(&mut this, "bufSize", "I", buf_size).await?;
 
             let char_buf: Vec<JavaChar> = jvm.load_array(&buf, 0, buf_size as _).await?;
-            pos = char_buf.iter().position(|&c| c == b'\n' as _);
+            pos = char_buf.iter().position(|&c| c == b'\n' as _).map(|x| {
+                // line terminator may be \r\n
+                let length = if x > 0 && char_buf[x - 1] == b'\r' as _ { x - 1 } else { x };
+
+                (x, length)
+            });
         }
 
         // can't fill buffer
@@ -75,9 +82,9 @@ impl BufferedReader {
             return Ok(None.into());
         }
 
-        Ok(if let Some(x) = pos {
+        Ok(if let Some((x, length)) = pos {
             // found newline
-            let result = jvm.new_class("java/lang/String", "([CII)V", (buf.clone(), 0, x as i32)).await?;
+            let result = jvm.new_class("java/lang/String", "([CII)V", (buf.clone(), 0, length as i32)).await?;
 
             // advance buffer
             let _: () = jvm
@@ -1,6 +1,7 @@
 use alloc::{vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{FieldAccessFlags, MethodAccessFlags};
 use jvm::{Array, ClassInstanceRef, Jvm, Result};
 
 use crate::{RuntimeClassProto, RuntimeContext};
@@ -13,16 +14,17 @@ impl ByteArrayOutputStream {
         RuntimeClassProto {
             name: "java/io/ByteArrayOutputStream",
             parent_class: Some("java/io/OutputStream"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("<init>", "(I)V", Self::init_with_size, Default::default()),
-                JavaMethodProto::new("write", "(I)V", Self::write, Default::default()),
-                JavaMethodProto::new("toByteArray", "()[B", Self::to_byte_array, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(I)V", Self::init_with_size, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("write", "(I)V", Self::write, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("toByteArray", "()[B", Self::to_byte_array, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![
-                JavaFieldProto::new("buf", "[B", Default::default()),
-                JavaFieldProto::new("pos", "I", Default::default()),
+                JavaFieldProto::new("buf", "[B", FieldAccessFlags::PROTECTED),
+                JavaFieldProto::new("pos", "I", FieldAccessFlags::PROTECTED),
             ],
         }
     }
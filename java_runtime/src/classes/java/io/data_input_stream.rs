@@ -3,6 +3,7 @@ use alloc::{string::String as RustString, vec};
 use bytemuck::cast_vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{FieldAccessFlags, MethodAccessFlags};
 use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{
@@ -18,25 +19,26 @@ impl DataInputStream {
         RuntimeClassProto {
             name: "java/io/DataInputStream",
             parent_class: Some("java/io/InputStream"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/InputStream;)V", Self::init, Default::default()),
-                JavaMethodProto::new("available", "()I", Self::available, Default::default()),
-                JavaMethodProto::new("read", "()I", Self::read_byte_int, Default::default()),
-                JavaMethodProto::new("read", "([BII)I", Self::read, Default::default()),
-                JavaMethodProto::new("readBoolean", "()Z", Self::read_boolean, Default::default()),
-                JavaMethodProto::new("readByte", "()B", Self::read_byte, Default::default()),
-                JavaMethodProto::new("readChar", "()C", Self::read_char, Default::default()),
-                JavaMethodProto::new("readDouble", "()D", Self::read_double, Default::default()),
-                JavaMethodProto::new("readFloat", "()F", Self::read_float, Default::default()),
-                JavaMethodProto::new("readInt", "()I", Self::read_int, Default::default()),
-                JavaMethodProto::new("readLong", "()J", Self::read_long, Default::default()),
-                JavaMethodProto::new("readShort", "()S", Self::read_short, Default::default()),
-                JavaMethodProto::new("readUnsignedShort", "()I", Self::read_unsigned_short, Default::default()),
-                JavaMethodProto::new("readUTF", "()Ljava/lang/String;", Self::read_utf, Default::default()),
-                JavaMethodProto::new("close", "()V", Self::close, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/InputStream;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("available", "()I", Self::available, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "()I", Self::read_byte_int, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "([BII)I", Self::read, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readBoolean", "()Z", Self::read_boolean, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readByte", "()B", Self::read_byte, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readChar", "()C", Self::read_char, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readDouble", "()D", Self::read_double, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readFloat", "()F", Self::read_float, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readInt", "()I", Self::read_int, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readLong", "()J", Self::read_long, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readShort", "()S", Self::read_short, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readUnsignedShort", "()I", Self::read_unsigned_short, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("readUTF", "()Ljava/lang/String;", Self::read_utf, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("close", "()V", Self::close, MethodAccessFlags::PUBLIC),
             ],
-            fields: vec![JavaFieldProto::new("in", "Ljava/io/InputStream;", Default::default())],
+            fields: vec![JavaFieldProto::new("in", "Ljava/io/InputStream;", FieldAccessFlags::PROTECTED)],
         }
     }
 
@@ -3,6 +3,7 @@ use alloc::vec;
 use bytemuck::cast_vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{FieldAccessFlags, MethodAccessFlags};
 use jvm::{Array, ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{classes::java::io::InputStream, RuntimeClassProto, RuntimeContext};
@@ -15,17 +16,18 @@ impl DataOutputStream {
         RuntimeClassProto {
             name: "java/io/DataOutputStream",
             parent_class: Some("java/io/OutputStream"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/OutputStream;)V", Self::init, Default::default()),
-                JavaMethodProto::new("write", "(I)V", Self::write, Default::default()),
-                JavaMethodProto::new("writeByte", "(I)V", Self::write, Default::default()),
-                JavaMethodProto::new("writeInt", "(I)V", Self::write_int, Default::default()),
-                JavaMethodProto::new("writeLong", "(J)V", Self::write_long, Default::default()),
-                JavaMethodProto::new("writeChars", "(Ljava/lang/String;)V", Self::write_chars, Default::default()),
-                JavaMethodProto::new("close", "()V", Self::close, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/OutputStream;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("write", "(I)V", Self::write, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("writeByte", "(I)V", Self::write, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("writeInt", "(I)V", Self::write_int, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("writeLong", "(J)V", Self::write_long, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("writeChars", "(Ljava/lang/String;)V", Self::write_chars, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("close", "()V", Self::close, MethodAccessFlags::PUBLIC),
             ],
-            fields: vec![JavaFieldProto::new("out", "Ljava/io/OutputStream;", Default::default())],
+            fields: vec![JavaFieldProto::new("out", "Ljava/io/OutputStream;", FieldAccessFlags::PROTECTED)],
         }
     }
 
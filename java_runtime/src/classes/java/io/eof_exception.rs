@@ -10,6 +10,7 @@ impl EOFException {
         RuntimeClassProto {
             name: "java/io/EOFException",
             parent_class: Some("java/io/IOException"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![],
             fields: vec![],
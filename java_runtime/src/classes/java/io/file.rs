@@ -1,6 +1,7 @@
-use alloc::vec;
+use alloc::{format, vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result};
 
 use crate::{classes::java::lang::String, FileType, RuntimeClassProto, RuntimeContext};
@@ -13,15 +14,16 @@ impl File {
         RuntimeClassProto {
             name: "java/io/File",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init, Default::default()),
-                JavaMethodProto::new("getPath", "()Ljava/lang/String;", Self::get_path, Default::default()),
-                JavaMethodProto::new("exists", "()Z", Self::exists, Default::default()),
-                JavaMethodProto::new("isDirectory", "()Z", Self::is_directory, Default::default()),
-                JavaMethodProto::new("isFile", "()Z", Self::is_file, Default::default()),
-                JavaMethodProto::new("delete", "()Z", Self::delete, Default::default()),
-                JavaMethodProto::new("length", "()J", Self::length, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getPath", "()Ljava/lang/String;", Self::get_path, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("exists", "()Z", Self::exists, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isDirectory", "()Z", Self::is_directory, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isFile", "()Z", Self::is_file, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("delete", "()Z", Self::delete, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("length", "()J", Self::length, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![JavaFieldProto::new("path", "Ljava/lang/String;", Default::default())],
         }
@@ -95,7 +97,10 @@ impl File {
         let path = jvm.invoke_virtual(&this, "getPath", "()Ljava/lang/String;", ()).await?;
         let path = JavaLangString::to_rust_string(jvm, &path).await?;
 
-        let stat = context.metadata(&path).await.unwrap();
+        let stat = context.metadata(&path).await;
+        let Ok(stat) = stat else {
+            return Err(jvm.exception("java/io/IOException", &format!("Cannot get length of {}", path)).await);
+        };
 
         Ok(stat.size as _)
     }
@@ -2,6 +2,7 @@ use alloc::vec;
 
 use bytemuck::cast_vec;
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -17,11 +18,14 @@ impl FileInputStream {
         RuntimeClassProto {
             name: "java/io/FileInputStream",
             parent_class: Some("java/io/InputStream"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/File;)V", Self::init, Default::default()),
-                JavaMethodProto::new("read", "([B)I", Self::read, Default::default()),
-                JavaMethodProto::new("close", "()V", Self::close, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/File;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "([B)I", Self::read, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "([BII)I", Self::read_offset_length, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "()I", Self::read_byte, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("close", "()V", Self::close, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![JavaFieldProto::new("fd", "Ljava/io/FileDescriptor;", Default::default())],
         }
@@ -53,7 +57,7 @@ impl FileInputStream {
         let mut rust_file = FileDescriptor::file(jvm, fd).await?;
 
         let mut rust_buf = vec![0; length];
-        let read = rust_file.read(&mut rust_buf).await.unwrap();
+        let read = jvm.blocking(rust_file.read(&mut rust_buf)).await.unwrap();
         if read == 0 {
             return Ok(-1);
         }
@@ -63,6 +67,50 @@ impl FileInputStream {
         Ok(read as _)
     }
 
+    async fn read_offset_length(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        mut buf: ClassInstanceRef<Array<i8>>,
+        offset: i32,
+        length: i32,
+    ) -> Result<i32> {
+        tracing::debug!("java.io.FileInputStream::read({:?}, {:?}, {:?}, {:?})", &this, &buf, offset, length);
+
+        if length == 0 {
+            return Ok(0);
+        }
+
+        let fd = jvm.get_field(&this, "fd", "Ljava/io/FileDescriptor;").await?;
+        let mut rust_file = FileDescriptor::file(jvm, fd).await?;
+
+        let mut rust_buf = vec![0; length as usize];
+        let read = jvm.blocking(rust_file.read(&mut rust_buf)).await.unwrap();
+        if read == 0 {
+            return Ok(-1);
+        }
+
+        rust_buf.truncate(read);
+        jvm.store_byte_array(&mut buf, offset as _, cast_vec(rust_buf)).await?;
+
+        Ok(read as _)
+    }
+
+    async fn read_byte(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
+        tracing::debug!("java.io.FileInputStream::read({:?})", &this);
+
+        let fd = jvm.get_field(&this, "fd", "Ljava/io/FileDescriptor;").await?;
+        let mut rust_file = FileDescriptor::file(jvm, fd).await?;
+
+        let mut rust_buf = [0; 1];
+        let read = jvm.blocking(rust_file.read(&mut rust_buf)).await.unwrap();
+        if read == 0 {
+            return Ok(-1);
+        }
+
+        Ok(rust_buf[0] as _)
+    }
+
     async fn close(_jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("stub java.io.FileInputStream::close({:?})", &this);
 
@@ -10,6 +10,7 @@ impl FileNotFoundException {
         RuntimeClassProto {
             name: "java/io/FileNotFoundException",
             parent_class: Some("java/io/IOException"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![],
             fields: vec![],
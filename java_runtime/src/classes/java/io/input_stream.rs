@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{Array, ClassInstanceRef, Jvm, Result};
 
 use crate::{RuntimeClassProto, RuntimeContext};
@@ -13,15 +14,16 @@ impl InputStream {
         RuntimeClassProto {
             name: "java/io/InputStream",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new_abstract("available", "()I", Default::default()),
-                JavaMethodProto::new_abstract("read", "([BII)I", Default::default()),
-                JavaMethodProto::new("read", "([B)I", Self::read, Default::default()),
-                JavaMethodProto::new_abstract("read", "()I", Default::default()),
-                JavaMethodProto::new_abstract("close", "()V", Default::default()),
-                JavaMethodProto::new("skip", "(J)J", Self::skip, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new_abstract("available", "()I", MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new_abstract("read", "([BII)I", MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "([B)I", Self::read, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new_abstract("read", "()I", MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new_abstract("close", "()V", MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("skip", "(J)J", Self::skip, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![],
         }
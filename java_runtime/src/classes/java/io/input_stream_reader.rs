@@ -7,6 +7,7 @@ use bytemuck::{cast_slice, cast_vec};
 use encoding_rs::{Decoder, EUC_KR, UTF_8};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{Array, ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{
@@ -24,11 +25,12 @@ impl InputStreamReader {
         RuntimeClassProto {
             name: "java/io/InputStreamReader",
             parent_class: Some("java/io/Reader"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/InputStream;)V", Self::init, Default::default()),
-                JavaMethodProto::new("read", "([CII)I", Self::read, Default::default()),
-                JavaMethodProto::new("close", "()V", Self::close, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/InputStream;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "([CII)I", Self::read, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("close", "()V", Self::close, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![
                 JavaFieldProto::new("in", "Ljava/io/InputStream;", Default::default()),
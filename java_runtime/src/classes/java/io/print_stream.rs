@@ -3,6 +3,7 @@ use alloc::{format, string::ToString, vec};
 use bytemuck::cast_vec;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{
@@ -21,18 +22,19 @@ impl PrintStream {
         RuntimeClassProto {
             name: "java/io/PrintStream",
             parent_class: Some("java/io/FilterOutputStream"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/OutputStream;)V", Self::init, Default::default()),
-                JavaMethodProto::new("println", "(Ljava/lang/Object;)V", Self::println_object, Default::default()),
-                JavaMethodProto::new("println", "(Ljava/lang/String;)V", Self::println_string, Default::default()),
-                JavaMethodProto::new("println", "(I)V", Self::println_int, Default::default()),
-                JavaMethodProto::new("println", "(J)V", Self::println_long, Default::default()),
-                JavaMethodProto::new("println", "(C)V", Self::println_char, Default::default()),
-                JavaMethodProto::new("println", "(B)V", Self::println_byte, Default::default()),
-                JavaMethodProto::new("println", "(S)V", Self::println_short, Default::default()),
-                JavaMethodProto::new("println", "(Z)V", Self::println_bool, Default::default()),
-                JavaMethodProto::new("println", "(D)V", Self::println_double, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/OutputStream;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(Ljava/lang/Object;)V", Self::println_object, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(Ljava/lang/String;)V", Self::println_string, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(I)V", Self::println_int, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(J)V", Self::println_long, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(C)V", Self::println_char, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(B)V", Self::println_byte, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(S)V", Self::println_short, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(Z)V", Self::println_bool, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(D)V", Self::println_double, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![],
         }
@@ -1,6 +1,7 @@
 use alloc::{format, vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{
@@ -16,11 +17,12 @@ impl PrintWriter {
         RuntimeClassProto {
             name: "java/io/PrintWriter",
             parent_class: Some("java/io/Writer"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/Writer;)V", Self::init, Default::default()),
-                JavaMethodProto::new("write", "([CII)I", Self::write, Default::default()),
-                JavaMethodProto::new("println", "(Ljava/lang/String;)V", Self::println, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/Writer;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("write", "([CII)I", Self::write, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("println", "(Ljava/lang/String;)V", Self::println, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![JavaFieldProto::new("out", "Ljava/io/Writer;", Default::default())],
         }
@@ -3,6 +3,7 @@ use alloc::vec;
 use bytemuck::cast_vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -21,19 +22,25 @@ impl RandomAccessFile {
         RuntimeClassProto {
             name: "java/io/RandomAccessFile",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/lang/String;Ljava/lang/String;)V", Self::init, Default::default()),
-                JavaMethodProto::new("<init>", "(Ljava/io/File;Ljava/lang/String;)V", Self::init_with_file, Default::default()),
-                JavaMethodProto::new("read", "([B)I", Self::read, Default::default()),
-                JavaMethodProto::new("read", "([BII)I", Self::read_offset_length, Default::default()),
-                JavaMethodProto::new("write", "([B)V", Self::write, Default::default()),
-                JavaMethodProto::new("write", "([BII)V", Self::write_offset_length, Default::default()),
-                JavaMethodProto::new("length", "()J", Self::length, Default::default()),
-                JavaMethodProto::new("getFilePointer", "()J", Self::get_file_pointer, Default::default()),
-                JavaMethodProto::new("seek", "(J)V", Self::seek, Default::default()),
-                JavaMethodProto::new("setLength", "(J)V", Self::set_length, Default::default()),
-                JavaMethodProto::new("close", "()V", Self::close, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;Ljava/lang/String;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljava/io/File;Ljava/lang/String;)V",
+                    Self::init_with_file,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("read", "([B)I", Self::read, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "([BII)I", Self::read_offset_length, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("write", "([B)V", Self::write, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("write", "([BII)V", Self::write_offset_length, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("length", "()J", Self::length, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getFilePointer", "()J", Self::get_file_pointer, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("seek", "(J)V", Self::seek, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setLength", "(J)V", Self::set_length, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("close", "()V", Self::close, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![JavaFieldProto::new("fd", "Ljava/io/FileDescriptor;", Default::default())],
         }
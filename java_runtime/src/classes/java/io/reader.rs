@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{RuntimeClassProto, RuntimeContext};
@@ -13,12 +14,13 @@ impl Reader {
         RuntimeClassProto {
             name: "java/io/Reader",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("read", "([C)I", Self::read, Default::default()),
-                JavaMethodProto::new_abstract("read", "([CII)I", Default::default()),
-                JavaMethodProto::new_abstract("close", "()V", Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new("read", "([C)I", Self::read, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new_abstract("read", "([CII)I", MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new_abstract("close", "()V", MethodAccessFlags::PUBLIC),
             ],
             fields: vec![],
         }
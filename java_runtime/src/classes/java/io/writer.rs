@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{Array, ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{RuntimeClassProto, RuntimeContext};
@@ -13,11 +14,12 @@ impl Writer {
         RuntimeClassProto {
             name: "java/io/Writer",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new_abstract("write", "([CII)I", Default::default()),
-                JavaMethodProto::new("write", "(Ljava/lang/String;)V", Self::write_string, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new_abstract("write", "([CII)I", MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("write", "(Ljava/lang/String;)V", Self::write_string, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![],
         }
@@ -1,9 +1,15 @@
+mod arithmetic_exception;
 mod array_index_out_of_bounds_exception;
 mod class;
+mod class_format_error;
 mod class_loader;
+mod class_not_found_exception;
 mod error;
 mod exception;
+mod illegal_access_error;
 mod illegal_argument_exception;
+mod illegal_monitor_state_exception;
+mod illegal_thread_state_exception;
 mod incompatible_class_change_error;
 mod index_out_of_bounds_exception;
 mod integer;
@@ -23,15 +29,19 @@ mod string;
 mod string_buffer;
 mod system;
 mod thread;
+mod thread_group;
 mod throwable;
 mod unsupported_operation_exception;
 
 pub use self::{
-    array_index_out_of_bounds_exception::ArrayIndexOutOfBoundsException, class::Class, class_loader::ClassLoader, error::Error, exception::Exception,
-    illegal_argument_exception::IllegalArgumentException, incompatible_class_change_error::IncompatibleClassChangeError,
-    index_out_of_bounds_exception::IndexOutOfBoundsException, integer::Integer, interrupted_exception::InterruptedException,
-    linkage_error::LinkageError, math::Math, no_class_def_found_error::NoClassDefFoundError, no_such_field_error::NoSuchFieldError,
-    no_such_method_error::NoSuchMethodError, null_pointer_exception::NullPointerException, object::Object, runnable::Runnable, runtime::Runtime,
-    runtime_exception::RuntimeException, security_exception::SecurityException, string::String, string_buffer::StringBuffer, system::System,
-    thread::Thread, throwable::Throwable, unsupported_operation_exception::UnsupportedOperationException,
+    arithmetic_exception::ArithmeticException, array_index_out_of_bounds_exception::ArrayIndexOutOfBoundsException, class::Class,
+    class_format_error::ClassFormatError, class_loader::ClassLoader, class_not_found_exception::ClassNotFoundException, error::Error,
+    exception::Exception, illegal_access_error::IllegalAccessError, illegal_argument_exception::IllegalArgumentException,
+    illegal_monitor_state_exception::IllegalMonitorStateException, illegal_thread_state_exception::IllegalThreadStateException,
+    incompatible_class_change_error::IncompatibleClassChangeError, index_out_of_bounds_exception::IndexOutOfBoundsException, integer::Integer,
+    interrupted_exception::InterruptedException, linkage_error::LinkageError, math::Math, no_class_def_found_error::NoClassDefFoundError,
+    no_such_field_error::NoSuchFieldError, no_such_method_error::NoSuchMethodError, null_pointer_exception::NullPointerException, object::Object,
+    runnable::Runnable, runtime::Runtime, runtime_exception::RuntimeException, security_exception::SecurityException, string::String,
+    string_buffer::StringBuffer, system::System, thread::Thread, thread_group::ThreadGroup, throwable::Throwable,
+    unsupported_operation_exception::UnsupportedOperationException,
 };
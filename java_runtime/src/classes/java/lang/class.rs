@@ -1,6 +1,7 @@
-use alloc::vec;
+use alloc::{borrow::ToOwned, format, vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{
     runtime::{JavaLangClass, JavaLangClassLoader, JavaLangString},
     ClassInstanceRef, Jvm, Result,
@@ -10,6 +11,7 @@ use crate::{
     classes::java::{
         io::InputStream,
         lang::{ClassLoader, String},
+        net::URL,
     },
     RuntimeClassProto, RuntimeContext,
 };
@@ -22,16 +24,46 @@ impl Class {
         RuntimeClassProto {
             name: "java/lang/Class",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("getName", "()Ljava/lang/String;", Self::get_name, Default::default()),
-                JavaMethodProto::new("isAssignableFrom", "(Ljava/lang/Class;)Z", Self::is_assignable_from, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PRIVATE),
+                JavaMethodProto::new("getName", "()Ljava/lang/String;", Self::get_name, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "isAssignableFrom",
+                    "(Ljava/lang/Class;)Z",
+                    Self::is_assignable_from,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "forName",
+                    "(Ljava/lang/String;)Ljava/lang/Class;",
+                    Self::for_name,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new(
+                    "forName",
+                    "(Ljava/lang/String;ZLjava/lang/ClassLoader;)Ljava/lang/Class;",
+                    Self::for_name_with_class_loader,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new(
+                    "getClassLoader",
+                    "()Ljava/lang/ClassLoader;",
+                    Self::get_class_loader,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getResource",
+                    "(Ljava/lang/String;)Ljava/net/URL;",
+                    Self::get_resource,
+                    MethodAccessFlags::PUBLIC,
+                ),
                 JavaMethodProto::new(
                     "getResourceAsStream",
                     "(Ljava/lang/String;)Ljava/io/InputStream;",
                     Self::get_resource_as_stream,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
             ],
             fields: vec![
@@ -67,25 +99,108 @@ impl Class {
         Ok(jvm.is_inherited_from(&*other_rust_class, &rust_class.name()).await)
     }
 
+    async fn for_name(jvm: &Jvm, _: &mut RuntimeContext, name: ClassInstanceRef<String>) -> Result<ClassInstanceRef<Self>> {
+        tracing::debug!("java.lang.Class::forName({:?})", &name);
+
+        // class is loaded with class loader of the caller
+        let class_loader = jvm.caller_class_loader().await?;
+
+        jvm.invoke_static(
+            "java/lang/Class",
+            "forName",
+            "(Ljava/lang/String;ZLjava/lang/ClassLoader;)Ljava/lang/Class;",
+            (name, true, class_loader),
+        )
+        .await
+    }
+
+    async fn for_name_with_class_loader(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        name: ClassInstanceRef<String>,
+        initialize: bool,
+        class_loader: ClassInstanceRef<ClassLoader>,
+    ) -> Result<ClassInstanceRef<Self>> {
+        tracing::debug!("java.lang.Class::forName({:?}, {:?}, {:?})", &name, initialize, &class_loader);
+
+        // classes are always initialized when they're loaded, so we can't honor initialize == false
+        if !class_loader.is_null() {
+            return jvm
+                .invoke_virtual(&class_loader, "loadClass", "(Ljava/lang/String;)Ljava/lang/Class;", (name,))
+                .await;
+        }
+
+        // null class loader means bootstrap class loader
+        let name = JavaLangString::to_rust_string(jvm, &name).await?;
+        let class = jvm.find_bootstrap_class(&name.replace('.', "/")).await?;
+
+        match class {
+            Some(x) => Ok(x.into()),
+            None => Err(jvm.exception("java/lang/ClassNotFoundException", &name).await),
+        }
+    }
+
+    async fn get_class_loader(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<ClassLoader>> {
+        tracing::debug!("java.lang.Class::getClassLoader({:?})", &this);
+
+        jvm.get_field(&this, "classLoader", "Ljava/lang/ClassLoader;").await
+    }
+
+    async fn get_resource(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        name: ClassInstanceRef<String>,
+    ) -> Result<ClassInstanceRef<URL>> {
+        tracing::debug!("java.lang.Class::getResource({:?}, {:?})", &this, &name);
+
+        let name = Self::resolve_resource_name(jvm, &this, name).await?;
+        let class_loader = Self::class_loader_or_system(jvm, &this).await?;
+
+        jvm.invoke_virtual(&class_loader, "getResource", "(Ljava/lang/String;)Ljava/net/URL;", (name,))
+            .await
+    }
+
     async fn get_resource_as_stream(
         jvm: &Jvm,
-        _context: &mut RuntimeContext,
+        _: &mut RuntimeContext,
         this: ClassInstanceRef<Self>,
         name: ClassInstanceRef<String>,
     ) -> Result<ClassInstanceRef<InputStream>> {
         tracing::debug!("java.lang.Class::getResourceAsStream({:?}, {:?})", &this, &name);
 
-        let class_loader: ClassInstanceRef<ClassLoader> = jvm.get_field(&this, "classLoader", "Ljava/lang/ClassLoader;").await?;
+        let name = Self::resolve_resource_name(jvm, &this, name).await?;
+        let class_loader = Self::class_loader_or_system(jvm, &this).await?;
+
+        jvm.invoke_virtual(&class_loader, "getResourceAsStream", "(Ljava/lang/String;)Ljava/io/InputStream;", (name,))
+            .await
+    }
 
-        let class_loader = if class_loader.is_null() {
-            // TODO ClassLoader.getSystemResourceAsStream?
-            JavaLangClassLoader::get_system_class_loader(jvm).await?
+    // relative names are resolved against package of the class, and names starting with '/' are absolute
+    async fn resolve_resource_name(jvm: &Jvm, this: &ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> Result<ClassInstanceRef<String>> {
+        let name = JavaLangString::to_rust_string(jvm, &name).await?;
+
+        let resolved = if let Some(x) = name.strip_prefix('/') {
+            x.to_owned()
         } else {
-            class_loader.into()
+            let class_name = JavaLangClass::to_rust_class(jvm, this).await?.name();
+            match class_name.rfind('/') {
+                Some(x) => format!("{}/{}", &class_name[..x], name),
+                None => name,
+            }
         };
 
-        jvm.invoke_virtual(&class_loader, "getResourceAsStream", "(Ljava/lang/String;)Ljava/io/InputStream;", (name,))
-            .await
+        Ok(JavaLangString::from_rust_string(jvm, &resolved).await?.into())
+    }
+
+    async fn class_loader_or_system(jvm: &Jvm, this: &ClassInstanceRef<Self>) -> Result<ClassInstanceRef<ClassLoader>> {
+        let class_loader: ClassInstanceRef<ClassLoader> = jvm.get_field(this, "classLoader", "Ljava/lang/ClassLoader;").await?;
+
+        if class_loader.is_null() {
+            Ok(JavaLangClassLoader::get_system_class_loader(jvm).await?.into())
+        } else {
+            Ok(class_loader)
+        }
     }
 }
 
@@ -0,0 +1,44 @@
+use alloc::vec;
+
+use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
+use jvm::{ClassInstanceRef, Jvm, Result};
+
+use crate::{classes::java::lang::String, RuntimeClassProto, RuntimeContext};
+
+// class java.lang.ClassFormatError
+pub struct ClassFormatError;
+
+impl ClassFormatError {
+    pub fn as_proto() -> RuntimeClassProto {
+        RuntimeClassProto {
+            name: "java/lang/ClassFormatError",
+            parent_class: Some("java/lang/LinkageError"),
+            is_interface: false,
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_message, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("java.lang.ClassFormatError::<init>({:?})", &this);
+
+        let _: () = jvm.invoke_special(&this, "java/lang/LinkageError", "<init>", "()V", ()).await?;
+
+        Ok(())
+    }
+
+    async fn init_with_message(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, message: ClassInstanceRef<String>) -> Result<()> {
+        tracing::debug!("java.lang.ClassFormatError::<init>({:?}, {:?})", &this, &message);
+
+        let _: () = jvm
+            .invoke_special(&this, "java/lang/LinkageError", "<init>", "(Ljava/lang/String;)V", (message,))
+            .await?;
+
+        Ok(())
+    }
+}
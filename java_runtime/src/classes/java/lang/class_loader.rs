@@ -1,15 +1,19 @@
-use alloc::{format, vec, vec::Vec};
+use alloc::{format, string::String as RustString, vec, vec::Vec};
 
 use bytemuck::cast_slice;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
 use java_constants::{FieldAccessFlags, MethodAccessFlags};
-use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, Jvm, Result};
+use jvm::{
+    runtime::{JavaLangClassLoader, JavaLangString},
+    Array, ClassInstanceRef, Jvm, Result,
+};
 
 use crate::{
     classes::java::{
-        lang::{Class, String},
+        lang::{Class, Object, String},
         net::URL,
+        util::{Enumeration, Vector},
     },
     RuntimeClassProto, RuntimeContext,
 };
@@ -22,46 +26,76 @@ impl ClassLoader {
         RuntimeClassProto {
             name: "java/lang/ClassLoader",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/lang/ClassLoader;)V", Self::init, Default::default()),
-                JavaMethodProto::new("loadClass", "(Ljava/lang/String;)Ljava/lang/Class;", Self::load_class, Default::default()),
-                JavaMethodProto::new("findClass", "(Ljava/lang/String;)Ljava/lang/Class;", Self::find_class, Default::default()),
+                JavaMethodProto::new("<clinit>", "()V", Self::cl_init, MethodAccessFlags::STATIC),
+                JavaMethodProto::new("<init>", "(Ljava/lang/ClassLoader;)V", Self::init, MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new(
+                    "loadClass",
+                    "(Ljava/lang/String;)Ljava/lang/Class;",
+                    Self::load_class,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "findClass",
+                    "(Ljava/lang/String;)Ljava/lang/Class;",
+                    Self::find_class,
+                    MethodAccessFlags::PROTECTED,
+                ),
                 JavaMethodProto::new(
                     "findLoadedClass",
                     "(Ljava/lang/String;)Ljava/lang/Class;",
                     Self::find_loaded_class,
-                    Default::default(),
+                    MethodAccessFlags::PROTECTED,
                 ),
                 JavaMethodProto::new(
                     "getSystemClassLoader",
                     "()Ljava/lang/ClassLoader;",
                     Self::get_system_class_loader,
-                    MethodAccessFlags::STATIC,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
                 ),
                 JavaMethodProto::new(
                     "getResource",
                     "(Ljava/lang/String;)Ljava/net/URL;",
                     Self::get_resource,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getResources",
+                    "(Ljava/lang/String;)Ljava/util/Enumeration;",
+                    Self::get_resources,
+                    MethodAccessFlags::PUBLIC,
                 ),
                 JavaMethodProto::new(
                     "getResourceAsStream",
                     "(Ljava/lang/String;)Ljava/io/InputStream;",
                     Self::get_resource_as_stream,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getSystemResource",
+                    "(Ljava/lang/String;)Ljava/net/URL;",
+                    Self::get_system_resource,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
                 ),
                 JavaMethodProto::new(
                     "findResource",
                     "(Ljava/lang/String;)Ljava/net/URL;",
                     Self::find_resource,
-                    Default::default(),
+                    MethodAccessFlags::PROTECTED,
+                ),
+                JavaMethodProto::new(
+                    "findResources",
+                    "(Ljava/lang/String;)Ljava/util/Enumeration;",
+                    Self::find_resources,
+                    MethodAccessFlags::PROTECTED,
                 ),
                 JavaMethodProto::new(
                     "defineClass",
                     "(Ljava/lang/String;[BII)Ljava/lang/Class;",
                     Self::define_class,
-                    Default::default(),
+                    MethodAccessFlags::PROTECTED,
                 ),
             ],
             fields: vec![
@@ -71,6 +105,16 @@ impl ClassLoader {
         }
     }
 
+    async fn cl_init(jvm: &Jvm, _: &mut RuntimeContext) -> Result<()> {
+        tracing::debug!("java.lang.ClassLoader::<clinit>()");
+
+        // class loaders throw ClassNotFoundException while looking up classes.
+        // load it upfront, as resolving it through a class loader which can't find it would never end
+        jvm.resolve_class("java/lang/ClassNotFoundException").await?;
+
+        Ok(())
+    }
+
     async fn init(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, parent: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("java.lang.ClassLoader::<init>({:?}, {:?})", &this, parent);
 
@@ -89,21 +133,13 @@ impl ClassLoader {
             .await?;
 
         if system_class_loader.is_null() {
-            let class_path: ClassInstanceRef<String> = jvm
-                .invoke_static(
-                    "java/lang/System",
-                    "getProperty",
-                    "(Ljava/lang/String;)Ljava/lang/String;",
-                    (JavaLangString::from_rust_string(jvm, "java.class.path").await?,),
-                )
-                .await?;
+            let class_path = Self::system_property(jvm, "java.class.path").await?;
 
-            let url_array = if !class_path.is_null() {
-                let class_path = JavaLangString::to_rust_string(jvm, &class_path).await?;
+            let url_array = if let Some(class_path) = class_path {
+                let separator = Self::system_property(jvm, "path.separator").await?.unwrap_or_else(|| ":".into());
 
                 let mut urls = Vec::new();
-                for path in class_path.split(':') {
-                    // TODO File.pathSeparator
+                for path in class_path.split(separator.as_str()) {
                     let path = JavaLangString::from_rust_string(jvm, &format!("file:{}", path)).await?;
                     let url = jvm.new_class("java/net/URL", "(Ljava/lang/String;)V", (path,)).await?;
 
@@ -144,6 +180,10 @@ impl ClassLoader {
     ) -> Result<ClassInstanceRef<Class>> {
         tracing::debug!("java.lang.ClassLoader::loadClass({:?}, {:?})", &this, name);
 
+        // java code passes binary names like `java.lang.String`, while jvm uses internal names
+        let name_str = JavaLangString::to_rust_string(jvm, &name).await?.replace('.', "/");
+        let name = JavaLangString::from_rust_string(jvm, &name_str).await?;
+
         let class: ClassInstanceRef<Class> = jvm
             .invoke_virtual(&this, "findLoadedClass", "(Ljava/lang/String;)Ljava/lang/Class;", (name.clone(),))
             .await?;
@@ -152,8 +192,6 @@ impl ClassLoader {
             return Ok(class);
         }
 
-        let name_str = JavaLangString::to_rust_string(jvm, &name).await?;
-
         if let Some(element_type_name) = name_str.strip_prefix('[') {
             // TODO do we need another class loader for array?
             let class = runtime.define_array_class(jvm, element_type_name).await?;
@@ -162,36 +200,40 @@ impl ClassLoader {
             return Ok(java_class.into());
         }
 
+        // class loaders without parent delegate to bootstrap class loader
         let parent: ClassInstanceRef<Self> = jvm.get_field(&this, "parent", "Ljava/lang/ClassLoader;").await?;
-        let class: ClassInstanceRef<Class> = if !parent.is_null() {
-            jvm.invoke_virtual(&parent, "loadClass", "(Ljava/lang/String;)Ljava/lang/Class;", (name.clone(),))
-                .await?
+        let class = if parent.is_null() {
+            jvm.find_bootstrap_class(&name_str).await?
         } else {
-            None.into()
+            JavaLangClassLoader::load_class(jvm, &parent, &name_str).await?
         };
 
-        if !class.is_null() {
-            return Ok(class);
+        if let Some(class) = class {
+            return Ok(class.into());
         }
 
-        let class = jvm
+        let class: ClassInstanceRef<Class> = jvm
             .invoke_virtual(&this, "findClass", "(Ljava/lang/String;)Ljava/lang/Class;", (name,))
             .await?;
 
+        if class.is_null() {
+            return Err(jvm.exception("java/lang/ClassNotFoundException", &name_str.replace('/', ".")).await);
+        }
+
         Ok(class)
     }
 
     async fn find_class(
-        _: &Jvm,
+        jvm: &Jvm,
         _: &mut RuntimeContext,
         this: ClassInstanceRef<Self>,
         name: ClassInstanceRef<String>,
     ) -> Result<ClassInstanceRef<Class>> {
         tracing::debug!("java.lang.ClassLoader::findClass({:?}, {:?})", &this, name);
 
-        // TODO raise ClassNotFoundException
+        let name = JavaLangString::to_rust_string(jvm, &name).await?;
 
-        Ok(None.into())
+        Err(jvm.exception("java/lang/ClassNotFoundException", &name.replace('/', ".")).await)
     }
 
     async fn find_loaded_class(
@@ -202,14 +244,9 @@ impl ClassLoader {
     ) -> Result<ClassInstanceRef<Class>> {
         tracing::debug!("java.lang.ClassLoader::findLoadedClass({:?}, {:?})", &this, name);
 
-        let rust_name = JavaLangString::to_rust_string(jvm, &name).await?;
-        if !jvm.has_class(&rust_name).await {
-            return Ok(None.into());
-        }
-
-        let class = jvm.resolve_class(&rust_name).await?;
+        let rust_name = JavaLangString::to_rust_string(jvm, &name).await?.replace('.', "/");
 
-        Ok(class.java_class(jvm).await?.into())
+        Ok(jvm.find_loaded_class(&this, &rust_name).await?.into())
     }
 
     async fn get_resource(
@@ -240,6 +277,41 @@ impl ClassLoader {
         Ok(result)
     }
 
+    async fn get_resources(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        name: ClassInstanceRef<String>,
+    ) -> Result<ClassInstanceRef<Enumeration>> {
+        tracing::debug!("java.lang.ClassLoader::getResources({:?}, {:?})", &this, &name);
+
+        let resources = jvm.new_class("java/util/Vector", "()V", ()).await?.into();
+
+        let parent: ClassInstanceRef<Self> = jvm.get_field(&this, "parent", "Ljava/lang/ClassLoader;").await?;
+        if !parent.is_null() {
+            let parent_resources = jvm
+                .invoke_virtual(&parent, "getResources", "(Ljava/lang/String;)Ljava/util/Enumeration;", (name.clone(),))
+                .await?;
+            Self::append_all(jvm, &resources, parent_resources).await?;
+        }
+
+        let own_resources = jvm
+            .invoke_virtual(&this, "findResources", "(Ljava/lang/String;)Ljava/util/Enumeration;", (name,))
+            .await?;
+        Self::append_all(jvm, &resources, own_resources).await?;
+
+        jvm.invoke_virtual(&resources, "elements", "()Ljava/util/Enumeration;", ()).await
+    }
+
+    async fn get_system_resource(jvm: &Jvm, _: &mut RuntimeContext, name: ClassInstanceRef<String>) -> Result<ClassInstanceRef<URL>> {
+        tracing::debug!("java.lang.ClassLoader::getSystemResource({:?})", &name);
+
+        let system_class_loader = JavaLangClassLoader::get_system_class_loader(jvm).await?;
+
+        jvm.invoke_virtual(&system_class_loader, "getResource", "(Ljava/lang/String;)Ljava/net/URL;", (name,))
+            .await
+    }
+
     async fn get_resource_as_stream(
         jvm: &Jvm,
         _: &mut RuntimeContext,
@@ -272,6 +344,19 @@ impl ClassLoader {
         Ok(None.into())
     }
 
+    async fn find_resources(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        _: ClassInstanceRef<String>,
+    ) -> Result<ClassInstanceRef<Enumeration>> {
+        tracing::debug!("java.lang.ClassLoader::findResources({:?})", &this);
+
+        let resources = jvm.new_class("java/util/Vector", "()V", ()).await?;
+
+        jvm.invoke_virtual(&resources, "elements", "()Ljava/util/Enumeration;", ()).await
+    }
+
     async fn define_class(
         jvm: &Jvm,
         runtime: &mut RuntimeContext,
@@ -297,4 +382,35 @@ impl ClassLoader {
 
         Ok(java_class.into())
     }
+
+    async fn system_property(jvm: &Jvm, key: &str) -> Result<Option<RustString>> {
+        let value: ClassInstanceRef<String> = jvm
+            .invoke_static(
+                "java/lang/System",
+                "getProperty",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                (JavaLangString::from_rust_string(jvm, key).await?,),
+            )
+            .await?;
+
+        if value.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(JavaLangString::to_rust_string(jvm, &value).await?))
+    }
+
+    async fn append_all(jvm: &Jvm, vector: &ClassInstanceRef<Vector>, enumeration: ClassInstanceRef<Enumeration>) -> Result<()> {
+        loop {
+            let has_more: bool = jvm.invoke_virtual(&enumeration, "hasMoreElements", "()Z", ()).await?;
+            if !has_more {
+                break;
+            }
+
+            let element: ClassInstanceRef<Object> = jvm.invoke_virtual(&enumeration, "nextElement", "()Ljava/lang/Object;", ()).await?;
+            let _: () = jvm.invoke_virtual(vector, "addElement", "(Ljava/lang/Object;)V", (element,)).await?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,44 @@
+use alloc::vec;
+
+use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
+use jvm::{ClassInstanceRef, Jvm, Result};
+
+use crate::{classes::java::lang::String, RuntimeClassProto, RuntimeContext};
+
+// class java.lang.ClassNotFoundException
+pub struct ClassNotFoundException;
+
+impl ClassNotFoundException {
+    pub fn as_proto() -> RuntimeClassProto {
+        RuntimeClassProto {
+            name: "java/lang/ClassNotFoundException",
+            parent_class: Some("java/lang/Exception"),
+            is_interface: false,
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_message, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("java.lang.ClassNotFoundException::<init>({:?})", &this);
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Exception", "<init>", "()V", ()).await?;
+
+        Ok(())
+    }
+
+    async fn init_with_message(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, message: ClassInstanceRef<String>) -> Result<()> {
+        tracing::debug!("java.lang.ClassNotFoundException::<init>({:?}, {:?})", &this, &message);
+
+        let _: () = jvm
+            .invoke_special(&this, "java/lang/Exception", "<init>", "(Ljava/lang/String;)V", (message,))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -14,11 +14,12 @@ impl Math {
         RuntimeClassProto {
             name: "java/lang/Math",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("abs", "(I)I", Self::abs, MethodAccessFlags::STATIC),
-                JavaMethodProto::new("max", "(II)I", Self::max, MethodAccessFlags::STATIC),
-                JavaMethodProto::new("min", "(II)I", Self::min, MethodAccessFlags::STATIC),
+                JavaMethodProto::new("abs", "(I)I", Self::abs, MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC),
+                JavaMethodProto::new("max", "(II)I", Self::max, MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC),
+                JavaMethodProto::new("min", "(II)I", Self::min, MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC),
             ],
             fields: vec![],
         }
@@ -1,49 +1,31 @@
 use core::time::Duration;
 
-use alloc::{boxed::Box, format, sync::Arc, vec};
+use alloc::{boxed::Box, format};
 
-use dyn_clone::clone_box;
-use event_listener::Event;
-use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use futures_lite::future;
+use java_class_proto::java_class;
 use java_constants::MethodAccessFlags;
-use jvm::{runtime::JavaLangString, Array, ClassInstance, ClassInstanceRef, Jvm, Result};
+use jvm::{runtime::JavaLangString, ClassInstance, ClassInstanceRef, Jvm, Result};
 
-use crate::{classes::java::lang::String, Runtime, RuntimeClassProto, RuntimeContext, SpawnCallback};
+use crate::{
+    classes::java::lang::{Class, String, Thread},
+    RuntimeContext,
+};
 
 // class java.lang.Object
 pub struct Object;
 
+#[java_class("java/lang/Object")]
 impl Object {
-    pub fn as_proto() -> RuntimeClassProto {
-        RuntimeClassProto {
-            name: "java/lang/Object",
-            parent_class: None,
-            interfaces: vec![],
-            methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("getClass", "()Ljava/lang/Class;", Self::get_class, Default::default()),
-                JavaMethodProto::new("hashCode", "()I", Self::hash_code, Default::default()),
-                JavaMethodProto::new("equals", "(Ljava/lang/Object;)Z", Self::equals, Default::default()),
-                JavaMethodProto::new("clone", "()Ljava/lang/Object;", Self::java_clone, MethodAccessFlags::NATIVE),
-                JavaMethodProto::new("toString", "()Ljava/lang/String;", Self::to_string, Default::default()),
-                JavaMethodProto::new("notify", "()V", Self::notify, MethodAccessFlags::NATIVE),
-                JavaMethodProto::new("notifyAll", "()V", Self::notify_all, MethodAccessFlags::NATIVE),
-                JavaMethodProto::new("wait", "(J)V", Self::wait_long, Default::default()),
-                JavaMethodProto::new("wait", "(JI)V", Self::wait_long_int, Default::default()),
-                JavaMethodProto::new("wait", "()V", Self::wait, Default::default()),
-                JavaMethodProto::new("finalize", "()V", Self::finalize, Default::default()),
-            ],
-            fields: vec![JavaFieldProto::new("waitEvent", "[B", Default::default())],
-        }
-    }
-
-    async fn init(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
+    #[java_method]
+    pub async fn init(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("java.lang.Object::<init>({:?})", &this);
 
         Ok(())
     }
 
-    async fn get_class(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Self>> {
+    #[java_method(descriptor = "()Ljava/lang/Class;")]
+    pub async fn get_class(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Class>> {
         tracing::debug!("java.lang.Object::getClass({:?})", &this);
 
         // TODO can we get class directly?
@@ -55,7 +37,8 @@ impl Object {
         Ok(class.into())
     }
 
-    async fn hash_code(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
+    #[java_method]
+    pub async fn hash_code(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
         tracing::debug!("java.lang.Object::hashCode({:?})", &this);
 
         let rust_this: Box<dyn ClassInstance> = this.into();
@@ -63,7 +46,8 @@ impl Object {
         Ok(rust_this.hash_code())
     }
 
-    async fn equals(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, other: ClassInstanceRef<Self>) -> Result<bool> {
+    #[java_method]
+    pub async fn equals(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, other: ClassInstanceRef<Self>) -> Result<bool> {
         tracing::debug!("java.lang.Object::equals({:?}, {:?})", &this, &other);
 
         if other.is_null() {
@@ -76,13 +60,15 @@ impl Object {
         rust_this.equals(&*rust_other)
     }
 
+    #[java_method(name = "clone", flags = MethodAccessFlags::PROTECTED | MethodAccessFlags::NATIVE)]
     async fn java_clone(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Self>> {
         tracing::warn!("stub java.lang.Object::clone({:?})", &this);
 
         Ok(None.into())
     }
 
-    async fn to_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<String>> {
+    #[java_method]
+    pub async fn to_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<String>> {
         tracing::debug!("java.lang.Object::toString({:?})", &this);
 
         let class = jvm.invoke_virtual(&this, "getClass", "()Ljava/lang/Class;", ()).await?;
@@ -96,35 +82,22 @@ impl Object {
         Ok(JavaLangString::from_rust_string(jvm, &result).await?.into())
     }
 
-    async fn notify(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
+    #[java_method(flags = MethodAccessFlags::NATIVE)]
+    pub async fn notify(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("java.lang.Object::notify({:?})", &this);
 
-        let wait_event: ClassInstanceRef<Array<i8>> = jvm.get_field(&this, "waitEvent", "[B").await?;
-        if wait_event.is_null() {
-            return Ok(());
-        }
-
-        let wait_event: Arc<Event> = jvm.get_rust_object_field(&this, "waitEvent").await?;
-        wait_event.notify(1);
-
-        Ok(())
+        jvm.monitor_notify(&this).await
     }
 
-    async fn notify_all(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
+    #[java_method(flags = MethodAccessFlags::NATIVE)]
+    pub async fn notify_all(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("java.lang.Object::notifyAll({:?})", &this);
 
-        let wait_event: ClassInstanceRef<Array<i8>> = jvm.get_field(&this, "waitEvent", "[B").await?;
-        if wait_event.is_null() {
-            return Ok(());
-        }
-
-        let wait_event: Arc<Event> = jvm.get_rust_object_field(&this, "waitEvent").await?;
-        wait_event.notify(usize::MAX);
-
-        Ok(())
+        jvm.monitor_notify_all(&this).await
     }
 
-    async fn wait_long(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, millis: i64) -> Result<()> {
+    #[java_method(name = "wait")]
+    pub async fn wait_long(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, millis: i64) -> Result<()> {
         tracing::debug!("java.lang.Object::wait({:?}, {:?})", &this, millis);
 
         let _: () = jvm.invoke_virtual(&this, "wait", "(JI)V", (millis, 0)).await?;
@@ -132,46 +105,34 @@ impl Object {
         Ok(())
     }
 
-    async fn wait_long_int(jvm: &Jvm, context: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, millis: i64, nanos: i32) -> Result<()> {
+    #[java_method(name = "wait")]
+    pub async fn wait_long_int(jvm: &Jvm, context: &mut RuntimeContext, this: ClassInstanceRef<Self>, millis: i64, nanos: i32) -> Result<()> {
         tracing::debug!("java.lang.Object::wait({:?}, {:?}, {:?})", &this, millis, nanos);
 
-        let wait_event = Arc::new(Event::new());
-        jvm.put_rust_object_field(&mut this, "waitEvent", wait_event.clone()).await?;
-
-        struct Waiter {
-            timeout: i64,
-            wait_event: Arc<Event>,
-            context: Box<dyn Runtime>,
+        if millis < 0 || !(0..=999999).contains(&nanos) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "timeout value is out of range").await);
         }
 
-        #[async_trait::async_trait]
-        impl SpawnCallback for Waiter {
-            async fn call(&self) -> Result<()> {
-                self.context.sleep(Duration::from_millis(self.timeout as _)).await;
+        // like openjdk, round up to next millisecond if nanos given
+        let timeout = if nanos > 0 && millis < i64::MAX { millis + 1 } else { millis };
 
-                self.wait_event.notify(1); // TODO this would notify other waiter
-                Ok(())
-            }
-        }
+        let result = if timeout != 0 {
+            jvm.monitor_wait(&this, Thread::interruptible(jvm, context.sleep(Duration::from_millis(timeout as _))))
+                .await?
+        } else {
+            jvm.monitor_wait(&this, Thread::interruptible(jvm, future::pending::<()>())).await?
+        };
 
-        let timeout = millis; // TODO nanos
-        if timeout != 0 {
-            context.spawn(
-                jvm,
-                Box::new(Waiter {
-                    timeout,
-                    wait_event: wait_event.clone(),
-                    context: clone_box(context),
-                }),
-            );
+        // `None` if notified, otherwise timed out or interrupted
+        if let Some(x) = result {
+            x?;
         }
 
-        wait_event.listen().await;
-
         Ok(())
     }
 
-    async fn wait(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
+    #[java_method]
+    pub async fn wait(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("java.lang.Object::wait({:?})", &this);
 
         let _: () = jvm.invoke_virtual(&this, "wait", "(JI)V", (0i64, 0)).await?;
@@ -179,6 +140,7 @@ impl Object {
         Ok(())
     }
 
+    #[java_method(flags = MethodAccessFlags::PROTECTED)]
     async fn finalize(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
         tracing::warn!("stub java.lang.Object::finalize({:?})", &this);
 
@@ -195,9 +157,14 @@ mod test {
 
     use alloc::{boxed::Box, collections::btree_map::BTreeMap, sync::Arc};
 
-    use jvm::{ClassInstanceRef, Jvm, Result};
+    use jvm::{ClassInstanceRef, JavaError, Jvm, Result};
 
-    use crate::{classes::java::lang::Object, runtime::test::TestRuntime, test::create_test_jvm, Runtime, SpawnCallback};
+    use crate::{
+        classes::java::lang::Object,
+        runtime::test::TestRuntime,
+        test::{create_test_jvm, test_jvm},
+        Runtime, SpawnCallback,
+    };
 
     #[tokio::test]
     async fn test_wait() -> Result<()> {
@@ -218,11 +185,13 @@ mod test {
         #[async_trait::async_trait]
         impl SpawnCallback for Notifier {
             async fn call(&self) -> Result<()> {
-                self.jvm.attach_thread().await?;
+                self.jvm.attach_thread("notifier", false).await?;
 
                 self.runtime.sleep(Duration::from_millis(100)).await;
+                self.jvm.monitor_enter(&self.target).await?;
                 self.notified.store(true, Ordering::Relaxed);
                 let _: () = self.jvm.invoke_virtual(&self.target, "notify", "()V", ()).await?;
+                self.jvm.monitor_exit(&self.target).await?;
 
                 self.jvm.detach_thread().await?;
 
@@ -240,9 +209,11 @@ mod test {
             }),
         );
 
+        jvm.monitor_enter(&object).await?;
         assert!(!notified.load(Ordering::Relaxed));
         let _: () = jvm.invoke_virtual(&object, "wait", "()V", ()).await?;
         assert!(notified.load(Ordering::Relaxed));
+        jvm.monitor_exit(&object).await?;
 
         Ok(())
     }
@@ -265,11 +236,13 @@ mod test {
         #[async_trait::async_trait]
         impl SpawnCallback for Notifier {
             async fn call(&self) -> Result<()> {
-                self.jvm.attach_thread().await?;
+                self.jvm.attach_thread("notifier", false).await?;
 
                 self.runtime.sleep(Duration::from_millis(1000)).await;
+                self.jvm.monitor_enter(&self.target).await?;
                 self.notified.store(true, Ordering::Relaxed);
                 let _: () = self.jvm.invoke_virtual(&self.target, "notify", "()V", ()).await?;
+                self.jvm.monitor_exit(&self.target).await?;
 
                 self.jvm.detach_thread().await?;
 
@@ -287,9 +260,29 @@ mod test {
             }),
         );
 
+        jvm.monitor_enter(&object).await?;
         assert!(!notified.load(Ordering::Relaxed));
         let _: () = jvm.invoke_virtual(&object, "wait", "(J)V", (100i64,)).await?;
         assert!(!notified.load(Ordering::Relaxed));
+        jvm.monitor_exit(&object).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wait_without_monitor() -> Result<()> {
+        let jvm = test_jvm().await?;
+
+        let object = jvm.new_class("java/lang/Object", "()V", ()).await?;
+
+        let result: Result<()> = jvm.invoke_virtual(&object, "wait", "()V", ()).await;
+        let Err(JavaError::JavaException(exception)) = result else {
+            panic!("wait without monitor should throw");
+        };
+        assert!(jvm.is_instance(&*exception, "java/lang/IllegalMonitorStateException").await?);
+
+        let result: Result<()> = jvm.invoke_virtual(&object, "notify", "()V", ()).await;
+        assert!(result.is_err());
 
         Ok(())
     }
@@ -1,21 +1,13 @@
-use alloc::vec;
+use java_class_proto::java_class;
+use jvm::{ClassInstanceRef, Jvm, Result};
 
-use java_class_proto::JavaMethodProto;
-
-use crate::RuntimeClassProto;
+use crate::RuntimeContext;
 
 // interface java.lang.Runnable
 pub struct Runnable;
 
+#[java_class("java/lang/Runnable", interface)]
 impl Runnable {
-    // TODO Create JavaInterfaceProto
-    pub fn as_proto() -> RuntimeClassProto {
-        RuntimeClassProto {
-            name: "java/lang/Runnable",
-            parent_class: None,
-            interfaces: vec![],
-            methods: vec![JavaMethodProto::new_abstract("run", "()V", Default::default())],
-            fields: vec![],
-        }
-    }
+    #[java_method]
+    async fn run(jvm: &Jvm, context: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()>;
 }
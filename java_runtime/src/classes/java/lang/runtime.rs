@@ -1,10 +1,14 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
-use java_class_proto::JavaMethodProto;
-use java_constants::MethodAccessFlags;
+use futures_lite::future;
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::{FieldAccessFlags, MethodAccessFlags};
 use jvm::{ClassInstanceRef, Jvm, Result};
 
-use crate::{RuntimeClassProto, RuntimeContext};
+use crate::{
+    classes::java::{lang::Thread, util::Vector},
+    RuntimeClassProto, RuntimeContext,
+};
 
 // class java.lang.Runtime
 pub struct Runtime;
@@ -14,15 +18,37 @@ impl Runtime {
         RuntimeClassProto {
             name: "java/lang/Runtime",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("getRuntime", "()Ljava/lang/Runtime;", Self::get_runtime, MethodAccessFlags::STATIC),
-                JavaMethodProto::new("totalMemory", "()J", Self::total_memory, Default::default()),
-                JavaMethodProto::new("freeMemory", "()J", Self::free_memory, Default::default()),
-                JavaMethodProto::new("gc", "()V", Self::gc, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "getRuntime",
+                    "()Ljava/lang/Runtime;",
+                    Self::get_runtime,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new("totalMemory", "()J", Self::total_memory, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("freeMemory", "()J", Self::free_memory, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("gc", "()V", Self::gc, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("exit", "(I)V", Self::exit, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("halt", "(I)V", Self::halt, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "addShutdownHook",
+                    "(Ljava/lang/Thread;)V",
+                    Self::add_shutdown_hook,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "removeShutdownHook",
+                    "(Ljava/lang/Thread;)Z",
+                    Self::remove_shutdown_hook,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                // rustjava internal
+                JavaMethodProto::new("runShutdownHooks", "()V", Self::run_shutdown_hooks, MethodAccessFlags::STATIC),
             ],
-            fields: vec![],
+            fields: vec![JavaFieldProto::new("shutdownHooks", "Ljava/util/Vector;", FieldAccessFlags::STATIC)],
         }
     }
 
@@ -52,8 +78,94 @@ impl Runtime {
         Ok(0x100000) // TODO: hardcoded
     }
 
-    async fn gc(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Runtime>) -> Result<()> {
-        tracing::warn!("stub java.lang.Runtime::gc({:?})", &this);
+    // objects are reference counted, so we only have to collect unreachable class loaders and their classes
+    async fn gc(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Runtime>) -> Result<()> {
+        tracing::debug!("java.lang.Runtime::gc({:?})", &this);
+
+        jvm.unload_classes().await?;
+
+        Ok(())
+    }
+
+    async fn exit(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Runtime>, status: i32) -> Result<()> {
+        tracing::debug!("java.lang.Runtime::exit({:?}, {:?})", &this, status);
+
+        let _: () = jvm.invoke_static("java/lang/Runtime", "runShutdownHooks", "()V", ()).await?;
+        jvm.exit(status).await;
+
+        // exit never returns, jvm will be torn down by the host
+        future::pending().await
+    }
+
+    async fn halt(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Runtime>, status: i32) -> Result<()> {
+        tracing::debug!("java.lang.Runtime::halt({:?}, {:?})", &this, status);
+
+        jvm.exit(status).await;
+
+        future::pending().await
+    }
+
+    async fn add_shutdown_hook(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Runtime>, hook: ClassInstanceRef<Thread>) -> Result<()> {
+        tracing::debug!("java.lang.Runtime::addShutdownHook({:?}, {:?})", &this, &hook);
+
+        let mut hooks: ClassInstanceRef<Vector> = jvm.get_static_field("java/lang/Runtime", "shutdownHooks", "Ljava/util/Vector;").await?;
+        if hooks.is_null() {
+            hooks = jvm.new_class("java/util/Vector", "()V", ()).await?.into();
+            jvm.put_static_field("java/lang/Runtime", "shutdownHooks", "Ljava/util/Vector;", hooks.clone())
+                .await?;
+        }
+
+        let _: () = jvm.invoke_virtual(&hooks, "addElement", "(Ljava/lang/Object;)V", (hook,)).await?;
+
+        Ok(())
+    }
+
+    async fn remove_shutdown_hook(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Runtime>,
+        hook: ClassInstanceRef<Thread>,
+    ) -> Result<bool> {
+        tracing::debug!("java.lang.Runtime::removeShutdownHook({:?}, {:?})", &this, &hook);
+
+        let hooks: ClassInstanceRef<Vector> = jvm.get_static_field("java/lang/Runtime", "shutdownHooks", "Ljava/util/Vector;").await?;
+        if hooks.is_null() {
+            return Ok(false);
+        }
+
+        let index: i32 = jvm.invoke_virtual(&hooks, "lastIndexOf", "(Ljava/lang/Object;)I", (hook,)).await?;
+        if index < 0 {
+            return Ok(false);
+        }
+
+        let _: () = jvm.invoke_virtual(&hooks, "removeElementAt", "(I)V", (index,)).await?;
+
+        Ok(true)
+    }
+
+    async fn run_shutdown_hooks(jvm: &Jvm, _: &mut RuntimeContext) -> Result<()> {
+        tracing::debug!("java.lang.Runtime::runShutdownHooks()");
+
+        let hooks: ClassInstanceRef<Vector> = jvm.get_static_field("java/lang/Runtime", "shutdownHooks", "Ljava/util/Vector;").await?;
+        if hooks.is_null() {
+            return Ok(());
+        }
+        // hooks run only once
+        jvm.put_static_field("java/lang/Runtime", "shutdownHooks", "Ljava/util/Vector;", None)
+            .await?;
+
+        let count: i32 = jvm.invoke_virtual(&hooks, "size", "()I", ()).await?;
+        let mut threads = Vec::with_capacity(count as _);
+        for i in 0..count {
+            let thread: ClassInstanceRef<Thread> = jvm.invoke_virtual(&hooks, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+            let _: () = jvm.invoke_virtual(&thread, "start", "()V", ()).await?;
+
+            threads.push(thread);
+        }
+
+        for thread in threads {
+            let _: () = jvm.invoke_virtual(&thread, "join", "()V", ()).await?;
+        }
 
         Ok(())
     }
@@ -3,19 +3,17 @@ use core::cmp::Ordering;
 use alloc::{
     str,
     string::{String as RustString, ToString},
-    vec,
     vec::Vec,
 };
 
 use bytemuck::{cast_slice, cast_vec};
 
-use java_class_proto::{JavaFieldProto, JavaMethodProto};
-use java_constants::MethodAccessFlags;
+use java_class_proto::java_class;
 use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{
     classes::java::lang::{Object, System},
-    RuntimeClassProto, RuntimeContext,
+    RuntimeContext,
 };
 
 use super::StringBuffer;
@@ -23,50 +21,18 @@ use super::StringBuffer;
 // class java.lang.String
 pub struct String;
 
+#[java_class("java/lang/String")]
 impl String {
-    pub fn as_proto() -> RuntimeClassProto {
-        RuntimeClassProto {
-            name: "java/lang/String",
-            parent_class: Some("java/lang/Object"),
-            interfaces: vec![],
-            methods: vec![
-                JavaMethodProto::new("<init>", "([B)V", Self::init_with_byte_array, Default::default()),
-                JavaMethodProto::new("<init>", "([C)V", Self::init_with_char_array, Default::default()),
-                JavaMethodProto::new("<init>", "([CII)V", Self::init_with_partial_char_array, Default::default()),
-                JavaMethodProto::new("<init>", "([BII)V", Self::init_with_partial_byte_array, Default::default()),
-                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_string, Default::default()),
-                JavaMethodProto::new("<init>", "(Ljava/lang/StringBuffer;)V", Self::init_with_string_buffer, Default::default()),
-                JavaMethodProto::new("equals", "(Ljava/lang/Object;)Z", Self::equals, Default::default()),
-                JavaMethodProto::new("compareTo", "(Ljava/lang/String;)I", Self::compare_to, Default::default()),
-                JavaMethodProto::new("hashCode", "()I", Self::hash_code, Default::default()),
-                JavaMethodProto::new("toString", "()Ljava/lang/String;", Self::to_string, Default::default()),
-                JavaMethodProto::new("charAt", "(I)C", Self::char_at, Default::default()),
-                JavaMethodProto::new("getBytes", "()[B", Self::get_bytes, Default::default()),
-                JavaMethodProto::new("toCharArray", "()[C", Self::to_char_array, Default::default()),
-                JavaMethodProto::new("toUpperCase", "()Ljava/lang/String;", Self::to_upper_case, Default::default()),
-                JavaMethodProto::new("length", "()I", Self::length, Default::default()),
-                JavaMethodProto::new("concat", "(Ljava/lang/String;)Ljava/lang/String;", Self::concat, Default::default()),
-                JavaMethodProto::new("substring", "(I)Ljava/lang/String;", Self::substring, Default::default()),
-                JavaMethodProto::new("substring", "(II)Ljava/lang/String;", Self::substring_with_end, Default::default()),
-                JavaMethodProto::new("valueOf", "(C)Ljava/lang/String;", Self::value_of_char, MethodAccessFlags::STATIC),
-                JavaMethodProto::new("valueOf", "(I)Ljava/lang/String;", Self::value_of_integer, MethodAccessFlags::STATIC),
-                JavaMethodProto::new(
-                    "valueOf",
-                    "(Ljava/lang/Object;)Ljava/lang/String;",
-                    Self::value_of_object,
-                    MethodAccessFlags::STATIC,
-                ),
-                JavaMethodProto::new("indexOf", "(I)I", Self::index_of, Default::default()),
-                JavaMethodProto::new("indexOf", "(II)I", Self::index_of_from, Default::default()),
-                JavaMethodProto::new("indexOf", "(Ljava/lang/String;)I", Self::index_of_string, Default::default()),
-                JavaMethodProto::new("indexOf", "(Ljava/lang/String;I)I", Self::index_of_string_from, Default::default()),
-                JavaMethodProto::new("trim", "()Ljava/lang/String;", Self::trim, Default::default()),
-            ],
-            fields: vec![JavaFieldProto::new("value", "[C", Default::default())],
-        }
-    }
+    #[java_field]
+    const value: Array<JavaChar>;
 
-    async fn init_with_byte_array(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, value: ClassInstanceRef<Array<i8>>) -> Result<()> {
+    #[java_method(name = "<init>")]
+    pub async fn init_with_byte_array(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        value: ClassInstanceRef<Array<i8>>,
+    ) -> Result<()> {
         tracing::debug!("java.lang.String::<init>({:?}, {:?})", &this, &value);
 
         let count = jvm.array_length(&value).await? as i32;
@@ -78,7 +44,8 @@ impl String {
         Ok(())
     }
 
-    async fn init_with_char_array(
+    #[java_method(name = "<init>")]
+    pub async fn init_with_char_array(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         this: ClassInstanceRef<Self>,
@@ -95,7 +62,8 @@ impl String {
         Ok(())
     }
 
-    async fn init_with_partial_char_array(
+    #[java_method(name = "<init>")]
+    pub async fn init_with_partial_char_array(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         mut this: ClassInstanceRef<Self>,
@@ -116,7 +84,8 @@ impl String {
         Ok(())
     }
 
-    async fn init_with_partial_byte_array(
+    #[java_method(name = "<init>")]
+    pub async fn init_with_partial_byte_array(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         this: ClassInstanceRef<Self>,
@@ -141,7 +110,8 @@ impl String {
         Ok(())
     }
 
-    async fn init_with_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, value: ClassInstanceRef<Self>) -> Result<()> {
+    #[java_method(name = "<init>")]
+    pub async fn init_with_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, value: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("java.lang.String::<init>({:?}, {:?})", &this, &value);
 
         let chars: ClassInstanceRef<Array<JavaChar>> = jvm.invoke_virtual(&value, "toCharArray", "()[C", ()).await?;
@@ -151,7 +121,8 @@ impl String {
         Ok(())
     }
 
-    async fn init_with_string_buffer(
+    #[java_method(name = "<init>")]
+    pub async fn init_with_string_buffer(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         this: ClassInstanceRef<Self>,
@@ -168,7 +139,8 @@ impl String {
         Ok(())
     }
 
-    async fn equals(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, other: ClassInstanceRef<Self>) -> Result<bool> {
+    #[java_method]
+    pub async fn equals(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, other: ClassInstanceRef<Object>) -> Result<bool> {
         tracing::debug!("java.lang.String::equals({:?}, {:?})", &this, &other);
 
         if other.is_null() {
@@ -185,7 +157,8 @@ impl String {
         }
     }
 
-    async fn compare_to(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, other: ClassInstanceRef<Self>) -> Result<i32> {
+    #[java_method]
+    pub async fn compare_to(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, other: ClassInstanceRef<Self>) -> Result<i32> {
         tracing::debug!("java.lang.String::compareTo({:?}, {:?})", &this, &other);
 
         let other_string = JavaLangString::to_rust_string(jvm, &other).await?;
@@ -200,7 +173,8 @@ impl String {
         }
     }
 
-    async fn hash_code(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
+    #[java_method]
+    pub async fn hash_code(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
         tracing::debug!("java.lang.String::hashCode({:?})", &this);
 
         let chars = jvm.get_field(&this, "value", "[C").await?;
@@ -211,13 +185,15 @@ impl String {
         Ok(hash)
     }
 
-    async fn to_string(_jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Self>> {
+    #[java_method]
+    pub async fn to_string(_jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Self>> {
         tracing::debug!("java.lang.String::toString({:?})", &this);
 
         Ok(this)
     }
 
-    async fn char_at(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, index: i32) -> Result<u16> {
+    #[java_method]
+    pub async fn char_at(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, index: i32) -> Result<u16> {
         tracing::debug!("java.lang.String::charAt({:?}, {})", &this, index);
 
         let value = jvm.get_field(&this, "value", "[C").await?;
@@ -225,7 +201,8 @@ impl String {
         Ok(jvm.load_array(&value, index as _, 1).await?[0])
     }
 
-    async fn concat(
+    #[java_method]
+    pub async fn concat(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         this: ClassInstanceRef<Self>,
@@ -241,7 +218,8 @@ impl String {
         Ok(JavaLangString::from_rust_string(jvm, &concat).await?.into())
     }
 
-    async fn get_bytes(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Array<i8>>> {
+    #[java_method]
+    pub async fn get_bytes(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Array<i8>>> {
         tracing::debug!("java.lang.String::getBytes({:?})", &this);
 
         let string = JavaLangString::to_rust_string(jvm, &this.clone()).await?;
@@ -255,7 +233,8 @@ impl String {
         Ok(byte_array.into())
     }
 
-    async fn to_char_array(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Array<JavaChar>>> {
+    #[java_method]
+    pub async fn to_char_array(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Array<JavaChar>>> {
         tracing::debug!("java.lang.String::toCharArray({:?})", &this);
 
         let value = jvm.get_field(&this, "value", "[C").await?;
@@ -263,7 +242,8 @@ impl String {
         Ok(value)
     }
 
-    async fn length(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
+    #[java_method]
+    pub async fn length(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
         tracing::debug!("java.lang.String::length({:?})", &this);
 
         let value = jvm.get_field(&this, "value", "[C").await?;
@@ -271,7 +251,8 @@ impl String {
         Ok(jvm.array_length(&value).await? as _)
     }
 
-    async fn substring(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, begin_index: i32) -> Result<ClassInstanceRef<Self>> {
+    #[java_method]
+    pub async fn substring(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, begin_index: i32) -> Result<ClassInstanceRef<Self>> {
         tracing::debug!("java.lang.String::substring({:?}, {})", &this, begin_index);
 
         let string = JavaLangString::to_rust_string(jvm, &this.clone()).await?;
@@ -281,7 +262,8 @@ impl String {
         Ok(JavaLangString::from_rust_string(jvm, &substr).await?.into())
     }
 
-    async fn substring_with_end(
+    #[java_method(name = "substring")]
+    pub async fn substring_with_end(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         this: ClassInstanceRef<Self>,
@@ -301,7 +283,8 @@ impl String {
         Ok(JavaLangString::from_rust_string(jvm, &substr).await?.into())
     }
 
-    async fn value_of_char(jvm: &Jvm, _: &mut RuntimeContext, value: JavaChar) -> Result<ClassInstanceRef<Self>> {
+    #[java_method(name = "valueOf")]
+    pub async fn value_of_char(jvm: &Jvm, _: &mut RuntimeContext, value: JavaChar) -> Result<ClassInstanceRef<Self>> {
         tracing::debug!("java.lang.String::valueOf({})", value);
 
         let string = RustString::from_utf16(&[value]).unwrap();
@@ -309,7 +292,8 @@ impl String {
         Ok(JavaLangString::from_rust_string(jvm, &string).await?.into())
     }
 
-    async fn value_of_integer(jvm: &Jvm, _: &mut RuntimeContext, value: i32) -> Result<ClassInstanceRef<Self>> {
+    #[java_method(name = "valueOf")]
+    pub async fn value_of_integer(jvm: &Jvm, _: &mut RuntimeContext, value: i32) -> Result<ClassInstanceRef<Self>> {
         tracing::debug!("java.lang.String::valueOf({})", value);
 
         let string = value.to_string();
@@ -317,7 +301,8 @@ impl String {
         Ok(JavaLangString::from_rust_string(jvm, &string).await?.into())
     }
 
-    async fn value_of_object(jvm: &Jvm, _: &mut RuntimeContext, value: ClassInstanceRef<Object>) -> Result<ClassInstanceRef<Self>> {
+    #[java_method(name = "valueOf")]
+    pub async fn value_of_object(jvm: &Jvm, _: &mut RuntimeContext, value: ClassInstanceRef<Object>) -> Result<ClassInstanceRef<Self>> {
         tracing::warn!("stub java.lang.String::valueOf({:?})", &value);
 
         Ok(if value.is_null() {
@@ -327,13 +312,15 @@ impl String {
         })
     }
 
-    async fn index_of(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, ch: i32) -> Result<i32> {
+    #[java_method]
+    pub async fn index_of(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, ch: i32) -> Result<i32> {
         tracing::debug!("java.lang.String::indexOf({:?}, {:?})", &this, ch);
 
         jvm.invoke_virtual(&this, "indexOf", "(II)I", (ch, 0)).await
     }
 
-    async fn index_of_from(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, ch: i32, from_index: i32) -> Result<i32> {
+    #[java_method(name = "indexOf")]
+    pub async fn index_of_from(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, ch: i32, from_index: i32) -> Result<i32> {
         tracing::debug!("java.lang.String::indexOf({:?}, {:?}, {:?})", &this, ch, from_index);
 
         let this_string = JavaLangString::to_rust_string(jvm, &this.clone()).await?;
@@ -347,13 +334,15 @@ impl String {
         Ok(index.unwrap_or(-1))
     }
 
-    async fn index_of_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, str: ClassInstanceRef<Self>) -> Result<i32> {
+    #[java_method(name = "indexOf")]
+    pub async fn index_of_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, str: ClassInstanceRef<Self>) -> Result<i32> {
         tracing::debug!("java.lang.String::indexOf({:?}, {:?})", &this, &str);
 
         jvm.invoke_virtual(&this, "indexOf", "(Ljava/lang/String;I)I", (str, 0)).await
     }
 
-    async fn index_of_string_from(
+    #[java_method(name = "indexOf")]
+    pub async fn index_of_string_from(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         this: ClassInstanceRef<Self>,
@@ -375,7 +364,18 @@ impl String {
         Ok(index.unwrap_or(-1))
     }
 
-    async fn trim(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Self>> {
+    #[java_method]
+    pub async fn starts_with(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, prefix: ClassInstanceRef<Self>) -> Result<bool> {
+        tracing::debug!("java.lang.String::startsWith({:?}, {:?})", &this, &prefix);
+
+        let this_string = JavaLangString::to_rust_string(jvm, &this).await?;
+        let prefix_string = JavaLangString::to_rust_string(jvm, &prefix).await?;
+
+        Ok(this_string.starts_with(&prefix_string))
+    }
+
+    #[java_method]
+    pub async fn trim(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Self>> {
         tracing::debug!("java.lang.String::trim({:?})", &this);
 
         let string = JavaLangString::to_rust_string(jvm, &this.clone()).await?;
@@ -385,7 +385,8 @@ impl String {
         Ok(JavaLangString::from_rust_string(jvm, &trimmed).await?.into()) // TODO buffer sharing
     }
 
-    async fn to_upper_case(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Self>> {
+    #[java_method]
+    pub async fn to_upper_case(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Self>> {
         tracing::debug!("java.lang.String::toUpperCase({:?})", &this);
 
         let string = JavaLangString::to_rust_string(jvm, &this.clone()).await?;
@@ -414,9 +415,35 @@ impl String {
 
 #[cfg(test)]
 mod test {
-    use jvm::{runtime::JavaLangString, Result};
+    use alloc::vec::Vec;
+
+    use java_constants::MethodAccessFlags;
+    use jvm::{runtime::JavaLangString, JavaError, JavaValue, Result};
 
-    use crate::test::test_jvm;
+    use crate::{classes::java::lang::String, test::test_jvm};
+
+    #[test]
+    fn test_proto() {
+        let proto = String::as_proto();
+
+        let index_of = proto
+            .methods
+            .iter()
+            .filter(|x| x.name == "indexOf")
+            .map(|x| x.descriptor.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(index_of, ["(I)I", "(II)I", "(Ljava/lang/String;)I", "(Ljava/lang/String;I)I"]);
+
+        let value_of = proto
+            .methods
+            .iter()
+            .find(|x| x.name == "valueOf" && x.descriptor == "(C)Ljava/lang/String;")
+            .unwrap();
+        assert_eq!(value_of.access_flags, MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC);
+
+        assert_eq!(proto.parent_class, Some("java/lang/Object"));
+        assert_eq!(proto.fields[0].descriptor, "[C");
+    }
 
     #[tokio::test]
     async fn test_string() -> Result<()> {
@@ -449,6 +476,16 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_missing_argument() -> Result<()> {
+        let jvm = test_jvm().await?;
+
+        let result: Result<JavaValue> = jvm.invoke_static("java/lang/String", "valueOf", "(C)Ljava/lang/String;", ()).await;
+        assert!(matches!(result, Err(JavaError::FatalError(_))));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_hash_code() -> Result<()> {
         let jvm = test_jvm().await?;
@@ -1,56 +1,28 @@
 use alloc::{
     string::{String as RustString, ToString},
-    vec,
     vec::Vec,
 };
 
-use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_class_proto::java_class;
 use jvm::{runtime::JavaLangString, Array, ClassInstanceRef, JavaChar, Jvm, Result};
 
 use crate::{
     classes::java::lang::{Object, String},
-    RuntimeClassProto, RuntimeContext,
+    RuntimeContext,
 };
 
 // class java.lang.StringBuffer
 pub struct StringBuffer;
 
+#[java_class("java/lang/StringBuffer")]
 impl StringBuffer {
-    pub fn as_proto() -> RuntimeClassProto {
-        RuntimeClassProto {
-            name: "java/lang/StringBuffer",
-            parent_class: Some("java/lang/Object"),
-            interfaces: vec![],
-            methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("<init>", "(I)V", Self::init_with_buffer_length, Default::default()),
-                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_string, Default::default()),
-                JavaMethodProto::new(
-                    "append",
-                    "(Ljava/lang/String;)Ljava/lang/StringBuffer;",
-                    Self::append_string,
-                    Default::default(),
-                ),
-                JavaMethodProto::new(
-                    "append",
-                    "(Ljava/lang/Object;)Ljava/lang/StringBuffer;",
-                    Self::append_object,
-                    Default::default(),
-                ),
-                JavaMethodProto::new("append", "(I)Ljava/lang/StringBuffer;", Self::append_integer, Default::default()),
-                JavaMethodProto::new("append", "(J)Ljava/lang/StringBuffer;", Self::append_long, Default::default()),
-                JavaMethodProto::new("append", "(C)Ljava/lang/StringBuffer;", Self::append_character, Default::default()),
-                JavaMethodProto::new("append", "([CII)Ljava/lang/StringBuffer;", Self::append_char_array, Default::default()),
-                JavaMethodProto::new("toString", "()Ljava/lang/String;", Self::to_string, Default::default()),
-            ],
-            fields: vec![
-                JavaFieldProto::new("value", "[C", Default::default()),
-                JavaFieldProto::new("count", "I", Default::default()),
-            ],
-        }
-    }
+    #[java_field]
+    const value: Array<JavaChar>;
+    #[java_field]
+    const count: i32;
 
-    async fn init(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
+    #[java_method]
+    pub async fn init(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("java.lang.StringBuffer::<init>({:?})", &this);
 
         let _: () = jvm.invoke_special(&this, "java/lang/StringBuffer", "<init>", "(I)V", (16,)).await?;
@@ -58,7 +30,8 @@ impl StringBuffer {
         Ok(())
     }
 
-    async fn init_with_buffer_length(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, length: i32) -> Result<()> {
+    #[java_method(name = "<init>")]
+    pub async fn init_with_buffer_length(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, length: i32) -> Result<()> {
         tracing::debug!("java.lang.StringBuffer::<init>({:?}, {:?})", &this, length);
 
         let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
@@ -70,7 +43,13 @@ impl StringBuffer {
         Ok(())
     }
 
-    async fn init_with_string(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, string: ClassInstanceRef<String>) -> Result<()> {
+    #[java_method(name = "<init>")]
+    pub async fn init_with_string(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        mut this: ClassInstanceRef<Self>,
+        string: ClassInstanceRef<String>,
+    ) -> Result<()> {
         tracing::debug!("java.lang.StringBuffer::<init>({:?}, {:?})", &this, &string,);
 
         let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
@@ -84,7 +63,8 @@ impl StringBuffer {
         Ok(())
     }
 
-    async fn append_string(
+    #[java_method(name = "append")]
+    pub async fn append_string(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         mut this: ClassInstanceRef<Self>,
@@ -103,7 +83,8 @@ impl StringBuffer {
         Ok(this)
     }
 
-    async fn append_object(
+    #[java_method(name = "append")]
+    pub async fn append_object(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         mut this: ClassInstanceRef<Self>,
@@ -123,7 +104,8 @@ impl StringBuffer {
         Ok(this)
     }
 
-    async fn append_integer(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, value: i32) -> Result<ClassInstanceRef<Self>> {
+    #[java_method(name = "append")]
+    pub async fn append_integer(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, value: i32) -> Result<ClassInstanceRef<Self>> {
         tracing::debug!("java.lang.StringBuffer::append({:?}, {:?})", &this, value);
 
         let digits = value.to_string();
@@ -133,7 +115,8 @@ impl StringBuffer {
         Ok(this)
     }
 
-    async fn append_long(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, value: i64) -> Result<ClassInstanceRef<Self>> {
+    #[java_method(name = "append")]
+    pub async fn append_long(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, value: i64) -> Result<ClassInstanceRef<Self>> {
         tracing::debug!("java.lang.StringBuffer::append({:?}, {:?})", &this, value);
 
         let digits = value.to_string();
@@ -143,7 +126,8 @@ impl StringBuffer {
         Ok(this)
     }
 
-    async fn append_character(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, value: u16) -> Result<ClassInstanceRef<Self>> {
+    #[java_method(name = "append")]
+    pub async fn append_character(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, value: u16) -> Result<ClassInstanceRef<Self>> {
         tracing::debug!("java.lang.StringBuffer::append({:?}, {:?})", &this, value);
 
         let value = RustString::from_utf16(&[value]).unwrap();
@@ -153,7 +137,8 @@ impl StringBuffer {
         Ok(this)
     }
 
-    async fn append_char_array(
+    #[java_method(name = "append")]
+    pub async fn append_char_array(
         jvm: &Jvm,
         _: &mut RuntimeContext,
         mut this: ClassInstanceRef<Self>,
@@ -171,7 +156,8 @@ impl StringBuffer {
         Ok(this)
     }
 
-    async fn to_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<String>> {
+    #[java_method]
+    pub async fn to_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<String>> {
         tracing::debug!("java.lang.StringBuffer::toString({:?})", &this);
 
         let java_value: ClassInstanceRef<Array<JavaChar>> = jvm.get_field(&this, "value", "[C").await?;
@@ -19,6 +19,7 @@ impl System {
         RuntimeClassProto {
             name: "java/lang/System",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
                 JavaMethodProto::new("<clinit>", "()V", Self::cl_init, MethodAccessFlags::STATIC),
@@ -26,30 +27,35 @@ impl System {
                     "currentTimeMillis",
                     "()J",
                     Self::current_time_millis,
-                    MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC,
                 ),
-                JavaMethodProto::new("gc", "()V", Self::gc, MethodAccessFlags::STATIC),
+                JavaMethodProto::new("gc", "()V", Self::gc, MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC),
+                JavaMethodProto::new("exit", "(I)V", Self::exit, MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC),
                 JavaMethodProto::new(
                     "arraycopy",
                     "(Ljava/lang/Object;ILjava/lang/Object;II)V",
                     Self::arraycopy,
-                    MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC,
                 ),
                 JavaMethodProto::new(
                     "getProperty",
                     "(Ljava/lang/String;)Ljava/lang/String;",
                     Self::get_property,
-                    MethodAccessFlags::STATIC,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
                 ),
                 JavaMethodProto::new(
                     "setProperty",
                     "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/Object;",
                     Self::set_property,
-                    MethodAccessFlags::STATIC,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
                 ),
             ],
             fields: vec![
-                JavaFieldProto::new("out", "Ljava/io/PrintStream;", FieldAccessFlags::STATIC),
+                JavaFieldProto::new(
+                    "out",
+                    "Ljava/io/PrintStream;",
+                    FieldAccessFlags::PUBLIC | FieldAccessFlags::STATIC | FieldAccessFlags::FINAL,
+                ),
                 JavaFieldProto::new("props", "Ljava/util/Properties;", FieldAccessFlags::STATIC),
             ],
         }
@@ -81,10 +87,20 @@ impl System {
         Ok(context.now() as _)
     }
 
-    async fn gc(_: &Jvm, _: &mut RuntimeContext) -> Result<i32> {
-        tracing::warn!("stub java.lang.System::gc()");
+    async fn gc(jvm: &Jvm, _: &mut RuntimeContext) -> Result<()> {
+        tracing::debug!("java.lang.System::gc()");
+
+        let runtime = jvm.invoke_static("java/lang/Runtime", "getRuntime", "()Ljava/lang/Runtime;", ()).await?;
+        jvm.invoke_virtual(&runtime, "gc", "()V", ()).await
+    }
+
+    async fn exit(jvm: &Jvm, _: &mut RuntimeContext, status: i32) -> Result<()> {
+        tracing::debug!("java.lang.System::exit({:?})", status);
+
+        let runtime = jvm.invoke_static("java/lang/Runtime", "getRuntime", "()Ljava/lang/Runtime;", ()).await?;
+        let _: () = jvm.invoke_virtual(&runtime, "exit", "(I)V", (status,)).await?;
 
-        Ok(0)
+        Ok(())
     }
 
     async fn arraycopy(
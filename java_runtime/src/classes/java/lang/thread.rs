@@ -1,46 +1,132 @@
-use alloc::{boxed::Box, sync::Arc, vec};
-use core::time::Duration;
+use alloc::{boxed::Box, format, sync::Arc, vec};
+use core::{future::Future, time::Duration};
 
 use event_listener::Event;
+use futures_lite::future;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
-use java_constants::MethodAccessFlags;
-use jvm::{Array, ClassInstanceRef, Jvm, Result};
+use java_constants::{FieldAccessFlags, MethodAccessFlags};
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result, ThreadState};
 
-use crate::{classes::java::lang::Runnable, RuntimeClassProto, RuntimeContext, SpawnCallback};
+use crate::{
+    classes::java::lang::{Runnable, String, ThreadGroup},
+    RuntimeClassProto, RuntimeContext, SpawnCallback,
+};
 
 // class java.lang.Thread
 pub struct Thread;
 
 impl Thread {
+    pub const MIN_PRIORITY: i32 = 1;
+    pub const NORM_PRIORITY: i32 = 5;
+    pub const MAX_PRIORITY: i32 = 10;
+
     pub fn as_proto() -> RuntimeClassProto {
         RuntimeClassProto {
             name: "java/lang/Thread",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("<init>", "(Ljava/lang/Runnable;)V", Self::init_with_runnable, Default::default()),
-                JavaMethodProto::new("start", "()V", Self::start, Default::default()),
-                JavaMethodProto::new("join", "()V", Self::join, Default::default()),
-                JavaMethodProto::new("run", "()V", Self::run, Default::default()),
-                JavaMethodProto::new("isAlive", "()Z", Self::is_alive, Default::default()),
-                JavaMethodProto::new("sleep", "(J)V", Self::sleep, MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC),
-                JavaMethodProto::new("yield", "()V", Self::r#yield, MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC),
-                JavaMethodProto::new("setPriority", "(I)V", Self::set_priority, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(Ljava/lang/Runnable;)V", Self::init_with_runnable, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_name, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljava/lang/Runnable;Ljava/lang/String;)V",
+                    Self::init_with_runnable_name,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljava/lang/ThreadGroup;Ljava/lang/Runnable;)V",
+                    Self::init_with_group_runnable,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljava/lang/ThreadGroup;Ljava/lang/String;)V",
+                    Self::init_with_group_name,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljava/lang/ThreadGroup;Ljava/lang/Runnable;Ljava/lang/String;)V",
+                    Self::init_with_group_runnable_name,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("start", "()V", Self::start, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("join", "()V", Self::join, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("run", "()V", Self::run, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isAlive", "()Z", Self::is_alive, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "sleep",
+                    "(J)V",
+                    Self::sleep,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new(
+                    "yield",
+                    "()V",
+                    Self::r#yield,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new("getName", "()Ljava/lang/String;", Self::get_name, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setName", "(Ljava/lang/String;)V", Self::set_name, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getPriority", "()I", Self::get_priority, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setPriority", "(I)V", Self::set_priority, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "getThreadGroup",
+                    "()Ljava/lang/ThreadGroup;",
+                    Self::get_thread_group,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "activeCount",
+                    "()I",
+                    Self::active_count,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new("setDaemon", "(Z)V", Self::set_daemon, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isDaemon", "()Z", Self::is_daemon, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("interrupt", "()V", Self::interrupt, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isInterrupted", "()Z", Self::is_interrupted, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "interrupted",
+                    "()Z",
+                    Self::interrupted,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new("toString", "()Ljava/lang/String;", Self::to_string, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "currentThread",
                     "()Ljava/lang/Thread;",
                     Self::current_thread,
-                    MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::NATIVE | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new("exit", "()V", Self::exit, MethodAccessFlags::PRIVATE),
+                JavaMethodProto::new(
+                    "nextThreadNum",
+                    "()I",
+                    Self::next_thread_num,
+                    MethodAccessFlags::PRIVATE | MethodAccessFlags::STATIC | MethodAccessFlags::SYNCHRONIZED,
                 ),
                 // rustjava internal
-                JavaMethodProto::new("<init>", "(Z)V", Self::init_internal, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;Z)V", Self::init_attached, Default::default()),
             ],
             fields: vec![
                 JavaFieldProto::new("id", "J", Default::default()),
+                JavaFieldProto::new("name", "Ljava/lang/String;", Default::default()),
+                JavaFieldProto::new("priority", "I", Default::default()),
+                JavaFieldProto::new("group", "Ljava/lang/ThreadGroup;", Default::default()),
                 JavaFieldProto::new("target", "Ljava/lang/Runnable;", Default::default()),
-                JavaFieldProto::new("joinEvent", "[B", Default::default()),
+                JavaFieldProto::new("alive", "Z", Default::default()),
+                JavaFieldProto::new("daemon", "Z", Default::default()),
+                JavaFieldProto::new("interrupted", "Z", Default::default()),
+                JavaFieldProto::new("interruptEvent", "[B", Default::default()),
+                JavaFieldProto::new("threadInitNumber", "I", FieldAccessFlags::STATIC),
+                // group of threads attached from host
+                JavaFieldProto::new("mainGroup", "Ljava/lang/ThreadGroup;", FieldAccessFlags::STATIC),
             ],
         }
     }
@@ -48,33 +134,141 @@ impl Thread {
     async fn init(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("Thread::<init>({:?})", &this);
 
-        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+        Self::init_thread(jvm, this, None.into(), None.into(), None.into()).await
+    }
 
-        Ok(())
+    async fn init_with_runnable(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, target: ClassInstanceRef<Runnable>) -> Result<()> {
+        tracing::debug!("Thread::<init>({:?}, {:?})", &this, &target);
+
+        Self::init_thread(jvm, this, None.into(), target, None.into()).await
     }
 
-    async fn init_with_runnable(
+    async fn init_with_name(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> Result<()> {
+        tracing::debug!("Thread::<init>({:?}, {:?})", &this, &name);
+
+        Self::init_thread(jvm, this, None.into(), None.into(), name).await
+    }
+
+    async fn init_with_runnable_name(
         jvm: &Jvm,
         _: &mut RuntimeContext,
-        mut this: ClassInstanceRef<Self>,
+        this: ClassInstanceRef<Self>,
         target: ClassInstanceRef<Runnable>,
+        name: ClassInstanceRef<String>,
     ) -> Result<()> {
-        tracing::debug!("Thread::<init>({:?}, {:?})", &this, &target);
+        tracing::debug!("Thread::<init>({:?}, {:?}, {:?})", &this, &target, &name);
+
+        Self::init_thread(jvm, this, None.into(), target, name).await
+    }
+
+    async fn init_with_group_runnable(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        group: ClassInstanceRef<ThreadGroup>,
+        target: ClassInstanceRef<Runnable>,
+    ) -> Result<()> {
+        tracing::debug!("Thread::<init>({:?}, {:?}, {:?})", &this, &group, &target);
+
+        Self::init_thread(jvm, this, group, target, None.into()).await
+    }
+
+    async fn init_with_group_name(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        group: ClassInstanceRef<ThreadGroup>,
+        name: ClassInstanceRef<String>,
+    ) -> Result<()> {
+        tracing::debug!("Thread::<init>({:?}, {:?}, {:?})", &this, &group, &name);
 
+        Self::init_thread(jvm, this, group, None.into(), name).await
+    }
+
+    async fn init_with_group_runnable_name(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        group: ClassInstanceRef<ThreadGroup>,
+        target: ClassInstanceRef<Runnable>,
+        name: ClassInstanceRef<String>,
+    ) -> Result<()> {
+        tracing::debug!("Thread::<init>({:?}, {:?}, {:?}, {:?})", &this, &group, &target, &name);
+
+        Self::init_thread(jvm, this, group, target, name).await
+    }
+
+    // group, daemon status and priority are inherited from current thread
+    async fn init_thread(
+        jvm: &Jvm,
+        mut this: ClassInstanceRef<Self>,
+        mut group: ClassInstanceRef<ThreadGroup>,
+        target: ClassInstanceRef<Runnable>,
+        mut name: ClassInstanceRef<String>,
+    ) -> Result<()> {
         let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
 
+        let parent = Self::current(jvm).await?;
+        if group.is_null() {
+            group = jvm.get_field(&parent, "group", "Ljava/lang/ThreadGroup;").await?;
+        }
+        if name.is_null() {
+            let number: i32 = jvm.invoke_static("java/lang/Thread", "nextThreadNum", "()I", ()).await?;
+            name = JavaLangString::from_rust_string(jvm, &format!("Thread-{}", number)).await?.into();
+        }
+
+        let daemon: bool = jvm.get_field(&parent, "daemon", "Z").await?;
+        let priority: i32 = jvm.get_field(&parent, "priority", "I").await?;
+        let max_priority: i32 = jvm.invoke_virtual(&group, "getMaxPriority", "()I", ()).await?;
+
+        jvm.put_field(&mut this, "name", "Ljava/lang/String;", name).await?;
+        jvm.put_field(&mut this, "group", "Ljava/lang/ThreadGroup;", group).await?;
         jvm.put_field(&mut this, "target", "Ljava/lang/Runnable;", target).await?;
+        jvm.put_field(&mut this, "daemon", "Z", daemon).await?;
+        jvm.put_field(&mut this, "priority", "I", priority.min(max_priority)).await?;
+        jvm.put_rust_object_field(&mut this, "interruptEvent", Arc::new(Event::new())).await?;
 
         Ok(())
     }
 
-    async fn init_internal(jvm: &Jvm, context: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, internal: bool) -> Result<()> {
-        tracing::debug!("Thread::<init>({:?}, {:?})", &this, internal);
+    async fn init_attached(
+        jvm: &Jvm,
+        context: &mut RuntimeContext,
+        mut this: ClassInstanceRef<Self>,
+        name: ClassInstanceRef<String>,
+        daemon: bool,
+    ) -> Result<()> {
+        tracing::debug!("Thread::<init>({:?}, {:?}, {:?})", &this, &name, daemon);
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        // there's no current thread to inherit from, as we're creating it
+        let mut group: ClassInstanceRef<ThreadGroup> = jvm.get_static_field("java/lang/Thread", "mainGroup", "Ljava/lang/ThreadGroup;").await?;
+        if group.is_null() {
+            let system_group = jvm.new_class("java/lang/ThreadGroup", "()V", ()).await?;
+            let main_group_name = JavaLangString::from_rust_string(jvm, "main").await?;
+            group = jvm
+                .new_class(
+                    "java/lang/ThreadGroup",
+                    "(Ljava/lang/ThreadGroup;Ljava/lang/String;)V",
+                    (system_group, main_group_name),
+                )
+                .await?
+                .into();
+            jvm.put_static_field("java/lang/Thread", "mainGroup", "Ljava/lang/ThreadGroup;", group.clone())
+                .await?;
+        }
 
         let id = context.current_task_id();
         jvm.put_field(&mut this, "id", "J", id as i64).await?;
-
-        Ok(())
+        jvm.put_field(&mut this, "name", "Ljava/lang/String;", name).await?;
+        jvm.put_field(&mut this, "group", "Ljava/lang/ThreadGroup;", group.clone()).await?;
+        jvm.put_field(&mut this, "daemon", "Z", daemon).await?;
+        jvm.put_field(&mut this, "priority", "I", Self::NORM_PRIORITY).await?;
+        jvm.put_field(&mut this, "alive", "Z", true).await?;
+        jvm.put_rust_object_field(&mut this, "interruptEvent", Arc::new(Event::new())).await?;
+
+        jvm.invoke_virtual(&group, "add", "(Ljava/lang/Thread;)V", (this,)).await
     }
 
     async fn start(jvm: &Jvm, context: &mut RuntimeContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
@@ -83,7 +277,7 @@ impl Thread {
         struct ThreadStartProxy {
             jvm: Jvm,
             thread_id: i32,
-            join_event: Arc<Event>,
+            daemon: bool,
             this: ClassInstanceRef<Thread>,
         }
 
@@ -93,20 +287,30 @@ impl Thread {
             async fn call(&self) -> Result<()> {
                 tracing::trace!("Thread start");
 
-                self.jvm.attach_thread().await?;
+                self.jvm.attach_java_thread(self.this.clone().into()).await?;
 
-                let _: () = self.jvm.invoke_virtual(&self.this, "run", "()V", []).await?;
+                let result: Result<()> = self.jvm.invoke_virtual(&self.this, "run", "()V", []).await;
 
                 self.jvm.detach_thread().await?;
 
-                self.join_event.notify(usize::MAX);
+                if !self.daemon {
+                    self.jvm.non_daemon_thread_finished();
+                }
 
-                Ok(())
+                result
             }
         }
 
-        let join_event = Arc::new(Event::new());
-        jvm.put_rust_object_field(&mut this, "joinEvent", join_event.clone()).await?;
+        jvm.put_field(&mut this, "alive", "Z", true).await?;
+
+        let group: ClassInstanceRef<ThreadGroup> = jvm.get_field(&this, "group", "Ljava/lang/ThreadGroup;").await?;
+        let _: () = jvm.invoke_virtual(&group, "add", "(Ljava/lang/Thread;)V", (this.clone(),)).await?;
+
+        // register before spawning, so jvm doesn't exit before thread gets scheduled
+        let daemon: bool = jvm.get_field(&this, "daemon", "Z").await?;
+        if !daemon {
+            jvm.non_daemon_thread_started();
+        }
 
         let id: i32 = jvm.invoke_virtual(&this, "hashCode", "()I", ()).await?;
 
@@ -115,7 +319,7 @@ impl Thread {
             Box::new(ThreadStartProxy {
                 jvm: jvm.clone(),
                 thread_id: id,
-                join_event,
+                daemon,
                 this: this.clone(),
             }),
         );
@@ -134,68 +338,262 @@ impl Thread {
         Ok(())
     }
 
-    async fn join(jvm: &Jvm, _context: &mut RuntimeContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+    async fn join(jvm: &Jvm, _context: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<()> {
         tracing::debug!("Thread::join({:?})", &this);
 
-        // TODO we don't have get same field twice
-        let raw_join_event: ClassInstanceRef<Array<i8>> = jvm.get_field(&this, "joinEvent", "[B").await?;
-        if raw_join_event.is_null() {
-            return Ok(()); // already joined or not started
-        }
+        jvm.monitor_enter(&this).await?;
 
-        let join_event: Arc<Event> = jvm.get_rust_object_field(&this, "joinEvent").await?;
-        join_event.listen().await;
+        let result = async {
+            loop {
+                let alive: bool = jvm.get_field(&this, "alive", "Z").await?;
+                if !alive {
+                    break Ok(());
+                }
 
-        jvm.put_field(&mut this, "joinEvent", "[B", None).await?;
+                let _: () = jvm.invoke_virtual(&this, "wait", "()V", ()).await?;
+            }
+        }
+        .await;
 
-        Ok(())
+        jvm.monitor_exit(&this).await?;
+
+        result
     }
 
-    async fn is_alive(_jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<bool> {
-        tracing::warn!("stub Thread::isAlive({:?})", &this);
+    async fn is_alive(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<bool> {
+        tracing::debug!("Thread::isAlive({:?})", &this);
 
-        Ok(true)
+        jvm.get_field(&this, "alive", "Z").await
     }
 
-    async fn sleep(_: &Jvm, context: &mut RuntimeContext, duration: i64) -> Result<i32> {
+    async fn sleep(jvm: &Jvm, context: &mut RuntimeContext, duration: i64) -> Result<()> {
         tracing::debug!("Thread::sleep({:?})", duration);
 
-        context.sleep(Duration::from_millis(duration as _)).await;
+        if duration < 0 {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "timeout value is negative").await);
+        }
+
+        jvm.set_thread_state(ThreadState::Sleeping).await;
+        let result = Self::interruptible(jvm, context.sleep(Duration::from_millis(duration as _))).await;
+        jvm.set_thread_state(ThreadState::Running).await;
 
-        Ok(0)
+        result?;
+
+        Ok(())
     }
 
-    async fn r#yield(_: &Jvm, context: &mut RuntimeContext) -> Result<i32> {
+    async fn r#yield(_: &Jvm, context: &mut RuntimeContext) -> Result<()> {
         tracing::debug!("Thread::yield()");
         context.r#yield().await;
 
-        Ok(0)
+        Ok(())
+    }
+
+    async fn get_name(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<String>> {
+        tracing::debug!("Thread::getName({:?})", &this);
+
+        jvm.get_field(&this, "name", "Ljava/lang/String;").await
+    }
+
+    async fn set_name(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> Result<()> {
+        tracing::debug!("Thread::setName({:?}, {:?})", &this, &name);
+
+        if name.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "name cannot be null").await);
+        }
+
+        jvm.put_field(&mut this, "name", "Ljava/lang/String;", name).await
+    }
+
+    async fn get_priority(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
+        tracing::debug!("Thread::getPriority({:?})", &this);
+
+        jvm.get_field(&this, "priority", "I").await
+    }
+
+    // priority is only informational, as threads are scheduled by the host
+    async fn set_priority(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, new_priority: i32) -> Result<()> {
+        tracing::debug!("Thread::setPriority({:?}, {:?})", &this, new_priority);
+
+        if !(Self::MIN_PRIORITY..=Self::MAX_PRIORITY).contains(&new_priority) {
+            return Err(jvm.exception("java/lang/IllegalArgumentException", "invalid priority").await);
+        }
+
+        let mut priority = new_priority;
+        let group: ClassInstanceRef<ThreadGroup> = jvm.get_field(&this, "group", "Ljava/lang/ThreadGroup;").await?;
+        if !group.is_null() {
+            let max_priority: i32 = jvm.invoke_virtual(&group, "getMaxPriority", "()I", ()).await?;
+            priority = priority.min(max_priority);
+        }
+
+        jvm.put_field(&mut this, "priority", "I", priority).await
+    }
+
+    async fn get_thread_group(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<ThreadGroup>> {
+        tracing::debug!("Thread::getThreadGroup({:?})", &this);
+
+        jvm.get_field(&this, "group", "Ljava/lang/ThreadGroup;").await
+    }
+
+    async fn active_count(jvm: &Jvm, _: &mut RuntimeContext) -> Result<i32> {
+        tracing::debug!("Thread::activeCount()");
+
+        let thread = Self::current(jvm).await?;
+        let group: ClassInstanceRef<ThreadGroup> = jvm.get_field(&thread, "group", "Ljava/lang/ThreadGroup;").await?;
+
+        jvm.invoke_virtual(&group, "activeCount", "()I", ()).await
+    }
+
+    async fn set_daemon(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, on: bool) -> Result<()> {
+        tracing::debug!("Thread::setDaemon({:?}, {:?})", &this, on);
+
+        let alive: bool = jvm.get_field(&this, "alive", "Z").await?;
+        if alive {
+            return Err(jvm.exception("java/lang/IllegalThreadStateException", "thread is alive").await);
+        }
+
+        jvm.put_field(&mut this, "daemon", "Z", on).await
+    }
+
+    async fn is_daemon(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<bool> {
+        tracing::debug!("Thread::isDaemon({:?})", &this);
+
+        jvm.get_field(&this, "daemon", "Z").await
     }
 
-    async fn set_priority(_: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Thread>, new_priority: i32) -> Result<()> {
-        tracing::warn!("stub java.lang.Thread::setPriority({:?}, {:?})", &this, new_priority);
+    async fn interrupt(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("Thread::interrupt({:?})", &this);
+
+        jvm.put_field(&mut this, "interrupted", "Z", true).await?;
+
+        let interrupt_event: Arc<Event> = jvm.get_rust_object_field(&this, "interruptEvent").await?;
+        interrupt_event.notify(usize::MAX);
 
         Ok(())
     }
 
+    async fn is_interrupted(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<bool> {
+        tracing::debug!("Thread::isInterrupted({:?})", &this);
+
+        jvm.get_field(&this, "interrupted", "Z").await
+    }
+
+    async fn interrupted(jvm: &Jvm, _: &mut RuntimeContext) -> Result<bool> {
+        tracing::debug!("Thread::interrupted()");
+
+        let mut thread = Self::current(jvm).await?;
+        let interrupted: bool = jvm.get_field(&thread, "interrupted", "Z").await?;
+        jvm.put_field(&mut thread, "interrupted", "Z", false).await?;
+
+        Ok(interrupted)
+    }
+
+    async fn to_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<String>> {
+        tracing::debug!("Thread::toString({:?})", &this);
+
+        let name = jvm.get_field(&this, "name", "Ljava/lang/String;").await?;
+        let name = JavaLangString::to_rust_string(jvm, &name).await?;
+        let priority: i32 = jvm.get_field(&this, "priority", "I").await?;
+
+        // terminated threads have no group
+        let group: ClassInstanceRef<ThreadGroup> = jvm.get_field(&this, "group", "Ljava/lang/ThreadGroup;").await?;
+        let group_name = if group.is_null() {
+            "".into()
+        } else {
+            let group_name = jvm.invoke_virtual(&group, "getName", "()Ljava/lang/String;", ()).await?;
+            JavaLangString::to_rust_string(jvm, &group_name).await?
+        };
+
+        let result = format!("Thread[{},{},{}]", name, priority, group_name);
+
+        Ok(JavaLangString::from_rust_string(jvm, &result).await?.into())
+    }
+
     async fn current_thread(jvm: &Jvm, _: &mut RuntimeContext) -> Result<ClassInstanceRef<Self>> {
-        tracing::warn!("stub Thread::currentThread()");
+        tracing::debug!("Thread::currentThread()");
+
+        Self::current(jvm).await
+    }
+
+    // called by jvm when thread is detached
+    async fn exit(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("Thread::exit({:?})", &this);
+
+        let group: ClassInstanceRef<ThreadGroup> = jvm.get_field(&this, "group", "Ljava/lang/ThreadGroup;").await?;
+        if !group.is_null() {
+            let _: () = jvm.invoke_virtual(&group, "remove", "(Ljava/lang/Thread;)V", (this.clone(),)).await?;
+            jvm.put_field(&mut this, "group", "Ljava/lang/ThreadGroup;", None).await?;
+        }
+
+        // wake up joining threads
+        jvm.monitor_enter(&this).await?;
+        jvm.put_field(&mut this, "alive", "Z", false).await?;
+        jvm.monitor_notify_all(&this).await?;
+        jvm.monitor_exit(&this).await
+    }
 
-        let thread = jvm.new_class("java/lang/Thread", "(Z)V", (true,)).await?;
+    async fn next_thread_num(jvm: &Jvm, _: &mut RuntimeContext) -> Result<i32> {
+        tracing::debug!("Thread::nextThreadNum()");
+
+        let number: i32 = jvm.get_static_field("java/lang/Thread", "threadInitNumber", "I").await?;
+        jvm.put_static_field("java/lang/Thread", "threadInitNumber", "I", number + 1).await?;
+
+        Ok(number)
+    }
+
+    async fn current(jvm: &Jvm) -> Result<ClassInstanceRef<Self>> {
+        Ok(jvm.current_java_thread().await?.into())
+    }
 
-        Ok(thread.into())
+    // waits for `future` unless current thread gets interrupted, throwing InterruptedException and clearing the interrupted status
+    pub(crate) async fn interruptible<F>(jvm: &Jvm, future: F) -> Result<F::Output>
+    where
+        F: Future,
+    {
+        let mut thread = Self::current(jvm).await?;
+        let interrupt_event: Arc<Event> = jvm.get_rust_object_field(&thread, "interruptEvent").await?;
+
+        // listen before checking the flag so we don't miss interrupt in between
+        let listener = interrupt_event.listen();
+
+        let interrupted: bool = jvm.get_field(&thread, "interrupted", "Z").await?;
+        let result = if interrupted {
+            None
+        } else {
+            future::or(async { Some(future.await) }, async {
+                listener.await;
+                None
+            })
+            .await
+        };
+
+        match result {
+            Some(x) => Ok(x),
+            None => {
+                jvm.put_field(&mut thread, "interrupted", "Z", false).await?;
+
+                Err(jvm.exception("java/lang/InterruptedException", "interrupted").await)
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use alloc::{boxed::Box, collections::BTreeMap, vec};
+    use alloc::{boxed::Box, collections::BTreeMap, string::String, sync::Arc, vec};
+
+    use async_lock::OnceCell;
 
     use java_class_proto::{JavaFieldProto, JavaMethodProto};
-    use jvm::{ClassInstanceRef, Jvm, Result};
+    use java_constants::MethodAccessFlags;
+    use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result};
     use jvm_rust::ClassDefinitionImpl;
 
-    use crate::{runtime::test::TestRuntime, test::create_test_jvm, RuntimeClassProto, RuntimeContext};
+    use crate::{
+        runtime::test::TestRuntime,
+        test::{create_test_jvm, test_jvm},
+        Runtime, RuntimeClassProto, RuntimeContext, SpawnCallback,
+    };
 
     struct TestClass;
     impl TestClass {
@@ -203,10 +601,11 @@ mod test {
             RuntimeClassProto {
                 name: "TestClass",
                 parent_class: Some("java/lang/Runnable"),
+                is_interface: false,
                 interfaces: vec![],
                 methods: vec![
-                    JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                    JavaMethodProto::new("run", "()V", Self::run, Default::default()),
+                    JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                    JavaMethodProto::new("run", "()V", Self::run, MethodAccessFlags::PUBLIC),
                 ],
                 fields: vec![JavaFieldProto::new("ran", "Z", Default::default())],
             }
@@ -250,4 +649,66 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_sleep_yield() -> Result<()> {
+        let jvm = test_jvm().await?;
+
+        let start: i64 = jvm.invoke_static("java/lang/System", "currentTimeMillis", "()J", ()).await?;
+        let _: () = jvm.invoke_static("java/lang/Thread", "sleep", "(J)V", (10i64,)).await?;
+        let _: () = jvm.invoke_static("java/lang/Thread", "yield", "()V", ()).await?;
+        let end: i64 = jvm.invoke_static("java/lang/System", "currentTimeMillis", "()J", ()).await?;
+
+        assert!(start > 0);
+        assert!(end - start >= 10);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_attach_thread() -> Result<()> {
+        let runtime = TestRuntime::new(BTreeMap::new());
+        let jvm = create_test_jvm(runtime.clone()).await?;
+
+        struct HostThread {
+            jvm: Jvm,
+            result: Arc<OnceCell<(String, bool, i32)>>,
+        }
+
+        #[async_trait::async_trait]
+        impl SpawnCallback for HostThread {
+            async fn call(&self) -> Result<()> {
+                self.jvm.attach_thread("host", true).await?;
+
+                let thread = self
+                    .jvm
+                    .invoke_static("java/lang/Thread", "currentThread", "()Ljava/lang/Thread;", ())
+                    .await?;
+                let name = self.jvm.invoke_virtual(&thread, "getName", "()Ljava/lang/String;", ()).await?;
+                let daemon: bool = self.jvm.invoke_virtual(&thread, "isDaemon", "()Z", ()).await?;
+                let active_count: i32 = self.jvm.invoke_static("java/lang/Thread", "activeCount", "()I", ()).await?;
+
+                let name = JavaLangString::to_rust_string(&self.jvm, &name).await?;
+                let _ = self.result.set((name, daemon, active_count)).await;
+
+                self.jvm.detach_thread().await
+            }
+        }
+
+        let result = Arc::new(OnceCell::new());
+        runtime.spawn(
+            &jvm,
+            Box::new(HostThread {
+                jvm: jvm.clone(),
+                result: result.clone(),
+            }),
+        );
+
+        let (name, daemon, active_count) = result.wait().await;
+        assert_eq!(name, "host");
+        assert!(*daemon);
+        assert_eq!(*active_count, 2); // main and host
+
+        Ok(())
+    }
 }
@@ -0,0 +1,180 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result};
+
+use crate::{
+    classes::{
+        java::lang::{String, Thread},
+        java::util::Vector,
+    },
+    RuntimeClassProto, RuntimeContext,
+};
+
+// class java.lang.ThreadGroup
+pub struct ThreadGroup;
+
+impl ThreadGroup {
+    pub fn as_proto() -> RuntimeClassProto {
+        RuntimeClassProto {
+            name: "java/lang/ThreadGroup",
+            parent_class: Some("java/lang/Object"),
+            is_interface: false,
+            interfaces: vec![],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "<init>",
+                    "(Ljava/lang/ThreadGroup;Ljava/lang/String;)V",
+                    Self::init_with_parent,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("getName", "()Ljava/lang/String;", Self::get_name, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getParent", "()Ljava/lang/ThreadGroup;", Self::get_parent, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getMaxPriority", "()I", Self::get_max_priority, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "activeCount",
+                    "()I",
+                    Self::active_count,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::SYNCHRONIZED,
+                ),
+                // rustjava internal
+                JavaMethodProto::new("<init>", "()V", Self::init_system, MethodAccessFlags::PRIVATE),
+                JavaMethodProto::new("add", "(Ljava/lang/Thread;)V", Self::add, MethodAccessFlags::SYNCHRONIZED),
+                JavaMethodProto::new("remove", "(Ljava/lang/Thread;)V", Self::remove, MethodAccessFlags::SYNCHRONIZED),
+            ],
+            fields: vec![
+                JavaFieldProto::new("name", "Ljava/lang/String;", Default::default()),
+                JavaFieldProto::new("parent", "Ljava/lang/ThreadGroup;", Default::default()),
+                JavaFieldProto::new("maxPriority", "I", Default::default()),
+                JavaFieldProto::new("threads", "Ljava/util/Vector;", Default::default()),
+                JavaFieldProto::new("groups", "Ljava/util/Vector;", Default::default()),
+            ],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, name: ClassInstanceRef<String>) -> Result<()> {
+        tracing::debug!("java.lang.ThreadGroup::<init>({:?}, {:?})", &this, &name);
+
+        let current_thread: ClassInstanceRef<Thread> = jvm.invoke_static("java/lang/Thread", "currentThread", "()Ljava/lang/Thread;", ()).await?;
+        let parent: ClassInstanceRef<Self> = jvm
+            .invoke_virtual(&current_thread, "getThreadGroup", "()Ljava/lang/ThreadGroup;", ())
+            .await?;
+
+        jvm.invoke_special(
+            &this,
+            "java/lang/ThreadGroup",
+            "<init>",
+            "(Ljava/lang/ThreadGroup;Ljava/lang/String;)V",
+            (parent, name),
+        )
+        .await
+    }
+
+    async fn init_with_parent(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        mut this: ClassInstanceRef<Self>,
+        parent: ClassInstanceRef<Self>,
+        name: ClassInstanceRef<String>,
+    ) -> Result<()> {
+        tracing::debug!("java.lang.ThreadGroup::<init>({:?}, {:?}, {:?})", &this, &parent, &name);
+
+        if parent.is_null() {
+            return Err(jvm.exception("java/lang/NullPointerException", "parent is null").await);
+        }
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        let max_priority: i32 = jvm.get_field(&parent, "maxPriority", "I").await?;
+        jvm.put_field(&mut this, "name", "Ljava/lang/String;", name).await?;
+        jvm.put_field(&mut this, "parent", "Ljava/lang/ThreadGroup;", parent.clone()).await?;
+        jvm.put_field(&mut this, "maxPriority", "I", max_priority).await?;
+        Self::init_members(jvm, &mut this).await?;
+
+        jvm.monitor_enter(&parent).await?;
+        let groups: ClassInstanceRef<Vector> = jvm.get_field(&parent, "groups", "Ljava/util/Vector;").await?;
+        let result: Result<()> = jvm.invoke_virtual(&groups, "addElement", "(Ljava/lang/Object;)V", (this.clone(),)).await;
+        jvm.monitor_exit(&parent).await?;
+
+        result
+    }
+
+    async fn init_system(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>) -> Result<()> {
+        tracing::debug!("java.lang.ThreadGroup::<init>({:?})", &this);
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        let name = JavaLangString::from_rust_string(jvm, "system").await?;
+        jvm.put_field(&mut this, "name", "Ljava/lang/String;", name).await?;
+        jvm.put_field(&mut this, "maxPriority", "I", Thread::MAX_PRIORITY).await?;
+        Self::init_members(jvm, &mut this).await
+    }
+
+    async fn get_name(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<String>> {
+        tracing::debug!("java.lang.ThreadGroup::getName({:?})", &this);
+
+        jvm.get_field(&this, "name", "Ljava/lang/String;").await
+    }
+
+    async fn get_parent(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Self>> {
+        tracing::debug!("java.lang.ThreadGroup::getParent({:?})", &this);
+
+        jvm.get_field(&this, "parent", "Ljava/lang/ThreadGroup;").await
+    }
+
+    async fn get_max_priority(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
+        tracing::debug!("java.lang.ThreadGroup::getMaxPriority({:?})", &this);
+
+        jvm.get_field(&this, "maxPriority", "I").await
+    }
+
+    // counts live threads in this group and its subgroups
+    async fn active_count(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<i32> {
+        tracing::debug!("java.lang.ThreadGroup::activeCount({:?})", &this);
+
+        let threads: ClassInstanceRef<Vector> = jvm.get_field(&this, "threads", "Ljava/util/Vector;").await?;
+        let mut count: i32 = jvm.invoke_virtual(&threads, "size", "()I", ()).await?;
+
+        let groups: ClassInstanceRef<Vector> = jvm.get_field(&this, "groups", "Ljava/util/Vector;").await?;
+        let group_count: i32 = jvm.invoke_virtual(&groups, "size", "()I", ()).await?;
+        for i in 0..group_count {
+            let group: ClassInstanceRef<Self> = jvm.invoke_virtual(&groups, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+            let group_active_count: i32 = jvm.invoke_virtual(&group, "activeCount", "()I", ()).await?;
+
+            count += group_active_count;
+        }
+
+        Ok(count)
+    }
+
+    async fn add(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, thread: ClassInstanceRef<Thread>) -> Result<()> {
+        tracing::debug!("java.lang.ThreadGroup::add({:?}, {:?})", &this, &thread);
+
+        let threads: ClassInstanceRef<Vector> = jvm.get_field(&this, "threads", "Ljava/util/Vector;").await?;
+
+        jvm.invoke_virtual(&threads, "addElement", "(Ljava/lang/Object;)V", (thread,)).await
+    }
+
+    async fn remove(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>, thread: ClassInstanceRef<Thread>) -> Result<()> {
+        tracing::debug!("java.lang.ThreadGroup::remove({:?}, {:?})", &this, &thread);
+
+        let threads: ClassInstanceRef<Vector> = jvm.get_field(&this, "threads", "Ljava/util/Vector;").await?;
+
+        let index: i32 = jvm.invoke_virtual(&threads, "lastIndexOf", "(Ljava/lang/Object;)I", (thread,)).await?;
+        if index >= 0 {
+            let _: () = jvm.invoke_virtual(&threads, "removeElementAt", "(I)V", (index,)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn init_members(jvm: &Jvm, this: &mut ClassInstanceRef<Self>) -> Result<()> {
+        let threads = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        jvm.put_field(this, "threads", "Ljava/util/Vector;", threads).await?;
+
+        let groups = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        jvm.put_field(this, "groups", "Ljava/util/Vector;", groups).await
+    }
+}
@@ -1,6 +1,7 @@
 use alloc::{boxed::Box, format, string::String as RustString, sync::Arc, vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, ClassInstance, ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -19,29 +20,31 @@ impl Throwable {
         RuntimeClassProto {
             name: "java/lang/Throwable",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_message, Default::default()),
-                JavaMethodProto::new("toString", "()Ljava/lang/String;", Self::to_string, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_message, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getMessage", "()Ljava/lang/String;", Self::get_message, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("toString", "()Ljava/lang/String;", Self::to_string, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "fillInStackTrace",
                     "()Ljava/lang/Throwable;",
                     Self::fill_in_stack_trace,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
-                JavaMethodProto::new("printStackTrace", "()V", Self::print_stack_trace, Default::default()),
+                JavaMethodProto::new("printStackTrace", "()V", Self::print_stack_trace, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "printStackTrace",
                     "(Ljava/io/PrintStream;)V",
                     Self::print_stack_trace_to_print_stream,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
                 JavaMethodProto::new(
                     "printStackTrace",
                     "(Ljava/io/PrintWriter;)V",
                     Self::print_stack_trace_to_print_writer,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
             ],
             fields: vec![
@@ -119,6 +122,12 @@ impl Throwable {
         Ok(())
     }
 
+    async fn get_message(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<String>> {
+        tracing::debug!("java.lang.Throwable::getMessage({:?})", &this);
+
+        jvm.get_field(&this, "detailMessage", "Ljava/lang/String;").await
+    }
+
     async fn to_string(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<String>> {
         tracing::debug!("java.lang.Throwable::toString({:?})", &this);
 
@@ -1,6 +1,7 @@
 use alloc::{format, vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -20,45 +21,51 @@ impl URL {
         RuntimeClassProto {
             name: "java/net/URL",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_spec, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_spec, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "<init>",
                     "(Ljava/net/URL;Ljava/lang/String;)V",
                     Self::init_with_context_spec,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
                 JavaMethodProto::new(
                     "<init>",
                     "(Ljava/net/URL;Ljava/lang/String;Ljava/net/URLStreamHandler;)V",
                     Self::init_with_context_spec_handler,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
                 JavaMethodProto::new(
                     "<init>",
                     "(Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;)V",
                     Self::init_with_protocol_host_file,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
                 JavaMethodProto::new(
                     "<init>",
                     "(Ljava/lang/String;Ljava/lang/String;ILjava/lang/String;Ljava/net/URLStreamHandler;)V",
                     Self::init_with_protocol_host_port_file_handler,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
-                JavaMethodProto::new("openConnection", "()Ljava/net/URLConnection;", Self::open_connection, Default::default()),
-                JavaMethodProto::new("openStream", "()Ljava/io/InputStream;", Self::open_stream, Default::default()),
+                JavaMethodProto::new(
+                    "openConnection",
+                    "()Ljava/net/URLConnection;",
+                    Self::open_connection,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("openStream", "()Ljava/io/InputStream;", Self::open_stream, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "set",
                     "(Ljava/lang/String;Ljava/lang/String;ILjava/lang/String;Ljava/lang/String;)V",
                     Self::set,
-                    Default::default(),
+                    MethodAccessFlags::PROTECTED,
                 ),
-                JavaMethodProto::new("getPort", "()I", Self::get_port, Default::default()),
-                JavaMethodProto::new("getProtocol", "()Ljava/lang/String;", Self::get_protocol, Default::default()),
-                JavaMethodProto::new("getHost", "()Ljava/lang/String;", Self::get_host, Default::default()),
-                JavaMethodProto::new("getFile", "()Ljava/lang/String;", Self::get_file, Default::default()),
+                JavaMethodProto::new("getPort", "()I", Self::get_port, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getProtocol", "()Ljava/lang/String;", Self::get_protocol, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getHost", "()Ljava/lang/String;", Self::get_host, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getFile", "()Ljava/lang/String;", Self::get_file, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![
                 JavaFieldProto::new("protocol", "Ljava/lang/String;", Default::default()),
@@ -323,4 +330,19 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_absolute_file_url() -> Result<()> {
+        let jvm = test_jvm().await?;
+
+        for spec in ["file:/tmp/test.jar", "file:///tmp/test.jar"] {
+            let url_spec = JavaLangString::from_rust_string(&jvm, spec).await?;
+            let url = jvm.new_class("java/net/URL", "(Ljava/lang/String;)V", (url_spec,)).await?;
+
+            let file = jvm.invoke_virtual(&url, "getFile", "()Ljava/lang/String;", ()).await?;
+            assert_eq!(JavaLangString::to_rust_string(&jvm, &file).await?, "/tmp/test.jar");
+        }
+
+        Ok(())
+    }
 }
@@ -1,10 +1,16 @@
-use alloc::{format, vec, vec::Vec};
+use alloc::{collections::BTreeSet, format, string::String as RustString, vec, vec::Vec};
+
+// XXX for zip..
+extern crate std;
+use std::io::{Cursor, Read};
 
 use bytemuck::cast_vec;
+use zip::ZipArchive;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{
-    runtime::{JavaIoInputStream, JavaLangString},
+    runtime::{JavaIoInputStream, JavaLangClassLoader, JavaLangString},
     Array, ClassInstanceRef, Jvm, Result,
 };
 
@@ -12,9 +18,12 @@ use crate::{
     classes::java::{
         lang::{Class, ClassLoader, String},
         net::{JarURLConnection, URL},
-        util::jar::JarEntry,
+        util::{
+            jar::{JarEntry, Manifest},
+            Enumeration,
+        },
     },
-    RuntimeClassProto, RuntimeContext,
+    FileType, RuntimeClassProto, RuntimeContext,
 };
 
 // class java.net.URLClassLoader
@@ -25,15 +34,33 @@ impl URLClassLoader {
         RuntimeClassProto {
             name: "java/net/URLClassLoader",
             parent_class: Some("java/lang/ClassLoader"), // TODO java.security.SecureClassLoader
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "([Ljava/net/URL;Ljava/lang/ClassLoader;)V", Self::init, Default::default()),
-                JavaMethodProto::new("findClass", "(Ljava/lang/String;)Ljava/lang/Class;", Self::find_class, Default::default()),
+                JavaMethodProto::new("<init>", "([Ljava/net/URL;)V", Self::init_with_system_parent, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "<init>",
+                    "([Ljava/net/URL;Ljava/lang/ClassLoader;)V",
+                    Self::init,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "findClass",
+                    "(Ljava/lang/String;)Ljava/lang/Class;",
+                    Self::find_class,
+                    MethodAccessFlags::PROTECTED,
+                ),
                 JavaMethodProto::new(
                     "findResource",
                     "(Ljava/lang/String;)Ljava/net/URL;",
                     Self::find_resource,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "findResources",
+                    "(Ljava/lang/String;)Ljava/util/Enumeration;",
+                    Self::find_resources,
+                    MethodAccessFlags::PUBLIC,
                 ),
             ],
             fields: vec![JavaFieldProto::new("urls", "[Ljava/net/URL;", Default::default())],
@@ -42,7 +69,7 @@ impl URLClassLoader {
 
     async fn init(
         jvm: &Jvm,
-        _: &mut RuntimeContext,
+        context: &mut RuntimeContext,
         mut this: ClassInstanceRef<Self>,
         urls: ClassInstanceRef<Array<URL>>,
         parent: ClassInstanceRef<ClassLoader>,
@@ -53,11 +80,36 @@ impl URLClassLoader {
             .invoke_special(&this, "java/lang/ClassLoader", "<init>", "(Ljava/lang/ClassLoader;)V", (parent,))
             .await?;
 
-        jvm.put_field(&mut this, "urls", "[Ljava/net/URL;", urls).await?;
+        let urls: Vec<ClassInstanceRef<URL>> = jvm.load_array(&urls, 0, jvm.array_length(&urls).await? as _).await?;
+        let urls = Self::expand_manifest_class_path(jvm, context, urls).await?;
+
+        let mut url_array = jvm.instantiate_array("Ljava/net/URL;", urls.len()).await?;
+        jvm.store_array(&mut url_array, 0, urls).await?;
+        jvm.put_field(&mut this, "urls", "[Ljava/net/URL;", url_array).await?;
 
         Ok(())
     }
 
+    async fn init_with_system_parent(
+        jvm: &Jvm,
+        _: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        urls: ClassInstanceRef<Array<URL>>,
+    ) -> Result<()> {
+        tracing::debug!("java.net.URLClassLoader::<init>({:?}, {:?})", &this, &urls);
+
+        let parent = JavaLangClassLoader::get_system_class_loader(jvm).await?;
+
+        jvm.invoke_special(
+            &this,
+            "java/net/URLClassLoader",
+            "<init>",
+            "([Ljava/net/URL;Ljava/lang/ClassLoader;)V",
+            (urls, parent),
+        )
+        .await
+    }
+
     async fn find_class(
         jvm: &Jvm,
         context: &mut RuntimeContext,
@@ -66,7 +118,8 @@ impl URLClassLoader {
     ) -> Result<ClassInstanceRef<Class>> {
         tracing::debug!("java.net.URLClassLoader::findClass({:?}, {:?})", &this, name);
 
-        let name_str = JavaLangString::to_rust_string(jvm, &name).await?;
+        // subclasses may call us with binary names like `java.lang.String`
+        let name_str = JavaLangString::to_rust_string(jvm, &name).await?.replace('.', "/");
 
         // find rustjar first
         let urls = jvm.get_field(&this, "urls", "[Ljava/net/URL;").await?;
@@ -86,14 +139,14 @@ impl URLClassLoader {
             }
         }
 
-        let resource_name = format!("{}.class", name_str.replace('.', "/"));
+        let resource_name = format!("{}.class", name_str);
         let resource_name = JavaLangString::from_rust_string(jvm, &resource_name).await?;
 
         let resource: ClassInstanceRef<URL> = jvm
             .invoke_virtual(&this, "findResource", "(Ljava/lang/String;)Ljava/net/URL;", (resource_name,))
             .await?;
         if resource.is_null() {
-            return Ok(None.into());
+            return Err(jvm.exception("java/lang/ClassNotFoundException", &name_str.replace('/', ".")).await);
         }
 
         let stream = jvm.invoke_virtual(&resource, "openStream", "()Ljava/io/InputStream;", ()).await?;
@@ -131,64 +184,208 @@ impl URLClassLoader {
         let urls: Vec<ClassInstanceRef<URL>> = jvm.load_array(&urls, 0, jvm.array_length(&urls).await? as _).await?;
 
         for url in urls {
+            let resource = Self::find_resource_in(jvm, runtime, &url, &name_str).await?;
+            if !resource.is_null() {
+                return Ok(resource);
+            }
+        }
+
+        Ok(None.into())
+    }
+
+    async fn find_resources(
+        jvm: &Jvm,
+        runtime: &mut RuntimeContext,
+        this: ClassInstanceRef<Self>,
+        name: ClassInstanceRef<String>,
+    ) -> Result<ClassInstanceRef<Enumeration>> {
+        tracing::debug!("java.net.URLClassLoader::findResources({:?}, {:?})", &this, name);
+
+        let name_str = JavaLangString::to_rust_string(jvm, &name).await?;
+
+        let urls = jvm.get_field(&this, "urls", "[Ljava/net/URL;").await?;
+        let urls: Vec<ClassInstanceRef<URL>> = jvm.load_array(&urls, 0, jvm.array_length(&urls).await? as _).await?;
+
+        let resources = jvm.new_class("java/util/Vector", "()V", ()).await?;
+        for url in urls {
+            let resource = Self::find_resource_in(jvm, runtime, &url, &name_str).await?;
+            if !resource.is_null() {
+                let _: () = jvm.invoke_virtual(&resources, "addElement", "(Ljava/lang/Object;)V", (resource,)).await?;
+            }
+        }
+
+        jvm.invoke_virtual(&resources, "elements", "()Ljava/util/Enumeration;", ()).await
+    }
+
+    // finds resource in directory or jar pointed by `url`
+    async fn find_resource_in(jvm: &Jvm, runtime: &mut RuntimeContext, url: &ClassInstanceRef<URL>, name_str: &str) -> Result<ClassInstanceRef<URL>> {
+        let file = jvm.invoke_virtual(url, "getFile", "()Ljava/lang/String;", ()).await?;
+        let file = JavaLangString::to_rust_string(jvm, &file).await?;
+        if file.ends_with('/') || file.is_empty() {
+            // directory
+            let final_path = if file.ends_with('/') {
+                format!("{}{}", file, name_str)
+            } else {
+                name_str.into()
+            };
+
+            if runtime.metadata(&final_path).await.is_ok() {
+                let new_url = jvm
+                    .new_class(
+                        "java/net/URL",
+                        "(Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;)V",
+                        (
+                            JavaLangString::from_rust_string(jvm, "file").await?,
+                            JavaLangString::from_rust_string(jvm, "").await?,
+                            JavaLangString::from_rust_string(jvm, &final_path).await?,
+                        ),
+                    )
+                    .await?;
+
+                return Ok(new_url.into());
+            }
+        } else if file.ends_with(".rustjar") {
+            // TODO rustjar resource
+        } else {
+            // treat as jar
+            let name_str = name_str.trim_start_matches('/');
+
+            let jar_url_str = format!("jar:file:{}!/{}", file, name_str); // TODO url might not be file
+            let jar_url = JavaLangString::from_rust_string(jvm, &jar_url_str).await?;
+            let jar_url = jvm.new_class("java/net/URL", "(Ljava/lang/String;)V", (jar_url,)).await?;
+            let connection: ClassInstanceRef<JarURLConnection> =
+                jvm.invoke_virtual(&jar_url, "openConnection", "()Ljava/net/URLConnection;", ()).await?;
+
+            let entry: ClassInstanceRef<JarEntry> = jvm.invoke_virtual(&connection, "getJarEntry", "()Ljava/util/jar/JarEntry;", ()).await?;
+
+            if !entry.is_null() {
+                return Ok(jar_url.into());
+            }
+        }
+
+        Ok(None.into())
+    }
+
+    // inserts jars listed in Class-Path manifest attribute right after the jar referencing them, transitively.
+    // each path is searched once, so reference cycles between jars are fine
+    async fn expand_manifest_class_path(
+        jvm: &Jvm,
+        context: &mut RuntimeContext,
+        urls: Vec<ClassInstanceRef<URL>>,
+    ) -> Result<Vec<ClassInstanceRef<URL>>> {
+        let mut result = Vec::with_capacity(urls.len());
+        let mut visited = BTreeSet::new();
+
+        let mut pending = urls;
+        pending.reverse();
+        while let Some(url) = pending.pop() {
             let file = jvm.invoke_virtual(&url, "getFile", "()Ljava/lang/String;", ()).await?;
             let file = JavaLangString::to_rust_string(jvm, &file).await?;
-            if file.ends_with('/') || file.is_empty() {
-                // directory
-                let final_path = if file.ends_with('/') {
-                    format!("{}{}", file, name_str)
-                } else {
-                    name_str.clone()
-                };
-
-                if runtime.metadata(&final_path).await.is_ok() {
-                    let new_url = jvm
-                        .new_class(
-                            "java/net/URL",
-                            "(Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;)V",
-                            (
-                                JavaLangString::from_rust_string(jvm, "file").await?,
-                                JavaLangString::from_rust_string(jvm, "").await?,
-                                JavaLangString::from_rust_string(jvm, &final_path).await?,
-                            ),
-                        )
-                        .await?;
-
-                    return Ok(new_url.into());
-                }
-            } else if file.ends_with(".rustjar") {
-                // TODO rustjar resource
-            } else {
-                // treat as jar
-                let name_str = name_str.trim_start_matches('/');
 
-                let jar_url_str = format!("jar:file:{}!/{}", file, name_str); // TODO url might not be file
-                let jar_url = JavaLangString::from_rust_string(jvm, &jar_url_str).await?;
-                let jar_url = jvm.new_class("java/net/URL", "(Ljava/lang/String;)V", (jar_url,)).await?;
-                let connection: ClassInstanceRef<JarURLConnection> =
-                    jvm.invoke_virtual(&jar_url, "openConnection", "()Ljava/net/URLConnection;", ()).await?;
+            if !visited.insert(normalize_path(&file)) {
+                continue;
+            }
+            result.push(url);
 
-                let entry: ClassInstanceRef<JarEntry> = jvm.invoke_virtual(&connection, "getJarEntry", "()Ljava/util/jar/JarEntry;", ()).await?;
+            let is_jar = !file.is_empty() && !file.ends_with('/') && !file.ends_with(".rustjar");
+            if !is_jar || !context.metadata(&file).await.is_ok_and(|x| x.r#type == FileType::File) {
+                continue;
+            }
 
-                if !entry.is_null() {
-                    return Ok(jar_url.into());
-                }
+            let base = file.rfind('/').map(|x| &file[..=x]).unwrap_or_default();
+            for entry in Self::manifest_class_path(context, &file).await.into_iter().rev() {
+                let path = if entry.starts_with('/') { entry } else { format!("{}{}", base, entry) };
+
+                let spec = JavaLangString::from_rust_string(jvm, &file_url_spec(&normalize_path(&path))).await?;
+                pending.push(jvm.new_class("java/net/URL", "(Ljava/lang/String;)V", (spec,)).await?.into());
             }
         }
 
-        Ok(None.into())
+        Ok(result)
+    }
+
+    // reads manifest without java classes, as they would be resolved through this class loader which isn't ready yet.
+    // unreadable jars are skipped like missing ones
+    async fn manifest_class_path(context: &mut RuntimeContext, jar_path: &str) -> Vec<RustString> {
+        let Ok(mut file) = context.open(jar_path, false, false).await else {
+            return Vec::new();
+        };
+
+        let mut data = Vec::new();
+        let mut buf = vec![0; 4096];
+        while let Ok(read) = file.read(&mut buf).await {
+            if read == 0 {
+                break;
+            }
+            data.extend_from_slice(&buf[..read]);
+        }
+
+        let Ok(mut archive) = ZipArchive::new(Cursor::new(data)) else {
+            return Vec::new();
+        };
+        let mut manifest = RustString::new();
+        let read = archive.by_name("META-INF/MANIFEST.MF").map(|mut x| x.read_to_string(&mut manifest));
+        if !matches!(read, Ok(Ok(_))) {
+            return Vec::new();
+        }
+
+        Manifest::parse_main_attributes(manifest.lines())
+            .into_iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("Class-Path"))
+            .map(|(_, value)| value.split_whitespace().map(|x| x.into()).collect())
+            .unwrap_or_default()
+    }
+}
+
+// absolute paths get empty authority as in `file:///app/lib.jar`, and relative ones are kept relative like `file:lib.jar`
+fn file_url_spec(path: &str) -> RustString {
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        format!("file:{}", path)
     }
 }
 
+// removes `.` and `..` segments, so that the same file is recognized through different relative paths
+fn normalize_path(path: &str) -> RustString {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "." => {}
+            ".." if segments.last().is_some_and(|x| !x.is_empty() && *x != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    // keep trailing slash of directories
+    if path.ends_with("/.") || path.ends_with("/..") {
+        segments.push("");
+    }
+
+    segments.join("/")
+}
+
 #[cfg(test)]
 mod test {
-    use alloc::vec;
+    use alloc::{boxed::Box, sync::Arc, vec};
+    use core::sync::atomic::{AtomicUsize, Ordering};
 
+    use async_lock::OnceCell;
     use bytemuck::cast_vec;
 
-    use jvm::{runtime::JavaLangString, ClassInstanceRef, Result};
+    use jvm::{
+        runtime::{JavaLangClass, JavaLangString},
+        ClassInstance, ClassInstanceRef, ClassUnloadHook, Jvm, Result, SchedulePoint,
+    };
 
-    use crate::{classes::java::net::URL, test::test_jvm_filesystem};
+    use crate::{
+        classes::java::{lang::Class, net::URL},
+        runtime::test::TestRuntime,
+        test::{create_test_jvm, test_jvm_filesystem},
+        Runtime, SpawnCallback,
+    };
 
     #[tokio::test]
     async fn test_jar_loading() -> Result<()> {
@@ -335,4 +532,171 @@ mod test {
 
         Ok(())
     }
+
+    struct UnloadCounter(Arc<AtomicUsize>);
+
+    impl ClassUnloadHook for UnloadCounter {
+        fn class_unloaded(&self, _: &str) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    // attached thread which keeps reaching schedule points until released
+    struct OtherThread {
+        jvm: Jvm,
+        attached: Arc<OnceCell<()>>,
+        release: Arc<OnceCell<()>>,
+        detached: Arc<OnceCell<()>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SpawnCallback for OtherThread {
+        async fn call(&self) -> Result<()> {
+            self.jvm.attach_thread("other", true).await?;
+            let _ = self.attached.set(()).await;
+
+            while !self.release.is_initialized() {
+                self.jvm.schedule(SchedulePoint::BackEdge).await;
+                tokio::task::yield_now().await;
+            }
+
+            self.jvm.detach_thread().await?;
+            let _ = self.detached.set(()).await;
+
+            Ok(())
+        }
+    }
+
+    async fn load_plugin_class(jvm: &Jvm) -> Result<(Box<dyn ClassInstance>, Box<dyn ClassInstance>)> {
+        let url_str = JavaLangString::from_rust_string(jvm, "file:a.jar").await?;
+        let url = jvm.new_class("java/net/URL", "(Ljava/lang/String;)V", (url_str,)).await?;
+        let mut urls = jvm.instantiate_array("Ljava/net/URL;", 1).await?;
+        jvm.store_array(&mut urls, 0, vec![url]).await?;
+
+        let class_loader = jvm
+            .new_class("java/net/URLClassLoader", "([Ljava/net/URL;Ljava/lang/ClassLoader;)V", (urls, None))
+            .await?;
+
+        let name = JavaLangString::from_rust_string(jvm, "com.acme.Util").await?;
+        let class = jvm
+            .invoke_virtual(&class_loader, "loadClass", "(Ljava/lang/String;)Ljava/lang/Class;", (name,))
+            .await?;
+
+        Ok((class_loader, class))
+    }
+
+    #[tokio::test]
+    async fn test_class_unloading() -> Result<()> {
+        let jar = include_bytes!("../../../../../test_data/plugins/a.jar");
+        let filesystem = [("a.jar".into(), jar.to_vec())].into_iter().collect();
+        let runtime = TestRuntime::new(filesystem);
+        let jvm = create_test_jvm(runtime.clone()).await?;
+
+        let unloaded = Arc::new(AtomicUsize::new(0));
+        jvm.set_class_unload_hook(Box::new(UnloadCounter(unloaded.clone()))).await;
+
+        // other threads are stopped at a safepoint while we unload
+        let (attached, release, detached) = (Arc::new(OnceCell::new()), Arc::new(OnceCell::new()), Arc::new(OnceCell::new()));
+        runtime.spawn(
+            &jvm,
+            Box::new(OtherThread {
+                jvm: jvm.clone(),
+                attached: attached.clone(),
+                release: release.clone(),
+                detached: detached.clone(),
+            }),
+        );
+        attached.wait().await;
+
+        let (class_loader, class) = load_plugin_class(&jvm).await?;
+        let instance = JavaLangClass::to_rust_class(&jvm, &class).await?.instantiate()?;
+
+        let name = JavaLangString::from_rust_string(&jvm, "[Lcom.acme.Util;").await?;
+        let _: ClassInstanceRef<Class> = jvm
+            .invoke_virtual(&class_loader, "loadClass", "(Ljava/lang/String;)Ljava/lang/Class;", (name,))
+            .await?;
+
+        assert!(jvm.unload_classes().await?.is_empty());
+
+        // instance keeps its class and class loader alive
+        drop(class_loader);
+        drop(class);
+        assert!(jvm.unload_classes().await?.is_empty());
+
+        drop(instance);
+        assert_eq!(jvm.unload_classes().await?, ["[Lcom/acme/Util;", "com/acme/Util"]);
+        assert_eq!(unloaded.load(Ordering::SeqCst), 2);
+
+        // new class loader takes over id of the unloaded one
+        let (class_loader, class) = load_plugin_class(&jvm).await?;
+        drop(class_loader);
+        drop(class);
+        assert_eq!(jvm.unload_classes().await?, ["com/acme/Util"]);
+        assert_eq!(unloaded.load(Ordering::SeqCst), 3);
+
+        let _ = release.set(()).await;
+        detached.wait().await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_absolute_manifest_class_path() -> Result<()> {
+        // absolute.jar references /opt/lib/first.jar, which references second.jar next to it
+        let filesystem = [
+            (
+                "absolute.jar".into(),
+                include_bytes!("../../../../../test_data/multijar/absolute.jar").to_vec(),
+            ),
+            (
+                "/opt/lib/first.jar".into(),
+                include_bytes!("../../../../../test_data/multijar/lib/first.jar").to_vec(),
+            ),
+            (
+                "/opt/lib/second.jar".into(),
+                include_bytes!("../../../../../test_data/multijar/lib/second.jar").to_vec(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let jvm = test_jvm_filesystem(filesystem).await?;
+
+        let url_str = JavaLangString::from_rust_string(&jvm, "file:absolute.jar").await?;
+        let url = jvm.new_class("java/net/URL", "(Ljava/lang/String;)V", (url_str,)).await?;
+        let mut urls = jvm.instantiate_array("Ljava/net/URL;", 1).await?;
+        jvm.store_array(&mut urls, 0, vec![url]).await?;
+
+        let class_loader = jvm
+            .new_class("java/net/URLClassLoader", "([Ljava/net/URL;Ljava/lang/ClassLoader;)V", (urls, None))
+            .await?;
+
+        for name in ["first.First", "second.Second"] {
+            let name = JavaLangString::from_rust_string(&jvm, name).await?;
+            let class: ClassInstanceRef<Class> = jvm
+                .invoke_virtual(&class_loader, "loadClass", "(Ljava/lang/String;)Ljava/lang/Class;", (name,))
+                .await?;
+            assert!(!class.is_null());
+        }
+
+        let resource_name = JavaLangString::from_rust_string(&jvm, "first/First.class").await?;
+        let resource: ClassInstanceRef<URL> = jvm
+            .invoke_virtual(&class_loader, "findResource", "(Ljava/lang/String;)Ljava/net/URL;", (resource_name,))
+            .await?;
+        let file = jvm.invoke_virtual(&resource, "getFile", "()Ljava/lang/String;", ()).await?;
+        assert_eq!(
+            JavaLangString::to_rust_string(&jvm, &file).await?,
+            "file:/opt/lib/first.jar!/first/First.class"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(super::normalize_path("./lib/../lib/first.jar"), "lib/first.jar");
+        assert_eq!(super::normalize_path("/app/./lib/../first.jar"), "/app/first.jar");
+        assert_eq!(super::normalize_path("../first.jar"), "../first.jar");
+        assert_eq!(super::normalize_path("lib/.."), "");
+        assert_eq!(super::normalize_path("classes/"), "classes/");
+    }
 }
@@ -3,6 +3,7 @@ use alloc::{borrow::ToOwned, string::ToString, vec};
 use url::Url;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -18,16 +19,22 @@ impl URLStreamHandler {
         RuntimeClassProto {
             name: "java/net/URLStreamHandler",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new_abstract("openConnection", "(Ljava/net/URL;)Ljava/net/URLConnection;", Default::default()),
-                JavaMethodProto::new("parseURL", "(Ljava/net/URL;Ljava/lang/String;II)V", Self::parse_url, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new_abstract("openConnection", "(Ljava/net/URL;)Ljava/net/URLConnection;", MethodAccessFlags::PROTECTED),
+                JavaMethodProto::new(
+                    "parseURL",
+                    "(Ljava/net/URL;Ljava/lang/String;II)V",
+                    Self::parse_url,
+                    MethodAccessFlags::PROTECTED,
+                ),
                 JavaMethodProto::new(
                     "setURL",
                     "(Ljava/net/URL;Ljava/lang/String;Ljava/lang/String;ILjava/lang/String;Ljava/lang/String;)V",
                     Self::set_url,
-                    Default::default(),
+                    MethodAccessFlags::PROTECTED,
                 ),
             ],
             fields: vec![],
@@ -106,8 +113,9 @@ impl URLStreamHandler {
 
         let protocol = parsed_url.scheme();
         let path = parsed_url.path().to_owned() + &parsed_url.query().map(|x| "?".to_owned() + x).unwrap_or("".into());
-        // TODO handle more elegantly..
-        let file = if protocol == "file" { path.trim_start_matches('/') } else { &path };
+        // url crate makes relative file path like `file:test.jar` absolute, while java keeps it relative
+        let is_relative_file = protocol == "file" && spec_str.get(5..).is_some_and(|x| !x.starts_with('/'));
+        let file = if is_relative_file { path.trim_start_matches('/') } else { &path };
 
         let protocol = JavaLangString::from_rust_string(jvm, parsed_url.scheme()).await?;
         let host = JavaLangString::from_rust_string(jvm, parsed_url.host_str().unwrap_or("")).await?;
@@ -16,9 +16,10 @@ mod stack;
 mod timer;
 mod timer_task;
 mod vector;
+mod vector_elements;
 
 pub use self::{
     abstract_collection::AbstractCollection, abstract_list::AbstractList, calendar::Calendar, date::Date, dictionary::Dictionary,
     empty_stack_exception::EmptyStackException, enumeration::Enumeration, gregorian_calendar::GregorianCalendar, hashtable::Hashtable,
-    properties::Properties, random::Random, stack::Stack, timer::Timer, timer_task::TimerTask, vector::Vector,
+    properties::Properties, random::Random, stack::Stack, timer::Timer, timer_task::TimerTask, vector::Vector, vector_elements::VectorElements,
 };
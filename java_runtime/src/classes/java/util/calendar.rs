@@ -14,13 +14,19 @@ impl Calendar {
         RuntimeClassProto {
             name: "java/util/Calendar",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("getInstance", "()Ljava/util/Calendar;", Self::get_instance, MethodAccessFlags::STATIC),
-                JavaMethodProto::new("setTime", "(Ljava/util/Date;)V", Self::set_time, Default::default()),
-                JavaMethodProto::new("getTime", "()Ljava/util/Date;", Self::get_time, Default::default()),
-                JavaMethodProto::new("set", "(II)V", Self::set, Default::default()),
-                JavaMethodProto::new("get", "(I)I", Self::get, Default::default()),
+                JavaMethodProto::new(
+                    "getInstance",
+                    "()Ljava/util/Calendar;",
+                    Self::get_instance,
+                    MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+                ),
+                JavaMethodProto::new("setTime", "(Ljava/util/Date;)V", Self::set_time, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getTime", "()Ljava/util/Date;", Self::get_time, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("set", "(II)V", Self::set, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("get", "(I)I", Self::get, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![],
         }
@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{ClassInstanceRef, Jvm, Result};
 
 use crate::{RuntimeClassProto, RuntimeContext};
@@ -13,12 +14,13 @@ impl Date {
         RuntimeClassProto {
             name: "java/util/Date",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("<init>", "(J)V", Self::init_with_time, Default::default()),
-                JavaMethodProto::new("getTime", "()J", Self::get_time, Default::default()),
-                JavaMethodProto::new("setTime", "(J)V", Self::set_time, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(J)V", Self::init_with_time, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getTime", "()J", Self::get_time, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("setTime", "(J)V", Self::set_time, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![JavaFieldProto::new("value", "J", Default::default())],
         }
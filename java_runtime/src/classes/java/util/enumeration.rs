@@ -1,23 +1,16 @@
-use alloc::vec;
+use java_class_proto::java_class;
+use jvm::{ClassInstanceRef, Jvm, Result};
 
-use java_class_proto::JavaMethodProto;
-
-use crate::RuntimeClassProto;
+use crate::{classes::java::lang::Object, RuntimeContext};
 
 // interface java.util.Enumeration
 pub struct Enumeration;
 
+#[java_class("java/util/Enumeration", interface)]
 impl Enumeration {
-    pub fn as_proto() -> RuntimeClassProto {
-        RuntimeClassProto {
-            name: "java/util/Enumeration",
-            parent_class: None,
-            interfaces: vec![],
-            methods: vec![
-                JavaMethodProto::new_abstract("hasMoreElements", "()Z", Default::default()),
-                JavaMethodProto::new_abstract("nextElement", "()Ljava/lang/Object;", Default::default()),
-            ],
-            fields: vec![],
-        }
-    }
+    #[java_method]
+    async fn has_more_elements(jvm: &Jvm, context: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<bool>;
+
+    #[java_method]
+    async fn next_element(jvm: &Jvm, context: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Object>>;
 }
@@ -5,6 +5,7 @@ use async_lock::Mutex;
 use hashbrown::HashMap;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{ClassInstanceRef, Jvm, Result};
 
 use crate::{classes::java::lang::Object, RuntimeClassProto, RuntimeContext};
@@ -21,18 +22,24 @@ impl Hashtable {
         RuntimeClassProto {
             name: "java/util/Hashtable",
             parent_class: Some("java/util/Dictionary"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("containsKey", "(Ljava/lang/Object;)Z", Self::contains_key, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("containsKey", "(Ljava/lang/Object;)Z", Self::contains_key, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "put",
                     "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
                     Self::put,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("get", "(Ljava/lang/Object;)Ljava/lang/Object;", Self::get, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "remove",
+                    "(Ljava/lang/Object;)Ljava/lang/Object;",
+                    Self::remove,
+                    MethodAccessFlags::PUBLIC,
                 ),
-                JavaMethodProto::new("get", "(Ljava/lang/Object;)Ljava/lang/Object;", Self::get, Default::default()),
-                JavaMethodProto::new("remove", "(Ljava/lang/Object;)Ljava/lang/Object;", Self::remove, Default::default()),
             ],
             fields: vec![JavaFieldProto::new("raw", "[B", Default::default())],
         }
@@ -59,8 +66,8 @@ impl Hashtable {
         let rust_hash_map = rust_hash_map.lock().await;
         let vec = rust_hash_map.get(&key_hash);
 
-        if vec.is_some() {
-            for (key, _) in vec.unwrap() {
+        if let Some(vec) = vec {
+            for (key, _) in vec {
                 let equals = jvm.invoke_virtual(key, "equals", "(Ljava/lang/Object;)Z", ((*key).clone(),)).await?;
                 if equals {
                     return Ok(true);
@@ -80,8 +87,8 @@ impl Hashtable {
         let rust_hash_map = rust_hash_map.lock().await;
         let vec = rust_hash_map.get(&key_hash);
 
-        if vec.is_some() {
-            for (key, value) in vec.unwrap() {
+        if let Some(vec) = vec {
+            for (key, value) in vec {
                 let equals = jvm.invoke_virtual(key, "equals", "(Ljava/lang/Object;)Z", ((*key).clone(),)).await?;
                 if equals {
                     return Ok(value.clone());
@@ -107,11 +114,11 @@ impl Hashtable {
         let mut rust_hash_map = rust_hash_map.lock().await;
         let vec = rust_hash_map.get_mut(&key_hash);
 
-        if vec.is_some() {
-            for (i, (bucket_key, _)) in vec.as_ref().unwrap().iter().enumerate() {
+        if let Some(vec) = vec {
+            for (i, (bucket_key, _)) in vec.iter().enumerate() {
                 let equals = jvm.invoke_virtual(bucket_key, "equals", "(Ljava/lang/Object;)Z", (key.clone(),)).await?;
                 if equals {
-                    let (_, old_value) = vec.unwrap().remove(i);
+                    let (_, old_value) = vec.remove(i);
 
                     return Ok(old_value);
                 }
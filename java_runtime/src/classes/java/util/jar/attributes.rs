@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{ClassInstanceRef, Jvm, Result};
 
 use crate::{classes::java::lang::String, RuntimeClassProto, RuntimeContext};
@@ -13,16 +14,22 @@ impl Attributes {
         RuntimeClassProto {
             name: "java/util/jar/Attributes",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "putValue",
                     "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/String;",
                     Self::put_value,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new(
+                    "getValue",
+                    "(Ljava/lang/String;)Ljava/lang/String;",
+                    Self::get_value,
+                    MethodAccessFlags::PUBLIC,
                 ),
-                JavaMethodProto::new("getValue", "(Ljava/lang/String;)Ljava/lang/String;", Self::get_value, Default::default()),
             ],
             fields: vec![JavaFieldProto::new("map", "Ljava/util/Map;", Default::default())],
         }
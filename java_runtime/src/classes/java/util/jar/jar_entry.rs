@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{ClassInstanceRef, Jvm, Result};
 
 use crate::{classes::java::util::zip::ZipEntry, RuntimeClassProto, RuntimeContext};
@@ -13,12 +14,13 @@ impl JarEntry {
         RuntimeClassProto {
             name: "java/util/jar/JarEntry",
             parent_class: Some("java/util/zip/ZipEntry"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![JavaMethodProto::new(
                 "<init>",
                 "(Ljava/util/zip/ZipEntry;)V",
                 Self::init,
-                Default::default(),
+                MethodAccessFlags::PUBLIC,
             )],
             fields: vec![],
         }
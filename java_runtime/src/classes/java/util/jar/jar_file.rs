@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -24,18 +25,19 @@ impl JarFile {
         RuntimeClassProto {
             name: "java/util/jar/JarFile",
             parent_class: Some("java/util/zip/ZipFile"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/File;)V", Self::init, Default::default()),
-                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_string, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/File;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(Ljava/lang/String;)V", Self::init_with_string, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "getJarEntry",
                     "(Ljava/lang/String;)Ljava/util/jar/JarEntry;",
                     Self::get_jar_entry,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
-                JavaMethodProto::new("entries", "()Ljava/util/Enumeration;", Self::entries, Default::default()),
-                JavaMethodProto::new("getManifest", "()Ljava/util/jar/Manifest;", Self::get_manifest, Default::default()),
+                JavaMethodProto::new("entries", "()Ljava/util/Enumeration;", Self::entries, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("getManifest", "()Ljava/util/jar/Manifest;", Self::get_manifest, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![],
         }
@@ -107,6 +109,9 @@ impl JarFile {
         let manifest_file: ClassInstanceRef<JarEntry> = jvm
             .invoke_virtual(&this, "getJarEntry", "(Ljava/lang/String;)Ljava/util/jar/JarEntry;", (manifest_name,))
             .await?;
+        if manifest_file.is_null() {
+            return Ok(None.into());
+        }
 
         let input_stream: ClassInstanceRef<InputStream> = jvm
             .invoke_virtual(
@@ -1,6 +1,7 @@
-use alloc::{vec, vec::Vec};
+use alloc::{string::String as RustString, vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -16,15 +17,16 @@ impl Manifest {
         RuntimeClassProto {
             name: "java/util/jar/Manifest",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/InputStream;)V", Self::init, Default::default()),
-                JavaMethodProto::new("read", "(Ljava/io/InputStream;)V", Self::read, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/InputStream;)V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("read", "(Ljava/io/InputStream;)V", Self::read, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "getMainAttributes",
                     "()Ljava/util/jar/Attributes;",
                     Self::get_main_attributes,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
             ],
             fields: vec![JavaFieldProto::new("attrs", "Ljava/util/jar/Attributes;", Default::default())],
@@ -51,22 +53,19 @@ impl Manifest {
         let reader = jvm.new_class("java/io/InputStreamReader", "(Ljava/io/InputStream;)V", (is,)).await?;
         let buffered_reader = jvm.new_class("java/io/BufferedReader", "(Ljava/io/Reader;)V", (reader,)).await?;
 
+        let mut lines: Vec<RustString> = Vec::new();
         loop {
             let line: ClassInstanceRef<String> = jvm.invoke_virtual(&buffered_reader, "readLine", "()Ljava/lang/String;", ()).await?;
             if line.is_null() {
                 break;
             }
 
-            let line = JavaLangString::to_rust_string(jvm, &line).await?;
-
-            if line.trim().is_empty() {
-                continue;
-            }
-
-            let parts = line.splitn(2, ':').collect::<Vec<_>>();
+            lines.push(JavaLangString::to_rust_string(jvm, &line).await?);
+        }
 
-            let key = JavaLangString::from_rust_string(jvm, parts[0].trim()).await?;
-            let value = JavaLangString::from_rust_string(jvm, parts[1].trim()).await?;
+        for (key, value) in Self::parse_main_attributes(lines.iter().map(|x| x.as_str())) {
+            let key = JavaLangString::from_rust_string(jvm, &key).await?;
+            let value = JavaLangString::from_rust_string(jvm, &value).await?;
 
             let _: ClassInstanceRef<String> = jvm
                 .invoke_virtual(
@@ -88,6 +87,35 @@ impl Manifest {
 
         jvm.get_field(&this, "attrs", "Ljava/util/jar/Attributes;").await
     }
+
+    // also used where java classes can't be loaded yet, e.g. while system class loader is being initialized
+    pub(crate) fn parse_main_attributes<'a, I>(lines: I) -> Vec<(RustString, RustString)>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        // long values are wrapped into continuation lines starting with a space
+        let mut joined_lines: Vec<RustString> = Vec::new();
+        for line in lines {
+            // main section ends at first blank line
+            if line.trim().is_empty() {
+                if joined_lines.is_empty() {
+                    continue;
+                }
+                break;
+            }
+
+            match (line.strip_prefix(' '), joined_lines.last_mut()) {
+                (Some(continuation), Some(last)) => last.push_str(continuation),
+                _ => joined_lines.push(line.into()),
+            }
+        }
+
+        joined_lines
+            .iter()
+            .filter_map(|x| x.split_once(':'))
+            .map(|(key, value)| (key.trim().into(), value.trim().into()))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -124,4 +152,32 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_manifest_continuation() -> Result<()> {
+        let jvm = test_jvm().await?;
+
+        let data = b"Manifest-Version: 1.0\r\nClass-Path: first.jar sec\r\n ond.jar\r\n\r\nName: entry\r\nClass-Path: other.jar\r\n";
+        let mut bytes = jvm.instantiate_array("B", data.len() as _).await?;
+        jvm.store_byte_array(&mut bytes, 0, cast_vec(data.to_vec())).await?;
+
+        let byte_array_stream = jvm.new_class("java/io/ByteArrayInputStream", "([B)V", (bytes,)).await?;
+        let manifest = jvm
+            .new_class("java/util/jar/Manifest", "(Ljava/io/InputStream;)V", (byte_array_stream,))
+            .await?;
+
+        let main_attributes = jvm
+            .invoke_virtual(&manifest, "getMainAttributes", "()Ljava/util/jar/Attributes;", ())
+            .await?;
+
+        // per-entry section must not override main attributes
+        let key = JavaLangString::from_rust_string(&jvm, "Class-Path").await?;
+        let value = jvm
+            .invoke_virtual(&main_attributes, "getValue", "(Ljava/lang/String;)Ljava/lang/String;", (key,))
+            .await?;
+
+        assert_eq!(JavaLangString::to_rust_string(&jvm, &value).await?, "first.jar second.jar");
+
+        Ok(())
+    }
 }
@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -16,20 +17,21 @@ impl Properties {
         RuntimeClassProto {
             name: "java/util/Properties",
             parent_class: Some("java/util/Hashtable"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "getProperty",
                     "(Ljava/lang/String;)Ljava/lang/String;",
                     Self::get_property,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
                 JavaMethodProto::new(
                     "setProperty",
                     "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/Object;",
                     Self::set_property,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
             ],
             fields: vec![],
@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{ClassInstanceRef, Jvm, Result};
 
 use crate::{classes::java::lang::Object, RuntimeClassProto, RuntimeContext};
@@ -13,14 +14,15 @@ impl Stack {
         RuntimeClassProto {
             name: "java/util/Stack",
             parent_class: Some("java/util/Vector"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("empty", "()Z", Self::empty, Default::default()),
-                JavaMethodProto::new("peek", "()Ljava/lang/Object;", Self::peek, Default::default()),
-                JavaMethodProto::new("pop", "()Ljava/lang/Object;", Self::pop, Default::default()),
-                JavaMethodProto::new("push", "(Ljava/lang/Object;)Ljava/lang/Object;", Self::push, Default::default()),
-                JavaMethodProto::new("search", "(Ljava/lang/Object;)I", Self::search, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("empty", "()Z", Self::empty, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("peek", "()Ljava/lang/Object;", Self::peek, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("pop", "()Ljava/lang/Object;", Self::pop, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("push", "(Ljava/lang/Object;)Ljava/lang/Object;", Self::push, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("search", "(Ljava/lang/Object;)I", Self::search, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![],
         }
@@ -10,6 +10,7 @@ impl Timer {
         RuntimeClassProto {
             name: "java/util/Timer",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![],
             fields: vec![],
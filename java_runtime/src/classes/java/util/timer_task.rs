@@ -10,6 +10,7 @@ impl TimerTask {
         RuntimeClassProto {
             name: "java/util/TimerTask",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![],
             fields: vec![],
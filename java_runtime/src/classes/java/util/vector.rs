@@ -6,9 +6,13 @@ use alloc::{boxed::Box, format, sync::Arc, vec::Vec};
 use async_lock::Mutex;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{ClassInstance, ClassInstanceRef, Jvm, Result};
 
-use crate::{classes::java::lang::Object, RuntimeClassProto, RuntimeContext};
+use crate::{
+    classes::java::{lang::Object, util::Enumeration},
+    RuntimeClassProto, RuntimeContext,
+};
 
 // I'm too lazy to implement vector in java, so i'm leveraging rust vector here...
 type RustVector = Arc<Mutex<Vec<ClassInstanceRef<Object>>>>;
@@ -21,23 +25,35 @@ impl Vector {
         RuntimeClassProto {
             name: "java/util/Vector",
             parent_class: Some("java/util/AbstractList"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
-                JavaMethodProto::new("<init>", "(I)V", Self::init_with_capacity, Default::default()),
-                JavaMethodProto::new("<init>", "(II)V", Self::init_with_capacity_increment, Default::default()),
-                JavaMethodProto::new("add", "(Ljava/lang/Object;)Z", Self::add, Default::default()),
-                JavaMethodProto::new("addElement", "(Ljava/lang/Object;)V", Self::add_element, Default::default()),
-                JavaMethodProto::new("insertElementAt", "(Ljava/lang/Object;I)V", Self::insert_element_at, Default::default()),
-                JavaMethodProto::new("elementAt", "(I)Ljava/lang/Object;", Self::element_at, Default::default()),
-                JavaMethodProto::new("set", "(ILjava/lang/Object;)Ljava/lang/Object;", Self::set, Default::default()),
-                JavaMethodProto::new("size", "()I", Self::size, Default::default()),
-                JavaMethodProto::new("isEmpty", "()Z", Self::is_empty, Default::default()),
-                JavaMethodProto::new("remove", "(I)Ljava/lang/Object;", Self::remove, Default::default()),
-                JavaMethodProto::new("removeAllElements", "()V", Self::remove_all_elements, Default::default()),
-                JavaMethodProto::new("removeElementAt", "(I)V", Self::remove_element_at, Default::default()),
-                JavaMethodProto::new("lastIndexOf", "(Ljava/lang/Object;)I", Self::last_index_of, Default::default()),
-                JavaMethodProto::new("lastIndexOf", "(Ljava/lang/Object;I)I", Self::last_index_of_index, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(I)V", Self::init_with_capacity, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("<init>", "(II)V", Self::init_with_capacity_increment, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("add", "(Ljava/lang/Object;)Z", Self::add, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("addElement", "(Ljava/lang/Object;)V", Self::add_element, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "insertElementAt",
+                    "(Ljava/lang/Object;I)V",
+                    Self::insert_element_at,
+                    MethodAccessFlags::PUBLIC,
+                ),
+                JavaMethodProto::new("elementAt", "(I)Ljava/lang/Object;", Self::element_at, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("elements", "()Ljava/util/Enumeration;", Self::elements, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("set", "(ILjava/lang/Object;)Ljava/lang/Object;", Self::set, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("size", "()I", Self::size, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("isEmpty", "()Z", Self::is_empty, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("remove", "(I)Ljava/lang/Object;", Self::remove, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("removeAllElements", "()V", Self::remove_all_elements, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("removeElementAt", "(I)V", Self::remove_element_at, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("lastIndexOf", "(Ljava/lang/Object;)I", Self::last_index_of, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new(
+                    "lastIndexOf",
+                    "(Ljava/lang/Object;I)I",
+                    Self::last_index_of_index,
+                    MethodAccessFlags::PUBLIC,
+                ),
             ],
             fields: vec![JavaFieldProto::new("raw", "[B", Default::default())],
         }
@@ -120,6 +136,14 @@ impl Vector {
         Ok(element)
     }
 
+    async fn elements(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Enumeration>> {
+        tracing::debug!("java.util.Vector::elements({:?})", &this);
+
+        let elements = jvm.new_class("java/util/Vector$Elements", "(Ljava/util/Vector;)V", (this,)).await?;
+
+        Ok(elements.into())
+    }
+
     async fn set(
         jvm: &Jvm,
         _: &mut RuntimeContext,
@@ -0,0 +1,66 @@
+use alloc::vec;
+
+use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
+use jvm::{ClassInstanceRef, Jvm, Result};
+
+use crate::{
+    classes::java::{lang::Object, util::Vector},
+    RuntimeClassProto, RuntimeContext,
+};
+
+// class java.util.Vector$Elements
+pub struct VectorElements;
+
+impl VectorElements {
+    pub fn as_proto() -> RuntimeClassProto {
+        RuntimeClassProto {
+            name: "java/util/Vector$Elements",
+            parent_class: Some("java/lang/Object"),
+            is_interface: false,
+            interfaces: vec!["java/util/Enumeration"],
+            methods: vec![
+                JavaMethodProto::new("<init>", "(Ljava/util/Vector;)V", Self::init, Default::default()),
+                JavaMethodProto::new("hasMoreElements", "()Z", Self::has_more_elements, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("nextElement", "()Ljava/lang/Object;", Self::next_element, MethodAccessFlags::PUBLIC),
+            ],
+            fields: vec![
+                JavaFieldProto::new("vector", "Ljava/util/Vector;", Default::default()),
+                JavaFieldProto::new("i", "I", Default::default()),
+            ],
+        }
+    }
+
+    async fn init(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>, vector: ClassInstanceRef<Vector>) -> Result<()> {
+        tracing::debug!("java.util.Vector$Elements::<init>({:?}, {:?})", &this, &vector);
+
+        let _: () = jvm.invoke_special(&this, "java/lang/Object", "<init>", "()V", ()).await?;
+
+        jvm.put_field(&mut this, "vector", "Ljava/util/Vector;", vector).await?;
+        jvm.put_field(&mut this, "i", "I", 0).await?;
+
+        Ok(())
+    }
+
+    async fn has_more_elements(jvm: &Jvm, _: &mut RuntimeContext, this: ClassInstanceRef<Self>) -> Result<bool> {
+        tracing::debug!("java.util.Vector$Elements::hasMoreElements({:?})", &this);
+
+        let i: i32 = jvm.get_field(&this, "i", "I").await?;
+        let vector: ClassInstanceRef<Vector> = jvm.get_field(&this, "vector", "Ljava/util/Vector;").await?;
+        let size: i32 = jvm.invoke_virtual(&vector, "size", "()I", ()).await?;
+
+        Ok(i < size)
+    }
+
+    async fn next_element(jvm: &Jvm, _: &mut RuntimeContext, mut this: ClassInstanceRef<Self>) -> Result<ClassInstanceRef<Object>> {
+        tracing::debug!("java.util.Vector$Elements::nextElement({:?})", &this);
+
+        let i: i32 = jvm.get_field(&this, "i", "I").await?;
+        let vector: ClassInstanceRef<Vector> = jvm.get_field(&this, "vector", "Ljava/util/Vector;").await?;
+        let element = jvm.invoke_virtual(&vector, "elementAt", "(I)Ljava/lang/Object;", (i,)).await?;
+
+        jvm.put_field(&mut this, "i", "I", i + 1).await?;
+
+        Ok(element)
+    }
+}
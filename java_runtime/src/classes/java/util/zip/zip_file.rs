@@ -10,6 +10,7 @@ use bytemuck::cast_vec;
 use zip::ZipArchive;
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -31,22 +32,23 @@ impl ZipFile {
         RuntimeClassProto {
             name: "java/util/zip/ZipFile",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "(Ljava/io/File;)V", Self::init, Default::default()),
+                JavaMethodProto::new("<init>", "(Ljava/io/File;)V", Self::init, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "getEntry",
                     "(Ljava/lang/String;)Ljava/util/zip/ZipEntry;",
                     Self::get_entry,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
                 JavaMethodProto::new(
                     "getInputStream",
                     "(Ljava/util/zip/ZipEntry;)Ljava/io/InputStream;",
                     Self::get_input_stream,
-                    Default::default(),
+                    MethodAccessFlags::PUBLIC,
                 ),
-                JavaMethodProto::new("entries", "()Ljava/util/Enumeration;", Self::entries, Default::default()),
+                JavaMethodProto::new("entries", "()Ljava/util/Enumeration;", Self::entries, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![JavaFieldProto::new("zip", "[B", Default::default())],
         }
@@ -1,6 +1,7 @@
 use alloc::{vec, vec::Vec};
 
 use java_class_proto::{JavaFieldProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
 use jvm::{Array, ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -19,11 +20,12 @@ impl ZipFileEntries {
         RuntimeClassProto {
             name: "java/util/zip/ZipFile$Entries",
             parent_class: Some("java/lang/Object"),
+            is_interface: false,
             interfaces: vec!["java/util/Enumeration"],
             methods: vec![
                 JavaMethodProto::new("<init>", "(Ljava/util/zip/ZipFile;[Ljava/lang/String;)V", Self::init, Default::default()),
-                JavaMethodProto::new("hasMoreElements", "()Z", Self::has_more_elements, Default::default()),
-                JavaMethodProto::new("nextElement", "()Ljava/lang/Object;", Self::next_element, Default::default()),
+                JavaMethodProto::new("hasMoreElements", "()Z", Self::has_more_elements, MethodAccessFlags::PUBLIC),
+                JavaMethodProto::new("nextElement", "()Ljava/lang/Object;", Self::next_element, MethodAccessFlags::PUBLIC),
             ],
             fields: vec![
                 JavaFieldProto::new("zipFile", "Ljava/util/zip/ZipFile;", Default::default()),
@@ -1,6 +1,7 @@
 use alloc::vec;
 
 use java_class_proto::JavaMethodProto;
+use java_constants::MethodAccessFlags;
 use jvm::{ClassInstanceRef, Jvm, Result};
 
 use crate::{
@@ -19,14 +20,15 @@ impl FileURLHandler {
         RuntimeClassProto {
             name: "org/rustjava/net/FileURLHandler",
             parent_class: Some("java/net/URLStreamHandler"),
+            is_interface: false,
             interfaces: vec![],
             methods: vec![
-                JavaMethodProto::new("<init>", "()V", Self::init, Default::default()),
+                JavaMethodProto::new("<init>", "()V", Self::init, MethodAccessFlags::PUBLIC),
                 JavaMethodProto::new(
                     "openConnection",
                     "(Ljava/net/URL;)Ljava/net/URLConnection;",
                     Self::open_connection,
-                    Default::default(),
+                    MethodAccessFlags::PROTECTED,
                 ),
             ],
             fields: vec![],
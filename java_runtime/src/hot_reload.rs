@@ -0,0 +1,137 @@
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+
+use jvm::{runtime::JavaLangString, ClassInstance, ClassInstanceRef, JavaError, Jvm, Result as JvmResult};
+
+use crate::{
+    classes::java::net::URL,
+    runtime::{FileChangeCallback, SpawnCallback},
+    IOResult, Runtime,
+};
+
+// watches class path directories through the runtime, and redefines loaded classes whose class files have changed.
+// only class loaders which load the class from the changed file are affected, so same named classes from elsewhere are kept.
+// directories should have trailing slash as in class path. failed redefinitions are reported to stderr, and watching goes on
+pub fn start_hot_reload(jvm: &Jvm, runtime: Box<dyn Runtime>, directories: Vec<String>) {
+    struct HotReloadWatcher {
+        jvm: Jvm,
+        runtime: Box<dyn Runtime>,
+        directories: Vec<String>,
+    }
+
+    #[async_trait::async_trait]
+    impl SpawnCallback for HotReloadWatcher {
+        async fn call(&self) -> JvmResult<()> {
+            self.jvm.attach_thread("Hot Reload", true).await?;
+
+            let callback = ClassFileChanged {
+                jvm: self.jvm.clone(),
+                runtime: self.runtime.clone(),
+                directories: self.directories.clone(),
+            };
+            let result = self.runtime.watch_files(self.directories.clone(), Box::new(callback)).await;
+
+            self.jvm.detach_thread().await?;
+
+            result
+        }
+    }
+
+    runtime.spawn(
+        jvm,
+        Box::new(HotReloadWatcher {
+            jvm: jvm.clone(),
+            runtime: runtime.clone(),
+            directories,
+        }),
+    );
+}
+
+struct ClassFileChanged {
+    jvm: Jvm,
+    runtime: Box<dyn Runtime>,
+    directories: Vec<String>,
+}
+
+impl ClassFileChanged {
+    async fn reload(&self, path: &str) -> JvmResult<()> {
+        // file might be removed or being written, we'll get notified again on next write
+        let Ok(data) = self.read(path).await else {
+            tracing::debug!("Failed to read {}", path);
+            return Ok(());
+        };
+
+        let definition = self.runtime.define_class(&self.jvm, &data).await?;
+        let resource_name = format!("{}.class", definition.name());
+
+        let mut count = 0;
+        for class_loader in self.jvm.defining_class_loaders(&definition.name()).await {
+            if self.is_loaded_from(&class_loader, &resource_name, path).await? && self.jvm.redefine_class(&*definition, &class_loader).await? {
+                count += 1;
+            }
+        }
+
+        tracing::debug!("Reloaded {} ({} classes)", path, count);
+
+        Ok(())
+    }
+
+    // checks if class loader finds the class file at the path by itself, without delegating to its parent
+    #[allow(clippy::borrowed_box)]
+    async fn is_loaded_from(&self, class_loader: &Box<dyn ClassInstance>, resource_name: &str, path: &str) -> JvmResult<bool> {
+        let resource_name = JavaLangString::from_rust_string(&self.jvm, resource_name).await?;
+        let url: ClassInstanceRef<URL> = self
+            .jvm
+            .invoke_virtual(class_loader, "findResource", "(Ljava/lang/String;)Ljava/net/URL;", (resource_name,))
+            .await?;
+        if url.is_null() {
+            return Ok(false);
+        }
+
+        let protocol = self.jvm.invoke_virtual(&url, "getProtocol", "()Ljava/lang/String;", ()).await?;
+        let file = self.jvm.invoke_virtual(&url, "getFile", "()Ljava/lang/String;", ()).await?;
+
+        Ok(JavaLangString::to_rust_string(&self.jvm, &protocol).await? == "file" && JavaLangString::to_rust_string(&self.jvm, &file).await? == path)
+    }
+
+    async fn read(&self, path: &str) -> IOResult<Vec<u8>> {
+        let mut file = self.runtime.open(path, false, false).await?;
+
+        let mut data = Vec::new();
+        let mut buf = [0; 4096];
+        loop {
+            let read = file.read(&mut buf).await?;
+            if read == 0 {
+                break;
+            }
+            data.extend_from_slice(&buf[..read]);
+        }
+
+        Ok(data)
+    }
+
+    async fn report(&self, path: &str, exception: Box<dyn ClassInstance>) -> JvmResult<()> {
+        let description = self.jvm.invoke_virtual(&exception, "toString", "()Ljava/lang/String;", ()).await?;
+        let description = JavaLangString::to_rust_string(&self.jvm, &description).await?;
+
+        if let Ok(mut stderr) = self.runtime.stderr() {
+            let _ = stderr.write(format!("Failed to reload {}: {}\n", path, description).as_bytes()).await;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl FileChangeCallback for ClassFileChanged {
+    async fn call(&self, path: &str) -> JvmResult<()> {
+        // other files under class path directories, like resources, are not reloaded
+        if !path.ends_with(".class") || !self.directories.iter().any(|x| path.starts_with(x.as_str())) {
+            return Ok(());
+        }
+
+        match self.reload(path).await {
+            Err(JavaError::JavaException(x)) => self.report(path, x).await,
+            x => x,
+        }
+    }
+}
@@ -2,12 +2,18 @@
 extern crate alloc;
 
 pub mod classes;
+mod hot_reload;
 mod loader;
 mod runtime;
+mod stub;
+mod time_slice;
 
 pub use self::{
-    loader::{get_bootstrap_class_loader, get_runtime_class_proto},
-    runtime::{File, FileSize, FileStat, FileType, IOError, IOResult, Runtime, SpawnCallback},
+    hot_reload::start_hot_reload,
+    loader::{get_bootstrap_class_loader, get_runtime_class_proto, get_runtime_class_proto_factory, get_runtime_class_protos},
+    runtime::{File, FileChangeCallback, FileSize, FileStat, FileType, IOError, IOResult, Runtime, SpawnCallback},
+    stub::{runtime_stub_jar, stub_class_file},
+    time_slice::TimeSliceHook,
 };
 
 pub(crate) type RuntimeContext = dyn runtime::Runtime;
@@ -37,7 +43,7 @@ pub mod test {
     where
         R: Runtime + Clone + 'static,
     {
-        let bootstrap_class_loader = get_bootstrap_class_loader(Box::new(runtime.clone()));
+        let bootstrap_class_loader = get_bootstrap_class_loader(Box::new(runtime.clone()), Vec::new());
 
         let properties = [("java.class.path", RT_RUSTJAR)].into_iter().collect();
 
@@ -1,100 +1,175 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::iter;
 
 use jvm::{BootstrapClassLoader, ClassDefinition, Jvm, Result};
 
 use crate::{Runtime, RuntimeClassProto, RT_RUSTJAR};
 
+type ProtoFactory = fn() -> RuntimeClassProto;
+
+// all classes in rt.rustjar by name. protos are built on lookup, so loading a class doesn't build every other proto
+const RUNTIME_CLASSES: &[(&str, ProtoFactory)] = &[
+    ("java/io/BufferedReader", crate::classes::java::io::BufferedReader::as_proto),
+    ("java/io/ByteArrayInputStream", crate::classes::java::io::ByteArrayInputStream::as_proto),
+    ("java/io/ByteArrayOutputStream", crate::classes::java::io::ByteArrayOutputStream::as_proto),
+    ("java/io/DataInputStream", crate::classes::java::io::DataInputStream::as_proto),
+    ("java/io/DataOutputStream", crate::classes::java::io::DataOutputStream::as_proto),
+    ("java/io/EOFException", crate::classes::java::io::EOFException::as_proto),
+    ("java/io/File", crate::classes::java::io::File::as_proto),
+    ("java/io/FileDescriptor", crate::classes::java::io::FileDescriptor::as_proto),
+    ("java/io/FileInputStream", crate::classes::java::io::FileInputStream::as_proto),
+    ("java/io/FileOutputStream", crate::classes::java::io::FileOutputStream::as_proto),
+    ("java/io/FilterOutputStream", crate::classes::java::io::FilterOutputStream::as_proto),
+    ("java/io/InputStream", crate::classes::java::io::InputStream::as_proto),
+    ("java/io/InputStreamReader", crate::classes::java::io::InputStreamReader::as_proto),
+    ("java/io/IOException", crate::classes::java::io::IOException::as_proto),
+    ("java/io/OutputStream", crate::classes::java::io::OutputStream::as_proto),
+    ("java/io/PrintStream", crate::classes::java::io::PrintStream::as_proto),
+    ("java/io/PrintWriter", crate::classes::java::io::PrintWriter::as_proto),
+    ("java/io/RandomAccessFile", crate::classes::java::io::RandomAccessFile::as_proto),
+    ("java/io/Reader", crate::classes::java::io::Reader::as_proto),
+    ("java/io/StringWriter", crate::classes::java::io::StringWriter::as_proto),
+    ("java/io/Writer", crate::classes::java::io::Writer::as_proto),
+    ("java/lang/ArithmeticException", crate::classes::java::lang::ArithmeticException::as_proto),
+    (
+        "java/lang/ArrayIndexOutOfBoundsException",
+        crate::classes::java::lang::ArrayIndexOutOfBoundsException::as_proto,
+    ),
+    ("java/lang/Class", crate::classes::java::lang::Class::as_proto),
+    ("java/lang/ClassFormatError", crate::classes::java::lang::ClassFormatError::as_proto),
+    ("java/lang/ClassLoader", crate::classes::java::lang::ClassLoader::as_proto),
+    (
+        "java/lang/ClassNotFoundException",
+        crate::classes::java::lang::ClassNotFoundException::as_proto,
+    ),
+    ("java/lang/Error", crate::classes::java::lang::Error::as_proto),
+    ("java/lang/Exception", crate::classes::java::lang::Exception::as_proto),
+    ("java/lang/IllegalAccessError", crate::classes::java::lang::IllegalAccessError::as_proto),
+    (
+        "java/lang/IllegalArgumentException",
+        crate::classes::java::lang::IllegalArgumentException::as_proto,
+    ),
+    (
+        "java/lang/IllegalMonitorStateException",
+        crate::classes::java::lang::IllegalMonitorStateException::as_proto,
+    ),
+    (
+        "java/lang/IllegalThreadStateException",
+        crate::classes::java::lang::IllegalThreadStateException::as_proto,
+    ),
+    (
+        "java/lang/IncompatibleClassChangeError",
+        crate::classes::java::lang::IncompatibleClassChangeError::as_proto,
+    ),
+    (
+        "java/lang/IndexOutOfBoundsException",
+        crate::classes::java::lang::IndexOutOfBoundsException::as_proto,
+    ),
+    ("java/lang/Integer", crate::classes::java::lang::Integer::as_proto),
+    (
+        "java/lang/InterruptedException",
+        crate::classes::java::lang::InterruptedException::as_proto,
+    ),
+    ("java/lang/LinkageError", crate::classes::java::lang::LinkageError::as_proto),
+    ("java/lang/Math", crate::classes::java::lang::Math::as_proto),
+    (
+        "java/lang/NoClassDefFoundError",
+        crate::classes::java::lang::NoClassDefFoundError::as_proto,
+    ),
+    ("java/lang/NoSuchFieldError", crate::classes::java::lang::NoSuchFieldError::as_proto),
+    ("java/lang/NoSuchMethodError", crate::classes::java::lang::NoSuchMethodError::as_proto),
+    (
+        "java/lang/NullPointerException",
+        crate::classes::java::lang::NullPointerException::as_proto,
+    ),
+    ("java/lang/Object", crate::classes::java::lang::Object::as_proto),
+    ("java/lang/Runnable", crate::classes::java::lang::Runnable::as_proto),
+    ("java/lang/Runtime", crate::classes::java::lang::Runtime::as_proto),
+    ("java/lang/RuntimeException", crate::classes::java::lang::RuntimeException::as_proto),
+    ("java/lang/SecurityException", crate::classes::java::lang::SecurityException::as_proto),
+    ("java/lang/String", crate::classes::java::lang::String::as_proto),
+    ("java/lang/StringBuffer", crate::classes::java::lang::StringBuffer::as_proto),
+    ("java/lang/System", crate::classes::java::lang::System::as_proto),
+    ("java/lang/Thread", crate::classes::java::lang::Thread::as_proto),
+    ("java/lang/ThreadGroup", crate::classes::java::lang::ThreadGroup::as_proto),
+    ("java/lang/Throwable", crate::classes::java::lang::Throwable::as_proto),
+    (
+        "java/lang/UnsupportedOperationException",
+        crate::classes::java::lang::UnsupportedOperationException::as_proto,
+    ),
+    ("java/net/JarURLConnection", crate::classes::java::net::JarURLConnection::as_proto),
+    (
+        "java/net/MalformedURLException",
+        crate::classes::java::net::MalformedURLException::as_proto,
+    ),
+    (
+        "java/io/UnknownServiceException",
+        crate::classes::java::net::UnknownServiceException::as_proto,
+    ),
+    ("java/net/URL", crate::classes::java::net::URL::as_proto),
+    ("java/net/URLClassLoader", crate::classes::java::net::URLClassLoader::as_proto),
+    ("java/net/URLConnection", crate::classes::java::net::URLConnection::as_proto),
+    ("java/net/URLStreamHandler", crate::classes::java::net::URLStreamHandler::as_proto),
+    ("java/util/AbstractCollection", crate::classes::java::util::AbstractCollection::as_proto),
+    ("java/util/AbstractList", crate::classes::java::util::AbstractList::as_proto),
+    ("java/util/Calendar", crate::classes::java::util::Calendar::as_proto),
+    ("java/util/Date", crate::classes::java::util::Date::as_proto),
+    ("java/util/Dictionary", crate::classes::java::util::Dictionary::as_proto),
+    ("java/lang/EmptyStackException", crate::classes::java::util::EmptyStackException::as_proto),
+    ("java/util/Enumeration", crate::classes::java::util::Enumeration::as_proto),
+    ("java/util/GregorianCalendar", crate::classes::java::util::GregorianCalendar::as_proto),
+    ("java/util/Hashtable", crate::classes::java::util::Hashtable::as_proto),
+    ("java/util/Properties", crate::classes::java::util::Properties::as_proto),
+    ("java/util/Random", crate::classes::java::util::Random::as_proto),
+    ("java/util/Stack", crate::classes::java::util::Stack::as_proto),
+    ("java/util/Timer", crate::classes::java::util::Timer::as_proto),
+    ("java/util/TimerTask", crate::classes::java::util::TimerTask::as_proto),
+    ("java/util/Vector", crate::classes::java::util::Vector::as_proto),
+    ("java/util/Vector$Elements", crate::classes::java::util::VectorElements::as_proto),
+    ("java/util/jar/Attributes", crate::classes::java::util::jar::Attributes::as_proto),
+    ("java/util/jar/JarEntry", crate::classes::java::util::jar::JarEntry::as_proto),
+    ("java/util/jar/JarFile", crate::classes::java::util::jar::JarFile::as_proto),
+    ("java/util/jar/JarFile$Entries", crate::classes::java::util::jar::JarFileEntries::as_proto),
+    ("java/util/jar/Manifest", crate::classes::java::util::jar::Manifest::as_proto),
+    ("java/util/zip/ZipEntry", crate::classes::java::util::zip::ZipEntry::as_proto),
+    ("java/util/zip/ZipFile", crate::classes::java::util::zip::ZipFile::as_proto),
+    ("java/util/zip/ZipFile$Entries", crate::classes::java::util::zip::ZipFileEntries::as_proto),
+    (
+        "org/rustjava/net/FileURLConnection",
+        crate::classes::org::rustjava::net::FileURLConnection::as_proto,
+    ),
+    (
+        "org/rustjava/net/FileURLHandler",
+        crate::classes::org::rustjava::net::FileURLHandler::as_proto,
+    ),
+    (
+        "org/rustjava/net/JarURLConnection",
+        crate::classes::org::rustjava::net::JarURLConnection::as_proto,
+    ),
+    (
+        "org/rustjava/net/JarURLHandler",
+        crate::classes::org::rustjava::net::JarURLHandler::as_proto,
+    ),
+];
+
+// builds proto of rt.rustjar class only when called, for callers which may not need it
+pub fn get_runtime_class_proto_factory(name: &str) -> Option<ProtoFactory> {
+    RUNTIME_CLASSES.iter().find(|(x, _)| *x == name).map(|(_, proto)| *proto)
+}
+
 pub fn get_runtime_class_proto(name: &str) -> Option<RuntimeClassProto> {
-    let protos = [
-        crate::classes::java::io::BufferedReader::as_proto(),
-        crate::classes::java::io::ByteArrayInputStream::as_proto(),
-        crate::classes::java::io::ByteArrayOutputStream::as_proto(),
-        crate::classes::java::io::DataInputStream::as_proto(),
-        crate::classes::java::io::DataOutputStream::as_proto(),
-        crate::classes::java::io::EOFException::as_proto(),
-        crate::classes::java::io::File::as_proto(),
-        crate::classes::java::io::FileDescriptor::as_proto(),
-        crate::classes::java::io::FileInputStream::as_proto(),
-        crate::classes::java::io::FileOutputStream::as_proto(),
-        crate::classes::java::io::FilterOutputStream::as_proto(),
-        crate::classes::java::io::InputStream::as_proto(),
-        crate::classes::java::io::InputStreamReader::as_proto(),
-        crate::classes::java::io::IOException::as_proto(),
-        crate::classes::java::io::OutputStream::as_proto(),
-        crate::classes::java::io::PrintStream::as_proto(),
-        crate::classes::java::io::PrintWriter::as_proto(),
-        crate::classes::java::io::RandomAccessFile::as_proto(),
-        crate::classes::java::io::Reader::as_proto(),
-        crate::classes::java::io::StringWriter::as_proto(),
-        crate::classes::java::io::Writer::as_proto(),
-        crate::classes::java::lang::ArrayIndexOutOfBoundsException::as_proto(),
-        crate::classes::java::lang::Class::as_proto(),
-        crate::classes::java::lang::ClassLoader::as_proto(),
-        crate::classes::java::lang::Error::as_proto(),
-        crate::classes::java::lang::Exception::as_proto(),
-        crate::classes::java::lang::IllegalArgumentException::as_proto(),
-        crate::classes::java::lang::IncompatibleClassChangeError::as_proto(),
-        crate::classes::java::lang::IndexOutOfBoundsException::as_proto(),
-        crate::classes::java::lang::Integer::as_proto(),
-        crate::classes::java::lang::InterruptedException::as_proto(),
-        crate::classes::java::lang::LinkageError::as_proto(),
-        crate::classes::java::lang::Math::as_proto(),
-        crate::classes::java::lang::NoClassDefFoundError::as_proto(),
-        crate::classes::java::lang::NoSuchFieldError::as_proto(),
-        crate::classes::java::lang::NoSuchMethodError::as_proto(),
-        crate::classes::java::lang::NullPointerException::as_proto(),
-        crate::classes::java::lang::Object::as_proto(),
-        crate::classes::java::lang::Runnable::as_proto(),
-        crate::classes::java::lang::Runtime::as_proto(),
-        crate::classes::java::lang::RuntimeException::as_proto(),
-        crate::classes::java::lang::SecurityException::as_proto(),
-        crate::classes::java::lang::String::as_proto(),
-        crate::classes::java::lang::StringBuffer::as_proto(),
-        crate::classes::java::lang::System::as_proto(),
-        crate::classes::java::lang::Thread::as_proto(),
-        crate::classes::java::lang::Throwable::as_proto(),
-        crate::classes::java::lang::UnsupportedOperationException::as_proto(),
-        crate::classes::java::net::JarURLConnection::as_proto(),
-        crate::classes::java::net::MalformedURLException::as_proto(),
-        crate::classes::java::net::UnknownServiceException::as_proto(),
-        crate::classes::java::net::URL::as_proto(),
-        crate::classes::java::net::URLClassLoader::as_proto(),
-        crate::classes::java::net::URLConnection::as_proto(),
-        crate::classes::java::net::URLStreamHandler::as_proto(),
-        crate::classes::java::util::AbstractCollection::as_proto(),
-        crate::classes::java::util::AbstractList::as_proto(),
-        crate::classes::java::util::Calendar::as_proto(),
-        crate::classes::java::util::Date::as_proto(),
-        crate::classes::java::util::Dictionary::as_proto(),
-        crate::classes::java::util::EmptyStackException::as_proto(),
-        crate::classes::java::util::Enumeration::as_proto(),
-        crate::classes::java::util::GregorianCalendar::as_proto(),
-        crate::classes::java::util::Hashtable::as_proto(),
-        crate::classes::java::util::Properties::as_proto(),
-        crate::classes::java::util::Random::as_proto(),
-        crate::classes::java::util::Stack::as_proto(),
-        crate::classes::java::util::Timer::as_proto(),
-        crate::classes::java::util::TimerTask::as_proto(),
-        crate::classes::java::util::Vector::as_proto(),
-        crate::classes::java::util::jar::Attributes::as_proto(),
-        crate::classes::java::util::jar::JarEntry::as_proto(),
-        crate::classes::java::util::jar::JarFile::as_proto(),
-        crate::classes::java::util::jar::JarFileEntries::as_proto(),
-        crate::classes::java::util::jar::Manifest::as_proto(),
-        crate::classes::java::util::zip::ZipEntry::as_proto(),
-        crate::classes::java::util::zip::ZipFile::as_proto(),
-        crate::classes::java::util::zip::ZipFileEntries::as_proto(),
-        crate::classes::org::rustjava::net::FileURLConnection::as_proto(),
-        crate::classes::org::rustjava::net::FileURLHandler::as_proto(),
-        crate::classes::org::rustjava::net::JarURLConnection::as_proto(),
-        crate::classes::org::rustjava::net::JarURLHandler::as_proto(),
-    ];
+    get_runtime_class_proto_factory(name).map(|proto| proto())
+}
 
-    protos.into_iter().find(|proto| proto.name == name)
+// all classes in rt.rustjar
+pub fn get_runtime_class_protos() -> Vec<RuntimeClassProto> {
+    RUNTIME_CLASSES.iter().map(|(_, proto)| proto()).collect()
 }
 
 struct JavaRuntimeClassLoader {
     runtime: Box<dyn Runtime>,
+    // rustjars searched after rt.rustjar
+    boot_class_path: Vec<String>,
 }
 
 #[async_trait::async_trait]
@@ -104,10 +179,29 @@ impl BootstrapClassLoader for JavaRuntimeClassLoader {
             return Ok(Some(self.runtime.define_array_class(jvm, element_type_name).await?));
         }
 
-        self.runtime.find_rustjar_class(jvm, RT_RUSTJAR, name).await
+        for rustjar in iter::once(RT_RUSTJAR).chain(self.boot_class_path.iter().map(|x| x.as_str())) {
+            let class = self.runtime.find_rustjar_class(jvm, rustjar, name).await?;
+            if class.is_some() {
+                return Ok(class);
+            }
+        }
+
+        Ok(None)
     }
 }
 
-pub fn get_bootstrap_class_loader(runtime: Box<dyn Runtime>) -> impl BootstrapClassLoader {
-    JavaRuntimeClassLoader { runtime }
+pub fn get_bootstrap_class_loader(runtime: Box<dyn Runtime>, boot_class_path: Vec<String>) -> impl BootstrapClassLoader {
+    JavaRuntimeClassLoader { runtime, boot_class_path }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RUNTIME_CLASSES;
+
+    #[test]
+    fn test_runtime_class_names() {
+        for (name, proto) in RUNTIME_CLASSES {
+            assert_eq!(*name, proto().name);
+        }
+    }
 }
@@ -1,6 +1,6 @@
 mod io;
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String, vec::Vec};
 use core::time::Duration;
 
 use dyn_clone::{clone_trait_object, DynClone};
@@ -14,6 +14,11 @@ pub trait SpawnCallback: Sync + Send {
     async fn call(&self) -> JvmResult<()>;
 }
 
+#[async_trait::async_trait]
+pub trait FileChangeCallback: Sync + Send {
+    async fn call(&self, path: &str) -> JvmResult<()>;
+}
+
 #[async_trait::async_trait]
 pub trait Runtime: Sync + Send + DynClone {
     async fn sleep(&self, duration: Duration);
@@ -31,6 +36,12 @@ pub trait Runtime: Sync + Send + DynClone {
     async fn unlink(&self, path: &str) -> IOResult<()>;
     async fn metadata(&self, path: &str) -> IOResult<FileStat>;
 
+    // calls callback with path of each file changed or created under directories, until callback fails.
+    // runtimes without file watching support return immediately
+    async fn watch_files(&self, _directories: Vec<String>, _callback: Box<dyn FileChangeCallback>) -> JvmResult<()> {
+        Ok(())
+    }
+
     async fn find_rustjar_class(&self, jvm: &Jvm, classpath: &str, class: &str) -> JvmResult<Option<Box<dyn ClassDefinition>>>;
     async fn define_class(&self, jvm: &Jvm, data: &[u8]) -> JvmResult<Box<dyn ClassDefinition>>;
     async fn define_array_class(&self, jvm: &Jvm, element_type_name: &str) -> JvmResult<Box<dyn ClassDefinition>>;
@@ -43,13 +54,20 @@ clone_trait_object!(Runtime);
 pub mod test {
     extern crate std;
 
-    use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+    use alloc::{
+        boxed::Box,
+        collections::BTreeMap,
+        string::{String, ToString},
+        vec::Vec,
+    };
     use core::{
         cmp::min,
         sync::atomic::{AtomicU64, Ordering},
         time::Duration,
     };
 
+    use std::time::{SystemTime, UNIX_EPOCH};
+
     use jvm::{ClassDefinition, Jvm};
     use jvm_rust::{ArrayClassDefinitionImpl, ClassDefinitionImpl};
 
@@ -83,7 +101,7 @@ pub mod test {
         }
 
         async fn r#yield(&self) {
-            todo!()
+            tokio::task::yield_now().await;
         }
 
         fn spawn(&self, _jvm: &Jvm, callback: Box<dyn SpawnCallback>) {
@@ -98,7 +116,7 @@ pub mod test {
         }
 
         fn now(&self) -> u64 {
-            todo!()
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
         }
 
         fn current_task_id(&self) -> u64 {
@@ -156,8 +174,11 @@ pub mod test {
             Ok(None)
         }
 
-        async fn define_class(&self, _jvm: &Jvm, data: &[u8]) -> jvm::Result<Box<dyn ClassDefinition>> {
-            ClassDefinitionImpl::from_classfile(data).map(|x| Box::new(x) as Box<_>)
+        async fn define_class(&self, jvm: &Jvm, data: &[u8]) -> jvm::Result<Box<dyn ClassDefinition>> {
+            match ClassDefinitionImpl::from_classfile(data) {
+                Ok(x) => Ok(Box::new(x) as Box<_>),
+                Err(x) => Err(jvm.exception("java/lang/ClassFormatError", &x.to_string()).await),
+            }
         }
 
         async fn define_array_class(&self, _jvm: &Jvm, element_type_name: &str) -> jvm::Result<Box<dyn ClassDefinition>> {
@@ -0,0 +1,279 @@
+use alloc::{collections::BTreeMap, format, vec::Vec};
+
+// XXX for zip..
+extern crate std;
+use std::io::{Cursor, Write};
+
+use java_class_proto::JavaClassProto;
+use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::get_runtime_class_protos;
+
+// java 1.2
+const STUB_MAJOR_VERSION: u16 = 46;
+
+const CONSTANT_UTF8: u8 = 1;
+const CONSTANT_CLASS: u8 = 7;
+
+const OPCODE_ACONST_NULL: u8 = 0x01;
+const OPCODE_ATHROW: u8 = 0xbf;
+
+// jar of stub classes for all classes in rt.rustjar, to be used as `javac -bootclasspath` to compile against classes we provide
+pub fn runtime_stub_jar() -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+
+    zip.start_file("META-INF/MANIFEST.MF", options).unwrap();
+    zip.write_all(b"Manifest-Version: 1.0\r\n\r\n").unwrap();
+
+    for proto in get_runtime_class_protos() {
+        zip.start_file(format!("{}.class", proto.name), options).unwrap();
+        zip.write_all(&stub_class_file(&proto)).unwrap();
+    }
+
+    zip.finish().unwrap().into_inner()
+}
+
+// class file with signatures of the proto, keeping public and protected members. method bodies just throw.
+// protos don't have checked exceptions methods may throw, so stubs don't have `throws` clause
+pub fn stub_class_file<C>(proto: &JavaClassProto<C>) -> Vec<u8>
+where
+    C: ?Sized + Send,
+{
+    let mut constant_pool = ConstantPool::default();
+
+    let access_flags = if proto.is_interface {
+        ClassAccessFlags::PUBLIC | ClassAccessFlags::INTERFACE | ClassAccessFlags::ABSTRACT
+    } else {
+        ClassAccessFlags::PUBLIC | ClassAccessFlags::SUPER
+    };
+    let this_class = constant_pool.class(proto.name);
+    let super_class = match proto.parent_class {
+        Some(x) => constant_pool.class(x),
+        None if proto.is_interface => constant_pool.class("java/lang/Object"),
+        None => 0,
+    };
+
+    let mut body = Vec::new();
+    write_u16(&mut body, access_flags.bits());
+    write_u16(&mut body, this_class);
+    write_u16(&mut body, super_class);
+
+    write_u16(&mut body, proto.interfaces.len() as _);
+    for interface in &proto.interfaces {
+        write_u16(&mut body, constant_pool.class(interface));
+    }
+
+    // fields holding rust objects as raw `[B` are package-private, so they are left out with other non-api members
+    let fields = proto
+        .fields
+        .iter()
+        .filter(|x| x.access_flags.intersects(FieldAccessFlags::PUBLIC | FieldAccessFlags::PROTECTED))
+        .collect::<Vec<_>>();
+    write_u16(&mut body, fields.len() as _);
+    for field in fields {
+        write_u16(&mut body, field.access_flags.bits());
+        write_u16(&mut body, constant_pool.utf8(&field.name));
+        write_u16(&mut body, constant_pool.utf8(&field.descriptor));
+        write_u16(&mut body, 0); // attributes_count
+    }
+
+    // static initializers are not part of class signature
+    let methods = proto
+        .methods
+        .iter()
+        .filter(|x| {
+            x.name != "<clinit>" && (proto.is_interface || x.access_flags.intersects(MethodAccessFlags::PUBLIC | MethodAccessFlags::PROTECTED))
+        })
+        .collect::<Vec<_>>();
+    write_u16(&mut body, methods.len() as _);
+    for method in methods {
+        let access_flags = if proto.is_interface {
+            MethodAccessFlags::PUBLIC | MethodAccessFlags::ABSTRACT
+        } else {
+            method.access_flags
+        };
+
+        write_u16(&mut body, access_flags.bits());
+        write_u16(&mut body, constant_pool.utf8(&method.name));
+        write_u16(&mut body, constant_pool.utf8(&method.descriptor));
+
+        if access_flags.intersects(MethodAccessFlags::ABSTRACT | MethodAccessFlags::NATIVE) {
+            write_u16(&mut body, 0); // attributes_count
+            continue;
+        }
+
+        let this_slot = if access_flags.contains(MethodAccessFlags::STATIC) { 0 } else { 1 };
+        let code = [OPCODE_ACONST_NULL, OPCODE_ATHROW];
+
+        write_u16(&mut body, 1); // attributes_count
+        write_u16(&mut body, constant_pool.utf8("Code"));
+        write_u32(&mut body, 12 + code.len() as u32);
+        write_u16(&mut body, 1); // max_stack
+        write_u16(&mut body, this_slot + parameter_slots(&method.descriptor));
+        write_u32(&mut body, code.len() as _);
+        body.extend_from_slice(&code);
+        write_u16(&mut body, 0); // exception_table_length
+        write_u16(&mut body, 0); // attributes_count
+    }
+
+    write_u16(&mut body, 0); // attributes_count
+
+    let mut result = Vec::new();
+    write_u32(&mut result, 0xCAFEBABE);
+    write_u16(&mut result, 0); // minor_version
+    write_u16(&mut result, STUB_MAJOR_VERSION);
+    write_u16(&mut result, constant_pool.items.len() as u16 + 1);
+    for item in &constant_pool.items {
+        result.extend_from_slice(item);
+    }
+    result.extend(body);
+
+    result
+}
+
+#[derive(Default)]
+struct ConstantPool {
+    items: Vec<Vec<u8>>,
+    indices: BTreeMap<Vec<u8>, u16>,
+}
+
+impl ConstantPool {
+    fn utf8(&mut self, value: &str) -> u16 {
+        let mut item = Vec::with_capacity(value.len() + 3);
+        item.push(CONSTANT_UTF8);
+        write_u16(&mut item, value.len() as _);
+        item.extend_from_slice(value.as_bytes());
+
+        self.add(item)
+    }
+
+    fn class(&mut self, name: &str) -> u16 {
+        let name_index = self.utf8(name);
+
+        let mut item = Vec::with_capacity(3);
+        item.push(CONSTANT_CLASS);
+        write_u16(&mut item, name_index);
+
+        self.add(item)
+    }
+
+    fn add(&mut self, item: Vec<u8>) -> u16 {
+        if let Some(x) = self.indices.get(&item) {
+            return *x;
+        }
+
+        self.items.push(item.clone());
+        let index = self.items.len() as u16;
+        self.indices.insert(item, index);
+
+        index
+    }
+}
+
+// local variable slots taken by parameters, long and double taking two
+fn parameter_slots(descriptor: &str) -> u16 {
+    let parameters = &descriptor.as_bytes()[1..descriptor.find(')').unwrap()];
+
+    let mut result = 0;
+    let mut i = 0;
+    while i < parameters.len() {
+        result += if matches!(parameters[i], b'J' | b'D') { 2 } else { 1 };
+
+        while parameters[i] == b'[' {
+            i += 1;
+        }
+        if parameters[i] == b'L' {
+            while parameters[i] != b';' {
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+
+    result
+}
+
+fn write_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn write_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+    use std::io::{Cursor, Read};
+
+    use alloc::vec::Vec;
+
+    use classfile::ClassInfo;
+    use java_constants::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+    use zip::ZipArchive;
+
+    use crate::classes::java::{
+        io::ByteArrayInputStream,
+        lang::{Runnable, String},
+        util::Hashtable,
+    };
+
+    use super::{parameter_slots, runtime_stub_jar, stub_class_file};
+
+    #[test]
+    fn test_stub_class_file() {
+        let class = ClassInfo::parse(&stub_class_file(&String::as_proto())).unwrap();
+
+        assert_eq!(class.this_class.as_str(), "java/lang/String");
+        assert_eq!(class.super_class.as_deref().map(|x| x.as_str()), Some("java/lang/Object"));
+
+        let method = class
+            .methods
+            .iter()
+            .find(|x| x.name.as_str() == "valueOf" && x.descriptor.as_str() == "(I)Ljava/lang/String;")
+            .unwrap();
+        assert_eq!(method.access_flags, MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC);
+        assert_eq!(method.attributes.len(), 1);
+
+        let class = ClassInfo::parse(&stub_class_file(&Runnable::as_proto())).unwrap();
+        assert!(class.access_flags.contains(ClassAccessFlags::INTERFACE));
+        assert_eq!(class.methods[0].access_flags, MethodAccessFlags::PUBLIC | MethodAccessFlags::ABSTRACT);
+        assert!(class.methods[0].attributes.is_empty());
+    }
+
+    #[test]
+    fn test_stub_class_file_visibility() {
+        let class = ClassInfo::parse(&stub_class_file(&Hashtable::as_proto())).unwrap();
+        assert!(class.fields.is_empty());
+        assert!(class.methods.iter().any(|x| x.name.as_str() == "put"));
+
+        let class = ClassInfo::parse(&stub_class_file(&ByteArrayInputStream::as_proto())).unwrap();
+        let field = class.fields.iter().find(|x| x.name.as_str() == "buf").unwrap();
+        assert_eq!(field.access_flags, FieldAccessFlags::PROTECTED);
+
+        let class = ClassInfo::parse(&stub_class_file(&String::as_proto())).unwrap();
+        assert!(class
+            .methods
+            .iter()
+            .all(|x| x.access_flags.intersects(MethodAccessFlags::PUBLIC | MethodAccessFlags::PROTECTED)));
+    }
+
+    #[test]
+    fn test_parameter_slots() {
+        assert_eq!(parameter_slots("()V"), 0);
+        assert_eq!(parameter_slots("(IJ[[Ljava/lang/String;D[J)V"), 7);
+    }
+
+    #[test]
+    fn test_runtime_stub_jar() {
+        let mut zip = ZipArchive::new(Cursor::new(runtime_stub_jar())).unwrap();
+
+        let mut class = Vec::new();
+        zip.by_name("java/lang/Object.class").unwrap().read_to_end(&mut class).unwrap();
+        let class = ClassInfo::parse(&class).unwrap();
+
+        assert_eq!(class.super_class, None);
+    }
+}
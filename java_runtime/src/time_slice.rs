@@ -0,0 +1,42 @@
+use alloc::boxed::Box;
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use jvm::{SchedulePoint, SchedulingHook};
+
+use crate::Runtime;
+
+// yields to other tasks once running java code has used up its time slice, so that busy threads can't starve the host executor.
+// slice start is shared between threads, which is exact on single-threaded executors where starvation matters.
+pub struct TimeSliceHook {
+    runtime: Box<dyn Runtime>,
+    slice: u64, // in millis
+    slice_start: AtomicU64,
+}
+
+impl TimeSliceHook {
+    pub fn new(runtime: Box<dyn Runtime>, slice: Duration) -> Self {
+        let slice_start = AtomicU64::new(runtime.now());
+
+        Self {
+            runtime,
+            slice: slice.as_millis() as u64,
+            slice_start,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SchedulingHook for TimeSliceHook {
+    fn should_schedule(&self, _point: SchedulePoint) -> bool {
+        self.runtime.now().saturating_sub(self.slice_start.load(Ordering::Relaxed)) >= self.slice
+    }
+
+    async fn schedule(&self, _point: SchedulePoint) {
+        self.runtime.r#yield().await;
+
+        self.slice_start.store(self.runtime.now(), Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,37 @@
+use java_constants::{FieldAccessFlags, MethodAccessFlags};
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Visibility {
+    Public,
+    Protected,
+    Package,
+    Private,
+}
+
+impl From<FieldAccessFlags> for Visibility {
+    fn from(flags: FieldAccessFlags) -> Self {
+        if flags.contains(FieldAccessFlags::PUBLIC) {
+            Self::Public
+        } else if flags.contains(FieldAccessFlags::PROTECTED) {
+            Self::Protected
+        } else if flags.contains(FieldAccessFlags::PRIVATE) {
+            Self::Private
+        } else {
+            Self::Package
+        }
+    }
+}
+
+impl From<MethodAccessFlags> for Visibility {
+    fn from(flags: MethodAccessFlags) -> Self {
+        if flags.contains(MethodAccessFlags::PUBLIC) {
+            Self::Public
+        } else if flags.contains(MethodAccessFlags::PROTECTED) {
+            Self::Protected
+        } else if flags.contains(MethodAccessFlags::PRIVATE) {
+            Self::Private
+        } else {
+            Self::Package
+        }
+    }
+}
@@ -2,6 +2,7 @@ use alloc::{
     boxed::Box,
     format,
     string::{String, ToString},
+    vec::Vec,
 };
 use dyn_clone::clone_trait_object;
 
@@ -10,6 +11,8 @@ use crate::{class_definition::ClassDefinition, class_instance::ClassInstance, fi
 pub trait ArrayClassDefinition: ClassDefinition {
     fn element_type_name(&self) -> String;
     fn instantiate_array(&self, length: usize) -> Result<Box<dyn ClassInstance>>;
+    fn is_same_definition(&self, other: &dyn ArrayClassDefinition) -> bool;
+    fn reference_count(&self) -> usize;
 }
 
 clone_trait_object!(ArrayClassDefinition);
@@ -28,6 +31,10 @@ impl<T: ArrayClassDefinition> ClassDefinition for T {
         panic!("Cannot instantiate array class")
     }
 
+    fn equals(&self, other: &dyn ClassDefinition) -> bool {
+        other.as_array_class_definition().is_some_and(|x| self.is_same_definition(x))
+    }
+
     fn method(&self, _name: &str, _descriptor: &str, _is_static: bool) -> Option<Box<dyn Method>> {
         None
     }
@@ -44,6 +51,16 @@ impl<T: ArrayClassDefinition> ClassDefinition for T {
         panic!("Array classes do not have static fields")
     }
 
+    fn reference_count(&self) -> usize {
+        ArrayClassDefinition::reference_count(self)
+    }
+
+    async fn static_references(&self) -> Vec<Box<dyn ClassInstance>> {
+        Vec::new()
+    }
+
+    async fn clear_static_fields(&mut self) {}
+
     fn as_array_class_definition(&self) -> Option<&dyn ArrayClassDefinition> {
         Some(self)
     }
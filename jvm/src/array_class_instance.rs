@@ -1,6 +1,6 @@
 use alloc::{boxed::Box, vec::Vec};
 
-use crate::{class_definition::ClassDefinition, class_instance::ClassInstance, field::Field, value::JavaValue, Result};
+use crate::{class_definition::ClassDefinition, class_instance::ClassInstance, field::Field, monitor::Monitor, value::JavaValue, Result};
 
 #[async_trait::async_trait]
 pub trait ArrayClassInstance: ClassInstance {
@@ -8,11 +8,15 @@ pub trait ArrayClassInstance: ClassInstance {
     fn destroy(self: Box<Self>);
     fn equals(&self, other: &dyn ClassInstance) -> Result<bool>;
     fn hash_code(&self) -> i32;
+    fn monitor(&self) -> &Monitor;
     async fn store(&mut self, offset: usize, values: Box<[JavaValue]>) -> Result<()>;
     async fn load(&self, offset: usize, count: usize) -> Result<Vec<JavaValue>>;
     async fn store_bytes(&mut self, offset: usize, values: Box<[i8]>) -> Result<()>;
     async fn load_bytes(&self, offset: usize, count: usize) -> Result<Vec<i8>>;
     fn length(&self) -> usize;
+    fn reference_count(&self) -> usize;
+    async fn references(&self) -> Vec<Box<dyn ClassInstance>>;
+    async fn clear_references(&mut self);
 }
 
 #[async_trait::async_trait]
@@ -33,6 +37,22 @@ impl<T: ArrayClassInstance> ClassInstance for T {
         ArrayClassInstance::hash_code(self)
     }
 
+    fn monitor(&self) -> &Monitor {
+        ArrayClassInstance::monitor(self)
+    }
+
+    fn reference_count(&self) -> usize {
+        ArrayClassInstance::reference_count(self)
+    }
+
+    async fn references(&self) -> Vec<Box<dyn ClassInstance>> {
+        ArrayClassInstance::references(self).await
+    }
+
+    async fn clear_references(&mut self) {
+        ArrayClassInstance::clear_references(self).await
+    }
+
     fn as_array_instance(&self) -> Option<&dyn ArrayClassInstance> {
         Some(self)
     }
@@ -1,4 +1,4 @@
-use alloc::{boxed::Box, string::String};
+use alloc::{boxed::Box, string::String, vec::Vec};
 use core::fmt::Debug;
 
 use dyn_clone::{clone_trait_object, DynClone};
@@ -9,11 +9,24 @@ use crate::{as_any::AsAny, ArrayClassDefinition, ClassInstance, Field, JavaValue
 pub trait ClassDefinition: Sync + Send + AsAny + Debug + DynClone {
     fn name(&self) -> String;
     fn super_class_name(&self) -> Option<String>;
+    fn nest_host_name(&self) -> Option<String> {
+        None
+    }
     fn instantiate(&self) -> Result<Box<dyn ClassInstance>>;
+    // same class name can be defined by multiple class loaders, so definitions are compared by identity
+    fn equals(&self, other: &dyn ClassDefinition) -> bool;
     fn method(&self, name: &str, descriptor: &str, is_static: bool) -> Option<Box<dyn Method>>;
     fn field(&self, name: &str, descriptor: &str, is_static: bool) -> Option<Box<dyn Field>>;
     async fn get_static_field(&self, field: &dyn Field) -> Result<JavaValue>; // TODO do we need to split class? or rename classdefinition?
     async fn put_static_field(&mut self, field: &dyn Field, value: JavaValue) -> Result<()>;
+    // number of definitions sharing static storage, including ones held by instances of the class
+    fn reference_count(&self) -> usize;
+    async fn static_references(&self) -> Vec<Box<dyn ClassInstance>>;
+    async fn clear_static_fields(&mut self);
+    // replaces methods with ones of given definition of the same class for future invocations, returning the reason if class shape differs
+    async fn redefine(&self, _definition: &dyn ClassDefinition) -> core::result::Result<(), String> {
+        Err("class redefinition is not supported".into())
+    }
     fn as_array_class_definition(&self) -> Option<&dyn ArrayClassDefinition> {
         None
     }
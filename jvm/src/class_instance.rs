@@ -1,4 +1,4 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::{
     fmt::{self, Debug, Formatter},
     marker::PhantomData,
@@ -7,7 +7,7 @@ use core::{
 
 use dyn_clone::{clone_trait_object, DynClone};
 
-use crate::{as_any::AsAny, value::JavaValue, ArrayClassInstance, ClassDefinition, Field, Result};
+use crate::{as_any::AsAny, monitor::Monitor, value::JavaValue, ArrayClassInstance, ClassDefinition, Field, JavaError, Result};
 
 #[async_trait::async_trait]
 pub trait ClassInstance: Sync + Send + AsAny + Debug + DynClone + 'static {
@@ -15,8 +15,13 @@ pub trait ClassInstance: Sync + Send + AsAny + Debug + DynClone + 'static {
     fn hash_code(&self) -> i32;
     fn class_definition(&self) -> Box<dyn ClassDefinition>;
     fn equals(&self, other: &dyn ClassInstance) -> Result<bool>;
+    fn monitor(&self) -> &Monitor;
     async fn get_field(&self, field: &dyn Field) -> Result<JavaValue>;
     async fn put_field(&mut self, field: &dyn Field, value: JavaValue) -> Result<()>;
+    // instances are reference counted, so unreachable class loaders are found by comparing counts with references we can see
+    fn reference_count(&self) -> usize;
+    async fn references(&self) -> Vec<Box<dyn ClassInstance>>;
+    async fn clear_references(&mut self);
     fn as_array_instance(&self) -> Option<&dyn ArrayClassInstance> {
         None
     }
@@ -107,12 +112,14 @@ impl<T> From<Option<Box<dyn ClassInstance>>> for ClassInstanceRef<T> {
     }
 }
 
-impl<T> From<JavaValue> for ClassInstanceRef<T> {
-    fn from(val: JavaValue) -> Self {
-        ClassInstanceRef {
-            instance: val.into(),
+impl<T> TryFrom<JavaValue> for ClassInstanceRef<T> {
+    type Error = JavaError;
+
+    fn try_from(val: JavaValue) -> Result<Self> {
+        Ok(ClassInstanceRef {
+            instance: val.try_into()?,
             _phantom: PhantomData,
-        }
+        })
     }
 }
 
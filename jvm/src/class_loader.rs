@@ -7,20 +7,40 @@ use crate::{
     ClassDefinition, ClassInstance, Jvm, Result,
 };
 
+// java class loaders are numbered from 1 in order of their first use, 0 is bootstrap class loader
+pub(crate) type ClassLoaderId = usize;
+pub(crate) const BOOTSTRAP_CLASS_LOADER_ID: ClassLoaderId = 0;
+
 #[derive(Clone)]
 pub struct Class {
     pub definition: Box<dyn ClassDefinition>,
+    pub(crate) class_loader_id: ClassLoaderId, // defining class loader
     java_class: Arc<RwLock<Option<Box<dyn ClassInstance>>>>,
 }
 
 impl Class {
-    pub fn new(definition: Box<dyn ClassDefinition>, java_class: Option<Box<dyn ClassInstance>>) -> Self {
+    pub(crate) fn new(definition: Box<dyn ClassDefinition>, class_loader_id: ClassLoaderId, java_class: Option<Box<dyn ClassInstance>>) -> Self {
         Self {
             definition,
+            class_loader_id,
             java_class: Arc::new(RwLock::new(java_class)),
         }
     }
 
+    // classes are identified by name and defining class loader
+    pub fn is_same_class(&self, other: &Class) -> bool {
+        self.class_loader_id == other.class_loader_id && self.definition.name() == other.definition.name()
+    }
+
+    // for unloading, these don't instantiate java/lang/Class if it's not there yet
+    pub(crate) async fn take_java_class(&self) -> Option<Box<dyn ClassInstance>> {
+        self.java_class.write().await.take()
+    }
+
+    pub(crate) async fn peek_java_class(&self) -> Option<Box<dyn ClassInstance>> {
+        self.java_class.read().await.clone()
+    }
+
     #[async_recursion::async_recursion]
     pub async fn java_class(&self, jvm: &Jvm) -> Result<Box<dyn ClassInstance>> {
         let java_class = self.java_class.read().await;
@@ -64,12 +84,10 @@ impl ClassLoaderWrapper for BootstrapClassLoaderWrapper<'_> {
     async fn load_class(&self, jvm: &Jvm, name: &str) -> Result<Option<Class>> {
         let definition = self.bootstrap_class_loader.load_class(jvm, name).await?;
         if let Some(definition) = definition {
-            let java_class = jvm.register_class(definition.clone(), None).await?;
+            jvm.register_class(definition, None).await?;
 
-            Ok(Some(Class {
-                definition,
-                java_class: Arc::new(RwLock::new(java_class)),
-            }))
+            // another task might have defined the class while we were loading, look up the class actually registered
+            Ok(jvm.defined_class(name, BOOTSTRAP_CLASS_LOADER_ID).await)
         } else {
             Ok(None)
         }
@@ -93,10 +111,9 @@ impl ClassLoaderWrapper for JavaClassLoaderWrapper {
 
         if let Some(class) = class {
             let definition = JavaLangClass::to_rust_class(jvm, &class).await?;
-            Ok(Some(Class {
-                definition,
-                java_class: Arc::new(RwLock::new(Some(class))),
-            }))
+
+            // class could have been defined by any class loader it delegated to
+            Ok(jvm.find_defined_class(&*definition).await)
         } else {
             Ok(None)
         }
@@ -0,0 +1,4 @@
+// called by jvm when classes of an unreachable class loader are unloaded, so that the host can release resources tied to them
+pub trait ClassUnloadHook: Sync + Send {
+    fn class_unloaded(&self, class_name: &str);
+}
@@ -3,13 +3,16 @@ use alloc::{
     fmt::{self, Display, Formatter},
     string::String,
 };
+use core::convert::Infallible;
 
 use crate::ClassInstance;
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum JavaError {
     JavaException(Box<dyn ClassInstance>),
     FatalError(String),
+    TypeMismatch { expected: &'static str, actual: &'static str },
 }
 
 impl Display for JavaError {
@@ -17,10 +20,17 @@ impl Display for JavaError {
         match self {
             JavaError::JavaException(e) => write!(f, "Java exception: {:?}", e),
             JavaError::FatalError(e) => write!(f, "Fatal error: {}", e),
+            JavaError::TypeMismatch { expected, actual } => write!(f, "Type mismatch: expected {}, got {}", expected, actual),
         }
     }
 }
 
+impl From<Infallible> for JavaError {
+    fn from(x: Infallible) -> Self {
+        match x {}
+    }
+}
+
 impl From<JavaError> for anyhow::Error {
     fn from(e: JavaError) -> Self {
         anyhow::anyhow!("{:?}", e)
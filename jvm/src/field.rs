@@ -3,11 +3,11 @@ use core::fmt::Debug;
 
 use java_constants::FieldAccessFlags;
 
-use crate::{as_any::AsAny, r#type::JavaType};
+use crate::{as_any::AsAny, r#type::JavaType, Result};
 
 pub trait Field: Sync + Send + AsAny + Debug {
     fn name(&self) -> String;
     fn descriptor(&self) -> String;
-    fn r#type(&self) -> JavaType;
+    fn r#type(&self) -> Result<JavaType>;
     fn access_flags(&self) -> FieldAccessFlags;
 }
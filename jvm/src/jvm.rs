@@ -1,41 +1,76 @@
 #![allow(clippy::borrowed_box)] // We have get parameter by Box<T> to make ergonomic interface
 
-use alloc::{borrow::ToOwned, boxed::Box, collections::BTreeMap, format, string::String, sync::Arc, vec::Vec};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    format,
+    string::String,
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 use core::{
     fmt::Debug,
+    future::{self, Future},
     iter,
     mem::{forget, size_of_val},
-    sync::atomic::{AtomicBool, Ordering},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
 };
 
-use async_lock::RwLock;
+use async_lock::{OnceCell, RwLock};
 use bytemuck::cast_slice;
 use dyn_clone::clone_box;
+use event_listener::Event;
 
-use java_constants::MethodAccessFlags;
+use java_constants::{FieldAccessFlags, MethodAccessFlags};
 
 use crate::{
+    access::Visibility,
     array_class_instance::ArrayClassInstance,
     class_definition::ClassDefinition,
     class_instance::ClassInstance,
-    class_loader::{BootstrapClassLoader, BootstrapClassLoaderWrapper, Class, ClassLoaderWrapper, JavaClassLoaderWrapper},
+    class_loader::{
+        BootstrapClassLoader, BootstrapClassLoaderWrapper, Class, ClassLoaderId, ClassLoaderWrapper, JavaClassLoaderWrapper,
+        BOOTSTRAP_CLASS_LOADER_ID,
+    },
+    class_unload_hook::ClassUnloadHook,
     error::JavaError,
     field::Field,
     invoke_arg::InvokeArg,
     method::Method,
     r#type::JavaType,
-    runtime::{JavaLangClass, JavaLangClassLoader, JavaLangString},
-    thread::JvmThread,
+    reference_graph::{object_id, ReferenceGraph},
+    runtime::{JavaLangClass, JavaLangClassLoader, JavaLangString, JavaLangThread},
+    scheduling_hook::{SchedulePoint, SchedulingHook},
+    thread::{is_same_instance, JvmThread, ThreadDump, ThreadState},
     value::JavaValue,
     Result,
 };
 
 struct JvmInner {
-    classes: RwLock<BTreeMap<String, Class>>,
+    // classes by name and defining class loader, as each class loader has its own namespace
+    classes: RwLock<BTreeMap<String, BTreeMap<ClassLoaderId, Class>>>,
+    // classes by name and initiating class loader, i.e. class loaders the class was resolved with
+    initiated_classes: RwLock<BTreeMap<String, BTreeMap<ClassLoaderId, Class>>>,
+    // sets of class loaders which must resolve the class name to the same class
+    loader_constraints: RwLock<BTreeMap<String, Vec<BTreeSet<ClassLoaderId>>>>,
+    // java class loaders by their id minus one, None if unloaded. vacant ids are reused by new class loaders
+    class_loaders: RwLock<Vec<Option<Box<dyn ClassInstance>>>>,
     threads: RwLock<BTreeMap<u64, JvmThread>>,
+    // set while a thread waits for other threads to stop at safepoints, see `begin_safepoint`
+    safepoint_requested: AtomicBool,
+    // notified when threads stop or leave running state, and when safepoint ends
+    safepoint_event: Event,
     get_current_thread_id: Box<dyn Fn() -> u64 + Sync + Send>,
     bootstrap_class_loader: Box<dyn BootstrapClassLoader>,
-    bootstrapping: AtomicBool,
+    // set after bootstrapping
+    system_class_loader_id: OnceCell<ClassLoaderId>,
+    non_daemon_threads: AtomicUsize,
+    non_daemon_threads_finished: Event,
+    exit_status: OnceCell<i32>,
+    scheduling_hook: OnceCell<Box<dyn SchedulingHook>>,
+    class_unload_hook: OnceCell<Box<dyn ClassUnloadHook>>,
 }
 
 #[derive(Clone)]
@@ -52,22 +87,31 @@ impl Jvm {
         let jvm = Self {
             inner: Arc::new(JvmInner {
                 classes: RwLock::new(BTreeMap::new()),
+                initiated_classes: RwLock::new(BTreeMap::new()),
+                loader_constraints: RwLock::new(BTreeMap::new()),
+                class_loaders: RwLock::new(Vec::new()),
                 threads: RwLock::new(BTreeMap::new()),
+                safepoint_requested: AtomicBool::new(false),
+                safepoint_event: Event::new(),
                 get_current_thread_id: Box::new(get_current_thread_id),
                 bootstrap_class_loader: Box::new(bootstrap_class_loader),
-                bootstrapping: AtomicBool::new(true),
+                system_class_loader_id: OnceCell::new(),
+                non_daemon_threads: AtomicUsize::new(0),
+                non_daemon_threads_finished: Event::new(),
+                exit_status: OnceCell::new(),
+                scheduling_hook: OnceCell::new(),
+                class_unload_hook: OnceCell::new(),
             }),
         };
 
         // load bootstrap classes
         let bootstrap_classes = ["java/lang/Object", "java/lang/Thread", "[B", "java/lang/Class"];
         for class_name in bootstrap_classes.iter() {
-            let class = jvm.inner.bootstrap_class_loader.load_class(&jvm, class_name).await?.unwrap();
-            jvm.register_class(class, None).await?;
+            jvm.resolve_class_internal(class_name, BOOTSTRAP_CLASS_LOADER_ID).await?;
         }
 
         // init startup thread
-        jvm.attach_thread().await?;
+        jvm.attach_thread("main", false).await?;
 
         // init properties
         for (key, value) in properties {
@@ -85,9 +129,9 @@ impl Jvm {
         }
 
         // load system class loader
-        JavaLangClassLoader::get_system_class_loader(&jvm).await?;
-
-        jvm.inner.bootstrapping.store(false, Ordering::Relaxed);
+        let system_class_loader = JavaLangClassLoader::get_system_class_loader(&jvm).await?;
+        let system_class_loader_id = jvm.class_loader_id(Some(&system_class_loader)).await;
+        let _ = jvm.inner.system_class_loader_id.set(system_class_loader_id).await;
 
         Ok(jvm)
     }
@@ -129,7 +173,8 @@ impl Jvm {
 
     pub async fn get_static_field<T>(&self, class_name: &str, name: &str, descriptor: &str) -> Result<T>
     where
-        T: From<JavaValue>,
+        T: TryFrom<JavaValue>,
+        JavaError: From<T::Error>,
     {
         tracing::trace!("Get static field {}.{}:{}", class_name, name, descriptor);
 
@@ -137,7 +182,7 @@ impl Jvm {
 
         let field = class.definition.field(name, descriptor, true);
         if let Some(field) = field {
-            Ok(class.definition.get_static_field(&*field).await?.into())
+            Ok(class.definition.get_static_field(&*field).await?.try_into()?)
         } else {
             Err(self
                 .exception("java/lang/NoSuchFieldError", &format!("{}.{}:{}", class_name, name, descriptor))
@@ -166,14 +211,15 @@ impl Jvm {
 
     pub async fn get_field<T>(&self, instance: &Box<dyn ClassInstance>, name: &str, descriptor: &str) -> Result<T>
     where
-        T: From<JavaValue>,
+        T: TryFrom<JavaValue>,
+        JavaError: From<T::Error>,
     {
         tracing::trace!("Get field {}.{}:{}", instance.class_definition().name(), name, descriptor);
 
         let field = self.find_field(&*instance.class_definition(), name, descriptor).await?;
 
         if let Some(field) = field {
-            Ok(instance.get_field(&*field).await?.into())
+            Ok(instance.get_field(&*field).await?.try_into()?)
         } else {
             Err(self
                 .exception(
@@ -207,7 +253,8 @@ impl Jvm {
     pub async fn invoke_static<T, U>(&self, class_name: &str, name: &str, descriptor: &str, args: T) -> Result<U>
     where
         T: InvokeArg,
-        U: From<JavaValue>,
+        U: TryFrom<JavaValue>,
+        JavaError: From<U::Error>,
     {
         let args = args.into_arg();
 
@@ -227,7 +274,7 @@ impl Jvm {
                     .await);
             }
 
-            Ok(self.execute_method(&class, None, &method, args).await?.into())
+            Ok(self.execute_method(&class, None, &method, args).await?.try_into()?)
         } else {
             tracing::error!("No such method: {}.{}:{}", class_name, name, descriptor);
 
@@ -240,7 +287,8 @@ impl Jvm {
     pub async fn invoke_virtual<T, U>(&self, instance: &Box<dyn ClassInstance>, name: &str, descriptor: &str, args: T) -> Result<U>
     where
         T: InvokeArg,
-        U: From<JavaValue>,
+        U: TryFrom<JavaValue>,
+        JavaError: From<U::Error>,
     {
         let args = args.into_arg();
         tracing::trace!(
@@ -251,23 +299,23 @@ impl Jvm {
             args
         );
 
-        let class = instance.class_definition();
-        let method = self.find_virtual_method(&*class, name, descriptor, false).await?;
-        if let Some(x) = method {
+        let class = self.class_of(&**instance).await?;
+        let method = self.find_virtual_method(class.clone(), name, descriptor, false).await?;
+        if let Some((declaring_class, x)) = method {
             let args = iter::once(JavaValue::Object(Some(clone_box(&**instance))))
                 .chain(args.into_vec())
                 .collect::<Vec<_>>();
 
-            let class = self.resolve_class(&class.name()).await?; // TODO we're resolving class twice
             Ok(self
-                .execute_method(&class, Some(instance.clone()), &x, args.into_boxed_slice())
+                .execute_method(&declaring_class, Some(instance.clone()), &x, args.into_boxed_slice())
                 .await?
-                .into())
+                .try_into()?)
         } else {
-            tracing::error!("No such method: {}.{}:{}", class.name(), name, descriptor);
+            let class_name = class.definition.name();
+            tracing::error!("No such method: {}.{}:{}", class_name, name, descriptor);
 
             Err(self
-                .exception("java/lang/NoSuchMethodError", &format!("{}.{}:{}", class.name(), name, descriptor))
+                .exception("java/lang/NoSuchMethodError", &format!("{}.{}:{}", class_name, name, descriptor))
                 .await)
         }
     }
@@ -277,15 +325,21 @@ impl Jvm {
     pub async fn invoke_special<T, U>(&self, instance: &Box<dyn ClassInstance>, class_name: &str, name: &str, descriptor: &str, args: T) -> Result<U>
     where
         T: InvokeArg,
-        U: From<JavaValue>,
+        U: TryFrom<JavaValue>,
+        JavaError: From<U::Error>,
     {
         let args = args.into_arg();
         tracing::trace!("Invoke special {}.{}:{}({:?})", class_name, name, descriptor, args);
 
         let class = self.resolve_class(class_name).await?;
-        let method = class.definition.method(name, descriptor, false);
+        // constructors are not inherited, but other methods can be declared in superclasses
+        let method = if name == "<init>" {
+            class.definition.method(name, descriptor, false).map(|x| (class, x))
+        } else {
+            self.find_method_declaring_class(class, name, descriptor, false).await?
+        };
 
-        if let Some(method) = method {
+        if let Some((class, method)) = method {
             let args = iter::once(JavaValue::Object(Some(clone_box(&**instance))))
                 .chain(args.into_vec())
                 .collect::<Vec<_>>();
@@ -302,7 +356,7 @@ impl Jvm {
             Ok(self
                 .execute_method(&class, Some(instance.clone()), &method, args.into_boxed_slice())
                 .await?
-                .into())
+                .try_into()?)
         } else {
             Err(self
                 .exception("java/lang/NoSuchMethodError", &format!("{}.{}:{}", class_name, name, descriptor))
@@ -310,6 +364,136 @@ impl Jvm {
         }
     }
 
+    // link-time access check of field reference, on behalf of currently executing java method.
+    // returns true if accessed instance should be checked with `check_protected_instance_access` on each access
+    pub async fn check_field_access(&self, class_name: &str, name: &str, descriptor: &str, is_static: bool, is_put: bool) -> Result<bool> {
+        let calling_method = self.find_calling_method().await?;
+        let (accessor, method) = if let Some(x) = calling_method {
+            x
+        } else {
+            return Ok(false); // called outside of java
+        };
+
+        let class = self.resolve_class(class_name).await?;
+        let field = if is_static {
+            class.definition.field(name, descriptor, true).map(|x| (class, x))
+        } else {
+            self.find_field_declaring_class(class, name, descriptor).await?
+        };
+        let (declaring_class, field) = if let Some(x) = field {
+            x
+        } else {
+            return Ok(false); // NoSuchFieldError will be thrown on access
+        };
+
+        let access_flags = field.access_flags();
+        let visibility = access_flags.into();
+        if !self.is_accessible(&accessor, &declaring_class, visibility).await? {
+            return Err(self
+                .exception(
+                    "java/lang/IllegalAccessError",
+                    &format!(
+                        "{} tried to access field {}.{}:{}",
+                        accessor.definition.name(),
+                        declaring_class.definition.name(),
+                        name,
+                        descriptor
+                    ),
+                )
+                .await);
+        }
+
+        self.check_loader_constraints(&accessor, &declaring_class, descriptor).await?;
+
+        // final fields can only be assigned in initializer of declaring class
+        if is_put && access_flags.contains(FieldAccessFlags::FINAL) {
+            let initializer = if is_static { "<clinit>(" } else { "<init>(" };
+
+            if accessor.definition.name() != declaring_class.definition.name() || !method.starts_with(initializer) {
+                return Err(self
+                    .exception(
+                        "java/lang/IllegalAccessError",
+                        &format!(
+                            "Update to final field {}.{}:{} attempted from {}.{}",
+                            declaring_class.definition.name(),
+                            name,
+                            descriptor,
+                            accessor.definition.name(),
+                            method
+                        ),
+                    )
+                    .await);
+            }
+        }
+
+        self.requires_protected_instance_check(&accessor, &declaring_class, visibility, is_static)
+            .await
+    }
+
+    // link-time access check of method reference, on behalf of currently executing java method.
+    // returns true if target instance should be checked with `check_protected_instance_access` on each invocation
+    pub async fn check_method_access(&self, class_name: &str, name: &str, descriptor: &str, is_static: bool) -> Result<bool> {
+        // array methods are public, including clone which is protected in Object
+        if class_name.starts_with('[') {
+            return Ok(false);
+        }
+
+        let calling_method = self.find_calling_method().await?;
+        let (accessor, _) = if let Some(x) = calling_method {
+            x
+        } else {
+            return Ok(false); // called outside of java
+        };
+
+        let class = self.resolve_class(class_name).await?;
+        let method = self.find_method_declaring_class(class, name, descriptor, is_static).await?;
+        let (declaring_class, method) = if let Some(x) = method {
+            x
+        } else {
+            return Ok(false); // NoSuchMethodError will be thrown on invocation
+        };
+
+        let visibility = method.access_flags().into();
+        if !self.is_accessible(&accessor, &declaring_class, visibility).await? {
+            return Err(self
+                .exception(
+                    "java/lang/IllegalAccessError",
+                    &format!(
+                        "{} tried to access method {}.{}{}",
+                        accessor.definition.name(),
+                        declaring_class.definition.name(),
+                        name,
+                        descriptor
+                    ),
+                )
+                .await);
+        }
+
+        self.check_loader_constraints(&accessor, &declaring_class, descriptor).await?;
+
+        self.requires_protected_instance_check(&accessor, &declaring_class, visibility, is_static)
+            .await
+    }
+
+    // protected instance members of other runtime package can only be accessed through instances of the calling class or its subclasses
+    pub async fn check_protected_instance_access(&self, instance: &dyn ClassInstance) -> Result<()> {
+        let Some((accessor, _)) = self.find_calling_method().await? else {
+            return Ok(());
+        };
+
+        let class = instance.class_definition();
+        if self.is_inherited_from(&*class, &accessor.definition.name()).await {
+            return Ok(());
+        }
+
+        Err(self
+            .exception(
+                "java/lang/IllegalAccessError",
+                &format!("{} tried to access protected member through {}", accessor.definition.name(), class.name()),
+            )
+            .await)
+    }
+
     #[async_recursion::async_recursion]
     pub async fn store_array<T, U>(&self, array: &mut Box<dyn ClassInstance>, offset: usize, values: T) -> Result<()>
     where
@@ -343,7 +527,8 @@ impl Jvm {
 
     pub async fn load_array<T>(&self, array: &Box<dyn ClassInstance>, offset: usize, count: usize) -> Result<Vec<T>>
     where
-        T: From<JavaValue>,
+        T: TryFrom<JavaValue>,
+        JavaError: From<T::Error>,
     {
         tracing::trace!("Load array {} at offset {}", array.class_definition().name(), offset);
 
@@ -362,7 +547,9 @@ impl Jvm {
         if let Some(array) = array {
             let values = array.load(offset, count).await?;
 
-            Ok(iter::IntoIterator::into_iter(values).map(|x| x.into()).collect::<Vec<_>>())
+            Ok(iter::IntoIterator::into_iter(values)
+                .map(|x| x.try_into())
+                .collect::<core::result::Result<Vec<_>, _>>()?)
         } else {
             Err(self.exception("java/lang/IllegalArgumentException", "Not an array").await)
         }
@@ -436,7 +623,7 @@ impl Jvm {
 
             let type_name = &class.name()[1..]; // TODO can we store JavaType on class?
 
-            Ok(JavaType::parse(type_name))
+            JavaType::parse(type_name)
         } else {
             Err(self.exception("java/lang/IllegalArgumentException", "Not an array").await)
         }
@@ -451,57 +638,320 @@ impl Jvm {
         Ok(())
     }
 
+    // whether any class loader has defined the class
     pub async fn has_class(&self, class_name: &str) -> bool {
         self.inner.classes.read().await.contains_key(class_name)
     }
 
+    // resolves class in namespace of class loader of the currently executing class
     #[async_recursion::async_recursion]
     pub async fn resolve_class(&self, class_name: &str) -> Result<Class> {
-        self.resolve_class_internal(class_name, None).await
+        let class_loader_id = self.current_class_loader_id().await?;
+
+        self.resolve_class_internal(class_name, class_loader_id).await
+    }
+
+    #[async_recursion::async_recursion]
+    async fn resolve_class_internal(&self, class_name: &str, class_loader_id: ClassLoaderId) -> Result<Class> {
+        let class = self.find_class_internal(class_name, class_loader_id).await?;
+
+        if let Some(x) = class {
+            Ok(x)
+        } else {
+            tracing::error!("No such class: {}", class_name);
+
+            Err(self.exception("java/lang/NoClassDefFoundError", class_name).await)
+        }
     }
 
+    // loads class with given initiating class loader, returns None if the class loader can't find the class
     #[async_recursion::async_recursion]
-    async fn resolve_class_internal(&self, class_name: &str, class_loader_wrapper: Option<&dyn ClassLoaderWrapper>) -> Result<Class> {
+    async fn find_class_internal(&self, class_name: &str, class_loader_id: ClassLoaderId) -> Result<Option<Class>> {
         tracing::trace!("Resolving class {}", class_name);
-        let class = self.inner.classes.read().await.get(class_name).cloned();
+        let class = self
+            .inner
+            .initiated_classes
+            .read()
+            .await
+            .get(class_name)
+            .and_then(|x| x.get(&class_loader_id))
+            .cloned();
 
         if let Some(x) = class {
-            return Ok(x);
+            return Ok(Some(x));
+        }
+
+        // only bootstrap class loader can define core classes, and we need them to call into java class loaders
+        if class_loader_id != BOOTSTRAP_CLASS_LOADER_ID && Self::is_bootstrap_only_class(class_name) {
+            return self.find_class_internal(class_name, BOOTSTRAP_CLASS_LOADER_ID).await;
         }
 
         if class_name.starts_with('[') {
             let stripped_name = class_name.trim_start_matches('[');
             if stripped_name.starts_with('L') {
-                self.resolve_class(&stripped_name[1..stripped_name.len() - 1]).await?;
+                self.resolve_class_internal(&stripped_name[1..stripped_name.len() - 1], class_loader_id)
+                    .await?;
                 // ensure element type is loaded
             }
         }
 
-        let class_loader_wrapper: &dyn ClassLoaderWrapper = if let Some(x) = class_loader_wrapper {
+        tracing::debug!("Loading class {}", class_name);
+
+        let class = if class_loader_id == BOOTSTRAP_CLASS_LOADER_ID {
+            BootstrapClassLoaderWrapper::new(&*self.inner.bootstrap_class_loader)
+                .load_class(self, class_name)
+                .await?
+        } else {
+            // class loader can't be unloaded while its classes are in use
+            let Some(class_loader) = self.class_loader_instance(class_loader_id).await else {
+                return Err(JavaError::FatalError(format!("Class loader {} is unloaded", class_loader_id)));
+            };
+
+            JavaClassLoaderWrapper::new(class_loader).load_class(self, class_name).await?
+        };
+
+        if let Some(x) = &class {
+            tracing::debug!("Loaded class {}", class_name);
+
+            self.record_initiated_class(class_name, class_loader_id, x).await?;
+        }
+
+        Ok(class)
+    }
+
+    // java.* classes, and arrays of them or primitive types
+    fn is_bootstrap_only_class(class_name: &str) -> bool {
+        let element_type_name = class_name.trim_start_matches('[');
+        if element_type_name.len() == class_name.len() {
+            return class_name.starts_with("java/");
+        }
+
+        match element_type_name.strip_prefix('L') {
+            Some(x) => x.starts_with("java/"),
+            None => true,
+        }
+    }
+
+    // returns java/lang/Class of the class the class loader has loaded, as ClassLoader.findLoadedClass does
+    #[allow(clippy::borrowed_box)]
+    pub async fn find_loaded_class(&self, class_loader: &Box<dyn ClassInstance>, class_name: &str) -> Result<Option<Box<dyn ClassInstance>>> {
+        let class_loader_id = self.class_loader_id(Some(class_loader)).await;
+
+        let class = self
+            .inner
+            .initiated_classes
+            .read()
+            .await
+            .get(class_name)
+            .and_then(|x| x.get(&class_loader_id))
+            .cloned();
+
+        match class {
+            Some(x) => Ok(Some(x.java_class(self).await?)),
+            None => Ok(None),
+        }
+    }
+
+    // loads class with bootstrap class loader, for class loaders without parent
+    pub async fn find_bootstrap_class(&self, class_name: &str) -> Result<Option<Box<dyn ClassInstance>>> {
+        let class = self.find_class_internal(class_name, BOOTSTRAP_CLASS_LOADER_ID).await?;
+
+        match class {
+            Some(x) => Ok(Some(x.java_class(self).await?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) async fn defined_class(&self, class_name: &str, class_loader_id: ClassLoaderId) -> Option<Class> {
+        self.inner
+            .classes
+            .read()
+            .await
+            .get(class_name)
+            .and_then(|x| x.get(&class_loader_id))
+            .cloned()
+    }
+
+    pub(crate) async fn find_defined_class(&self, definition: &dyn ClassDefinition) -> Option<Class> {
+        self.inner
+            .classes
+            .read()
+            .await
+            .get(&definition.name())
+            .and_then(|x| x.values().find(|x| x.definition.equals(definition)))
+            .cloned()
+    }
+
+    async fn class_of(&self, instance: &dyn ClassInstance) -> Result<Class> {
+        let definition = instance.class_definition();
+
+        match self.find_defined_class(&*definition).await {
+            Some(x) => Ok(x),
+            None => Err(JavaError::FatalError(format!("Class {} is not registered", definition.name()))),
+        }
+    }
+
+    // resolves superclass with defining class loader of the class
+    async fn super_class(&self, class: &Class) -> Result<Option<Class>> {
+        match class.definition.super_class_name() {
+            Some(x) => Ok(Some(self.resolve_class_internal(&x, class.class_loader_id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn current_class_loader_id(&self) -> Result<ClassLoaderId> {
+        let calling_class = self.find_calling_class().await?;
+
+        Ok(if let Some((class, _)) = calling_class {
+            class.class_loader_id
+        } else {
+            // called outside of java, system class loader is not available while bootstrapping
+            self.inner.system_class_loader_id.get().copied().unwrap_or(BOOTSTRAP_CLASS_LOADER_ID)
+        })
+    }
+
+    // java class loaders get their id on first use
+    #[allow(clippy::borrowed_box)]
+    async fn class_loader_id(&self, class_loader: Option<&Box<dyn ClassInstance>>) -> ClassLoaderId {
+        let class_loader = if let Some(x) = class_loader {
             x
-        } else if self.inner.bootstrapping.load(Ordering::Relaxed) {
-            &BootstrapClassLoaderWrapper::new(&*self.inner.bootstrap_class_loader)
         } else {
-            &JavaClassLoaderWrapper::new(self.current_class_loader().await?)
+            return BOOTSTRAP_CLASS_LOADER_ID;
         };
 
-        self.load_class(class_name, class_loader_wrapper).await
+        let mut class_loaders = self.inner.class_loaders.write().await;
+        if let Some(index) = class_loaders
+            .iter()
+            .position(|x| x.as_ref().is_some_and(|x| is_same_instance(&**x, &**class_loader)))
+        {
+            return index + 1;
+        }
+
+        // id of an unloaded class loader isn't referenced anymore, as its classes are gone with it
+        if let Some(index) = class_loaders.iter().position(Option::is_none) {
+            class_loaders[index] = Some(class_loader.clone());
+
+            return index + 1;
+        }
+
+        class_loaders.push(Some(class_loader.clone()));
+
+        class_loaders.len()
     }
 
-    async fn load_class(&self, class_name: &str, class_loader_wrapper: &dyn ClassLoaderWrapper) -> Result<Class> {
-        tracing::debug!("Loading class {}", class_name);
+    async fn class_loader_instance(&self, class_loader_id: ClassLoaderId) -> Option<Box<dyn ClassInstance>> {
+        if class_loader_id == BOOTSTRAP_CLASS_LOADER_ID {
+            None
+        } else {
+            self.inner.class_loaders.read().await[class_loader_id - 1].clone()
+        }
+    }
 
-        let class = class_loader_wrapper.load_class(self, class_name).await?;
+    // initiating class loaders in the same loader constraint must agree on the class
+    async fn record_initiated_class(&self, class_name: &str, class_loader_id: ClassLoaderId, class: &Class) -> Result<()> {
+        let constraint = self
+            .inner
+            .loader_constraints
+            .read()
+            .await
+            .get(class_name)
+            .and_then(|x| x.iter().find(|x| x.contains(&class_loader_id)).cloned());
+
+        if let Some(class_loaders) = constraint {
+            let violated = self
+                .inner
+                .initiated_classes
+                .read()
+                .await
+                .get(class_name)
+                .is_some_and(|x| class_loaders.iter().filter_map(|y| x.get(y)).any(|y| !y.is_same_class(class)));
 
-        if class.is_none() {
-            tracing::error!("No such class: {}", class_name);
+            if violated {
+                return Err(self
+                    .exception(
+                        "java/lang/LinkageError",
+                        &format!("loader constraint violation: loading {} would define a different class", class_name),
+                    )
+                    .await);
+            }
+        }
+
+        self.inner
+            .initiated_classes
+            .write()
+            .await
+            .entry(class_name.to_owned())
+            .or_default()
+            .insert(class_loader_id, class.clone());
+
+        Ok(())
+    }
 
-            return Err(self.exception("java/lang/NoClassDefFoundError", class_name).await);
+    // classes in signature of a member referenced across class loaders must be the same class in both class loaders
+    async fn check_loader_constraints(&self, accessor: &Class, declaring_class: &Class, descriptor: &str) -> Result<()> {
+        if accessor.class_loader_id == declaring_class.class_loader_id {
+            return Ok(());
         }
 
-        tracing::debug!("Loaded class {}", class_name);
+        let r#type = JavaType::parse(descriptor)?;
+        let types = match &r#type {
+            JavaType::Method(params, return_type) => params.iter().chain(iter::once(&**return_type)).collect::<Vec<_>>(),
+            x => vec![x],
+        };
+
+        for mut r#type in types {
+            while let JavaType::Array(element_type) = r#type {
+                r#type = &**element_type;
+            }
 
-        Ok(class.unwrap())
+            if let JavaType::Class(class_name) = r#type {
+                self.add_loader_constraint(class_name, accessor.class_loader_id, declaring_class.class_loader_id)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn add_loader_constraint(&self, class_name: &str, class_loader1: ClassLoaderId, class_loader2: ClassLoaderId) -> Result<()> {
+        let mut class_loaders = [class_loader1, class_loader2].into_iter().collect::<BTreeSet<_>>();
+
+        if let Some(constraints) = self.inner.loader_constraints.read().await.get(class_name) {
+            if constraints.iter().any(|x| x.is_superset(&class_loaders)) {
+                return Ok(());
+            }
+
+            // merge with existing constraints on either class loader
+            let merged = constraints
+                .iter()
+                .filter(|x| !x.is_disjoint(&class_loaders))
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>();
+            class_loaders.extend(merged);
+        }
+
+        let violated = self.inner.initiated_classes.read().await.get(class_name).is_some_and(|x| {
+            let mut classes = class_loaders.iter().filter_map(|y| x.get(y));
+
+            classes.next().is_some_and(|first| classes.any(|y| !y.is_same_class(first)))
+        });
+
+        if violated {
+            return Err(self
+                .exception(
+                    "java/lang/LinkageError",
+                    &format!("loader constraint violation: class loaders have different classes for {}", class_name),
+                )
+                .await);
+        }
+
+        let mut loader_constraints = self.inner.loader_constraints.write().await;
+        let constraints = loader_constraints.entry(class_name.to_owned()).or_default();
+        constraints.retain(|x| x.is_disjoint(&class_loaders));
+        constraints.push(class_loaders);
+
+        Ok(())
     }
 
     async fn find_calling_class(&self) -> Result<Option<(Class, Option<Box<dyn ClassInstance>>)>> {
@@ -513,6 +963,15 @@ impl Jvm {
         Ok(thread.top_frame().map(|x| (x.class.clone(), x.class_instance.clone())))
     }
 
+    async fn find_calling_method(&self) -> Result<Option<(Class, String)>> {
+        let thread_id = (self.inner.get_current_thread_id)();
+
+        let threads = self.inner.threads.read().await;
+        let thread = threads.get(&thread_id).unwrap();
+
+        Ok(thread.top_frame().map(|x| (x.class.clone(), x.method.clone())))
+    }
+
     pub async fn register_class(
         &self,
         class: Box<dyn ClassDefinition>,
@@ -527,21 +986,29 @@ impl Jvm {
             None
         };
 
-        let class = Class::new(class, java_class.clone());
+        let class_loader_id = self.class_loader_id(class_loader.as_ref()).await;
+        let class = Class::new(class, class_loader_id, java_class.clone());
 
-        if let Some(x) = class_loader {
-            self.register_class_internal(class, Some(&JavaClassLoaderWrapper::new(x))).await?;
-        } else {
-            self.register_class_internal(class, None).await?;
-        };
+        self.register_class_internal(class).await?;
 
         Ok(java_class)
     }
 
+    // compares class names along the superclass chain, and then the class with the class visible from current class
     pub async fn is_instance(&self, instance: &dyn ClassInstance, class_name: &str) -> Result<bool> {
-        let instance_class = instance.class_definition();
+        let mut class = Some(self.class_of(instance).await?);
 
-        self.is_instance_by_name(&instance_class.name(), class_name).await
+        while let Some(x) = class {
+            if x.definition.name() == class_name {
+                let target_class = self.resolve_class(class_name).await?;
+
+                return Ok(x.is_same_class(&target_class));
+            }
+
+            class = self.super_class(&x).await?;
+        }
+
+        Ok(false)
     }
 
     #[async_recursion::async_recursion]
@@ -550,12 +1017,16 @@ impl Jvm {
             return true;
         }
 
-        if let Some(super_class) = class.super_class_name() {
-            self.is_inherited_from(&*self.inner.classes.read().await.get(&super_class).unwrap().definition, class_name)
-                .await
-        } else {
-            false
+        let mut class = self.find_defined_class(class).await;
+        while let Some(x) = class {
+            if x.definition.name() == class_name {
+                return true;
+            }
+
+            class = self.super_class(&x).await.ok().flatten();
         }
+
+        false
     }
 
     pub async fn exception(&self, r#type: &str, message: &str) -> JavaError {
@@ -588,42 +1059,280 @@ impl Jvm {
         result
     }
 
-    #[async_recursion::async_recursion]
-    async fn is_instance_by_name(&self, instance_class_name: &str, class_name: &str) -> Result<bool> {
-        let instance_class = self.resolve_class(instance_class_name).await?.definition;
+    async fn register_class_internal(&self, class: Class) -> Result<()> {
+        let class_name = class.definition.name();
 
-        if instance_class.name() == class_name {
-            return Ok(true);
+        if !class_name.starts_with('[') {
+            if let Some(super_class) = class.definition.super_class_name() {
+                // ensure superclass is loaded
+                self.resolve_class_internal(&super_class, class.class_loader_id).await?;
+            }
         }
 
-        if let Some(super_class) = instance_class.super_class_name() {
-            return self.is_instance_by_name(&super_class, class_name).await;
+        let is_duplicate = match self
+            .inner
+            .classes
+            .write()
+            .await
+            .entry(class_name.clone())
+            .or_default()
+            .entry(class.class_loader_id)
+        {
+            Entry::Occupied(_) => true,
+            Entry::Vacant(x) => {
+                x.insert(class.clone());
+
+                false
+            }
+        };
+
+        if is_duplicate {
+            // bootstrap class loader can race with itself on other threads, while java class loaders must not define class twice
+            if class.class_loader_id == BOOTSTRAP_CLASS_LOADER_ID {
+                return Ok(());
+            }
+
+            return Err(self
+                .exception("java/lang/LinkageError", &format!("duplicate class definition: {}", class_name))
+                .await);
         }
 
-        Ok(false)
+        self.record_initiated_class(&class_name, class.class_loader_id, &class).await?;
+
+        let clinit = class.definition.method("<clinit>", "()V", true);
+
+        if let Some(x) = clinit {
+            tracing::debug!("Calling <clinit> for {}", class_name);
+
+            self.execute_method(&class, None, &x, Box::new([])).await?;
+        }
+
+        Ok(())
     }
 
-    async fn register_class_internal(&self, class: Class, class_loader_wrapper: Option<&dyn ClassLoaderWrapper>) -> Result<()> {
-        if !class.definition.name().starts_with('[') {
-            if let Some(super_class) = class.definition.super_class_name() {
-                if !self.has_class(&super_class).await {
-                    // ensure superclass is loaded
-                    self.resolve_class_internal(&super_class, class_loader_wrapper).await?;
+    // java class loaders which defined a class of given name. bootstrap classes are not included
+    pub async fn defining_class_loaders(&self, class_name: &str) -> Vec<Box<dyn ClassInstance>> {
+        let class_loader_ids = self
+            .inner
+            .classes
+            .read()
+            .await
+            .get(class_name)
+            .map(|x| x.keys().copied().filter(|&x| x != BOOTSTRAP_CLASS_LOADER_ID).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let mut result = Vec::with_capacity(class_loader_ids.len());
+        for class_loader_id in class_loader_ids {
+            if let Some(x) = self.class_loader_instance(class_loader_id).await {
+                result.push(x);
+            }
+        }
+
+        result
+    }
+
+    // replaces methods of the class defined by given class loader with ones of the definition, so that future invocations run the new code like HotSwap.
+    // returns false if the class loader didn't define the class. throws UnsupportedOperationException if fields or method signatures are changed
+    pub async fn redefine_class(&self, definition: &dyn ClassDefinition, class_loader: &Box<dyn ClassInstance>) -> Result<bool> {
+        let class_loader_id = self
+            .inner
+            .class_loaders
+            .read()
+            .await
+            .iter()
+            .position(|x| x.as_ref().is_some_and(|x| is_same_instance(&**x, &**class_loader)))
+            .map(|x| x + 1);
+        let Some(class_loader_id) = class_loader_id else {
+            return Ok(false);
+        };
+
+        let class = self
+            .inner
+            .classes
+            .read()
+            .await
+            .get(&definition.name())
+            .and_then(|x| x.get(&class_loader_id).cloned());
+        let Some(class) = class else {
+            return Ok(false);
+        };
+
+        tracing::debug!("Redefine class {}", definition.name());
+
+        if let Err(x) = class.definition.redefine(definition).await {
+            return Err(self
+                .exception("java/lang/UnsupportedOperationException", &format!("class redefinition failed: {}", x))
+                .await);
+        }
+
+        Ok(true)
+    }
+
+    // unloads classes of java class loaders which became unreachable, and returns their names.
+    // reference counts can't be read consistently while other java threads are moving references around, so they're stopped at a safepoint meanwhile
+    pub async fn unload_classes(&self) -> Result<Vec<String>> {
+        self.begin_safepoint().await;
+        let result = self.unload_unreachable_class_loaders().await;
+        self.end_safepoint();
+
+        result
+    }
+
+    async fn unload_unreachable_class_loaders(&self) -> Result<Vec<String>> {
+        let mut result = Vec::new();
+
+        // unloading a class loader can make class loaders it referenced unreachable
+        loop {
+            let system_class_loader_id = self.inner.system_class_loader_id.get().copied();
+            let class_loader_count = self.inner.class_loaders.read().await.len();
+
+            let mut unloaded = false;
+            for class_loader_id in 1..=class_loader_count {
+                if Some(class_loader_id) == system_class_loader_id {
+                    continue;
+                }
+
+                if let Some(x) = self.unload_class_loader(class_loader_id).await? {
+                    result.extend(x);
+                    unloaded = true;
                 }
             }
+
+            if !unloaded {
+                break;
+            }
         }
 
-        self.inner.classes.write().await.insert(class.definition.name().to_owned(), class.clone());
+        Ok(result)
+    }
 
-        let clinit = class.definition.method("<clinit>", "()V", true);
+    // class loader is unloaded with its classes and their java/lang/Class, if nothing but themselves references any of them.
+    // we don't have tracing gc, so we walk objects reachable from them and compare the references we found with reference counts
+    async fn unload_class_loader(&self, class_loader_id: ClassLoaderId) -> Result<Option<Vec<String>>> {
+        let Some(class_loader) = self.class_loader_instance(class_loader_id).await else {
+            return Ok(None);
+        };
 
-        if let Some(x) = clinit {
-            tracing::debug!("Calling <clinit> for {}", class.definition.name());
+        let classes = self
+            .inner
+            .classes
+            .read()
+            .await
+            .values()
+            .filter_map(|x| x.get(&class_loader_id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut initiated_counts = Vec::with_capacity(classes.len());
+        for class in &classes {
+            let initiating_class_loaders = self
+                .inner
+                .initiated_classes
+                .read()
+                .await
+                .get(&class.definition.name())
+                .into_iter()
+                .flatten()
+                .filter(|(_, x)| x.is_same_class(class))
+                .map(|(&x, _)| x)
+                .collect::<Vec<_>>();
+
+            // other class loaders keep classes they have initiated
+            if initiating_class_loaders.iter().any(|&x| x != class_loader_id) {
+                return Ok(None);
+            }
 
-            x.run(self, Box::new([])).await?;
+            initiated_counts.push(initiating_class_loaders.len());
         }
 
-        Ok(())
+        let class_loader_object_id = object_id(&*class_loader);
+        let mut java_class_object_ids = Vec::with_capacity(classes.len());
+        let mut roots = vec![class_loader];
+        for class in &classes {
+            let java_class = class.peek_java_class().await;
+            java_class_object_ids.push(java_class.as_ref().map(|x| object_id(&**x)));
+
+            roots.extend(java_class);
+            roots.extend(class.definition.static_references().await);
+        }
+
+        let graph = ReferenceGraph::new(roots).await;
+
+        // objects of the classes, found in the graph
+        let mut instances = Vec::new();
+        for (id, instance) in graph.instances() {
+            let definition = instance.class_definition();
+            if let Some(index) = classes.iter().position(|x| x.definition.equals(&*definition)) {
+                instances.push((id, index));
+            }
+        }
+
+        let reachable = graph.externally_reachable();
+        if reachable.contains(&class_loader_object_id)
+            || java_class_object_ids.iter().flatten().any(|x| reachable.contains(x))
+            || instances.iter().any(|(id, _)| reachable.contains(id))
+        {
+            return Ok(None);
+        }
+
+        // class definitions are held by defining and initiating class loader tables, our clone, raw pointer in java/lang/Class and instances.
+        // any other reference is from an instance outside of the graph, or from java stack
+        for (index, class) in classes.iter().enumerate() {
+            let known_references =
+                2 + initiated_counts[index] + java_class_object_ids[index].iter().count() + instances.iter().filter(|(_, x)| *x == index).count();
+
+            if class.definition.reference_count() > known_references {
+                return Ok(None);
+            }
+        }
+
+        tracing::debug!("Unloading class loader {}", class_loader_id);
+
+        self.inner.class_loaders.write().await[class_loader_id - 1] = None;
+        for classes in [&self.inner.classes, &self.inner.initiated_classes] {
+            let mut classes = classes.write().await;
+            for x in classes.values_mut() {
+                x.remove(&class_loader_id);
+            }
+            classes.retain(|_, x| !x.is_empty());
+        }
+
+        let mut loader_constraints = self.inner.loader_constraints.write().await;
+        for constraints in loader_constraints.values_mut() {
+            for x in constraints.iter_mut() {
+                x.remove(&class_loader_id);
+            }
+            constraints.retain(|x| x.len() > 1);
+        }
+        loader_constraints.retain(|_, x| !x.is_empty());
+        drop(loader_constraints);
+
+        let mut class_names = Vec::with_capacity(classes.len());
+        for mut class in classes {
+            if let Some(mut java_class) = class.take_java_class().await {
+                self.drop_rust_object_field::<Box<dyn ClassDefinition>>(&mut java_class, "raw").await?;
+            }
+            class.definition.clear_static_fields().await;
+
+            class_names.push(class.definition.name());
+        }
+
+        // break reference cycles between unreachable objects, so that they can be dropped
+        for (id, mut instance) in graph.into_instances() {
+            if !reachable.contains(&id) {
+                instance.clear_references().await;
+            }
+        }
+
+        for class_name in &class_names {
+            tracing::debug!("Unloaded class {}", class_name);
+
+            if let Some(x) = self.inner.class_unload_hook.get() {
+                x.class_unloaded(class_name);
+            }
+        }
+
+        Ok(Some(class_names))
     }
 
     pub async fn get_rust_object_field<T>(&self, instance: &Box<dyn ClassInstance>, name: &str) -> Result<T>
@@ -655,42 +1364,401 @@ impl Jvm {
         Ok(())
     }
 
-    pub async fn attach_thread(&self) -> Result<()> {
+    // drops rust object stored by put_rust_object_field, leaving the field null
+    pub async fn drop_rust_object_field<T>(&self, instance: &mut Box<dyn ClassInstance>, name: &str) -> Result<()> {
+        let raw_storage: Option<Box<dyn ClassInstance>> = self.get_field(instance, name, "[B").await?;
+        let Some(raw_storage) = raw_storage else {
+            return Ok(());
+        };
+
+        let raw = self.load_byte_array(&raw_storage, 0, self.array_length(&raw_storage).await?).await?;
+        let rust_raw = usize::from_le_bytes(cast_slice(&raw).try_into().unwrap());
+
+        self.put_field(instance, name, "[B", None::<Box<dyn ClassInstance>>).await?;
+        drop(unsafe { Box::from_raw(rust_raw as *mut T) });
+
+        Ok(())
+    }
+
+    // attaches current task of the host as a java thread, backed by a new java.lang.Thread.
+    // jvm doesn't wait for host attached threads on exit, as their lifetime is managed by the host
+    pub async fn attach_thread(&self, name: &str, daemon: bool) -> Result<()> {
         let thread_id = (self.inner.get_current_thread_id)();
-        self.inner.threads.write().await.insert(thread_id, JvmThread::new());
+        self.after_safepoint(|x| x.insert(thread_id, JvmThread::new(None))).await;
+
+        let java_thread = JavaLangThread::new_attached(self, name, daemon).await?;
+        self.inner.threads.write().await.get_mut(&thread_id).unwrap().java_thread = Some(java_thread);
+
+        Ok(())
+    }
+
+    // attaches current task as a thread started with Thread.start
+    pub async fn attach_java_thread(&self, java_thread: Box<dyn ClassInstance>) -> Result<()> {
+        let thread_id = (self.inner.get_current_thread_id)();
+        self.after_safepoint(|x| x.insert(thread_id, JvmThread::new(Some(java_thread)))).await;
 
         Ok(())
     }
 
     pub async fn detach_thread(&self) -> Result<()> {
+        let java_thread = self.current_java_thread().await?;
+        let result = JavaLangThread::exit(self, &java_thread).await;
+
         let thread_id = (self.inner.get_current_thread_id)();
         self.inner.threads.write().await.remove(&thread_id);
+        self.inner.safepoint_event.notify(usize::MAX);
+
+        result
+    }
+
+    // java.lang.Thread instance of current thread
+    pub async fn current_java_thread(&self) -> Result<Box<dyn ClassInstance>> {
+        let thread_id = (self.inner.get_current_thread_id)();
+        let threads = self.inner.threads.read().await;
+        let thread = threads.get(&thread_id).unwrap();
+
+        Ok(thread.java_thread.clone().unwrap())
+    }
+
+    pub async fn monitor_enter(&self, instance: &Box<dyn ClassInstance>) -> Result<()> {
+        self.schedule(SchedulePoint::MonitorEnter).await;
+
+        let thread_id = (self.inner.get_current_thread_id)();
+
+        if !instance.monitor().try_enter(thread_id).await {
+            self.set_thread_state(ThreadState::Blocked(instance.clone())).await;
+            instance.monitor().enter(thread_id).await;
+            self.set_thread_state(ThreadState::Running).await;
+        }
+
+        self.update_current_thread(|x| x.held_monitors.push(instance.clone())).await;
 
         Ok(())
     }
 
-    pub async fn current_class_loader(&self) -> Result<Box<dyn ClassInstance>> {
-        let calling_class = self.find_calling_class().await?;
+    pub async fn monitor_exit(&self, instance: &Box<dyn ClassInstance>) -> Result<()> {
+        let thread_id = self.check_monitor_owner(instance).await?;
+        instance.monitor().exit(thread_id).await;
+
+        self.schedule(SchedulePoint::MonitorExit).await;
+
+        self.update_current_thread(|x| {
+            if let Some(position) = x.held_monitors.iter().rposition(|x| is_same_instance(&**x, &**instance)) {
+                x.held_monitors.remove(position);
+            }
+        })
+        .await;
+
+        Ok(())
+    }
+
+    // releases monitor of `instance` until notified or `until` completes. returns output of `until` if it completed first
+    pub async fn monitor_wait<F>(&self, instance: &Box<dyn ClassInstance>, until: F) -> Result<Option<F::Output>>
+    where
+        F: Future + Send,
+    {
+        let thread_id = self.check_monitor_owner(instance).await?;
+
+        let mut held = Vec::new();
+        self.update_current_thread(|x| {
+            let (this, others) = x.held_monitors.drain(..).partition(|x| is_same_instance(&**x, &**instance));
+            held = this;
+            x.held_monitors = others;
+        })
+        .await;
+
+        self.set_thread_state(ThreadState::Waiting(instance.clone())).await;
+        let (result, entry_count) = instance.monitor().wait(thread_id, until).await;
+
+        self.set_thread_state(ThreadState::Blocked(instance.clone())).await;
+        instance.monitor().reenter(thread_id, entry_count).await;
+        self.set_thread_state(ThreadState::Running).await;
+
+        self.update_current_thread(|x| x.held_monitors.extend(held)).await;
+
+        Ok(result)
+    }
+
+    pub async fn monitor_notify(&self, instance: &Box<dyn ClassInstance>) -> Result<()> {
+        self.check_monitor_owner(instance).await?;
+        instance.monitor().notify(1).await;
+
+        Ok(())
+    }
+
+    pub async fn monitor_notify_all(&self, instance: &Box<dyn ClassInstance>) -> Result<()> {
+        self.check_monitor_owner(instance).await?;
+        instance.monitor().notify(usize::MAX).await;
+
+        Ok(())
+    }
+
+    async fn check_monitor_owner(&self, instance: &Box<dyn ClassInstance>) -> Result<u64> {
+        let thread_id = (self.inner.get_current_thread_id)();
+        if !instance.monitor().is_owned_by(thread_id).await {
+            return Err(self
+                .exception("java/lang/IllegalMonitorStateException", "current thread is not owner")
+                .await);
+        }
+
+        Ok(thread_id)
+    }
+
+    // jvm stays alive until every non-daemon thread registered here calls `non_daemon_thread_finished`
+    pub fn non_daemon_thread_started(&self) {
+        self.inner.non_daemon_threads.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn non_daemon_thread_finished(&self) {
+        if self.inner.non_daemon_threads.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.inner.non_daemon_threads_finished.notify(usize::MAX);
+        }
+    }
+
+    pub async fn wait_for_non_daemon_threads(&self) {
+        loop {
+            let listener = self.inner.non_daemon_threads_finished.listen();
+            if self.inner.non_daemon_threads.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+
+            self.blocking(listener).await;
+        }
+    }
+
+    // requests jvm termination, status of first call wins
+    pub async fn exit(&self, status: i32) {
+        let _ = self.inner.exit_status.set(status).await;
+    }
+
+    pub fn is_exiting(&self) -> bool {
+        self.inner.exit_status.is_initialized()
+    }
+
+    pub async fn wait_for_exit(&self) -> i32 {
+        *self.inner.exit_status.wait().await
+    }
+
+    // can be set once, before running java code on other threads
+    pub async fn set_scheduling_hook(&self, hook: Box<dyn SchedulingHook>) {
+        let _ = self.inner.scheduling_hook.set(hook).await;
+    }
+
+    pub async fn set_class_unload_hook(&self, hook: Box<dyn ClassUnloadHook>) {
+        let _ = self.inner.class_unload_hook.set(hook).await;
+    }
+
+    // every java thread reaches schedule points while running, so this is where threads stop after exit
+    pub async fn schedule(&self, point: SchedulePoint) {
+        if self.is_exiting() {
+            future::pending::<()>().await;
+        }
 
-        if let Some((class, class_instance)) = calling_class {
-            // called in java
+        self.safepoint_poll().await;
 
-            if self.is_inherited_from(&*class.definition, "java/lang/ClassLoader").await {
-                return Ok(class_instance.unwrap());
+        if let Some(x) = self.inner.scheduling_hook.get() {
+            if x.should_schedule(point) {
+                x.schedule(point).await;
             }
+        }
+    }
+
+    // synchronous part of `schedule`, for callers which can't await like compiled code
+    pub fn should_schedule(&self, point: SchedulePoint) -> bool {
+        self.is_exiting()
+            || self.inner.safepoint_requested.load(Ordering::SeqCst)
+            || self.inner.scheduling_hook.get().is_some_and(|x| x.should_schedule(point))
+    }
+
+    pub async fn set_thread_state(&self, state: ThreadState) {
+        // waiting threads don't start running while other threads are stopped
+        if state.is_running() {
+            self.resume_current_thread(|x| x.state = state).await;
+        } else {
+            self.update_current_thread(|x| x.state = state).await;
+            self.inner.safepoint_event.notify(usize::MAX);
+        }
+    }
+
+    // runs `future` as if current thread is stopped at a safepoint, so that waiting outside of java code doesn't hold up other threads' safepoints.
+    // `future` must not touch java objects
+    pub async fn blocking<F>(&self, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        self.update_current_thread(|x| x.stopped = true).await;
+        self.inner.safepoint_event.notify(usize::MAX);
+
+        let result = future.await;
+
+        self.resume_current_thread(|x| x.stopped = false).await;
 
-            let calling_class_class_loader = JavaLangClass::class_loader(self, &class.java_class(self).await?).await?;
-            if let Some(x) = calling_class_class_loader {
-                Ok(x)
-            } else {
-                let system_class_loader = JavaLangClassLoader::get_system_class_loader(self).await?;
-                Ok(system_class_loader)
+        result
+    }
+
+    // stops every other java thread at its next schedule point, or while it's waiting. only one thread holds the safepoint at a time
+    async fn begin_safepoint(&self) {
+        while self
+            .inner
+            .safepoint_requested
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            // other thread holds the safepoint, so we stop like other threads
+            self.safepoint_poll().await;
+        }
+
+        let thread_id = (self.inner.get_current_thread_id)();
+        loop {
+            let listener = self.inner.safepoint_event.listen();
+            let stopped = self
+                .inner
+                .threads
+                .read()
+                .await
+                .iter()
+                .all(|(&id, x)| id == thread_id || x.stopped || !x.state.is_running());
+            if stopped {
+                break;
             }
+
+            listener.await;
+        }
+    }
+
+    fn end_safepoint(&self) {
+        self.inner.safepoint_requested.store(false, Ordering::SeqCst);
+        self.inner.safepoint_event.notify(usize::MAX);
+    }
+
+    // parks current thread while other thread holds the safepoint
+    async fn safepoint_poll(&self) {
+        if !self.inner.safepoint_requested.load(Ordering::SeqCst) {
+            return;
+        }
+
+        self.update_current_thread(|x| x.stopped = true).await;
+        self.inner.safepoint_event.notify(usize::MAX);
+
+        self.resume_current_thread(|x| x.stopped = false).await;
+    }
+
+    async fn resume_current_thread<F>(&self, f: F)
+    where
+        F: FnOnce(&mut JvmThread),
+    {
+        let thread_id = (self.inner.get_current_thread_id)();
+        self.after_safepoint(|x| {
+            if let Some(thread) = x.get_mut(&thread_id) {
+                f(thread);
+            }
+        })
+        .await;
+    }
+
+    // waits until no safepoint is held, then updates threads which are about to run.
+    // update is done under the same lock `begin_safepoint` reads threads with, so that the holder never misses a running thread
+    async fn after_safepoint<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut BTreeMap<u64, JvmThread>) -> R,
+    {
+        loop {
+            let listener = self.inner.safepoint_event.listen();
+
+            let mut threads = self.inner.threads.write().await;
+            if !self.inner.safepoint_requested.load(Ordering::SeqCst) {
+                return f(&mut threads);
+            }
+            drop(threads);
+
+            listener.await;
+        }
+    }
+
+    pub async fn thread_dump(&self) -> Vec<ThreadDump> {
+        let threads = self.inner.threads.read().await;
+        let java_threads = threads.iter().map(|(id, x)| (*id, x.java_thread.clone())).collect::<Vec<_>>();
+        drop(threads);
+
+        let mut result = Vec::with_capacity(java_threads.len());
+        for (id, java_thread) in java_threads {
+            let name = match java_thread {
+                Some(x) => JavaLangThread::name(self, &x).await.unwrap_or_default(),
+                None => String::new(),
+            };
+
+            // thread may have exited while we were reading names
+            if let Some(thread) = self.inner.threads.read().await.get(&id) {
+                result.push(thread.dump(id, name));
+            }
+        }
+
+        result
+    }
+
+    // returns cycles of threads blocked on monitors owned by next thread in cycle
+    pub async fn find_deadlocks(&self) -> Vec<Vec<u64>> {
+        let mut waits_for = BTreeMap::new();
+        for (id, thread) in self.inner.threads.read().await.iter() {
+            if let ThreadState::Blocked(x) = &thread.state {
+                if let Some(owner) = x.monitor().owner().await {
+                    waits_for.insert(*id, owner);
+                }
+            }
+        }
+
+        // each blocked thread waits for single owner, so following the chain finds every cycle
+        let mut visited = BTreeSet::new();
+        let mut result = Vec::new();
+        for start in waits_for.keys() {
+            let mut path = Vec::new();
+            let mut current = *start;
+            loop {
+                if let Some(position) = path.iter().position(|x| *x == current) {
+                    result.push(path.split_off(position));
+                    break;
+                }
+                if !visited.insert(current) {
+                    break;
+                }
+
+                path.push(current);
+                match waits_for.get(&current) {
+                    Some(x) => current = *x,
+                    None => break,
+                }
+            }
+        }
+
+        result
+    }
+
+    async fn update_current_thread<F>(&self, f: F)
+    where
+        F: FnOnce(&mut JvmThread),
+    {
+        let thread_id = (self.inner.get_current_thread_id)();
+        if let Some(thread) = self.inner.threads.write().await.get_mut(&thread_id) {
+            f(thread);
+        }
+    }
+
+    // class loader of the class which called currently executing method, as Class.forName uses. None if it's bootstrap class loader
+    pub async fn caller_class_loader(&self) -> Result<Option<Box<dyn ClassInstance>>> {
+        let thread_id = (self.inner.get_current_thread_id)();
+
+        let caller_class_loader_id = {
+            let threads = self.inner.threads.read().await;
+            let stack = &threads.get(&thread_id).unwrap().stack;
+
+            stack.len().checked_sub(2).map(|x| stack[x].class.class_loader_id)
+        };
+
+        if let Some(x) = caller_class_loader_id {
+            Ok(self.class_loader_instance(x).await)
         } else {
             // called outside of java
-
-            let system_class_loader = JavaLangClassLoader::get_system_class_loader(self).await?;
-            Ok(system_class_loader)
+            Ok(Some(JavaLangClassLoader::get_system_class_loader(self).await?))
         }
     }
 
@@ -700,31 +1768,110 @@ impl Jvm {
 
         if let Some(x) = field {
             Ok(Some(x))
-        } else if let Some(x) = class.super_class_name() {
-            let super_class = self.inner.classes.read().await.get(&x).unwrap().definition.clone();
-            self.find_field(&*super_class, name, descriptor).await
+        } else if let Some(x) = self.find_defined_class(class).await {
+            Ok(self.find_field_declaring_class(x, name, descriptor).await?.map(|x| x.1))
         } else {
             Ok(None)
         }
     }
 
     #[async_recursion::async_recursion]
-    async fn find_virtual_method(
+    async fn find_field_declaring_class(&self, class: Class, name: &str, descriptor: &str) -> Result<Option<(Class, Box<dyn Field>)>> {
+        let field = class.definition.field(name, descriptor, false);
+
+        if let Some(x) = field {
+            Ok(Some((class, x)))
+        } else if let Some(super_class) = self.super_class(&class).await? {
+            self.find_field_declaring_class(super_class, name, descriptor).await
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[async_recursion::async_recursion]
+    async fn find_method_declaring_class(
         &self,
-        class: &dyn ClassDefinition,
+        class: Class,
         name: &str,
         descriptor: &str,
         is_static: bool,
-    ) -> Result<Option<Box<dyn Method>>> {
-        let method = class.method(name, descriptor, false);
+    ) -> Result<Option<(Class, Box<dyn Method>)>> {
+        let method = class.definition.method(name, descriptor, is_static);
+
+        if let Some(x) = method {
+            Ok(Some((class, x)))
+        } else if let Some(super_class) = self.super_class(&class).await? {
+            self.find_method_declaring_class(super_class, name, descriptor, is_static).await
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn is_accessible(&self, accessor: &Class, declaring_class: &Class, visibility: Visibility) -> Result<bool> {
+        Ok(match visibility {
+            Visibility::Public => true,
+            Visibility::Protected => {
+                self.is_same_runtime_package(accessor, declaring_class).await?
+                    || self.is_inherited_from(&*accessor.definition, &declaring_class.definition.name()).await
+            }
+            Visibility::Package => self.is_same_runtime_package(accessor, declaring_class).await?,
+            Visibility::Private => {
+                if accessor.definition.name() == declaring_class.definition.name() {
+                    true
+                } else {
+                    // nestmates can access each other's private members
+                    let accessor_nest_host = accessor.definition.nest_host_name().unwrap_or_else(|| accessor.definition.name());
+                    let declaring_nest_host = declaring_class
+                        .definition
+                        .nest_host_name()
+                        .unwrap_or_else(|| declaring_class.definition.name());
+
+                    accessor_nest_host == declaring_nest_host && self.is_same_runtime_package(accessor, declaring_class).await?
+                }
+            }
+        })
+    }
+
+    // protected members accessible only by inheritance have restriction on instances accessed through
+    async fn requires_protected_instance_check(
+        &self,
+        accessor: &Class,
+        declaring_class: &Class,
+        visibility: Visibility,
+        is_static: bool,
+    ) -> Result<bool> {
+        if is_static || visibility != Visibility::Protected {
+            return Ok(false);
+        }
+
+        Ok(!self.is_same_runtime_package(accessor, declaring_class).await?)
+    }
+
+    // runtime package is determined by package name and defining class loader
+    async fn is_same_runtime_package(&self, class1: &Class, class2: &Class) -> Result<bool> {
+        fn package_name(class_name: &str) -> &str {
+            class_name.rsplit_once('/').map(|x| x.0).unwrap_or("")
+        }
+
+        let class1_name = class1.definition.name();
+        let class2_name = class2.definition.name();
+        if package_name(&class1_name) != package_name(&class2_name) {
+            return Ok(false);
+        }
+
+        Ok(class1.class_loader_id == class2.class_loader_id)
+    }
+
+    #[async_recursion::async_recursion]
+    async fn find_virtual_method(&self, class: Class, name: &str, descriptor: &str, is_static: bool) -> Result<Option<(Class, Box<dyn Method>)>> {
+        let method = class.definition.method(name, descriptor, false);
 
         if let Some(x) = method {
             if x.access_flags().contains(MethodAccessFlags::STATIC) == is_static {
-                return Ok(Some(x));
+                return Ok(Some((class, x)));
             }
-        } else if let Some(x) = class.super_class_name() {
-            let super_class = self.inner.classes.read().await.get(&x).unwrap().definition.clone();
-            return self.find_virtual_method(&*super_class, name, descriptor, is_static).await;
+        } else if let Some(super_class) = self.super_class(&class).await? {
+            return self.find_virtual_method(super_class, name, descriptor, is_static).await;
         }
 
         Ok(None)
@@ -737,9 +1884,21 @@ impl Jvm {
         method: &Box<dyn Method>,
         args: Box<[JavaValue]>,
     ) -> Result<JavaValue> {
+        self.schedule(SchedulePoint::Invoke).await;
+
         let thread_id = (self.inner.get_current_thread_id)();
         let method_str = format!("{}{}", method.name(), method.descriptor());
 
+        // synchronized methods lock on instance, or class object if static
+        let lock = if method.access_flags().contains(MethodAccessFlags::SYNCHRONIZED) {
+            Some(match &class_instance {
+                Some(x) => x.clone(),
+                None => class.java_class(self).await?,
+            })
+        } else {
+            None
+        };
+
         self.inner
             .threads
             .write()
@@ -748,7 +1907,18 @@ impl Jvm {
             .unwrap()
             .push_frame(class, class_instance, &method_str);
 
-        let result = method.run(self, args).await;
+        let result = if let Some(lock) = lock {
+            match self.monitor_enter(&lock).await {
+                Ok(()) => {
+                    let result = method.run(self, args).await;
+
+                    self.monitor_exit(&lock).await.and(result)
+                }
+                Err(x) => Err(x),
+            }
+        } else {
+            method.run(self, args).await
+        };
 
         tracing::trace!("Execute result: {:?}", result);
 
@@ -1,17 +1,22 @@
 #![no_std]
 extern crate alloc;
 
+mod access;
 mod array_class_definition;
 mod array_class_instance;
 mod as_any;
 mod class_definition;
 mod class_instance;
 mod class_loader;
+mod class_unload_hook;
 mod error;
 mod field;
 mod invoke_arg;
 mod jvm;
 mod method;
+mod monitor;
+mod reference_graph;
+mod scheduling_hook;
 mod thread;
 mod r#type;
 mod value;
@@ -34,10 +39,14 @@ pub use self::{
     class_definition::ClassDefinition,
     class_instance::{Array, ClassInstance, ClassInstanceRef},
     class_loader::BootstrapClassLoader,
+    class_unload_hook::ClassUnloadHook,
     error::JavaError,
     field::Field,
     jvm::Jvm,
     method::Method,
+    monitor::Monitor,
     r#type::JavaType,
+    scheduling_hook::{SchedulePoint, SchedulingHook},
+    thread::{ThreadDump, ThreadState},
     value::{JavaChar, JavaValue},
 };
@@ -0,0 +1,169 @@
+use alloc::{collections::VecDeque, sync::Arc};
+use core::{
+    future::Future,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use async_lock::Mutex;
+use event_listener::Event;
+use futures_lite::future;
+
+struct Waiter {
+    notified: AtomicBool,
+    event: Event,
+}
+
+struct MonitorState {
+    owner: Option<u64>,
+    entry_count: usize,
+    wait_set: VecDeque<Arc<Waiter>>,
+}
+
+// reentrant object monitor with wait set, callers should check ownership before wait, notify and exit
+pub struct Monitor {
+    state: Mutex<MonitorState>,
+    released: Event,
+}
+
+impl Monitor {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(MonitorState {
+                owner: None,
+                entry_count: 0,
+                wait_set: VecDeque::new(),
+            }),
+            released: Event::new(),
+        }
+    }
+
+    pub(crate) async fn enter(&self, thread_id: u64) {
+        self.acquire(thread_id, 1).await
+    }
+
+    pub(crate) async fn try_enter(&self, thread_id: u64) -> bool {
+        let mut state = self.state.lock().await;
+        if state.owner.is_none() || state.owner == Some(thread_id) {
+            state.owner = Some(thread_id);
+            state.entry_count += 1;
+
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) async fn exit(&self, thread_id: u64) {
+        let mut state = self.state.lock().await;
+        debug_assert!(state.owner == Some(thread_id));
+
+        state.entry_count -= 1;
+        if state.entry_count == 0 {
+            state.owner = None;
+            drop(state);
+
+            self.released.notify(1);
+        }
+    }
+
+    pub(crate) async fn owner(&self) -> Option<u64> {
+        self.state.lock().await.owner
+    }
+
+    pub(crate) async fn is_owned_by(&self, thread_id: u64) -> bool {
+        self.owner().await == Some(thread_id)
+    }
+
+    // releases monitor until notified or `until` completes, returns output of `until` if it completed first and entry count to `reenter` with
+    pub(crate) async fn wait<F>(&self, thread_id: u64, until: F) -> (Option<F::Output>, usize)
+    where
+        F: Future,
+    {
+        let waiter = Arc::new(Waiter {
+            notified: AtomicBool::new(false),
+            event: Event::new(),
+        });
+
+        let entry_count = {
+            let mut state = self.state.lock().await;
+            debug_assert!(state.owner == Some(thread_id));
+
+            state.wait_set.push_back(waiter.clone());
+            state.owner = None;
+
+            core::mem::replace(&mut state.entry_count, 0)
+        };
+        self.released.notify(1);
+
+        let listener = waiter.event.listen();
+        let result = if waiter.notified.load(Ordering::SeqCst) {
+            None
+        } else {
+            future::or(
+                async {
+                    listener.await;
+                    None
+                },
+                async { Some(until.await) },
+            )
+            .await
+        };
+
+        if result.is_some() {
+            let mut state = self.state.lock().await;
+            let position = state.wait_set.iter().position(|x| Arc::ptr_eq(x, &waiter));
+            if let Some(position) = position {
+                state.wait_set.remove(position);
+            } else {
+                // notified while `until` completed, pass the notification on so it isn't lost
+                Self::notify_waiters(&mut state, 1);
+            }
+        }
+
+        (result, entry_count)
+    }
+
+    pub(crate) async fn reenter(&self, thread_id: u64, entry_count: usize) {
+        self.acquire(thread_id, entry_count).await
+    }
+
+    pub(crate) async fn notify(&self, count: usize) {
+        let mut state = self.state.lock().await;
+
+        Self::notify_waiters(&mut state, count);
+    }
+
+    fn notify_waiters(state: &mut MonitorState, count: usize) {
+        for _ in 0..count {
+            let Some(waiter) = state.wait_set.pop_front() else {
+                break;
+            };
+
+            waiter.notified.store(true, Ordering::SeqCst);
+            waiter.event.notify(1);
+        }
+    }
+
+    async fn acquire(&self, thread_id: u64, count: usize) {
+        loop {
+            let listener = self.released.listen();
+            {
+                let mut state = self.state.lock().await;
+                if state.owner.is_none() || state.owner == Some(thread_id) {
+                    state.owner = Some(thread_id);
+                    state.entry_count += count;
+
+                    return;
+                }
+            }
+
+            listener.await;
+        }
+    }
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
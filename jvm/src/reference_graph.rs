@@ -0,0 +1,89 @@
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use crate::ClassInstance;
+
+struct Node {
+    instance: Box<dyn ClassInstance>,
+    references: Vec<usize>,
+    // references from other nodes and from the caller
+    known_references: usize,
+}
+
+// objects reachable from given roots. as objects are reference counted, an object with more references than we know of
+// is referenced from outside of the graph, e.g. from java stack, host code or objects not reachable from the roots
+pub(crate) struct ReferenceGraph {
+    nodes: BTreeMap<usize, Node>,
+}
+
+impl ReferenceGraph {
+    // each root should be a reference the caller knows the holder of, e.g. a static field
+    pub async fn new(roots: Vec<Box<dyn ClassInstance>>) -> Self {
+        let mut nodes = BTreeMap::new();
+
+        let root_ids = roots.iter().map(|x| object_id(&**x)).collect::<Vec<_>>();
+
+        let mut queue = roots;
+        while let Some(instance) = queue.pop() {
+            let id = object_id(&*instance);
+            if nodes.contains_key(&id) {
+                continue;
+            }
+
+            let references = instance.references().await;
+            nodes.insert(
+                id,
+                Node {
+                    instance,
+                    references: references.iter().map(|x| object_id(&**x)).collect(),
+                    known_references: 0,
+                },
+            );
+
+            queue.extend(references);
+        }
+
+        let edges = nodes.values().flat_map(|x| x.references.clone()).collect::<Vec<_>>();
+        for id in edges.into_iter().chain(root_ids) {
+            nodes.get_mut(&id).unwrap().known_references += 1;
+        }
+
+        Self { nodes }
+    }
+
+    // objects referenced from outside, and objects reachable from them
+    pub fn externally_reachable(&self) -> BTreeSet<usize> {
+        // each object is also referenced by the graph itself
+        let mut queue = self
+            .nodes
+            .iter()
+            .filter(|(_, x)| x.instance.reference_count() > x.known_references + 1)
+            .map(|(&id, _)| id)
+            .collect::<Vec<_>>();
+
+        let mut result = BTreeSet::new();
+        while let Some(id) = queue.pop() {
+            if result.insert(id) {
+                queue.extend(self.nodes[&id].references.iter().copied());
+            }
+        }
+
+        result
+    }
+
+    pub fn instances(&self) -> impl Iterator<Item = (usize, &Box<dyn ClassInstance>)> {
+        self.nodes.iter().map(|(&id, x)| (id, &x.instance))
+    }
+
+    pub fn into_instances(self) -> impl Iterator<Item = (usize, Box<dyn ClassInstance>)> {
+        self.nodes.into_iter().map(|(id, x)| (id, x.instance))
+    }
+}
+
+// each instance has its own monitor, shared by its clones
+pub(crate) fn object_id(instance: &dyn ClassInstance) -> usize {
+    instance.monitor() as *const _ as usize
+}
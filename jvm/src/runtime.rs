@@ -2,8 +2,9 @@ mod java_io_input_stream;
 mod java_lang_class;
 mod java_lang_class_loader;
 mod java_lang_string;
+mod java_lang_thread;
 
 pub use self::{
     java_io_input_stream::JavaIoInputStream, java_lang_class::JavaLangClass, java_lang_class_loader::JavaLangClassLoader,
-    java_lang_string::JavaLangString,
+    java_lang_string::JavaLangString, java_lang_thread::JavaLangThread,
 };
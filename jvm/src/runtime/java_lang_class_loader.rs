@@ -1,6 +1,6 @@
 use alloc::boxed::Box;
 
-use crate::{class_instance::ClassInstance, jvm::Jvm, Result};
+use crate::{class_instance::ClassInstance, jvm::Jvm, JavaError, Result};
 
 use super::JavaLangString;
 
@@ -17,13 +17,18 @@ impl JavaLangClassLoader {
 
     #[allow(clippy::borrowed_box)]
     pub async fn load_class(jvm: &Jvm, this: &Box<dyn ClassInstance>, class_name: &str) -> Result<Option<Box<dyn ClassInstance>>> {
-        let java_class_name = JavaLangString::from_rust_string(jvm, class_name).await?;
+        // class loaders take binary names like `java.lang.String`
+        let java_class_name = JavaLangString::from_rust_string(jvm, &class_name.replace('/', ".")).await?;
 
-        let java_class: Option<Box<dyn ClassInstance>> = jvm
+        let result = jvm
             .invoke_virtual(this, "loadClass", "(Ljava/lang/String;)Ljava/lang/Class;", (java_class_name,))
-            .await?;
+            .await;
 
-        Ok(java_class)
+        // missing class is reported as None, so that caller can raise NoClassDefFoundError
+        match result {
+            Err(JavaError::JavaException(x)) if jvm.is_instance(&*x, "java/lang/ClassNotFoundException").await? => Ok(None),
+            x => x,
+        }
     }
 
     #[allow(clippy::borrowed_box)]
@@ -0,0 +1,28 @@
+use alloc::{boxed::Box, string::String};
+
+use crate::{class_instance::ClassInstance, jvm::Jvm, Result};
+
+use super::JavaLangString;
+
+pub struct JavaLangThread;
+
+impl JavaLangThread {
+    // creates Thread object for a thread attached from host
+    pub async fn new_attached(jvm: &Jvm, name: &str, daemon: bool) -> Result<Box<dyn ClassInstance>> {
+        let name = JavaLangString::from_rust_string(jvm, name).await?;
+
+        jvm.new_class("java/lang/Thread", "(Ljava/lang/String;Z)V", (name, daemon)).await
+    }
+
+    #[allow(clippy::borrowed_box)]
+    pub async fn name(jvm: &Jvm, this: &Box<dyn ClassInstance>) -> Result<String> {
+        let name = jvm.get_field(this, "name", "Ljava/lang/String;").await?;
+
+        JavaLangString::to_rust_string(jvm, &name).await
+    }
+
+    #[allow(clippy::borrowed_box)]
+    pub async fn exit(jvm: &Jvm, this: &Box<dyn ClassInstance>) -> Result<()> {
+        jvm.invoke_special(this, "java/lang/Thread", "exit", "()V", ()).await
+    }
+}
@@ -0,0 +1,22 @@
+use alloc::boxed::Box;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SchedulePoint {
+    Invoke,
+    MonitorEnter,
+    MonitorExit,
+    BackEdge,
+    // reached every few instructions, so that long straight-line code can be preempted too
+    Instructions,
+}
+
+// called by jvm at points where the host may switch to another java thread
+#[async_trait::async_trait]
+pub trait SchedulingHook: Sync + Send {
+    // checked on every point before calling `schedule`, so that hooks can skip the async call on hot paths
+    fn should_schedule(&self, _point: SchedulePoint) -> bool {
+        true
+    }
+
+    async fn schedule(&self, point: SchedulePoint);
+}
@@ -1,18 +1,49 @@
 use alloc::{
     boxed::Box,
+    format,
     string::{String, ToString},
     vec::Vec,
 };
+use core::fmt::{self, Display, Formatter};
 
 use crate::{class_loader::Class, ClassInstance};
 
+#[derive(Clone, Debug)]
+pub enum ThreadState {
+    Running,
+    Sleeping,
+    // in Object.wait, or Thread.join
+    Waiting(Box<dyn ClassInstance>),
+    // waiting to enter monitor
+    Blocked(Box<dyn ClassInstance>),
+}
+
+impl ThreadState {
+    // threads in other states are waiting, and don't move references around until they're running again
+    pub(crate) fn is_running(&self) -> bool {
+        matches!(self, Self::Running)
+    }
+}
+
 pub struct JvmThread {
+    pub state: ThreadState,
     pub stack: Vec<JvmStackFrame>,
+    pub held_monitors: Vec<Box<dyn ClassInstance>>,
+    // None only while Thread object of host attached thread is being constructed
+    pub java_thread: Option<Box<dyn ClassInstance>>,
+    // parked at a safepoint, or waiting outside of java code with `Jvm::blocking`
+    pub stopped: bool,
 }
 
 impl JvmThread {
-    pub fn new() -> Self {
-        Self { stack: Vec::new() }
+    pub fn new(java_thread: Option<Box<dyn ClassInstance>>) -> Self {
+        Self {
+            state: ThreadState::Running,
+            stack: Vec::new(),
+            held_monitors: Vec::new(),
+            java_thread,
+            stopped: false,
+        }
     }
 
     pub fn push_frame(&mut self, class: &Class, class_instance: Option<Box<dyn ClassInstance>>, method: &str) {
@@ -30,6 +61,29 @@ impl JvmThread {
     pub fn top_frame(&self) -> Option<&JvmStackFrame> {
         self.stack.last()
     }
+
+    pub fn dump(&self, id: u64, name: String) -> ThreadDump {
+        // held_monitors has an entry per monitorenter, report each monitor once
+        let mut held_monitors: Vec<Box<dyn ClassInstance>> = Vec::new();
+        for monitor in &self.held_monitors {
+            if !held_monitors.iter().any(|x| is_same_instance(&**x, &**monitor)) {
+                held_monitors.push(monitor.clone());
+            }
+        }
+
+        ThreadDump {
+            id,
+            name,
+            state: self.state.clone(),
+            stack: self
+                .stack
+                .iter()
+                .rev()
+                .map(|x| format!("{}.{}", x.class.definition.name(), x.method))
+                .collect(),
+            held_monitors,
+        }
+    }
 }
 
 pub struct JvmStackFrame {
@@ -37,3 +91,41 @@ pub struct JvmStackFrame {
     pub class_instance: Option<Box<dyn ClassInstance>>,
     pub method: String,
 }
+
+pub struct ThreadDump {
+    pub id: u64,
+    pub name: String,
+    pub state: ThreadState,
+    pub stack: Vec<String>,
+    pub held_monitors: Vec<Box<dyn ClassInstance>>,
+}
+
+impl Display for ThreadDump {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" id={} ", self.name, self.id)?;
+        match &self.state {
+            ThreadState::Running => writeln!(f, "running")?,
+            ThreadState::Sleeping => writeln!(f, "sleeping")?,
+            ThreadState::Waiting(x) => writeln!(f, "waiting on {}", monitor_name(&**x))?,
+            ThreadState::Blocked(x) => writeln!(f, "blocked on {}", monitor_name(&**x))?,
+        }
+
+        for frame in &self.stack {
+            writeln!(f, "\tat {}", frame)?;
+        }
+        for monitor in &self.held_monitors {
+            writeln!(f, "\t- locked {}", monitor_name(&**monitor))?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn is_same_instance(a: &dyn ClassInstance, b: &dyn ClassInstance) -> bool {
+    // each instance has its own monitor
+    core::ptr::eq(a.monitor(), b.monitor())
+}
+
+fn monitor_name(instance: &dyn ClassInstance) -> String {
+    format!("{}@{:x}", instance.class_definition().name(), instance.hash_code())
+}
@@ -1,5 +1,6 @@
 use alloc::{
     boxed::Box,
+    format,
     string::{String, ToString},
     vec::Vec,
 };
@@ -12,7 +13,7 @@ use nom::{
     IResult,
 };
 
-use crate::JavaValue;
+use crate::{JavaError, JavaValue, Result};
 
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub enum JavaType {
@@ -31,9 +32,9 @@ pub enum JavaType {
 }
 
 impl JavaType {
-    pub fn default(&self) -> JavaValue {
-        match self {
-            Self::Void => panic!("Cannot create default value for void"),
+    pub fn default(&self) -> Result<JavaValue> {
+        Ok(match self {
+            Self::Void => return Err(JavaError::FatalError("Cannot create default value for void".into())),
             Self::Boolean => JavaValue::Boolean(false),
             Self::Byte => JavaValue::Byte(0),
             Self::Char => JavaValue::Char(0),
@@ -44,12 +45,15 @@ impl JavaType {
             Self::Double => JavaValue::Double(0.0),
             Self::Class(_) => JavaValue::Object(None),
             Self::Array(_) => JavaValue::Object(None),
-            Self::Method(_, _) => panic!("Cannot create default value for method"),
-        }
+            Self::Method(_, _) => return Err(JavaError::FatalError("Cannot create default value for method".into())),
+        })
     }
 
-    pub fn parse(descriptor: &str) -> Self {
-        Self::parse_type(descriptor).unwrap().1
+    pub fn parse(descriptor: &str) -> Result<Self> {
+        match Self::parse_type(descriptor) {
+            Ok(("", x)) => Ok(x),
+            _ => Err(JavaError::FatalError(format!("Invalid type descriptor: {}", descriptor))),
+        }
     }
 
     pub fn as_method(&self) -> (&[Self], &Self) {
@@ -89,7 +93,7 @@ impl JavaType {
 
                 Ok((remaining, Self::Method(param_types, Box::new(return_type))))
             }
-            _ => panic!("Invalid type descriptor: {}", descriptor),
+            _ => Err(nom::Err::Error(nom::error::Error::new(descriptor, nom::error::ErrorKind::Char))),
         }
     }
 }
@@ -103,7 +107,7 @@ mod test {
     #[test]
     fn test_parse_method_descriptor() {
         assert!(
-            JavaType::parse("(Ljava/lang/String;I)V")
+            JavaType::parse("(Ljava/lang/String;I)V").unwrap()
                 == JavaType::Method(vec![JavaType::Class("java/lang/String".into()), JavaType::Int], Box::new(JavaType::Void))
         );
     }
@@ -111,23 +115,31 @@ mod test {
     #[test]
     fn test_parse_method_descriptor_array() {
         assert!(
-            JavaType::parse("([CI)V") == JavaType::Method(vec![JavaType::Array(Box::new(JavaType::Char)), JavaType::Int], Box::new(JavaType::Void))
+            JavaType::parse("([CI)V").unwrap()
+                == JavaType::Method(vec![JavaType::Array(Box::new(JavaType::Char)), JavaType::Int], Box::new(JavaType::Void))
         )
     }
 
     #[test]
     fn test_parse() {
-        assert!(JavaType::parse("V") == JavaType::Void);
-        assert!(JavaType::parse("I") == JavaType::Int);
-        assert!(JavaType::parse("J") == JavaType::Long);
-        assert!(JavaType::parse("F") == JavaType::Float);
-        assert!(JavaType::parse("D") == JavaType::Double);
-        assert!(JavaType::parse("C") == JavaType::Char);
-        assert!(JavaType::parse("Ljava/lang/String;") == JavaType::Class("java/lang/String".into()));
-        assert!(JavaType::parse("[Ljava/lang/String;") == JavaType::Array(Box::new(JavaType::Class("java/lang/String".into()))));
+        assert!(JavaType::parse("V").unwrap() == JavaType::Void);
+        assert!(JavaType::parse("I").unwrap() == JavaType::Int);
+        assert!(JavaType::parse("J").unwrap() == JavaType::Long);
+        assert!(JavaType::parse("F").unwrap() == JavaType::Float);
+        assert!(JavaType::parse("D").unwrap() == JavaType::Double);
+        assert!(JavaType::parse("C").unwrap() == JavaType::Char);
+        assert!(JavaType::parse("Ljava/lang/String;").unwrap() == JavaType::Class("java/lang/String".into()));
+        assert!(JavaType::parse("[Ljava/lang/String;").unwrap() == JavaType::Array(Box::new(JavaType::Class("java/lang/String".into()))));
         assert!(
-            JavaType::parse("[[Ljava/lang/String;")
+            JavaType::parse("[[Ljava/lang/String;").unwrap()
                 == JavaType::Array(Box::new(JavaType::Array(Box::new(JavaType::Class("java/lang/String".into())))))
         );
     }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(JavaType::parse("X").is_err());
+        assert!(JavaType::parse("Ljava/lang/String").is_err());
+        assert!(JavaType::parse("II").is_err());
+    }
 }
@@ -1,6 +1,6 @@
 use alloc::boxed::Box;
 
-use crate::class_instance::ClassInstance;
+use crate::{class_instance::ClassInstance, JavaError};
 
 pub type JavaChar = u16;
 
@@ -18,101 +18,148 @@ pub enum JavaValue {
     Object(Option<Box<dyn ClassInstance>>),
 }
 
-impl From<JavaValue> for bool {
-    fn from(x: JavaValue) -> Self {
+impl JavaValue {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            JavaValue::Void => "void",
+            JavaValue::Boolean(_) => "boolean",
+            JavaValue::Byte(_) => "byte",
+            JavaValue::Char(_) => "char",
+            JavaValue::Short(_) => "short",
+            JavaValue::Int(_) => "int",
+            JavaValue::Long(_) => "long",
+            JavaValue::Float(_) => "float",
+            JavaValue::Double(_) => "double",
+            JavaValue::Object(Some(_)) => "object",
+            JavaValue::Object(None) => "null",
+        }
+    }
+
+    fn type_mismatch(&self, expected: &'static str) -> JavaError {
+        JavaError::TypeMismatch {
+            expected,
+            actual: self.type_name(),
+        }
+    }
+}
+
+impl TryFrom<JavaValue> for bool {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Boolean(x) => x,
-            _ => panic!("Expected boolean, got {:?}", x),
+            JavaValue::Boolean(x) => Ok(x),
+            _ => Err(x.type_mismatch("boolean")),
         }
     }
 }
 
-impl From<JavaValue> for i8 {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for i8 {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Byte(x) => x,
-            _ => panic!("Expected byte, got {:?}", x),
+            JavaValue::Byte(x) => Ok(x),
+            _ => Err(x.type_mismatch("byte")),
         }
     }
 }
 
-impl From<JavaValue> for JavaChar {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for JavaChar {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Char(x) => x,
-            _ => panic!("Expected char, got {:?}", x),
+            JavaValue::Char(x) => Ok(x),
+            _ => Err(x.type_mismatch("char")),
         }
     }
 }
 
-impl From<JavaValue> for i16 {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for i16 {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Short(x) => x,
-            _ => panic!("Expected short, got {:?}", x),
+            JavaValue::Short(x) => Ok(x),
+            _ => Err(x.type_mismatch("short")),
         }
     }
 }
 
-impl From<JavaValue> for i32 {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for i32 {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Int(x) => x,
-            _ => panic!("Expected int, got {:?}", x),
+            JavaValue::Int(x) => Ok(x),
+            _ => Err(x.type_mismatch("int")),
         }
     }
 }
 
-impl From<JavaValue> for i64 {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for i64 {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Long(x) => x,
-            _ => panic!("Expected long, got {:?}", x),
+            JavaValue::Long(x) => Ok(x),
+            _ => Err(x.type_mismatch("long")),
         }
     }
 }
 
-impl From<JavaValue> for f32 {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for f32 {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Float(x) => x,
-            _ => panic!("Expected float, got {:?}", x),
+            JavaValue::Float(x) => Ok(x),
+            _ => Err(x.type_mismatch("float")),
         }
     }
 }
 
-impl From<JavaValue> for f64 {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for f64 {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Double(x) => x,
-            _ => panic!("Expected double, got {:?}", x),
+            JavaValue::Double(x) => Ok(x),
+            _ => Err(x.type_mismatch("double")),
         }
     }
 }
 
-impl From<JavaValue> for Option<Box<dyn ClassInstance>> {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for Option<Box<dyn ClassInstance>> {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Object(x) => x,
-            _ => panic!("Expected object, got {:?}", x),
+            JavaValue::Object(x) => Ok(x),
+            _ => Err(x.type_mismatch("object")),
         }
     }
 }
 
-impl From<JavaValue> for Box<dyn ClassInstance> {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for Box<dyn ClassInstance> {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Object(x) => x.unwrap(),
-            _ => panic!("Expected object, got {:?}", x),
+            JavaValue::Object(Some(x)) => Ok(x),
+            _ => Err(x.type_mismatch("object")),
         }
     }
 }
 
-impl From<JavaValue> for () {
-    fn from(x: JavaValue) -> Self {
+impl TryFrom<JavaValue> for () {
+    type Error = JavaError;
+
+    fn try_from(x: JavaValue) -> Result<Self, Self::Error> {
         match x {
-            JavaValue::Void => (),
-            _ => panic!("Expected void, got {:?}", x),
+            JavaValue::Void => Ok(()),
+            _ => Err(x.type_mismatch("void")),
         }
     }
 }
@@ -176,3 +223,22 @@ impl From<Option<Box<dyn ClassInstance>>> for JavaValue {
         JavaValue::Object(x)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{JavaError, JavaValue};
+
+    #[test]
+    fn test_type_mismatch() {
+        assert_eq!(i32::try_from(JavaValue::Int(1)).unwrap(), 1);
+
+        let error = i32::try_from(JavaValue::Long(1)).unwrap_err();
+        assert!(matches!(
+            error,
+            JavaError::TypeMismatch {
+                expected: "int",
+                actual: "long"
+            }
+        ));
+    }
+}
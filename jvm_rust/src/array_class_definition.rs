@@ -34,7 +34,15 @@ impl ArrayClassDefinition for ArrayClassDefinitionImpl {
     }
 
     fn instantiate_array(&self, length: usize) -> Result<Box<dyn ClassInstance>> {
-        Ok(Box::new(ArrayClassInstanceImpl::new(self, length)))
+        Ok(Box::new(ArrayClassInstanceImpl::new(self, length)?))
+    }
+
+    fn is_same_definition(&self, other: &dyn ArrayClassDefinition) -> bool {
+        other.as_any().downcast_ref::<Self>().is_some_and(|x| Arc::ptr_eq(&self.inner, &x.inner))
+    }
+
+    fn reference_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
     }
 }
 
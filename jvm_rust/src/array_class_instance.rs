@@ -4,7 +4,7 @@ use core::fmt::{self, Debug, Formatter};
 use async_lock::RwLock;
 use bytemuck::cast_vec;
 
-use jvm::{ArrayClassDefinition, ArrayClassInstance, ClassDefinition, ClassInstance, JavaType, JavaValue, Result};
+use jvm::{ArrayClassDefinition, ArrayClassInstance, ClassDefinition, ClassInstance, JavaType, JavaValue, Monitor, Result};
 
 use crate::array_class_definition::ArrayClassDefinitionImpl;
 
@@ -18,6 +18,7 @@ struct ArrayClassInstanceInner {
     length: usize,
     element_type: JavaType,
     elements: RwLock<ArrayElements>,
+    monitor: Monitor,
 }
 
 #[derive(Clone)]
@@ -26,25 +27,26 @@ pub struct ArrayClassInstanceImpl {
 }
 
 impl ArrayClassInstanceImpl {
-    pub fn new(class: &ArrayClassDefinitionImpl, length: usize) -> Self {
-        let element_type = JavaType::parse(&class.element_type_name());
+    pub fn new(class: &ArrayClassDefinitionImpl, length: usize) -> Result<Self> {
+        let element_type = JavaType::parse(&class.element_type_name())?;
 
         let elements = if matches!(element_type, JavaType::Class(_) | JavaType::Array(_)) {
-            let default_value = element_type.default();
+            let default_value = element_type.default()?;
             ArrayElements::NonPrimitive(vec![default_value; length])
         } else {
             let element_size = Self::primitive_element_size(&element_type);
             ArrayElements::Primitive(vec![0; length * element_size])
         };
 
-        Self {
+        Ok(Self {
             inner: Arc::new(ArrayClassInstanceInner {
                 class: Box::new(class.clone()),
                 length,
                 element_type,
                 elements: RwLock::new(elements),
+                monitor: Monitor::new(),
             }),
-        }
+        })
     }
 
     fn primitive_element_size(element_type: &JavaType) -> usize {
@@ -61,18 +63,20 @@ impl ArrayClassInstanceImpl {
         }
     }
 
-    fn convert_values_to_primitive(&self, values: Box<[JavaValue]>) -> Vec<u8> {
-        match self.inner.element_type {
-            JavaType::Boolean => values.into_vec().into_iter().map(|x| bool::from(x) as u8).collect::<Vec<_>>(),
-            JavaType::Byte => values.into_vec().into_iter().map(|x| i8::from(x) as u8).collect::<Vec<_>>(),
-            JavaType::Char => values.into_vec().into_iter().flat_map(|x| u16::from(x).to_le_bytes()).collect::<Vec<_>>(),
-            JavaType::Short => values.into_vec().into_iter().flat_map(|x| i16::from(x).to_le_bytes()).collect::<Vec<_>>(),
-            JavaType::Int => values.into_vec().into_iter().flat_map(|x| i32::from(x).to_le_bytes()).collect::<Vec<_>>(),
-            JavaType::Long => values.into_vec().into_iter().flat_map(|x| i64::from(x).to_le_bytes()).collect::<Vec<_>>(),
-            JavaType::Float => values.into_vec().into_iter().flat_map(|x| f32::from(x).to_le_bytes()).collect::<Vec<_>>(),
-            JavaType::Double => values.into_vec().into_iter().flat_map(|x| f64::from(x).to_le_bytes()).collect::<Vec<_>>(),
+    fn convert_values_to_primitive(&self, values: Box<[JavaValue]>) -> Result<Vec<u8>> {
+        let values = values.into_vec().into_iter();
+
+        Ok(match self.inner.element_type {
+            JavaType::Boolean => values.map(|x| Ok(bool::try_from(x)? as u8)).collect::<Result<Vec<_>>>()?,
+            JavaType::Byte => values.map(|x| Ok(i8::try_from(x)? as u8)).collect::<Result<Vec<_>>>()?,
+            JavaType::Char => values.map(|x| Ok(u16::try_from(x)?.to_le_bytes())).collect::<Result<Vec<_>>>()?.concat(),
+            JavaType::Short => values.map(|x| Ok(i16::try_from(x)?.to_le_bytes())).collect::<Result<Vec<_>>>()?.concat(),
+            JavaType::Int => values.map(|x| Ok(i32::try_from(x)?.to_le_bytes())).collect::<Result<Vec<_>>>()?.concat(),
+            JavaType::Long => values.map(|x| Ok(i64::try_from(x)?.to_le_bytes())).collect::<Result<Vec<_>>>()?.concat(),
+            JavaType::Float => values.map(|x| Ok(f32::try_from(x)?.to_le_bytes())).collect::<Result<Vec<_>>>()?.concat(),
+            JavaType::Double => values.map(|x| Ok(f64::try_from(x)?.to_le_bytes())).collect::<Result<Vec<_>>>()?.concat(),
             _ => unreachable!(),
-        }
+        })
     }
 
     fn convert_primitive_to_values(&self, values_raw: &[u8]) -> Vec<JavaValue> {
@@ -126,11 +130,15 @@ impl ArrayClassInstance for ArrayClassInstanceImpl {
         Arc::as_ptr(&self.inner) as i32
     }
 
+    fn monitor(&self) -> &Monitor {
+        &self.inner.monitor
+    }
+
     async fn store(&mut self, offset: usize, values: Box<[JavaValue]>) -> Result<()> {
         match &mut *self.inner.elements.write().await {
             ArrayElements::Primitive(x) => {
                 let element_size = Self::primitive_element_size(&self.inner.element_type);
-                let values_raw = self.convert_values_to_primitive(values);
+                let values_raw = self.convert_values_to_primitive(values)?;
 
                 x.splice(offset * element_size..offset * element_size + values_raw.len(), values_raw);
             }
@@ -175,6 +183,29 @@ impl ArrayClassInstance for ArrayClassInstanceImpl {
     fn length(&self) -> usize {
         self.inner.length
     }
+
+    fn reference_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
+    async fn references(&self) -> Vec<Box<dyn ClassInstance>> {
+        match &*self.inner.elements.read().await {
+            ArrayElements::Primitive(_) => Vec::new(),
+            ArrayElements::NonPrimitive(x) => x
+                .iter()
+                .filter_map(|x| match x {
+                    JavaValue::Object(Some(x)) => Some(x.clone()),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    async fn clear_references(&mut self) {
+        if let ArrayElements::NonPrimitive(x) = &mut *self.inner.elements.write().await {
+            x.fill(JavaValue::Object(None));
+        }
+    }
 }
 
 impl Debug for ArrayClassInstanceImpl {
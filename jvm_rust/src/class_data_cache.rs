@@ -0,0 +1,78 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::{Arc, Weak},
+};
+use core::ops::{Deref, DerefMut};
+
+use async_lock::RwLock;
+
+use classfile::ClassFileError;
+use java_class_proto::JavaClassProto;
+
+use crate::class_definition::{ClassData, ClassDefinitionImpl};
+
+// caches parsed classfiles by its contents, so that multiple jvm instances can share parsed class data.
+// rust class protos are cached by name, and share only fields and method signatures as their method bodies are bound to per-jvm runtime context.
+// class data is only shared while some class definition uses it, so that unloaded classes release their methods.
+#[derive(Default)]
+pub struct ClassDataCache {
+    classfiles: RwLock<BTreeMap<Box<[u8]>, Weak<ClassData>>>,
+    protos: RwLock<BTreeMap<String, Weak<ClassData>>>,
+}
+
+impl ClassDataCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn define_class(&self, data: &[u8]) -> Result<ClassDefinitionImpl, ClassFileError> {
+        if let Some(x) = self.classfiles.read().await.get(data).and_then(|x| x.upgrade()) {
+            return Ok(ClassDefinitionImpl::from_class_data(x));
+        }
+
+        let class_data = Arc::new(ClassData::from_classfile(data)?);
+
+        let mut classfiles = self.classfiles.write().await;
+        classfiles.retain(|_, x| x.strong_count() > 0);
+
+        // another jvm might have parsed the same classfile while we were parsing
+        let class_data = match classfiles.get(data).and_then(|x| x.upgrade()) {
+            Some(x) => x,
+            None => {
+                classfiles.insert(data.into(), Arc::downgrade(&class_data));
+
+                class_data
+            }
+        };
+
+        Ok(ClassDefinitionImpl::from_class_data(class_data))
+    }
+
+    // metadata is looked up by class name before building the proto. the proto is still built on cache hit,
+    // as its method bodies are bound to given context
+    pub async fn define_class_proto<C, Context, F>(&self, name: &str, proto: F, context: Context) -> ClassDefinitionImpl
+    where
+        C: ?Sized + 'static + Send,
+        Context: Sync + Send + DerefMut + Deref<Target = C> + Clone + 'static,
+        F: FnOnce() -> JavaClassProto<C>,
+    {
+        let cached = self.protos.read().await.get(name).and_then(|x| x.upgrade());
+
+        let mut class_data = ClassData::from_class_proto(proto(), context);
+        if let Some(x) = cached {
+            class_data.share_metadata(&x);
+
+            return ClassDefinitionImpl::from_class_data(Arc::new(class_data));
+        }
+
+        let class_data = Arc::new(class_data);
+
+        let mut protos = self.protos.write().await;
+        protos.retain(|_, x| x.strong_count() > 0);
+        protos.insert(class_data.name().to_string(), Arc::downgrade(&class_data));
+
+        ClassDefinitionImpl::from_class_data(class_data)
+    }
+}
@@ -12,36 +12,36 @@ use core::{
 
 use async_lock::RwLock;
 
-use classfile::ClassInfo;
+use classfile::{AttributeInfo, ClassFileError, ClassInfo};
 use java_class_proto::JavaClassProto;
 use java_constants::{FieldAccessFlags, MethodAccessFlags};
 use jvm::{ClassDefinition, ClassInstance, Field, JavaValue, Method, Result};
 
 use crate::{class_instance::ClassInstanceImpl, field::FieldImpl, method::MethodImpl};
 
-struct ClassDefinitionInner {
+// immutable parsed class data, can be shared between multiple jvm instances
+pub struct ClassData {
     name: String,
     super_class_name: Option<String>,
+    nest_host_name: Option<String>,
     methods: Vec<MethodImpl>,
     fields: Vec<FieldImpl>,
-    storage: RwLock<BTreeMap<FieldImpl, JavaValue>>, // TODO we should use field offset or something
 }
 
-#[derive(Clone)]
-pub struct ClassDefinitionImpl {
-    inner: Arc<ClassDefinitionInner>,
-}
-
-impl ClassDefinitionImpl {
-    pub fn new(name: &str, super_class_name: Option<String>, methods: Vec<MethodImpl>, fields: Vec<FieldImpl>) -> Self {
+impl ClassData {
+    pub fn new(
+        name: &str,
+        super_class_name: Option<String>,
+        nest_host_name: Option<String>,
+        methods: Vec<MethodImpl>,
+        fields: Vec<FieldImpl>,
+    ) -> Self {
         Self {
-            inner: Arc::new(ClassDefinitionInner {
-                name: name.to_string(),
-                super_class_name,
-                methods,
-                fields,
-                storage: RwLock::new(BTreeMap::new()),
-            }),
+            name: name.to_string(),
+            super_class_name,
+            nest_host_name,
+            methods,
+            fields,
         }
     }
 
@@ -58,41 +58,189 @@ impl ClassDefinitionImpl {
 
         let fields = proto.fields.into_iter().map(FieldImpl::from_field_proto).collect::<Vec<_>>();
 
-        Self::new(proto.name, proto.parent_class.map(|x| x.to_string()), methods, fields)
+        Self::new(proto.name, proto.parent_class.map(|x| x.to_string()), None, methods, fields)
     }
 
-    pub fn from_classfile(data: &[u8]) -> Result<Self> {
-        let class = ClassInfo::parse(data).unwrap(); // TODO ClassFormatError
-        assert_eq!(class.magic, 0xCAFEBABE);
+    pub fn from_classfile(data: &[u8]) -> core::result::Result<Self, ClassFileError> {
+        let class = ClassInfo::parse(data)?;
 
         let fields = class.fields.into_iter().map(FieldImpl::from_field_info).collect::<Vec<_>>();
 
         let methods = class.methods.into_iter().map(MethodImpl::from_method_info).collect::<Vec<_>>();
 
-        Ok(Self::new(&class.this_class, class.super_class.map(|x| x.to_string()), methods, fields))
+        let nest_host_name = class.attributes.iter().find_map(|x| match x {
+            AttributeInfo::NestHost(x) => Some(x.to_string()),
+            _ => None,
+        });
+
+        Ok(Self::new(
+            &class.this_class,
+            class.super_class.map(|x| x.to_string()),
+            nest_host_name,
+            methods,
+            fields,
+        ))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // shares fields and method signatures with class data of the same rust proto bound to other jvm, keeping own method bodies
+    pub(crate) fn share_metadata(&mut self, other: &ClassData) {
+        if self.fields == other.fields {
+            self.fields = other.fields.clone();
+        }
+
+        for (method, other_method) in self.methods.iter_mut().zip(other.methods.iter()) {
+            method.share_signature(other_method);
+        }
+    }
+
+    // only method bodies can be replaced, as instances and static storage are laid out by fields
+    fn check_redefinition(&self, other: &ClassData) -> core::result::Result<(), String> {
+        if self.name != other.name {
+            return Err("class name is changed".into());
+        }
+        if self.super_class_name != other.super_class_name {
+            return Err("attempted to change superclass".into());
+        }
+        if self.fields != other.fields {
+            return Err("attempted to change the schema (add/remove fields)".into());
+        }
+
+        for method in &self.methods {
+            let new_method = other
+                .methods
+                .iter()
+                .find(|x| x.name() == method.name() && x.descriptor() == method.descriptor());
+            match new_method {
+                Some(x) if x.access_flags() != method.access_flags() => return Err("attempted to change method modifiers".into()),
+                Some(_) => {}
+                None => return Err("attempted to delete a method".into()),
+            }
+        }
+        if self.methods.len() != other.methods.len() {
+            return Err("attempted to add a method".into());
+        }
+
+        Ok(())
+    }
+}
+
+// class data with methods having runtime state of the definition
+#[derive(Clone)]
+struct DefinedClassData {
+    data: Arc<ClassData>,
+    methods: Arc<[MethodImpl]>,
+}
+
+impl DefinedClassData {
+    fn new(data: Arc<ClassData>) -> Self {
+        let methods = data.methods.iter().map(|x| x.clone_for_definition()).collect();
+
+        Self { data, methods }
+    }
+}
+
+// class data of a definition, which is replaced on redefinition.
+// methods being executed hold their own references, so replaced data is released once they return
+struct ClassDataSlot {
+    current: spin::RwLock<DefinedClassData>,
+}
+
+impl ClassDataSlot {
+    fn new(data: Arc<ClassData>) -> Self {
+        Self {
+            current: spin::RwLock::new(DefinedClassData::new(data)),
+        }
+    }
+
+    fn get(&self) -> DefinedClassData {
+        self.current.read().clone()
     }
 
-    pub fn fields(&self) -> &[FieldImpl] {
-        &self.inner.fields
+    fn replace(&self, data: Arc<ClassData>) -> core::result::Result<(), String> {
+        let mut current = self.current.write();
+
+        current.data.check_redefinition(&data)?;
+        *current = DefinedClassData::new(data);
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct ClassDefinitionImpl {
+    data: Arc<ClassDataSlot>,
+    storage: Arc<RwLock<BTreeMap<FieldImpl, JavaValue>>>, // TODO we should use field offset or something
+}
+
+impl ClassDefinitionImpl {
+    pub fn new(
+        name: &str,
+        super_class_name: Option<String>,
+        nest_host_name: Option<String>,
+        methods: Vec<MethodImpl>,
+        fields: Vec<FieldImpl>,
+    ) -> Self {
+        Self::from_class_data(Arc::new(ClassData::new(name, super_class_name, nest_host_name, methods, fields)))
+    }
+
+    // creates new class definition with its own static storage
+    pub fn from_class_data(data: Arc<ClassData>) -> Self {
+        Self {
+            data: Arc::new(ClassDataSlot::new(data)),
+            storage: Arc::new(RwLock::new(BTreeMap::new())),
+        }
+    }
+
+    pub fn from_class_proto<C, Context>(proto: JavaClassProto<C>, context: Context) -> Self
+    where
+        C: ?Sized + 'static + Send,
+        Context: Sync + Send + DerefMut + Deref<Target = C> + Clone + 'static,
+    {
+        Self::from_class_data(Arc::new(ClassData::from_class_proto(proto, context)))
+    }
+
+    pub fn from_classfile(data: &[u8]) -> core::result::Result<Self, ClassFileError> {
+        Ok(Self::from_class_data(Arc::new(ClassData::from_classfile(data)?)))
+    }
+
+    pub fn fields(&self) -> Vec<FieldImpl> {
+        self.data.get().data.fields.clone()
     }
 }
 
 #[async_trait::async_trait]
 impl ClassDefinition for ClassDefinitionImpl {
     fn name(&self) -> String {
-        self.inner.name.clone()
+        self.data.get().data.name.clone()
     }
 
     fn super_class_name(&self) -> Option<String> {
-        self.inner.super_class_name.as_ref().map(|x| x.to_string())
+        self.data.get().data.super_class_name.as_ref().map(|x| x.to_string())
+    }
+
+    fn nest_host_name(&self) -> Option<String> {
+        self.data.get().data.nest_host_name.clone()
     }
 
     fn instantiate(&self) -> Result<Box<dyn ClassInstance>> {
         Ok(Box::new(ClassInstanceImpl::new(self)))
     }
 
+    fn equals(&self, other: &dyn ClassDefinition) -> bool {
+        // class data can be shared between jvms, but static storage is per definition
+        other
+            .as_any()
+            .downcast_ref::<Self>()
+            .is_some_and(|x| Arc::ptr_eq(&self.storage, &x.storage))
+    }
+
     fn method(&self, name: &str, descriptor: &str, is_static: bool) -> Option<Box<dyn Method>> {
-        self.inner
+        self.data
+            .get()
             .methods
             .iter()
             .find(|&method| {
@@ -102,7 +250,9 @@ impl ClassDefinition for ClassDefinitionImpl {
     }
 
     fn field(&self, name: &str, descriptor: &str, is_static: bool) -> Option<Box<dyn Field>> {
-        self.inner
+        self.data
+            .get()
+            .data
             .fields
             .iter()
             .find(|&field| {
@@ -114,23 +264,50 @@ impl ClassDefinition for ClassDefinitionImpl {
     async fn get_static_field(&self, field: &dyn Field) -> Result<JavaValue> {
         let field = field.as_any().downcast_ref::<FieldImpl>().unwrap();
 
-        let storage = self.inner.storage.read().await;
+        let storage = self.storage.read().await;
         let value = storage.get(field);
 
         if let Some(x) = value {
             Ok(x.clone())
         } else {
-            Ok(field.r#type().default())
+            field.r#type()?.default()
         }
     }
 
     async fn put_static_field(&mut self, field: &dyn Field, value: JavaValue) -> Result<()> {
         let field = field.as_any().downcast_ref::<FieldImpl>().unwrap();
 
-        self.inner.storage.write().await.insert(field.clone(), value);
+        self.storage.write().await.insert(field.clone(), value);
 
         Ok(())
     }
+
+    fn reference_count(&self) -> usize {
+        Arc::strong_count(&self.storage)
+    }
+
+    async fn static_references(&self) -> Vec<Box<dyn ClassInstance>> {
+        self.storage
+            .read()
+            .await
+            .values()
+            .filter_map(|x| match x {
+                JavaValue::Object(Some(x)) => Some(x.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    async fn clear_static_fields(&mut self) {
+        self.storage.write().await.clear();
+    }
+
+    async fn redefine(&self, definition: &dyn ClassDefinition) -> core::result::Result<(), String> {
+        let Some(definition) = definition.as_any().downcast_ref::<Self>() else {
+            return Err("class is not defined from classfile".into());
+        };
+        self.data.replace(definition.data.get().data)
+    }
 }
 
 impl Debug for ClassDefinitionImpl {
@@ -1,15 +1,16 @@
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
 use core::fmt::{self, Debug, Formatter};
 
 use async_lock::RwLock;
 
-use jvm::{ClassDefinition, ClassInstance, Field, JavaValue, Result};
+use jvm::{ClassDefinition, ClassInstance, Field, JavaValue, Monitor, Result};
 
 use crate::{class_definition::ClassDefinitionImpl, FieldImpl};
 
 struct ClassInstanceInner {
     class: Box<dyn ClassDefinition>,
     storage: RwLock<BTreeMap<FieldImpl, JavaValue>>, // TODO we should use field offset or something
+    monitor: Monitor,
 }
 
 #[derive(Clone)]
@@ -23,6 +24,7 @@ impl ClassInstanceImpl {
             inner: Arc::new(ClassInstanceInner {
                 class: Box::new(class.clone()),
                 storage: RwLock::new(BTreeMap::new()),
+                monitor: Monitor::new(),
             }),
         }
     }
@@ -46,6 +48,10 @@ impl ClassInstance for ClassInstanceImpl {
         Arc::as_ptr(&self.inner) as i32
     }
 
+    fn monitor(&self) -> &Monitor {
+        &self.inner.monitor
+    }
+
     async fn get_field(&self, field: &dyn Field) -> Result<JavaValue> {
         let field = field.as_any().downcast_ref::<FieldImpl>().unwrap();
 
@@ -55,7 +61,7 @@ impl ClassInstance for ClassInstanceImpl {
         if let Some(x) = value {
             Ok(x.clone())
         } else {
-            Ok(field.r#type().default())
+            field.r#type()?.default()
         }
     }
 
@@ -66,6 +72,27 @@ impl ClassInstance for ClassInstanceImpl {
 
         Ok(())
     }
+
+    fn reference_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
+    async fn references(&self) -> Vec<Box<dyn ClassInstance>> {
+        self.inner
+            .storage
+            .read()
+            .await
+            .values()
+            .filter_map(|x| match x {
+                JavaValue::Object(Some(x)) => Some(x.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    async fn clear_references(&mut self) {
+        self.inner.storage.write().await.clear();
+    }
 }
 
 impl Debug for ClassInstanceImpl {
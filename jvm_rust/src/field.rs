@@ -6,7 +6,7 @@ use alloc::{
 use classfile::FieldInfo;
 use java_class_proto::JavaFieldProto;
 use java_constants::FieldAccessFlags;
-use jvm::{Field, JavaType};
+use jvm::{Field, JavaType, Result};
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
 struct FieldInner {
@@ -59,7 +59,7 @@ impl Field for FieldImpl {
         self.inner.access_flags
     }
 
-    fn r#type(&self) -> JavaType {
+    fn r#type(&self) -> Result<JavaType> {
         JavaType::parse(&self.inner.descriptor)
     }
 }
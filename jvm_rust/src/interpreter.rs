@@ -1,51 +1,125 @@
 use alloc::{boxed::Box, format, vec::Vec};
-use core::iter;
+use core::sync::atomic::{AtomicU32, AtomicU8, Ordering};
 
-use classfile::{AttributeInfoCode, Opcode, ValueConstant};
-use jvm::{runtime::JavaLangString, ClassInstance, JavaChar, JavaError, JavaType, JavaValue, Jvm, Result};
+use classfile::{AttributeInfoCode, Opcode, ReferenceConstant, ValueConstant};
+use jvm::{runtime::JavaLangString, ClassInstance, JavaChar, JavaError, JavaType, JavaValue, Jvm, Result, SchedulePoint};
 
 use crate::stack_frame::StackFrame;
 
+// instructions executed between `SchedulePoint::Instructions`
+const INSTRUCTIONS_PER_SCHEDULE_POINT: u32 = 1024;
+
 enum ExecuteNext {
     Continue,
     Jump(u32),
     Return(JavaValue),
 }
 
+#[cfg(all(feature = "jit", not(target_arch = "wasm32")))]
+pub(crate) enum Step {
+    Continue(u32), // offset of next instruction
+    Return(JavaValue),
+}
+
+// result of access checks of field and method references, resolved once per instruction
+pub struct LinkedReferences {
+    states: Box<[AtomicU8]>, // indexed by code offset
+}
+
+impl LinkedReferences {
+    const UNLINKED: u8 = 0;
+    const LINKED: u8 = 1;
+    const LINKED_PROTECTED: u8 = 2; // needs protected instance check on each access
+
+    pub fn new(code_attribute: Option<&AttributeInfoCode>) -> Self {
+        let length = code_attribute.and_then(|x| x.code.keys().last()).map(|x| *x as usize + 1).unwrap_or(0);
+
+        Self {
+            states: (0..length).map(|_| AtomicU8::new(Self::UNLINKED)).collect(),
+        }
+    }
+
+    fn get(&self, offset: u32) -> Option<bool> {
+        match self.states[offset as usize].load(Ordering::Acquire) {
+            Self::UNLINKED => None,
+            x => Some(x == Self::LINKED_PROTECTED),
+        }
+    }
+
+    fn set(&self, offset: u32, protected: bool) {
+        let state = if protected { Self::LINKED_PROTECTED } else { Self::LINKED };
+
+        self.states[offset as usize].store(state, Ordering::Release);
+    }
+}
+
 pub struct Interpreter;
 
 impl Interpreter {
-    pub async fn run(jvm: &Jvm, code_attribute: &AttributeInfoCode, args: Box<[JavaValue]>, return_type: &JavaType) -> Result<JavaValue> {
-        let mut stack_frame = StackFrame::new();
+    pub async fn run(
+        jvm: &Jvm,
+        code_attribute: &AttributeInfoCode,
+        args: Box<[JavaValue]>,
+        return_type: &JavaType,
+        hotness: &AtomicU32,
+        linked: &LinkedReferences,
+    ) -> Result<JavaValue> {
+        let mut stack_frame = StackFrame::new(code_attribute.max_locals as usize, code_attribute.max_stack as usize);
 
-        stack_frame.local_variables = args.into_vec().into_iter().map(Self::to_stack_frame_type).collect();
+        stack_frame.set_arguments(args);
 
-        stack_frame
-            .local_variables
-            .extend(iter::repeat(JavaValue::Void).take(code_attribute.max_locals as usize));
+        Self::resume(jvm, code_attribute, stack_frame, 0, return_type, hotness, linked).await
+    }
+
+    // continues execution of method from given offset, used by compiled code to deoptimize
+    pub(crate) async fn resume(
+        jvm: &Jvm,
+        code_attribute: &AttributeInfoCode,
+        mut stack_frame: StackFrame,
+        offset: u32,
+        return_type: &JavaType,
+        hotness: &AtomicU32,
+        linked: &LinkedReferences,
+    ) -> Result<JavaValue> {
+        // index opcodes by position, so that we can dispatch and look ahead without walking the tree
+        let code = code_attribute.code.iter().collect::<Vec<_>>();
+        let position = |offset: u32| code.binary_search_by_key(&offset, |x| *x.0).unwrap();
+
+        let mut pc = position(offset);
+        let mut instructions = 0;
+        while pc < code.len() {
+            let (offset, opcode) = code[pc];
+            pc += 1;
+
+            instructions += 1;
+            if instructions == INSTRUCTIONS_PER_SCHEDULE_POINT {
+                instructions = 0;
+
+                jvm.schedule(SchedulePoint::Instructions).await;
+            }
 
-        let mut iter = code_attribute.code.range(0..);
-        while let Some((offset, opcode)) = iter.next() {
             tracing::trace!("Opcode {:?}", opcode);
 
-            let result = Self::execute_opcode(jvm, *offset, opcode, &mut stack_frame, return_type).await;
+            let result = if let Some((next, fused)) = Self::execute_superinstruction(opcode, code[pc..].iter().copied(), &mut stack_frame) {
+                pc += fused;
+
+                Ok(next)
+            } else {
+                Self::execute_instruction(jvm, *offset, opcode, &mut stack_frame, return_type, linked).await
+            };
             match result {
                 Ok(ExecuteNext::Continue) => {}
-                Ok(ExecuteNext::Jump(offset)) => {
-                    iter = code_attribute.code.range(offset..);
-                }
-                Ok(ExecuteNext::Return(value)) => return Ok(value),
-                Err(JavaError::JavaException(e)) => {
-                    let exception_handler = Self::find_exception_handler(jvm, &*e, code_attribute, *offset).await;
-                    if let Some(x) = exception_handler {
-                        stack_frame.operand_stack.clear();
-                        stack_frame.operand_stack.push(JavaValue::Object(Some(e)));
-
-                        iter = code_attribute.code.range(x..);
-                    } else {
-                        return Err(JavaError::JavaException(e));
+                Ok(ExecuteNext::Jump(x)) => {
+                    if x <= *offset {
+                        hotness.fetch_add(1, Ordering::Relaxed);
+
+                        jvm.schedule(SchedulePoint::BackEdge).await;
                     }
+
+                    pc = position(x);
                 }
+                Ok(ExecuteNext::Return(value)) => return Ok(value),
+                Err(JavaError::JavaException(e)) => pc = position(Self::handle_exception(jvm, code_attribute, &mut stack_frame, *offset, e).await?),
                 Err(e) => return Err(e),
             }
         }
@@ -53,616 +127,615 @@ impl Interpreter {
         panic!("Should not reach here")
     }
 
+    // executes single instruction on behalf of compiled code, returning where execution continues
+    #[cfg(all(feature = "jit", not(target_arch = "wasm32")))]
+    pub(crate) async fn step(
+        jvm: &Jvm,
+        code_attribute: &AttributeInfoCode,
+        stack_frame: &mut StackFrame,
+        offset: u32,
+        return_type: &JavaType,
+        linked: &LinkedReferences,
+    ) -> Result<Step> {
+        let opcode = &code_attribute.code[&offset];
+
+        match Self::execute_instruction(jvm, offset, opcode, stack_frame, return_type, linked).await {
+            Ok(ExecuteNext::Continue) => Ok(Step::Continue(code_attribute.code.range(offset + 1..).next().map(|x| *x.0).unwrap())),
+            Ok(ExecuteNext::Jump(x)) => Ok(Step::Continue(x)),
+            Ok(ExecuteNext::Return(value)) => Ok(Step::Return(value)),
+            Err(JavaError::JavaException(e)) => Ok(Step::Continue(Self::handle_exception(jvm, code_attribute, stack_frame, offset, e).await?)),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn execute_instruction(
+        jvm: &Jvm,
+        offset: u32,
+        opcode: &Opcode,
+        stack_frame: &mut StackFrame,
+        return_type: &JavaType,
+        linked: &LinkedReferences,
+    ) -> Result<ExecuteNext> {
+        match Self::execute_simple_opcode(offset, opcode, stack_frame)? {
+            Some(x) => Ok(x),
+            None => Self::execute_opcode(jvm, offset, opcode, stack_frame, return_type, linked).await,
+        }
+    }
+
+    // returns offset of exception handler with exception pushed on the stack, or rethrows exception if there's no handler
+    async fn handle_exception(
+        jvm: &Jvm,
+        code_attribute: &AttributeInfoCode,
+        stack_frame: &mut StackFrame,
+        offset: u32,
+        exception: Box<dyn ClassInstance>,
+    ) -> Result<u32> {
+        let Some(handler) = Self::find_exception_handler(jvm, &*exception, code_attribute, offset).await else {
+            return Err(JavaError::JavaException(exception));
+        };
+
+        stack_frame.clear_operand_stack();
+        stack_frame.push_reference(Some(exception));
+
+        Ok(handler)
+    }
+
+    // opcodes which need jvm, called when execute_simple_opcode returned None
     async fn execute_opcode(
         jvm: &Jvm,
-        current_offset: u32,
+        offset: u32,
         opcode: &Opcode,
         stack_frame: &mut StackFrame,
         return_type: &JavaType,
+        linked: &LinkedReferences,
     ) -> Result<ExecuteNext> {
         match opcode {
             Opcode::Aaload | Opcode::Baload | Opcode::Caload | Opcode::Daload | Opcode::Faload | Opcode::Iaload | Opcode::Laload | Opcode::Saload => {
                 // TODO type checking
-                let index: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let array: Option<Box<dyn ClassInstance>> = stack_frame.operand_stack.pop().unwrap().into();
-                if array.is_none() {
+                let index = stack_frame.pop_int();
+                let Some(array) = stack_frame.pop_reference_ref() else {
                     return Err(jvm.exception("java/lang/NullPointerException", "Array is null").await);
-                }
+                };
 
-                let value = jvm.load_array(&array.unwrap(), index as usize, 1).await?.pop().unwrap();
+                let value = jvm.load_array(array, index as usize, 1).await?.pop().unwrap();
 
-                stack_frame.operand_stack.push(Self::to_stack_frame_type(value));
+                stack_frame.push_value(value);
             }
-            Opcode::Aastore
-            | Opcode::Bastore
-            | Opcode::Castore
-            | Opcode::Dastore
-            | Opcode::Fastore
-            | Opcode::Iastore
-            | Opcode::Lastore
-            | Opcode::Sastore => {
-                // TODO type checking
-                let value = stack_frame.operand_stack.pop().unwrap();
-                let index: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let mut array: Option<Box<dyn ClassInstance>> = stack_frame.operand_stack.pop().unwrap().into();
-                if array.is_none() {
+            Opcode::Aastore => {
+                let value = JavaValue::Object(stack_frame.pop_reference());
+                Self::store_array(jvm, stack_frame, value).await?;
+            }
+            Opcode::Bastore | Opcode::Castore | Opcode::Iastore | Opcode::Sastore => {
+                let value = JavaValue::Int(stack_frame.pop_int());
+                Self::store_array(jvm, stack_frame, value).await?;
+            }
+            Opcode::Dastore => {
+                let value = JavaValue::Double(stack_frame.pop_double());
+                Self::store_array(jvm, stack_frame, value).await?;
+            }
+            Opcode::Fastore => {
+                let value = JavaValue::Float(stack_frame.pop_float());
+                Self::store_array(jvm, stack_frame, value).await?;
+            }
+            Opcode::Lastore => {
+                let value = JavaValue::Long(stack_frame.pop_long());
+                Self::store_array(jvm, stack_frame, value).await?;
+            }
+            Opcode::Anewarray(x) => {
+                let length = stack_frame.pop_int();
+                let element_type_name = format!("L{};", x.as_class());
+                let array = jvm.instantiate_array(&element_type_name, length as _).await?;
+
+                stack_frame.push_reference(Some(array));
+            }
+            Opcode::Areturn => return Ok(ExecuteNext::Return(JavaValue::Object(stack_frame.pop_reference()))),
+            Opcode::Dreturn => return Ok(ExecuteNext::Return(JavaValue::Double(stack_frame.pop_double()))),
+            Opcode::Freturn => return Ok(ExecuteNext::Return(JavaValue::Float(stack_frame.pop_float()))),
+            Opcode::Ireturn => {
+                let value = stack_frame.pop_int();
+
+                return Ok(ExecuteNext::Return(match return_type {
+                    JavaType::Boolean => JavaValue::Boolean(value == 1),
+                    JavaType::Char => JavaValue::Char(value as _),
+                    JavaType::Byte => JavaValue::Byte(value as _),
+                    JavaType::Short => JavaValue::Short(value as _),
+                    _ => JavaValue::Int(value),
+                }));
+            }
+            Opcode::Lreturn => return Ok(ExecuteNext::Return(JavaValue::Long(stack_frame.pop_long()))),
+            Opcode::Arraylength => {
+                let Some(array) = stack_frame.pop_reference_ref() else {
                     return Err(jvm.exception("java/lang/NullPointerException", "Array is null").await);
+                };
+
+                let length = jvm.array_length(array).await?;
+                stack_frame.push_int(length as _);
+            }
+            Opcode::Athrow => {
+                let Some(exception) = stack_frame.pop_reference() else {
+                    return Err(jvm.exception("java/lang/NullPointerException", "Exception is null").await);
+                };
+
+                return Err(JavaError::JavaException(exception));
+            }
+            Opcode::Getfield(x) => {
+                let protected = Self::link_field(jvm, linked, offset, x, false, false).await?;
+
+                let Some(instance) = stack_frame.pop_reference_ref() else {
+                    return Err(jvm
+                        .exception(
+                            "java/lang/NullPointerException",
+                            &format!("Field {}::{} is read from null", x.class, x.name),
+                        )
+                        .await);
+                };
+                if protected {
+                    jvm.check_protected_instance_access(&**instance).await?;
                 }
 
-                let element_type = jvm.array_element_type(array.as_ref().unwrap()).await?;
+                let value = jvm.get_field(instance, &x.name, &x.descriptor).await?;
 
-                // operand stack has only integer, so convert it to the correct type
-                let value = match element_type {
-                    JavaType::Boolean => JavaValue::Boolean(i32::from(value) == 1),
-                    JavaType::Byte => JavaValue::Byte(i32::from(value) as _),
-                    JavaType::Char => JavaValue::Char(i32::from(value) as _),
-                    JavaType::Short => JavaValue::Short(i32::from(value) as _),
-                    JavaType::Int => JavaValue::Int(i32::from(value)),
-                    _ => value,
+                stack_frame.push_value(value);
+            }
+            Opcode::Getstatic(x) => {
+                Self::link_field(jvm, linked, offset, x, true, false).await?;
+
+                let value = jvm.get_static_field(&x.class, &x.name, &x.descriptor).await?;
+
+                stack_frame.push_value(value);
+            }
+            Opcode::Instanceof(x) => {
+                // null is not an instance of any class
+                let result = match stack_frame.pop_reference_ref() {
+                    Some(instance) => jvm.is_instance(&**instance, x.as_class()).await?,
+                    None => false,
                 };
+                stack_frame.push_int(result as _);
+            }
+            Opcode::Invokeinterface(x, _count, _zero) => {
+                let protected = Self::link_method(jvm, linked, offset, x, false).await?;
 
-                jvm.store_array(array.as_mut().unwrap(), index as usize, [value]).await?;
+                let params = Self::extract_invoke_params(stack_frame, &x.descriptor)?;
+                let instance = Self::pop_invoke_instance(jvm, stack_frame, x, protected).await?;
+
+                let result = jvm.invoke_virtual(instance, &x.name, &x.descriptor, params).await?;
+                stack_frame.push_value(result);
             }
-            Opcode::AconstNull => stack_frame.operand_stack.push(JavaValue::Object(None)),
-            Opcode::Aload(x) | Opcode::Dload(x) | Opcode::Fload(x) | Opcode::Iload(x) | Opcode::Lload(x) => {
-                let value = stack_frame.local_variables[*x as usize].clone();
-                stack_frame.operand_stack.push(value);
+            Opcode::Invokespecial(x) => {
+                let protected = Self::link_method(jvm, linked, offset, x, false).await?;
+
+                let params = Self::extract_invoke_params(stack_frame, &x.descriptor)?;
+                let instance = Self::pop_invoke_instance(jvm, stack_frame, x, protected).await?;
+
+                let result = jvm.invoke_special(instance, &x.class, &x.name, &x.descriptor, params).await?;
+                stack_frame.push_value(result);
             }
-            Opcode::Athrow => {
-                let exception = stack_frame.operand_stack.pop().unwrap().into();
+            Opcode::Invokestatic(x) => {
+                Self::link_method(jvm, linked, offset, x, true).await?;
 
-                return Err(JavaError::JavaException(exception));
+                let params = Self::extract_invoke_params(stack_frame, &x.descriptor)?;
+
+                let result = jvm.invoke_static(&x.class, &x.name, &x.descriptor, params).await?;
+                stack_frame.push_value(result);
             }
-            Opcode::Anewarray(x) => {
-                let length: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let element_type_name = format!("L{};", x.as_class());
-                let array = jvm.instantiate_array(&element_type_name, length as _).await?;
+            Opcode::Invokevirtual(x) => {
+                let protected = Self::link_method(jvm, linked, offset, x, false).await?;
 
-                stack_frame.operand_stack.push(JavaValue::Object(Some(array)));
-            }
-            Opcode::Areturn | Opcode::Dreturn | Opcode::Freturn | Opcode::Ireturn | Opcode::Lreturn => {
-                let return_value = stack_frame.operand_stack.pop().unwrap();
-                if matches!(opcode, Opcode::Ireturn) {
-                    let value: i32 = return_value.into();
-                    if *return_type == JavaType::Boolean {
-                        return Ok(ExecuteNext::Return(JavaValue::Boolean(value == 1)));
-                    } else if *return_type == JavaType::Char {
-                        return Ok(ExecuteNext::Return(JavaValue::Char(value as _)));
-                    } else if *return_type == JavaType::Byte {
-                        return Ok(ExecuteNext::Return(JavaValue::Byte(value as _)));
-                    } else if *return_type == JavaType::Short {
-                        return Ok(ExecuteNext::Return(JavaValue::Short(value as _)));
-                    } else {
-                        return Ok(ExecuteNext::Return(JavaValue::Int(value)));
-                    }
+                let params = Self::extract_invoke_params(stack_frame, &x.descriptor)?;
+                let instance = Self::pop_invoke_instance(jvm, stack_frame, x, protected).await?;
+
+                let result = jvm.invoke_virtual(instance, &x.name, &x.descriptor, params).await?;
+                stack_frame.push_value(result);
+            }
+            Opcode::Ldc(x) | Opcode::LdcW(x) | Opcode::Ldc2W(x) => stack_frame.push_value(Self::constant_to_value(jvm, x).await?),
+            Opcode::Multianewarray(x, d) => {
+                let mut dimensions: Vec<i32> = (0..*d).map(|_| stack_frame.pop_int()).collect();
+                dimensions.reverse();
+
+                let array = Self::new_multi_array(jvm, x.as_class(), &dimensions).await?;
+
+                stack_frame.push_reference(Some(array));
+            }
+            Opcode::New(x) => {
+                let class = jvm.instantiate_class(x.as_class()).await?;
+
+                stack_frame.push_reference(Some(class));
+            }
+            Opcode::Newarray(x) => {
+                let element_type_name = match x {
+                    4 => "Z",
+                    5 => "C",
+                    6 => "F",
+                    7 => "D",
+                    8 => "B",
+                    9 => "S",
+                    10 => "I",
+                    11 => "J",
+                    _ => panic!("Invalid array type {}", x),
+                };
+
+                let length = stack_frame.pop_int();
+                let array = jvm.instantiate_array(element_type_name, length as _).await?;
+
+                stack_frame.push_reference(Some(array));
+            }
+            Opcode::Putfield(x) => {
+                let protected = Self::link_field(jvm, linked, offset, x, false, true).await?;
+
+                let value = stack_frame.pop_value(&JavaType::parse(&x.descriptor)?);
+                let Some(mut instance) = stack_frame.pop_reference() else {
+                    return Err(jvm
+                        .exception(
+                            "java/lang/NullPointerException",
+                            &format!("Field {}::{} is written to null", x.class, x.name),
+                        )
+                        .await);
+                };
+                if protected {
+                    jvm.check_protected_instance_access(&*instance).await?;
                 }
-                return Ok(ExecuteNext::Return(return_value));
+
+                jvm.put_field(&mut instance, &x.name, &x.descriptor, value).await?;
             }
-            Opcode::Arraylength => {
-                let array: Option<Box<dyn ClassInstance>> = stack_frame.operand_stack.pop().unwrap().into();
-                if array.is_none() {
-                    return Err(jvm.exception("java/lang/NullPointerException", "Array is null").await);
+            Opcode::Monitorenter | Opcode::Monitorexit => {
+                let Some(instance) = stack_frame.pop_reference_ref() else {
+                    return Err(jvm.exception("java/lang/NullPointerException", "Monitor object is null").await);
+                };
+
+                if matches!(opcode, Opcode::Monitorenter) {
+                    jvm.monitor_enter(instance).await?;
+                } else {
+                    jvm.monitor_exit(instance).await?;
                 }
+            }
+            Opcode::Putstatic(x) => {
+                Self::link_field(jvm, linked, offset, x, true, true).await?;
 
-                let length = jvm.array_length(&array.unwrap()).await?;
-                stack_frame.operand_stack.push(JavaValue::Int(length as _));
+                let value = stack_frame.pop_value(&JavaType::parse(&x.descriptor)?);
+
+                jvm.put_static_field(&x.class, &x.name, &x.descriptor, value).await?
             }
-            Opcode::Astore(x) | Opcode::Dstore(x) | Opcode::Fstore(x) | Opcode::Istore(x) | Opcode::Lstore(x) => {
-                let value = stack_frame.operand_stack.pop();
-                stack_frame.local_variables[*x as usize] = value.unwrap();
+            // division by zero, other divisors are handled in execute_simple_opcode
+            Opcode::Idiv | Opcode::Irem | Opcode::Ldiv | Opcode::Lrem => {
+                return Err(jvm.exception("java/lang/ArithmeticException", "/ by zero").await);
             }
-            Opcode::Bipush(x) => stack_frame.operand_stack.push(JavaValue::Int(*x as i32)),
-            Opcode::Checkcast(x) => {
-                let top_stack = stack_frame.operand_stack.last().unwrap();
+            _ => unreachable!("{:?} doesn't need jvm", opcode),
+        }
+
+        Ok(ExecuteNext::Continue)
+    }
+
+    // access check of field reference is done on first execution of the instruction.
+    // returns true if accessed instance needs protected access check
+    async fn link_field(jvm: &Jvm, linked: &LinkedReferences, offset: u32, x: &ReferenceConstant, is_static: bool, is_put: bool) -> Result<bool> {
+        if let Some(protected) = linked.get(offset) {
+            return Ok(protected);
+        }
 
-                tracing::warn!("Unimplemented checkcast: {:?} {:?}", top_stack, x);
+        let protected = jvm.check_field_access(&x.class, &x.name, &x.descriptor, is_static, is_put).await?;
+        linked.set(offset, protected);
+
+        Ok(protected)
+    }
+
+    async fn link_method(jvm: &Jvm, linked: &LinkedReferences, offset: u32, x: &ReferenceConstant, is_static: bool) -> Result<bool> {
+        if let Some(protected) = linked.get(offset) {
+            return Ok(protected);
+        }
+
+        let protected = jvm.check_method_access(&x.class, &x.name, &x.descriptor, is_static).await?;
+        linked.set(offset, protected);
+
+        Ok(protected)
+    }
+
+    // opcodes which don't need jvm, executed without creating future. returns None for other opcodes
+    fn execute_simple_opcode(current_offset: u32, opcode: &Opcode, stack_frame: &mut StackFrame) -> Result<Option<ExecuteNext>> {
+        match opcode {
+            Opcode::AconstNull => stack_frame.push_reference(None),
+            Opcode::Aload(x) | Opcode::Fload(x) | Opcode::Iload(x) => stack_frame.load(*x as usize, 1),
+            Opcode::Dload(x) | Opcode::Lload(x) => stack_frame.load(*x as usize, 2),
+            Opcode::Astore(x) | Opcode::Fstore(x) | Opcode::Istore(x) => stack_frame.store(*x as usize, 1),
+            Opcode::Dstore(x) | Opcode::Lstore(x) => stack_frame.store(*x as usize, 2),
+            Opcode::Bipush(x) => stack_frame.push_int(*x as i32),
+            Opcode::Checkcast(x) => {
+                tracing::warn!("Unimplemented checkcast: {:?}", x);
             }
             Opcode::D2f => {
-                let value: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Float(value as _));
+                let value = stack_frame.pop_double();
+                stack_frame.push_float(value as _);
             }
             Opcode::D2i => {
-                let value: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Int(value as _));
+                let value = stack_frame.pop_double();
+                stack_frame.push_int(value as _);
             }
             Opcode::D2l => {
-                let value: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Long(value as _));
+                let value = stack_frame.pop_double();
+                stack_frame.push_long(value as _);
             }
             Opcode::Dadd => {
-                let value2: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_double();
+                let value1 = stack_frame.pop_double();
 
-                stack_frame.operand_stack.push(JavaValue::Double(value1 + value2));
+                stack_frame.push_double(value1 + value2);
             }
             Opcode::Dcmpg => {
-                let value2: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_double();
+                let value1 = stack_frame.pop_double();
 
                 if value1.is_nan() || value2.is_nan() {
-                    stack_frame.operand_stack.push(JavaValue::Int(1));
+                    stack_frame.push_int(1);
                 } else {
-                    stack_frame.operand_stack.push(JavaValue::Int(value1.partial_cmp(&value2).unwrap() as _));
+                    stack_frame.push_int(value1.partial_cmp(&value2).unwrap() as _);
                 }
             }
             Opcode::Dcmpl => {
-                let value2: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_double();
+                let value1 = stack_frame.pop_double();
 
                 if value1.is_nan() || value2.is_nan() {
-                    stack_frame.operand_stack.push(JavaValue::Int(-1));
+                    stack_frame.push_int(-1);
                 } else {
-                    stack_frame.operand_stack.push(JavaValue::Int(value1.partial_cmp(&value2).unwrap() as _));
+                    stack_frame.push_int(value1.partial_cmp(&value2).unwrap() as _);
                 }
             }
             Opcode::Dconst(x) => {
-                stack_frame.operand_stack.push(JavaValue::Double(*x as f64));
+                stack_frame.push_double(*x as f64);
             }
             Opcode::Ddiv => {
-                let value2: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_double();
+                let value1 = stack_frame.pop_double();
 
-                stack_frame.operand_stack.push(JavaValue::Double(value1 / value2));
+                stack_frame.push_double(value1 / value2);
             }
             Opcode::Dmul => {
-                let value2: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_double();
+                let value1 = stack_frame.pop_double();
 
-                stack_frame.operand_stack.push(JavaValue::Double(value1 * value2));
+                stack_frame.push_double(value1 * value2);
             }
             Opcode::Dneg => {
-                let value: f64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value = stack_frame.pop_double();
 
-                stack_frame.operand_stack.push(JavaValue::Double(-value));
-            }
-            Opcode::Dup => {
-                let value = stack_frame.operand_stack.pop().unwrap();
-                stack_frame.operand_stack.push(value.clone());
-                stack_frame.operand_stack.push(value);
-            }
-            Opcode::Dup2 => {
-                let value = stack_frame.operand_stack.pop().unwrap();
-                if matches!(value, JavaValue::Long(_) | JavaValue::Double(_)) {
-                    stack_frame.operand_stack.push(value.clone());
-                    stack_frame.operand_stack.push(value);
-                } else {
-                    let value2 = stack_frame.operand_stack.pop().unwrap();
-                    stack_frame.operand_stack.push(value2.clone());
-                    stack_frame.operand_stack.push(value.clone());
-                    stack_frame.operand_stack.push(value2);
-                    stack_frame.operand_stack.push(value);
-                }
-            }
-            Opcode::Dup2X1 => {
-                let value1 = stack_frame.operand_stack.pop().unwrap();
-                let value2 = stack_frame.operand_stack.pop().unwrap();
-
-                if matches!(value1, JavaValue::Long(_) | JavaValue::Double(_)) {
-                    stack_frame.operand_stack.push(value1.clone());
-                    stack_frame.operand_stack.push(value2);
-                    stack_frame.operand_stack.push(value1);
-                } else {
-                    let value3 = stack_frame.operand_stack.pop().unwrap();
-                    stack_frame.operand_stack.push(value2.clone());
-                    stack_frame.operand_stack.push(value1.clone());
-                    stack_frame.operand_stack.push(value3);
-                    stack_frame.operand_stack.push(value2);
-                    stack_frame.operand_stack.push(value1);
-                }
-            }
-            Opcode::Dup2X2 => {
-                let value1 = stack_frame.operand_stack.pop().unwrap();
-                let value2 = stack_frame.operand_stack.pop().unwrap();
-
-                if matches!(value1, JavaValue::Long(_) | JavaValue::Double(_)) && matches!(value2, JavaValue::Long(_) | JavaValue::Double(_)) {
-                    // form4
-                    stack_frame.operand_stack.push(value1.clone());
-                    stack_frame.operand_stack.push(value2);
-                    stack_frame.operand_stack.push(value1);
-                } else if matches!(value1, JavaValue::Long(_) | JavaValue::Double(_)) {
-                    // form2
-                    let value3 = stack_frame.operand_stack.pop().unwrap();
-
-                    stack_frame.operand_stack.push(value1.clone());
-                    stack_frame.operand_stack.push(value3);
-                    stack_frame.operand_stack.push(value2);
-                    stack_frame.operand_stack.push(value1);
-                } else {
-                    let value3 = stack_frame.operand_stack.pop().unwrap();
-
-                    if matches!(value3, JavaValue::Long(_) | JavaValue::Double(_)) {
-                        // form3
-                        stack_frame.operand_stack.push(value2.clone());
-                        stack_frame.operand_stack.push(value1.clone());
-                        stack_frame.operand_stack.push(value3);
-                        stack_frame.operand_stack.push(value2);
-                        stack_frame.operand_stack.push(value1);
-                    } else {
-                        // form1
-                        let value4 = stack_frame.operand_stack.pop().unwrap();
-
-                        stack_frame.operand_stack.push(value2.clone());
-                        stack_frame.operand_stack.push(value1.clone());
-                        stack_frame.operand_stack.push(value4);
-                        stack_frame.operand_stack.push(value3);
-                        stack_frame.operand_stack.push(value2);
-                        stack_frame.operand_stack.push(value1);
-                    }
-                }
-            }
-            Opcode::DupX1 => {
-                let value1 = stack_frame.operand_stack.pop().unwrap();
-                let value2 = stack_frame.operand_stack.pop().unwrap();
-
-                stack_frame.operand_stack.push(value1.clone());
-                stack_frame.operand_stack.push(value2.clone());
-                stack_frame.operand_stack.push(value1);
-            }
-            Opcode::DupX2 => {
-                let value1 = stack_frame.operand_stack.pop().unwrap();
-                let value2 = stack_frame.operand_stack.pop().unwrap();
-                if matches!(value2, JavaValue::Long(_) | JavaValue::Double(_)) {
-                    stack_frame.operand_stack.push(value1.clone());
-                    stack_frame.operand_stack.push(value2.clone());
-                    stack_frame.operand_stack.push(value1);
-                } else {
-                    let value3 = stack_frame.operand_stack.pop().unwrap();
-                    stack_frame.operand_stack.push(value1.clone());
-                    stack_frame.operand_stack.push(value3.clone());
-                    stack_frame.operand_stack.push(value2.clone());
-                    stack_frame.operand_stack.push(value1);
-                }
+                stack_frame.push_double(-value);
             }
+            // long and double take two slots, so dup forms are defined by slot counts
+            Opcode::Dup => stack_frame.dup(1, 1),
+            Opcode::Dup2 => stack_frame.dup(2, 2),
+            Opcode::Dup2X1 => stack_frame.dup(2, 3),
+            Opcode::Dup2X2 => stack_frame.dup(2, 4),
+            Opcode::DupX1 => stack_frame.dup(1, 2),
+            Opcode::DupX2 => stack_frame.dup(1, 3),
             Opcode::Drem => {
-                let value2: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_double();
+                let value1 = stack_frame.pop_double();
 
-                stack_frame.operand_stack.push(JavaValue::Double(value1 % value2));
+                stack_frame.push_double(value1 % value2);
             }
             Opcode::Dsub => {
-                let value2: f64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_double();
+                let value1 = stack_frame.pop_double();
 
-                stack_frame.operand_stack.push(JavaValue::Double(value1 - value2));
+                stack_frame.push_double(value1 - value2);
             }
             Opcode::F2d => {
-                let value: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Double(value as _));
+                let value = stack_frame.pop_float();
+                stack_frame.push_double(value as _);
             }
             Opcode::F2i => {
-                let value: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Int(value as _));
+                let value = stack_frame.pop_float();
+                stack_frame.push_int(value as _);
             }
             Opcode::F2l => {
-                let value: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Long(value as _));
+                let value = stack_frame.pop_float();
+                stack_frame.push_long(value as _);
             }
             Opcode::Fadd => {
-                let value2: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_float();
+                let value1 = stack_frame.pop_float();
 
-                stack_frame.operand_stack.push(JavaValue::Float(value1 + value2));
+                stack_frame.push_float(value1 + value2);
             }
             Opcode::Fcmpg => {
-                let value2: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_float();
+                let value1 = stack_frame.pop_float();
 
                 if value1.is_nan() || value2.is_nan() {
-                    stack_frame.operand_stack.push(JavaValue::Int(1));
+                    stack_frame.push_int(1);
                 } else {
-                    stack_frame.operand_stack.push(JavaValue::Int(value1.partial_cmp(&value2).unwrap() as _));
+                    stack_frame.push_int(value1.partial_cmp(&value2).unwrap() as _);
                 }
             }
             Opcode::Fcmpl => {
-                let value2: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_float();
+                let value1 = stack_frame.pop_float();
 
                 if value1.is_nan() || value2.is_nan() {
-                    stack_frame.operand_stack.push(JavaValue::Int(-1));
+                    stack_frame.push_int(-1);
                 } else {
-                    stack_frame.operand_stack.push(JavaValue::Int(value1.partial_cmp(&value2).unwrap() as _));
+                    stack_frame.push_int(value1.partial_cmp(&value2).unwrap() as _);
                 }
             }
             Opcode::Fconst(x) => {
-                stack_frame.operand_stack.push(JavaValue::Float(*x as f32));
+                stack_frame.push_float(*x as f32);
             }
             Opcode::Fdiv => {
-                let value2: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_float();
+                let value1 = stack_frame.pop_float();
 
-                stack_frame.operand_stack.push(JavaValue::Float(value1 / value2));
+                stack_frame.push_float(value1 / value2);
             }
             Opcode::Fmul => {
-                let value2: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_float();
+                let value1 = stack_frame.pop_float();
 
-                stack_frame.operand_stack.push(JavaValue::Float(value1 * value2));
+                stack_frame.push_float(value1 * value2);
             }
             Opcode::Fneg => {
-                let value: f32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value = stack_frame.pop_float();
 
-                stack_frame.operand_stack.push(JavaValue::Float(-value));
+                stack_frame.push_float(-value);
             }
             Opcode::Frem => {
-                let value2: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_float();
+                let value1 = stack_frame.pop_float();
 
-                stack_frame.operand_stack.push(JavaValue::Float(value1 % value2));
+                stack_frame.push_float(value1 % value2);
             }
             Opcode::Fsub => {
-                let value2: f32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: f32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_float();
+                let value1 = stack_frame.pop_float();
 
-                stack_frame.operand_stack.push(JavaValue::Float(value1 - value2));
+                stack_frame.push_float(value1 - value2);
             }
-            Opcode::Getfield(x) => {
-                let instance = stack_frame.operand_stack.pop().unwrap();
-
-                let value = jvm.get_field(&instance.into(), &x.name, &x.descriptor).await?;
-
-                stack_frame.operand_stack.push(Self::to_stack_frame_type(value));
-            }
-            Opcode::Getstatic(x) => {
-                let value = jvm.get_static_field(&x.class, &x.name, &x.descriptor).await?;
-
-                stack_frame.operand_stack.push(Self::to_stack_frame_type(value));
-            }
-            Opcode::Goto(x) => return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32)),
-            Opcode::GotoW(x) => return Ok(ExecuteNext::Jump((current_offset as i32 + *x) as u32)),
+            Opcode::Goto(x) => return Ok(Some(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32))),
+            Opcode::GotoW(x) => return Ok(Some(ExecuteNext::Jump((current_offset as i32 + *x) as u32))),
             Opcode::I2b => {
-                let value: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Int(value as u8 as _));
+                let value = stack_frame.pop_int();
+                stack_frame.push_int(value as u8 as _);
             }
             Opcode::I2c => {
-                let value: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Int(value as JavaChar as _));
+                let value = stack_frame.pop_int();
+                stack_frame.push_int(value as JavaChar as _);
             }
             Opcode::I2d => {
-                let value: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Double(value as _));
+                let value = stack_frame.pop_int();
+                stack_frame.push_double(value as _);
             }
             Opcode::I2f => {
-                let value: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Float(value as _));
+                let value = stack_frame.pop_int();
+                stack_frame.push_float(value as _);
             }
             Opcode::I2l => {
-                let value: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Long(value as _));
+                let value = stack_frame.pop_int();
+                stack_frame.push_long(value as _);
             }
             Opcode::I2s => {
-                let value: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Int(value as u16 as _));
+                let value = stack_frame.pop_int();
+                stack_frame.push_int(value as u16 as _);
             }
             Opcode::Iadd => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1.wrapping_add(value2)));
+                stack_frame.push_int(value1.wrapping_add(value2));
             }
             Opcode::Iand => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1 & value2));
+                stack_frame.push_int(value1 & value2);
             }
-            Opcode::Iconst(x) => stack_frame.operand_stack.push(JavaValue::Int(*x as i32)),
+            Opcode::Iconst(x) => stack_frame.push_int(*x as i32),
             Opcode::Idiv => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                if value2 == 0 {
+                    stack_frame.push_int(value2);
+                    return Ok(None);
+                }
+
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1 / value2));
+                stack_frame.push_int(value1.wrapping_div(value2));
             }
             Opcode::IfAcmpeq(x) => {
-                let value2: Box<dyn ClassInstance> = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: Box<dyn ClassInstance> = stack_frame.operand_stack.pop().unwrap().into();
-
-                if value1.equals(&*value2)? {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
+                if stack_frame.pop_same_references()? {
+                    return Ok(Some(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32)));
                 }
             }
             Opcode::IfAcmpne(x) => {
-                let value2: Box<dyn ClassInstance> = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: Box<dyn ClassInstance> = stack_frame.operand_stack.pop().unwrap().into();
-
-                if !value1.equals(&*value2)? {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::IfIcmpeq(x) => {
-                if Self::integer_condition(stack_frame, |x, y| x == y) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::IfIcmpge(x) => {
-                if Self::integer_condition(stack_frame, |x, y| x >= y) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::IfIcmpgt(x) => {
-                if Self::integer_condition(stack_frame, |x, y| x > y) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::IfIcmple(x) => {
-                if Self::integer_condition(stack_frame, |x, y| x <= y) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::IfIcmplt(x) => {
-                if Self::integer_condition(stack_frame, |x, y| x < y) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::IfIcmpne(x) => {
-                if Self::integer_condition(stack_frame, |x, y| x != y) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::Ifeq(x) => {
-                if Self::integer_condition_single(stack_frame, |x| x == 0) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::Ifge(x) => {
-                if Self::integer_condition_single(stack_frame, |x| x >= 0) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
+                if !stack_frame.pop_same_references()? {
+                    return Ok(Some(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32)));
                 }
             }
-            Opcode::Ifgt(x) => {
-                if Self::integer_condition_single(stack_frame, |x| x > 0) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::Ifle(x) => {
-                if Self::integer_condition_single(stack_frame, |x| x <= 0) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
-            }
-            Opcode::Iflt(x) => {
-                if Self::integer_condition_single(stack_frame, |x| x < 0) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
+            Opcode::IfIcmpeq(_) | Opcode::IfIcmpne(_) | Opcode::IfIcmplt(_) | Opcode::IfIcmpge(_) | Opcode::IfIcmpgt(_) | Opcode::IfIcmple(_) => {
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
+
+                let condition = Self::two_operand_condition(opcode, value1, value2).unwrap();
+                return Ok(Some(Self::branch(current_offset, opcode, condition)));
             }
-            Opcode::Ifne(x) => {
-                if Self::integer_condition_single(stack_frame, |x| x != 0) {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
-                }
+            Opcode::Ifeq(_) | Opcode::Ifne(_) | Opcode::Iflt(_) | Opcode::Ifge(_) | Opcode::Ifgt(_) | Opcode::Ifle(_) => {
+                let value = stack_frame.pop_int();
+
+                let condition = Self::single_operand_condition(opcode, value).unwrap();
+                return Ok(Some(Self::branch(current_offset, opcode, condition)));
             }
             Opcode::Ifnonnull(x) => {
-                let value: Option<Box<dyn ClassInstance>> = stack_frame.operand_stack.pop().unwrap().into();
-
-                if value.is_some() {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
+                if !stack_frame.pop_is_null() {
+                    return Ok(Some(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32)));
                 }
             }
             Opcode::Ifnull(x) => {
-                let value: Option<Box<dyn ClassInstance>> = stack_frame.operand_stack.pop().unwrap().into();
-
-                if value.is_none() {
-                    return Ok(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32));
+                if stack_frame.pop_is_null() {
+                    return Ok(Some(ExecuteNext::Jump((current_offset as i32 + *x as i32) as u32)));
                 }
             }
             Opcode::Iinc(x, y) => {
-                let value = stack_frame.local_variables[*x as usize].clone();
-                let value: i32 = value.into();
+                let value = stack_frame.local_int(*x as usize);
 
-                stack_frame.local_variables[*x as usize] = JavaValue::Int(value + *y as i32);
+                stack_frame.set_local_int(*x as usize, value.wrapping_add(*y as i32));
             }
             Opcode::Imul => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1.wrapping_mul(value2)));
+                stack_frame.push_int(value1.wrapping_mul(value2));
             }
             Opcode::Ineg => {
-                let value: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(-value));
-            }
-            Opcode::Instanceof(x) => {
-                let instance: Box<dyn ClassInstance> = stack_frame.operand_stack.pop().unwrap().into();
-
-                let result = jvm.is_instance(&*instance, x.as_class()).await?;
-                stack_frame.operand_stack.push(JavaValue::Int(result as _));
+                stack_frame.push_int(value.wrapping_neg());
             }
             Opcode::Invokedynamic(_) => {
                 todo!()
             }
-            Opcode::Invokeinterface(x, _count, _zero) => {
-                let params = Self::extract_invoke_params(stack_frame, &x.descriptor);
-
-                let instance: Option<Box<dyn ClassInstance>> = stack_frame.operand_stack.pop().unwrap().into();
-                if instance.is_none() {
-                    return Err(jvm
-                        .exception(
-                            "java/lang/NullPointerException",
-                            &format!("Method {}::{}:{} is called on null", x.class, x.name, x.descriptor),
-                        )
-                        .await);
-                }
-
-                let result = jvm.invoke_virtual(&instance.unwrap(), &x.name, &x.descriptor, params).await?;
-                Self::push_invoke_result(stack_frame, result);
-            }
-            Opcode::Invokespecial(x) => {
-                let params = Self::extract_invoke_params(stack_frame, &x.descriptor);
-
-                let instance: Option<Box<dyn ClassInstance>> = stack_frame.operand_stack.pop().unwrap().into();
-                if instance.is_none() {
-                    return Err(jvm
-                        .exception(
-                            "java/lang/NullPointerException",
-                            &format!("Method {}::{}:{} is called on null", x.class, x.name, x.descriptor),
-                        )
-                        .await);
-                }
-
-                let result = jvm.invoke_special(&instance.unwrap(), &x.class, &x.name, &x.descriptor, params).await?;
-                Self::push_invoke_result(stack_frame, result);
-            }
-            Opcode::Invokestatic(x) => {
-                let params = Self::extract_invoke_params(stack_frame, &x.descriptor);
-
-                let result = jvm.invoke_static(&x.class, &x.name, &x.descriptor, params).await?;
-                Self::push_invoke_result(stack_frame, result);
-            }
-            Opcode::Invokevirtual(x) => {
-                let params = Self::extract_invoke_params(stack_frame, &x.descriptor);
-
-                let instance: Option<Box<dyn ClassInstance>> = stack_frame.operand_stack.pop().unwrap().into();
-                if instance.is_none() {
-                    return Err(jvm
-                        .exception(
-                            "java/lang/NullPointerException",
-                            &format!("Method {}::{}:{} is called on null", x.class, x.name, x.descriptor),
-                        )
-                        .await);
-                }
-
-                let result = jvm.invoke_virtual(&instance.unwrap(), &x.name, &x.descriptor, params).await?;
-                Self::push_invoke_result(stack_frame, result);
-            }
             Opcode::Ior => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1 | value2));
+                stack_frame.push_int(value1 | value2);
             }
             Opcode::Irem => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                if value2 == 0 {
+                    stack_frame.push_int(value2);
+                    return Ok(None);
+                }
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1 % value2));
+                let value1 = stack_frame.pop_int();
+
+                stack_frame.push_int(value1.wrapping_rem(value2));
             }
             Opcode::Ishl => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1 << value2));
+                stack_frame.push_int(value1.wrapping_shl(value2 as u32));
             }
             Opcode::Ishr => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1 >> value2));
+                stack_frame.push_int(value1.wrapping_shr(value2 as u32));
             }
             Opcode::Isub => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1.wrapping_sub(value2)));
+                stack_frame.push_int(value1.wrapping_sub(value2));
             }
             Opcode::Iushr => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(((value1 as u32) >> (value2 as u32)) as _));
+                stack_frame.push_int((value1 as u32).wrapping_shr(value2 as u32) as _);
             }
             Opcode::Ixor => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i32 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_int();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1 ^ value2));
+                stack_frame.push_int(value1 ^ value2);
             }
             Opcode::Jsr(_) => {
                 todo!()
@@ -671,180 +744,225 @@ impl Interpreter {
                 todo!()
             }
             Opcode::L2d => {
-                let value: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Double(value as _));
+                let value = stack_frame.pop_long();
+                stack_frame.push_double(value as _);
             }
             Opcode::L2f => {
-                let value: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Float(value as _));
+                let value = stack_frame.pop_long();
+                stack_frame.push_float(value as _);
             }
             Opcode::L2i => {
-                let value: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                stack_frame.operand_stack.push(JavaValue::Int(value as _));
+                let value = stack_frame.pop_long();
+                stack_frame.push_int(value as _);
             }
             Opcode::Ladd => {
-                let value2: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_long();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 + value2));
+                stack_frame.push_long(value1.wrapping_add(value2));
             }
             Opcode::Land => {
-                let value2: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_long();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 & value2));
+                stack_frame.push_long(value1 & value2);
             }
             Opcode::Lcmp => {
-                let value2: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_long();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Int(value1.cmp(&value2) as _));
+                stack_frame.push_int(value1.cmp(&value2) as _);
             }
-            Opcode::Lconst(x) => stack_frame.operand_stack.push(JavaValue::Long(*x as i64)),
-            Opcode::Ldc(x) | Opcode::LdcW(x) => stack_frame.operand_stack.push(Self::constant_to_value(jvm, x).await?),
-            Opcode::Ldc2W(x) => stack_frame.operand_stack.push(Self::constant_to_value(jvm, x).await?),
+            Opcode::Lconst(x) => stack_frame.push_long(*x as i64),
             Opcode::Ldiv => {
-                let value2: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_long();
+                if value2 == 0 {
+                    stack_frame.push_long(value2);
+                    return Ok(None);
+                }
+
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 / value2));
+                stack_frame.push_long(value1.wrapping_div(value2));
             }
             Opcode::Lmul => {
-                let value2: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_long();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 * value2));
+                stack_frame.push_long(value1.wrapping_mul(value2));
             }
             Opcode::Lneg => {
-                let value: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(-value));
+                stack_frame.push_long(value.wrapping_neg());
             }
             Opcode::Lor => {
-                let value2: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_long();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 | value2));
+                stack_frame.push_long(value1 | value2);
             }
             Opcode::Lrem => {
-                let value2: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_long();
+                if value2 == 0 {
+                    stack_frame.push_long(value2);
+                    return Ok(None);
+                }
+
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 % value2));
+                stack_frame.push_long(value1.wrapping_rem(value2));
             }
             Opcode::Lshl => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 << value2));
+                stack_frame.push_long(value1.wrapping_shl(value2 as u32));
             }
             Opcode::Lshr => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 >> value2));
+                stack_frame.push_long(value1.wrapping_shr(value2 as u32));
             }
             Opcode::Lsub => {
-                let value2: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_long();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 - value2));
+                stack_frame.push_long(value1.wrapping_sub(value2));
             }
             Opcode::Lushr => {
-                let value2: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_int();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(((value1 as u64) >> (value2 as u64)) as _));
+                stack_frame.push_long((value1 as u64).wrapping_shr(value2 as u32) as _);
             }
             Opcode::Lxor => {
-                let value2: i64 = stack_frame.operand_stack.pop().unwrap().into();
-                let value1: i64 = stack_frame.operand_stack.pop().unwrap().into();
+                let value2 = stack_frame.pop_long();
+                let value1 = stack_frame.pop_long();
 
-                stack_frame.operand_stack.push(JavaValue::Long(value1 ^ value2));
+                stack_frame.push_long(value1 ^ value2);
             }
             Opcode::Lookupswitch(default, pairs) | Opcode::Tableswitch(default, pairs) => {
-                let key = stack_frame.operand_stack.pop().unwrap().into();
+                let key = stack_frame.pop_int();
 
                 for (k, offset) in pairs {
                     if *k == key {
-                        return Ok(ExecuteNext::Jump((current_offset as i32 + *offset) as u32));
+                        return Ok(Some(ExecuteNext::Jump((current_offset as i32 + *offset) as u32)));
                     }
                 }
 
-                return Ok(ExecuteNext::Jump((current_offset as i32 + *default) as u32));
-            }
-            Opcode::Monitorenter => {
-                tracing::warn!("Unimplemented monitorenter");
-            }
-            Opcode::Monitorexit => {
-                tracing::warn!("Unimplemented monitorexit");
-            }
-            Opcode::Multianewarray(x, d) => {
-                let mut dimensions: Vec<i32> = (0..*d).map(|_| stack_frame.operand_stack.pop().unwrap().into()).collect();
-                dimensions.reverse();
-
-                let array = Self::new_multi_array(jvm, x.as_class(), &dimensions).await?;
-
-                stack_frame.operand_stack.push(JavaValue::Object(Some(array)));
-            }
-            Opcode::New(x) => {
-                let class = jvm.instantiate_class(x.as_class()).await?;
-
-                stack_frame.operand_stack.push(JavaValue::Object(Some(class)));
-            }
-            Opcode::Newarray(x) => {
-                let element_type_name = match x {
-                    4 => "Z",
-                    5 => "C",
-                    6 => "F",
-                    7 => "D",
-                    8 => "B",
-                    9 => "S",
-                    10 => "I",
-                    11 => "J",
-                    _ => panic!("Invalid array type {}", x),
-                };
-
-                let length: i32 = stack_frame.operand_stack.pop().unwrap().into();
-                let array = jvm.instantiate_array(element_type_name, length as _).await?;
-
-                stack_frame.operand_stack.push(JavaValue::Object(Some(array)));
+                return Ok(Some(ExecuteNext::Jump((current_offset as i32 + *default) as u32)));
             }
             Opcode::Nop => {}
-            Opcode::Pop => {
-                stack_frame.operand_stack.pop().unwrap();
-            }
-            Opcode::Pop2 => {
-                stack_frame.operand_stack.pop().unwrap();
-            }
-            Opcode::Putfield(x) => {
-                let value = stack_frame.operand_stack.pop().unwrap();
-                let instance = stack_frame.operand_stack.pop().unwrap();
-
-                jvm.put_field(&mut instance.into(), &x.name, &x.descriptor, value).await?;
-            }
-            Opcode::Putstatic(x) => {
-                jvm.put_static_field(&x.class, &x.name, &x.descriptor, stack_frame.operand_stack.pop().unwrap())
-                    .await?
-            }
+            Opcode::Pop => stack_frame.pop(1),
+            Opcode::Pop2 => stack_frame.pop(2),
             Opcode::Ret(_) => {
                 todo!()
             }
-            Opcode::Return => return Ok(ExecuteNext::Return(JavaValue::Void)),
-            Opcode::Sipush(x) => stack_frame.operand_stack.push(JavaValue::Int(*x as i32)),
-            Opcode::Swap => {
-                let value1 = stack_frame.operand_stack.pop().unwrap();
-                let value2 = stack_frame.operand_stack.pop().unwrap();
-
-                stack_frame.operand_stack.push(value1);
-                stack_frame.operand_stack.push(value2);
-            }
+            Opcode::Return => return Ok(Some(ExecuteNext::Return(JavaValue::Void))),
+            Opcode::Sipush(x) => stack_frame.push_int(*x as i32),
+            Opcode::Swap => stack_frame.swap(),
             Opcode::Wide => {
                 todo!()
             }
+            Opcode::Ldc(x) | Opcode::LdcW(x) | Opcode::Ldc2W(x) => match Self::primitive_constant_to_value(x) {
+                Some(x) => stack_frame.push_value(x),
+                None => return Ok(None),
+            },
+            // opcodes which need jvm, executed by execute_opcode
+            _ => return Ok(None),
         }
 
-        Ok(ExecuteNext::Continue)
+        Ok(Some(ExecuteNext::Continue))
+    }
+
+    // fused execution of common int sequences, `iload; iload|iconst|bipush|sipush; iadd|isub|imul|iand|ior|ixor|if_icmp<cond>`
+    // and `iload; if<cond>`. returns number of additional opcodes consumed
+    fn execute_superinstruction<'a, I>(opcode: &Opcode, mut next: I, stack_frame: &mut StackFrame) -> Option<(ExecuteNext, usize)>
+    where
+        I: Iterator<Item = (&'a u32, &'a Opcode)>,
+    {
+        let Opcode::Iload(x) = opcode else {
+            return None;
+        };
+        let value1 = stack_frame.local_int(*x as usize);
+
+        let (second_offset, second) = next.next()?;
+        let value2 = match second {
+            Opcode::Iload(x) => stack_frame.local_int(*x as usize),
+            Opcode::Iconst(x) | Opcode::Bipush(x) => *x as i32,
+            Opcode::Sipush(x) => *x as i32,
+            _ => {
+                let condition = Self::single_operand_condition(second, value1)?;
+                return Some((Self::branch(*second_offset, second, condition), 1));
+            }
+        };
+
+        let (third_offset, third) = next.next()?;
+        let result = match third {
+            Opcode::Iadd => value1.wrapping_add(value2),
+            Opcode::Isub => value1.wrapping_sub(value2),
+            Opcode::Imul => value1.wrapping_mul(value2),
+            Opcode::Iand => value1 & value2,
+            Opcode::Ior => value1 | value2,
+            Opcode::Ixor => value1 ^ value2,
+            _ => {
+                let condition = Self::two_operand_condition(third, value1, value2)?;
+                return Some((Self::branch(*third_offset, third, condition), 2));
+            }
+        };
+        stack_frame.push_int(result);
+
+        Some((ExecuteNext::Continue, 2))
+    }
+
+    // condition of if<cond>, which compares value with zero
+    fn single_operand_condition(opcode: &Opcode, value: i32) -> Option<bool> {
+        Some(match opcode {
+            Opcode::Ifeq(_) => value == 0,
+            Opcode::Ifne(_) => value != 0,
+            Opcode::Iflt(_) => value < 0,
+            Opcode::Ifge(_) => value >= 0,
+            Opcode::Ifgt(_) => value > 0,
+            Opcode::Ifle(_) => value <= 0,
+            _ => return None,
+        })
+    }
+
+    // condition of if_icmp<cond>
+    fn two_operand_condition(opcode: &Opcode, value1: i32, value2: i32) -> Option<bool> {
+        Some(match opcode {
+            Opcode::IfIcmpeq(_) => value1 == value2,
+            Opcode::IfIcmpne(_) => value1 != value2,
+            Opcode::IfIcmplt(_) => value1 < value2,
+            Opcode::IfIcmpge(_) => value1 >= value2,
+            Opcode::IfIcmpgt(_) => value1 > value2,
+            Opcode::IfIcmple(_) => value1 <= value2,
+            _ => return None,
+        })
+    }
+
+    fn branch(offset: u32, opcode: &Opcode, condition: bool) -> ExecuteNext {
+        match opcode {
+            Opcode::Ifeq(x)
+            | Opcode::Ifne(x)
+            | Opcode::Iflt(x)
+            | Opcode::Ifge(x)
+            | Opcode::Ifgt(x)
+            | Opcode::Ifle(x)
+            | Opcode::IfIcmpeq(x)
+            | Opcode::IfIcmpne(x)
+            | Opcode::IfIcmplt(x)
+            | Opcode::IfIcmpge(x)
+            | Opcode::IfIcmpgt(x)
+            | Opcode::IfIcmple(x)
+                if condition =>
+            {
+                ExecuteNext::Jump((offset as i32 + *x as i32) as u32)
+            }
+            _ => ExecuteNext::Continue,
+        }
     }
 
     async fn find_exception_handler(jvm: &Jvm, exception: &dyn ClassInstance, code_attribute: &AttributeInfoCode, pc: u32) -> Option<u32> {
@@ -864,73 +982,77 @@ impl Interpreter {
         None
     }
 
-    fn integer_condition<T>(stack_frame: &mut StackFrame, pred: T) -> bool
-    where
-        T: Fn(i32, i32) -> bool,
-    {
-        let value2 = stack_frame.operand_stack.pop().unwrap().into();
-        let value1 = stack_frame.operand_stack.pop().unwrap().into();
-
-        pred(value1, value2)
+    // pops array and index below the value to store
+    async fn store_array(jvm: &Jvm, stack_frame: &mut StackFrame, value: JavaValue) -> Result<()> {
+        // TODO type checking
+        let index = stack_frame.pop_int();
+        let Some(mut array) = stack_frame.pop_reference() else {
+            return Err(jvm.exception("java/lang/NullPointerException", "Array is null").await);
+        };
+
+        let element_type = jvm.array_element_type(&array).await?;
+
+        // operand stack has only integer, so convert it to the correct type
+        let value = match (element_type, value) {
+            (JavaType::Boolean, JavaValue::Int(x)) => JavaValue::Boolean(x == 1),
+            (JavaType::Byte, JavaValue::Int(x)) => JavaValue::Byte(x as _),
+            (JavaType::Char, JavaValue::Int(x)) => JavaValue::Char(x as _),
+            (JavaType::Short, JavaValue::Int(x)) => JavaValue::Short(x as _),
+            (_, value) => value,
+        };
+
+        jvm.store_array(&mut array, index as usize, [value]).await
     }
 
-    fn integer_condition_single<T>(stack_frame: &mut StackFrame, pred: T) -> bool
-    where
-        T: Fn(i32) -> bool,
-    {
-        let value = stack_frame.operand_stack.pop().unwrap().into();
+    // pops instance method is invoked on, after arguments are popped
+    #[allow(clippy::borrowed_box)]
+    async fn pop_invoke_instance<'a>(
+        jvm: &Jvm,
+        stack_frame: &'a mut StackFrame,
+        x: &ReferenceConstant,
+        protected: bool,
+    ) -> Result<&'a Box<dyn ClassInstance>> {
+        let Some(instance) = stack_frame.pop_reference_ref() else {
+            return Err(jvm
+                .exception(
+                    "java/lang/NullPointerException",
+                    &format!("Method {}::{}:{} is called on null", x.class, x.name, x.descriptor),
+                )
+                .await);
+        };
+        if protected {
+            jvm.check_protected_instance_access(&**instance).await?;
+        }
 
-        pred(value)
+        Ok(instance)
     }
 
-    fn extract_invoke_params(stack_frame: &mut StackFrame, descriptor: &str) -> Vec<JavaValue> {
-        let method_type = JavaType::parse(descriptor);
+    fn extract_invoke_params(stack_frame: &mut StackFrame, descriptor: &str) -> Result<Vec<JavaValue>> {
+        let method_type = JavaType::parse(descriptor)?;
         let (param_type, _) = method_type.as_method();
 
-        let mut values = param_type
-            .iter()
-            .rev()
-            .map(|x| {
-                let value = stack_frame.operand_stack.pop().unwrap();
-                match x {
-                    JavaType::Boolean => JavaValue::Boolean(i32::from(value) == 1),
-                    JavaType::Byte => JavaValue::Byte(i32::from(value) as _),
-                    JavaType::Char => JavaValue::Char(i32::from(value) as _),
-                    JavaType::Short => JavaValue::Short(i32::from(value) as _),
-                    _ => value,
-                }
-            })
-            .collect::<Vec<_>>();
-
+        let mut values = param_type.iter().rev().map(|x| stack_frame.pop_value(x)).collect::<Vec<_>>();
         values.reverse();
 
-        values
+        Ok(values)
     }
 
-    fn push_invoke_result(stack_frame: &mut StackFrame, value: JavaValue) {
-        match value {
-            JavaValue::Void => {}
-            _ => stack_frame.operand_stack.push(Self::to_stack_frame_type(value)),
-        }
+    fn primitive_constant_to_value(constant: &ValueConstant) -> Option<JavaValue> {
+        Some(match constant {
+            ValueConstant::Integer(x) => JavaValue::Int(*x),
+            ValueConstant::Float(x) => JavaValue::Float(*x),
+            ValueConstant::Long(x) => JavaValue::Long(*x),
+            ValueConstant::Double(x) => JavaValue::Double(*x),
+            _ => return None,
+        })
     }
 
-    // convert into stack frame type, which is always integer for number types
-    fn to_stack_frame_type(value: JavaValue) -> JavaValue {
-        match value {
-            JavaValue::Boolean(x) => JavaValue::Int(x as _),
-            JavaValue::Byte(x) => JavaValue::Int(x as _),
-            JavaValue::Char(x) => JavaValue::Int(x as _),
-            JavaValue::Short(x) => JavaValue::Int(x as _),
-            _ => value,
+    async fn constant_to_value(jvm: &Jvm, constant: &ValueConstant) -> Result<JavaValue> {
+        if let Some(x) = Self::primitive_constant_to_value(constant) {
+            return Ok(x);
         }
-    }
 
-    async fn constant_to_value(jvm: &Jvm, constant: &ValueConstant) -> Result<JavaValue> {
         Ok(match constant {
-            ValueConstant::Integer(x) => JavaValue::Int(*x),
-            ValueConstant::Float(x) => JavaValue::Float(*x),
-            ValueConstant::Long(x) => JavaValue::Long(*x),
-            ValueConstant::Double(x) => JavaValue::Double(*x),
             ValueConstant::String(x) => JavaValue::Object(Some(JavaLangString::from_rust_string(jvm, x).await?)),
             ValueConstant::Class(x) => JavaValue::Object(Some(jvm.resolve_class(x).await?.java_class(jvm).await?)),
             _ => unimplemented!(),
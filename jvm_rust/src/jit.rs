@@ -0,0 +1,1262 @@
+// baseline jit compiling hot methods with cranelift.
+// compiled code works on the same untyped slots as interpreter, keeping values in registers between instructions which need jvm.
+// on those, like allocation, invokes, field access and exceptions, it spills its state into the frame and exits,
+// so that interpreter executes the instruction and compiled code is resumed at the next block.
+// if execution continues where compiled code can't be entered, like exception handlers, the invocation is deoptimized to interpreter.
+extern crate std;
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::String,
+    vec,
+    vec::Vec,
+};
+use core::{
+    iter,
+    mem::{offset_of, replace, transmute},
+    sync::atomic::{AtomicU32, Ordering},
+};
+use std::sync::{Mutex, OnceLock};
+
+use cranelift_codegen::{
+    ir::{condcodes::IntCC, types, AbiParam, Block, InstBuilder, MemFlagsData, SigRef, TrapCode, Type, Value},
+    settings::{self, Configurable},
+};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext, Switch, Variable};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{default_libcall_names, Module};
+
+use classfile::{AttributeInfoCode, Opcode, ValueConstant};
+use java_constants::MethodAccessFlags;
+use jvm::{JavaType, JavaValue, Jvm, SchedulePoint};
+
+use crate::{
+    interpreter::{Interpreter, LinkedReferences, Step},
+    stack_frame::StackFrame,
+};
+
+const COMPILE_THRESHOLD: u32 = 1000;
+// exception handlers always run in interpreter, so methods deoptimizing this often are not entered anymore
+const DEOPTIMIZATION_LIMIT: u32 = 100;
+
+// kind of exit from compiled code, returned in upper half of the result with offset of the instruction in lower half
+const EXIT_INTERPRET: u64 = 0; // instruction needs jvm, so interpreter executes it
+const EXIT_SAFEPOINT: u64 = 1; // back-edge to the instruction is taken while current thread should yield
+
+// state shared with compiled code
+#[repr(C)]
+struct Context {
+    slots: *mut u64,
+    is_reference: *mut bool,
+    jvm: *const Jvm,
+    top: usize, // set on exit
+}
+
+// takes context and offset of the block to resume, returns exit
+type CompiledFn = unsafe extern "C" fn(*mut Context, u32) -> u64;
+
+// compiled code of all methods lives in one module, which is never freed as we don't track when methods are dropped
+static MODULE: OnceLock<Result<Mutex<JITModule>, String>> = OnceLock::new();
+
+fn module() -> Result<&'static Mutex<JITModule>, String> {
+    MODULE
+        .get_or_init(|| {
+            let mut flag_builder = settings::builder();
+            flag_builder.set("opt_level", "speed").map_err(|x| format!("{}", x))?;
+            let isa = cranelift_native::builder()?
+                .finish(settings::Flags::new(flag_builder))
+                .map_err(|x| format!("{}", x))?;
+
+            Ok(Mutex::new(JITModule::new(JITBuilder::with_isa(isa, default_libcall_names()))))
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+// called by compiled code on back-edges
+extern "C" fn should_yield(jvm: *const Jvm) -> u8 {
+    // SAFETY: compiled code passes jvm of the context, which outlives the call
+    unsafe { &*jvm }.should_schedule(SchedulePoint::BackEdge) as u8
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum Kind {
+    Int,       // also float, as raw bits
+    Long,      // also double, as raw bits
+    Reference, // handle into reference table of the frame
+}
+
+impl Kind {
+    fn from_type(r#type: &JavaType) -> Option<Self> {
+        match r#type {
+            JavaType::Boolean | JavaType::Byte | JavaType::Char | JavaType::Short | JavaType::Int | JavaType::Float => Some(Self::Int),
+            JavaType::Long | JavaType::Double => Some(Self::Long),
+            JavaType::Class(_) | JavaType::Array(_) => Some(Self::Reference),
+            JavaType::Void | JavaType::Method(_, _) => None,
+        }
+    }
+
+    fn ir_type(&self) -> Type {
+        match self {
+            Self::Int => types::I32,
+            Self::Long | Self::Reference => types::I64,
+        }
+    }
+
+    // number of slots taken
+    fn size(&self) -> usize {
+        match self {
+            Self::Long => 2,
+            Self::Int | Self::Reference => 1,
+        }
+    }
+}
+
+// kinds of local variables and operand stack at an instruction.
+// local variable is None if it's unset or its kind differs between paths reaching the instruction
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct State {
+    locals: Vec<Option<Kind>>,
+    stack: Vec<Kind>,
+}
+
+impl State {
+    fn new(is_static: bool, params: &[JavaType], max_locals: usize) -> Result<Self, String> {
+        let mut state = Self {
+            locals: vec![None; max_locals],
+            stack: Vec::new(),
+        };
+
+        let mut index = 0;
+        if !is_static {
+            state.store(Kind::Reference, 0)?;
+            index += 1;
+        }
+        for param in params {
+            let kind = Kind::from_type(param).ok_or("Invalid parameter type")?;
+            state.store(kind, index)?;
+            index += kind.size();
+        }
+
+        Ok(state)
+    }
+
+    fn load(&self, kind: Kind, index: u8) -> Result<(), String> {
+        if self.locals.get(index as usize) != Some(&Some(kind)) {
+            return Err(format!("Local variable {} is not {:?}", index, kind));
+        }
+
+        Ok(())
+    }
+
+    fn store(&mut self, kind: Kind, index: usize) -> Result<(), String> {
+        if index + kind.size() > self.locals.len() {
+            return Err("Invalid local variable index".into());
+        }
+
+        // overwriting second half of long invalidates it
+        if index > 0 && self.locals[index - 1] == Some(Kind::Long) {
+            self.locals[index - 1] = None;
+        }
+        self.locals[index] = Some(kind);
+        if kind == Kind::Long {
+            self.locals[index + 1] = None;
+        }
+
+        Ok(())
+    }
+
+    fn pop(&mut self, kind: Kind) -> Result<(), String> {
+        if self.stack.pop() != Some(kind) {
+            return Err("Inconsistent stack".into());
+        }
+
+        Ok(())
+    }
+
+    // in slots
+    fn stack_size(&self) -> usize {
+        self.stack.iter().map(Kind::size).sum()
+    }
+
+    // merges state of another path, returns true if this state has changed
+    fn merge(&mut self, other: &State) -> Result<bool, String> {
+        if self.stack != other.stack {
+            return Err("Inconsistent stack".into());
+        }
+
+        let mut changed = false;
+        for (local, other) in self.locals.iter_mut().zip(other.locals.iter()) {
+            if local.is_some() && local != other {
+                *local = None;
+                changed = true;
+            }
+        }
+
+        Ok(changed)
+    }
+}
+
+pub struct CompiledMethod {
+    function: CompiledFn,
+    resume_points: BTreeSet<u32>,
+    deoptimizations: AtomicU32,
+}
+
+impl CompiledMethod {
+    pub async fn run(
+        &self,
+        jvm: &Jvm,
+        code: &AttributeInfoCode,
+        args: Box<[JavaValue]>,
+        return_type: &JavaType,
+        hotness: &AtomicU32,
+        linked: &LinkedReferences,
+    ) -> jvm::Result<JavaValue> {
+        let mut stack_frame = StackFrame::new(code.max_locals as usize, code.max_stack as usize);
+        stack_frame.set_arguments(args);
+
+        let mut offset = 0;
+        loop {
+            let exit = self.enter(jvm, &mut stack_frame, offset);
+            let exit_offset = exit as u32;
+
+            offset = if exit >> 32 == EXIT_SAFEPOINT {
+                jvm.schedule(SchedulePoint::BackEdge).await;
+
+                exit_offset
+            } else {
+                match Interpreter::step(jvm, code, &mut stack_frame, exit_offset, return_type, linked).await? {
+                    Step::Continue(x) => x,
+                    Step::Return(x) => return Ok(x),
+                }
+            };
+
+            if !self.resume_points.contains(&offset) {
+                tracing::debug!("Deoptimizing at {}", offset);
+                self.deoptimizations.fetch_add(1, Ordering::Relaxed);
+
+                return Interpreter::resume(jvm, code, stack_frame, offset, return_type, hotness, linked).await;
+            }
+        }
+    }
+
+    fn enter(&self, jvm: &Jvm, stack_frame: &mut StackFrame, offset: u32) -> u64 {
+        let (slots, is_reference) = stack_frame.raw_parts();
+        let mut context = Context {
+            slots,
+            is_reference,
+            jvm,
+            top: 0,
+        };
+
+        // SAFETY: frame is sized from the code function is compiled from, and offset is one of its resume points
+        let exit = unsafe { (self.function)(&mut context, offset) };
+        stack_frame.set_top(context.top);
+
+        exit
+    }
+}
+
+pub struct JitState {
+    compiled: OnceLock<Option<CompiledMethod>>,
+}
+
+impl JitState {
+    pub fn new() -> Self {
+        Self { compiled: OnceLock::new() }
+    }
+
+    // returns None if method should be run in interpreter
+    pub fn compiled(
+        &self,
+        name: &str,
+        descriptor: &str,
+        access_flags: MethodAccessFlags,
+        code: &AttributeInfoCode,
+        hotness: &AtomicU32,
+    ) -> Option<&CompiledMethod> {
+        if self.compiled.get().is_none() && hotness.fetch_add(1, Ordering::Relaxed) < COMPILE_THRESHOLD {
+            return None;
+        }
+
+        let compiled = self.compiled.get_or_init(|| match Compiler::compile(descriptor, access_flags, code) {
+            Ok(x) => {
+                tracing::debug!("Compiled {}{}", name, descriptor);
+
+                Some(x)
+            }
+            Err(x) => {
+                tracing::debug!("Not compiling {}{}: {}", name, descriptor, x);
+
+                None
+            }
+        });
+
+        compiled
+            .as_ref()
+            .filter(|x| x.deoptimizations.load(Ordering::Relaxed) < DEOPTIMIZATION_LIMIT)
+    }
+}
+
+struct Compiler<'a, 'b> {
+    builder: FunctionBuilder<'a>,
+    states: &'b BTreeMap<u32, State>,
+    blocks: BTreeMap<u32, Block>,
+    safepoints: BTreeMap<u32, Block>, // by back-edge target
+    state: State,
+    locals: Vec<[Variable; 3]>,
+    stack_vars: Vec<[Variable; 3]>,
+    max_locals: usize,
+    pointer_type: Type,
+    context: Value,
+    slots: Value,
+    is_reference: Value,
+    should_yield: SigRef,
+}
+
+impl<'a, 'b> Compiler<'a, 'b> {
+    fn compile(descriptor: &str, access_flags: MethodAccessFlags, code: &AttributeInfoCode) -> Result<CompiledMethod, String> {
+        let r#type = JavaType::parse(descriptor).map_err(|x| format!("{}", x))?;
+        let entry = State::new(
+            access_flags.contains(MethodAccessFlags::STATIC),
+            r#type.as_method().0,
+            code.max_locals as usize,
+        )?;
+
+        let states = analyze(code, entry)?;
+
+        let mut module = module()?.lock().map_err(|_| "JIT module is poisoned")?;
+
+        let pointer_type = module.target_config().pointer_type();
+        let mut context = module.make_context();
+        context.func.signature.params.push(AbiParam::new(pointer_type));
+        context.func.signature.params.push(AbiParam::new(types::I32));
+        context.func.signature.returns.push(AbiParam::new(types::I64));
+
+        let mut should_yield_signature = module.make_signature();
+        should_yield_signature.params.push(AbiParam::new(pointer_type));
+        should_yield_signature.returns.push(AbiParam::new(types::I8));
+
+        let mut function_builder_context = FunctionBuilderContext::new();
+        let mut builder = FunctionBuilder::new(&mut context.func, &mut function_builder_context);
+        let should_yield = builder.import_signature(should_yield_signature);
+
+        let entry_block = builder.create_block();
+        builder.append_block_params_for_function_params(entry_block);
+        builder.switch_to_block(entry_block);
+        let context_ptr = builder.block_params(entry_block)[0];
+        let resume = builder.block_params(entry_block)[1];
+
+        let declare = |builder: &mut FunctionBuilder| {
+            [
+                builder.declare_var(Kind::Int.ir_type()),
+                builder.declare_var(Kind::Long.ir_type()),
+                builder.declare_var(Kind::Reference.ir_type()),
+            ]
+        };
+        let locals = (0..code.max_locals).map(|_| declare(&mut builder)).collect::<Vec<_>>();
+        let stack_vars = (0..code.max_stack).map(|_| declare(&mut builder)).collect::<Vec<_>>();
+
+        // every variable needs a definition on entry
+        for vars in locals.iter().chain(stack_vars.iter()) {
+            for (var, kind) in vars.iter().zip([Kind::Int, Kind::Long, Kind::Reference]) {
+                let zero = builder.ins().iconst(kind.ir_type(), 0);
+                builder.def_var(*var, zero);
+            }
+        }
+
+        let slots = builder
+            .ins()
+            .load(pointer_type, MemFlagsData::trusted(), context_ptr, offset_of!(Context, slots) as i32);
+        let is_reference = builder.ins().load(
+            pointer_type,
+            MemFlagsData::trusted(),
+            context_ptr,
+            offset_of!(Context, is_reference) as i32,
+        );
+
+        let blocks = states.keys().map(|x| (*x, builder.create_block())).collect::<BTreeMap<_, _>>();
+
+        let mut compiler = Compiler {
+            builder,
+            states: &states,
+            blocks,
+            safepoints: BTreeMap::new(),
+            state: states[&0].clone(),
+            locals,
+            stack_vars,
+            max_locals: code.max_locals as usize,
+            pointer_type,
+            context: context_ptr,
+            slots,
+            is_reference,
+            should_yield,
+        };
+        compiler.translate_resume(resume);
+        compiler.translate(code)?;
+        compiler.translate_safepoints();
+
+        let mut builder = compiler.builder;
+        builder.seal_all_blocks();
+        builder.finalize(module.isa().frontend_config());
+
+        let id = module.declare_anonymous_function(&context.func.signature).map_err(|x| format!("{}", x))?;
+        module.define_function(id, &mut context).map_err(|x| format!("{:?}", x))?;
+        module.clear_context(&mut context);
+        module.finalize_definitions().map_err(|x| format!("{}", x))?;
+
+        // SAFETY: signature of the function matches CompiledFn
+        let function = unsafe { transmute::<*const u8, CompiledFn>(module.get_finalized_function(id)) };
+
+        Ok(CompiledMethod {
+            function,
+            resume_points: states.into_keys().collect(),
+            deoptimizations: AtomicU32::new(0),
+        })
+    }
+
+    // dispatches to the block to resume, reloading its state from the frame
+    fn translate_resume(&mut self, resume: Value) {
+        let mut switch = Switch::new();
+        let resume_blocks = self
+            .blocks
+            .keys()
+            .map(|offset| {
+                let block = self.builder.create_block();
+                switch.set_entry(*offset as u128, block);
+
+                (*offset, block)
+            })
+            .collect::<Vec<_>>();
+
+        let invalid_block = self.builder.create_block();
+        switch.emit(&mut self.builder, resume, invalid_block);
+
+        self.builder.switch_to_block(invalid_block);
+        self.builder.ins().trap(TrapCode::unwrap_user(1));
+
+        for (offset, block) in resume_blocks {
+            self.builder.switch_to_block(block);
+            self.state = self.states[&offset].clone();
+
+            for index in 0..self.state.locals.len() {
+                if let Some(kind) = self.state.locals[index] {
+                    let value = self.load_slot(index, kind);
+                    self.builder.def_var(self.locals[index][kind as usize], value);
+                }
+            }
+
+            let mut position = self.max_locals;
+            for (index, kind) in self.state.stack.clone().into_iter().enumerate() {
+                let value = self.load_slot(position, kind);
+                self.builder.def_var(self.stack_vars[index][kind as usize], value);
+                position += kind.size();
+            }
+
+            self.builder.ins().jump(self.blocks[&offset], &[]);
+        }
+    }
+
+    fn translate(&mut self, code: &AttributeInfoCode) -> Result<(), String> {
+        let mut reachable = false;
+        for (offset, opcode) in code.code.iter() {
+            if let Some(block) = self.blocks.get(offset).copied() {
+                if reachable {
+                    self.builder.ins().jump(block, &[]);
+                }
+                self.builder.switch_to_block(block);
+                self.state = self.states[offset].clone();
+            } else if !reachable {
+                continue;
+            }
+
+            if is_native(opcode) {
+                reachable = self.translate_opcode(*offset, opcode, next_offset(code, *offset))?;
+            } else {
+                self.exit(EXIT_INTERPRET, *offset);
+                reachable = false;
+            }
+        }
+
+        Ok(())
+    }
+
+    // checks whether to yield on back-edges
+    fn translate_safepoints(&mut self) {
+        for (target, block) in core::mem::take(&mut self.safepoints) {
+            self.builder.switch_to_block(block);
+
+            let jvm = self
+                .builder
+                .ins()
+                .load(self.pointer_type, MemFlagsData::trusted(), self.context, offset_of!(Context, jvm) as i32);
+            let callee = self.builder.ins().iconst(self.pointer_type, should_yield as *const () as usize as i64);
+            let call = self.builder.ins().call_indirect(self.should_yield, callee, &[jvm]);
+            let result = self.builder.inst_results(call)[0];
+
+            let exit_block = self.builder.create_block();
+            self.builder.ins().brif(result, exit_block, &[], self.blocks[&target], &[]);
+
+            self.builder.switch_to_block(exit_block);
+            self.state = self.states[&target].clone();
+            self.exit(EXIT_SAFEPOINT, target);
+        }
+    }
+
+    // returns true if execution falls through to the next instruction
+    fn translate_opcode(&mut self, offset: u32, opcode: &Opcode, next: Option<u32>) -> Result<bool, String> {
+        let jump = |x: i32| (offset as i32 + x) as u32;
+
+        match opcode {
+            Opcode::Nop => {}
+            Opcode::AconstNull => self.push_const(Kind::Reference, 0),
+            Opcode::Iconst(x) => self.push_const(Kind::Int, *x as i64),
+            Opcode::Bipush(x) => self.push_const(Kind::Int, *x as i64),
+            Opcode::Sipush(x) => self.push_const(Kind::Int, *x as i64),
+            Opcode::Fconst(x) => self.push_const(Kind::Int, (*x as f32).to_bits() as i64),
+            Opcode::Lconst(x) => self.push_const(Kind::Long, *x as i64),
+            Opcode::Dconst(x) => self.push_const(Kind::Long, (*x as f64).to_bits() as i64),
+            Opcode::Ldc(ValueConstant::Integer(x)) | Opcode::LdcW(ValueConstant::Integer(x)) => self.push_const(Kind::Int, *x as i64),
+            Opcode::Ldc(ValueConstant::Float(x)) | Opcode::LdcW(ValueConstant::Float(x)) => self.push_const(Kind::Int, x.to_bits() as i64),
+            Opcode::Ldc2W(ValueConstant::Long(x)) => self.push_const(Kind::Long, *x),
+            Opcode::Ldc2W(ValueConstant::Double(x)) => self.push_const(Kind::Long, x.to_bits() as i64),
+            Opcode::Iload(x) | Opcode::Fload(x) => self.load(Kind::Int, *x),
+            Opcode::Lload(x) | Opcode::Dload(x) => self.load(Kind::Long, *x),
+            Opcode::Aload(x) => self.load(Kind::Reference, *x),
+            Opcode::Istore(x) | Opcode::Fstore(x) => self.store(Kind::Int, *x)?,
+            Opcode::Lstore(x) | Opcode::Dstore(x) => self.store(Kind::Long, *x)?,
+            Opcode::Astore(x) => self.store(Kind::Reference, *x)?,
+            Opcode::Iinc(x, value) => {
+                let var = self.locals[*x as usize][Kind::Int as usize];
+                let current = self.builder.use_var(var);
+                let result = self.builder.ins().iadd_imm_s(current, *value as i64);
+                self.builder.def_var(var, result);
+            }
+            Opcode::Iadd | Opcode::Isub | Opcode::Imul | Opcode::Iand | Opcode::Ior | Opcode::Ixor => self.binary(Kind::Int, opcode),
+            Opcode::Ladd | Opcode::Lsub | Opcode::Lmul | Opcode::Land | Opcode::Lor | Opcode::Lxor => self.binary(Kind::Long, opcode),
+            Opcode::Idiv | Opcode::Irem => self.divide(offset, Kind::Int, matches!(opcode, Opcode::Irem)),
+            Opcode::Ldiv | Opcode::Lrem => self.divide(offset, Kind::Long, matches!(opcode, Opcode::Lrem)),
+            Opcode::Ishl | Opcode::Ishr | Opcode::Iushr | Opcode::Lshl | Opcode::Lshr | Opcode::Lushr => {
+                let kind = if matches!(opcode, Opcode::Ishl | Opcode::Ishr | Opcode::Iushr) {
+                    Kind::Int
+                } else {
+                    Kind::Long
+                };
+
+                // cranelift masks shift amount to type width as java does
+                let amount = self.pop(Kind::Int);
+                let value = self.pop(kind);
+                let result = match opcode {
+                    Opcode::Ishl | Opcode::Lshl => self.builder.ins().ishl(value, amount),
+                    Opcode::Ishr | Opcode::Lshr => self.builder.ins().sshr(value, amount),
+                    _ => self.builder.ins().ushr(value, amount),
+                };
+                self.push(kind, result);
+            }
+            Opcode::Ineg | Opcode::Lneg => {
+                let kind = if matches!(opcode, Opcode::Ineg) { Kind::Int } else { Kind::Long };
+                let value = self.pop(kind);
+                let result = self.builder.ins().ineg(value);
+                self.push(kind, result);
+            }
+            Opcode::I2b | Opcode::I2c | Opcode::I2s => {
+                let value = self.pop(Kind::Int);
+                let narrow_type = if matches!(opcode, Opcode::I2b) { types::I8 } else { types::I16 };
+                let narrow = self.builder.ins().ireduce(narrow_type, value);
+                let result = if matches!(opcode, Opcode::I2c) {
+                    self.builder.ins().uextend(types::I32, narrow)
+                } else {
+                    self.builder.ins().sextend(types::I32, narrow)
+                };
+                self.push(Kind::Int, result);
+            }
+            Opcode::I2l => {
+                let value = self.pop(Kind::Int);
+                let result = self.builder.ins().sextend(types::I64, value);
+                self.push(Kind::Long, result);
+            }
+            Opcode::L2i => {
+                let value = self.pop(Kind::Long);
+                let result = self.builder.ins().ireduce(types::I32, value);
+                self.push(Kind::Int, result);
+            }
+            Opcode::Lcmp => {
+                let value2 = self.pop(Kind::Long);
+                let value1 = self.pop(Kind::Long);
+                let greater = self.builder.ins().icmp(IntCC::SignedGreaterThan, value1, value2);
+                let less = self.builder.ins().icmp(IntCC::SignedLessThan, value1, value2);
+                let greater = self.builder.ins().uextend(types::I32, greater);
+                let less = self.builder.ins().uextend(types::I32, less);
+                let result = self.builder.ins().isub(greater, less);
+                self.push(Kind::Int, result);
+            }
+            Opcode::Pop => self.pop_slots(1),
+            Opcode::Pop2 => self.pop_slots(2),
+            Opcode::Dup => self.dup(1, 1),
+            Opcode::DupX1 => self.dup(1, 2),
+            Opcode::DupX2 => self.dup(1, 3),
+            Opcode::Dup2 => self.dup(2, 2),
+            Opcode::Dup2X1 => self.dup(2, 3),
+            Opcode::Dup2X2 => self.dup(2, 4),
+            Opcode::Swap => {
+                // dup_x1 followed by pop
+                self.dup(1, 2);
+                self.pop_slots(1);
+            }
+            Opcode::Goto(x) => {
+                let target = self.target(offset, jump(*x as i32));
+                self.builder.ins().jump(target, &[]);
+                return Ok(false);
+            }
+            Opcode::GotoW(x) => {
+                let target = self.target(offset, jump(*x));
+                self.builder.ins().jump(target, &[]);
+                return Ok(false);
+            }
+            Opcode::Ifeq(x) | Opcode::Ifne(x) | Opcode::Iflt(x) | Opcode::Ifge(x) | Opcode::Ifgt(x) | Opcode::Ifle(x) => {
+                let value = self.pop(Kind::Int);
+                let condition = self.builder.ins().icmp_imm_s(int_condition(opcode), value, 0);
+                self.branch(offset, condition, jump(*x as i32), next)?;
+                return Ok(false);
+            }
+            Opcode::IfIcmpeq(x) | Opcode::IfIcmpne(x) | Opcode::IfIcmplt(x) | Opcode::IfIcmpge(x) | Opcode::IfIcmpgt(x) | Opcode::IfIcmple(x) => {
+                let value2 = self.pop(Kind::Int);
+                let value1 = self.pop(Kind::Int);
+                let condition = self.builder.ins().icmp(int_condition(opcode), value1, value2);
+                self.branch(offset, condition, jump(*x as i32), next)?;
+                return Ok(false);
+            }
+            Opcode::Ifnull(x) | Opcode::Ifnonnull(x) => {
+                let value = self.pop(Kind::Reference);
+                let condition = if matches!(opcode, Opcode::Ifnull(_)) {
+                    IntCC::Equal
+                } else {
+                    IntCC::NotEqual
+                };
+                let condition = self.builder.ins().icmp_imm_s(condition, value, 0);
+                self.branch(offset, condition, jump(*x as i32), next)?;
+                return Ok(false);
+            }
+            Opcode::Tableswitch(default, pairs) | Opcode::Lookupswitch(default, pairs) => {
+                let key = self.pop(Kind::Int);
+
+                let mut switch = Switch::new();
+                for (k, x) in pairs {
+                    let target = self.target(offset, jump(*x));
+                    switch.set_entry(*k as u32 as u128, target);
+                }
+                let default = self.target(offset, jump(*default));
+                switch.emit(&mut self.builder, key, default);
+                return Ok(false);
+            }
+            _ => return Err(format!("Unsupported opcode {:?}", opcode)),
+        }
+
+        Ok(true)
+    }
+
+    // spills local variables and operand stack into the frame, and returns to driver
+    fn exit(&mut self, kind: u64, offset: u32) {
+        let state = self.state.clone();
+
+        for (index, local) in state.locals.iter().enumerate() {
+            if let Some(x) = local {
+                let value = self.builder.use_var(self.locals[index][*x as usize]);
+                self.store_slot(index, *x, value);
+            }
+        }
+
+        let mut position = self.max_locals;
+        for (index, x) in state.stack.iter().enumerate() {
+            let value = self.builder.use_var(self.stack_vars[index][*x as usize]);
+            self.store_slot(position, *x, value);
+            position += x.size();
+        }
+
+        let top = self.builder.ins().iconst(self.pointer_type, position as i64);
+        self.builder
+            .ins()
+            .store(MemFlagsData::trusted(), top, self.context, offset_of!(Context, top) as i32);
+
+        let exit = self.builder.ins().iconst(types::I64, ((kind << 32) | offset as u64) as i64);
+        self.builder.ins().return_(&[exit]);
+    }
+
+    fn load_slot(&mut self, index: usize, kind: Kind) -> Value {
+        let value = self
+            .builder
+            .ins()
+            .load(types::I64, MemFlagsData::trusted(), self.slots, (index * 8) as i32);
+
+        if kind == Kind::Int {
+            self.builder.ins().ireduce(types::I32, value)
+        } else {
+            value
+        }
+    }
+
+    // int is stored zero extended as interpreter does, long and double use the first of two slots
+    fn store_slot(&mut self, index: usize, kind: Kind, value: Value) {
+        let value = if kind == Kind::Int {
+            self.builder.ins().uextend(types::I64, value)
+        } else {
+            value
+        };
+        self.builder.ins().store(MemFlagsData::trusted(), value, self.slots, (index * 8) as i32);
+
+        for i in 0..kind.size() {
+            let is_reference = self.builder.ins().iconst(types::I8, (i == 0 && kind == Kind::Reference) as i64);
+            self.builder
+                .ins()
+                .store(MemFlagsData::trusted(), is_reference, self.is_reference, (index + i) as i32);
+        }
+    }
+
+    // block to jump to, checking whether to yield first on back-edges
+    fn target(&mut self, offset: u32, target: u32) -> Block {
+        if target > offset {
+            return self.blocks[&target];
+        }
+
+        if let Some(x) = self.safepoints.get(&target) {
+            return *x;
+        }
+        let block = self.builder.create_block();
+        self.safepoints.insert(target, block);
+
+        block
+    }
+
+    fn branch(&mut self, offset: u32, condition: Value, target: u32, next: Option<u32>) -> Result<(), String> {
+        let target = self.target(offset, target);
+        let next = self.blocks[&next.ok_or("Code falls off the end")?];
+
+        self.builder.ins().brif(condition, target, &[], next, &[]);
+
+        Ok(())
+    }
+
+    fn push(&mut self, kind: Kind, value: Value) {
+        let var = self.stack_vars[self.state.stack.len()][kind as usize];
+        self.builder.def_var(var, value);
+        self.state.stack.push(kind);
+    }
+
+    fn pop(&mut self, kind: Kind) -> Value {
+        let popped = self.state.stack.pop();
+        debug_assert_eq!(popped, Some(kind));
+
+        let var = self.stack_vars[self.state.stack.len()][kind as usize];
+        self.builder.use_var(var)
+    }
+
+    fn pop_any(&mut self) -> (Kind, Value) {
+        let kind = *self.state.stack.last().unwrap();
+
+        (kind, self.pop(kind))
+    }
+
+    fn pop_slots(&mut self, size: usize) {
+        // size is verified by analysis
+        for _ in 0..items(&self.state.stack, size).unwrap() {
+            self.pop_any();
+        }
+    }
+
+    // duplicates top `count` slots, and inserts them below `depth` slots from top
+    fn dup(&mut self, count: usize, depth: usize) {
+        // sizes are verified by analysis
+        let count = items(&self.state.stack, count).unwrap();
+        let depth = items(&self.state.stack, depth).unwrap();
+
+        let mut values = (0..depth).map(|_| self.pop_any()).collect::<Vec<_>>();
+        values.reverse();
+
+        for (kind, value) in values[depth - count..].iter().chain(values.iter()).copied().collect::<Vec<_>>() {
+            self.push(kind, value);
+        }
+    }
+
+    fn push_const(&mut self, kind: Kind, value: i64) {
+        let value = self.builder.ins().iconst(kind.ir_type(), value);
+        self.push(kind, value);
+    }
+
+    fn load(&mut self, kind: Kind, index: u8) {
+        let value = self.builder.use_var(self.locals[index as usize][kind as usize]);
+        self.push(kind, value);
+    }
+
+    fn store(&mut self, kind: Kind, index: u8) -> Result<(), String> {
+        let value = self.pop(kind);
+        self.state.store(kind, index as usize)?;
+        self.builder.def_var(self.locals[index as usize][kind as usize], value);
+
+        Ok(())
+    }
+
+    fn binary(&mut self, kind: Kind, opcode: &Opcode) {
+        let value2 = self.pop(kind);
+        let value1 = self.pop(kind);
+
+        let result = match opcode {
+            Opcode::Iadd | Opcode::Ladd => self.builder.ins().iadd(value1, value2),
+            Opcode::Isub | Opcode::Lsub => self.builder.ins().isub(value1, value2),
+            Opcode::Imul | Opcode::Lmul => self.builder.ins().imul(value1, value2),
+            Opcode::Iand | Opcode::Land => self.builder.ins().band(value1, value2),
+            Opcode::Ior | Opcode::Lor => self.builder.ins().bor(value1, value2),
+            _ => self.builder.ins().bxor(value1, value2),
+        };
+        self.push(kind, result);
+    }
+
+    fn divide(&mut self, offset: u32, kind: Kind, remainder: bool) {
+        let state = self.state.clone();
+        let value2 = self.pop(kind);
+        let value1 = self.pop(kind);
+
+        // division by zero throws exception, so let interpreter execute it
+        let continue_block = self.builder.create_block();
+        let exit_block = self.builder.create_block();
+        self.builder.ins().brif(value2, continue_block, &[], exit_block, &[]);
+
+        self.builder.switch_to_block(exit_block);
+        let current = replace(&mut self.state, state);
+        self.exit(EXIT_INTERPRET, offset);
+        self.state = current;
+
+        self.builder.switch_to_block(continue_block);
+
+        // MIN / -1 overflows in java, but traps in cranelift
+        let is_minus_one = self.builder.ins().icmp_imm_s(IntCC::Equal, value2, -1);
+        let one = self.builder.ins().iconst(kind.ir_type(), 1);
+        let divisor = self.builder.ins().select(is_minus_one, one, value2);
+        let result = if remainder {
+            let zero = self.builder.ins().iconst(kind.ir_type(), 0);
+            let result = self.builder.ins().srem(value1, divisor);
+            self.builder.ins().select(is_minus_one, zero, result)
+        } else {
+            let negated = self.builder.ins().ineg(value1);
+            let result = self.builder.ins().sdiv(value1, divisor);
+            self.builder.ins().select(is_minus_one, negated, result)
+        };
+        self.push(kind, result);
+    }
+}
+
+fn int_condition(opcode: &Opcode) -> IntCC {
+    match opcode {
+        Opcode::Ifeq(_) | Opcode::IfIcmpeq(_) => IntCC::Equal,
+        Opcode::Ifne(_) | Opcode::IfIcmpne(_) => IntCC::NotEqual,
+        Opcode::Iflt(_) | Opcode::IfIcmplt(_) => IntCC::SignedLessThan,
+        Opcode::Ifge(_) | Opcode::IfIcmpge(_) => IntCC::SignedGreaterThanOrEqual,
+        Opcode::Ifgt(_) | Opcode::IfIcmpgt(_) => IntCC::SignedGreaterThan,
+        Opcode::Ifle(_) | Opcode::IfIcmple(_) => IntCC::SignedLessThanOrEqual,
+        _ => unreachable!(),
+    }
+}
+
+fn next_offset(code: &AttributeInfoCode, offset: u32) -> Option<u32> {
+    code.code.range(offset + 1..).next().map(|x| *x.0)
+}
+
+// number of stack items making up top `size` slots, None if a long is split
+fn items(stack: &[Kind], size: usize) -> Option<usize> {
+    let mut total = 0;
+    for (index, kind) in stack.iter().rev().enumerate() {
+        if total == size {
+            return Some(index);
+        }
+
+        total += kind.size();
+        if total > size {
+            return None;
+        }
+    }
+
+    (total == size).then_some(stack.len())
+}
+
+// opcodes compiled to native code. others exit to interpreter
+fn is_native(opcode: &Opcode) -> bool {
+    matches!(
+        opcode,
+        Opcode::Nop
+            | Opcode::AconstNull
+            | Opcode::Iconst(_)
+            | Opcode::Bipush(_)
+            | Opcode::Sipush(_)
+            | Opcode::Fconst(_)
+            | Opcode::Lconst(_)
+            | Opcode::Dconst(_)
+            | Opcode::Ldc(ValueConstant::Integer(_) | ValueConstant::Float(_))
+            | Opcode::LdcW(ValueConstant::Integer(_) | ValueConstant::Float(_))
+            | Opcode::Ldc2W(ValueConstant::Long(_) | ValueConstant::Double(_))
+            | Opcode::Iload(_)
+            | Opcode::Fload(_)
+            | Opcode::Lload(_)
+            | Opcode::Dload(_)
+            | Opcode::Aload(_)
+            | Opcode::Istore(_)
+            | Opcode::Fstore(_)
+            | Opcode::Lstore(_)
+            | Opcode::Dstore(_)
+            | Opcode::Astore(_)
+            | Opcode::Iinc(_, _)
+            | Opcode::Iadd
+            | Opcode::Isub
+            | Opcode::Imul
+            | Opcode::Idiv
+            | Opcode::Irem
+            | Opcode::Iand
+            | Opcode::Ior
+            | Opcode::Ixor
+            | Opcode::Ishl
+            | Opcode::Ishr
+            | Opcode::Iushr
+            | Opcode::Ladd
+            | Opcode::Lsub
+            | Opcode::Lmul
+            | Opcode::Ldiv
+            | Opcode::Lrem
+            | Opcode::Land
+            | Opcode::Lor
+            | Opcode::Lxor
+            | Opcode::Lshl
+            | Opcode::Lshr
+            | Opcode::Lushr
+            | Opcode::Ineg
+            | Opcode::Lneg
+            | Opcode::I2b
+            | Opcode::I2c
+            | Opcode::I2s
+            | Opcode::I2l
+            | Opcode::L2i
+            | Opcode::Lcmp
+            | Opcode::Pop
+            | Opcode::Pop2
+            | Opcode::Dup
+            | Opcode::DupX1
+            | Opcode::DupX2
+            | Opcode::Dup2
+            | Opcode::Dup2X1
+            | Opcode::Dup2X2
+            | Opcode::Swap
+            | Opcode::Goto(_)
+            | Opcode::GotoW(_)
+            | Opcode::Ifeq(_)
+            | Opcode::Ifne(_)
+            | Opcode::Iflt(_)
+            | Opcode::Ifge(_)
+            | Opcode::Ifgt(_)
+            | Opcode::Ifle(_)
+            | Opcode::IfIcmpeq(_)
+            | Opcode::IfIcmpne(_)
+            | Opcode::IfIcmplt(_)
+            | Opcode::IfIcmpge(_)
+            | Opcode::IfIcmpgt(_)
+            | Opcode::IfIcmple(_)
+            | Opcode::Ifnull(_)
+            | Opcode::Ifnonnull(_)
+            | Opcode::Tableswitch(_, _)
+            | Opcode::Lookupswitch(_, _)
+    )
+}
+
+fn branch_targets<J>(opcode: &Opcode, jump: J) -> Vec<u32>
+where
+    J: Fn(i32) -> u32,
+{
+    match opcode {
+        Opcode::Goto(x)
+        | Opcode::Ifeq(x)
+        | Opcode::Ifne(x)
+        | Opcode::Iflt(x)
+        | Opcode::Ifge(x)
+        | Opcode::Ifgt(x)
+        | Opcode::Ifle(x)
+        | Opcode::IfIcmpeq(x)
+        | Opcode::IfIcmpne(x)
+        | Opcode::IfIcmplt(x)
+        | Opcode::IfIcmpge(x)
+        | Opcode::IfIcmpgt(x)
+        | Opcode::IfIcmple(x)
+        | Opcode::IfAcmpeq(x)
+        | Opcode::IfAcmpne(x)
+        | Opcode::Ifnull(x)
+        | Opcode::Ifnonnull(x) => vec![jump(*x as i32)],
+        Opcode::GotoW(x) => vec![jump(*x)],
+        Opcode::Tableswitch(default, pairs) | Opcode::Lookupswitch(default, pairs) => {
+            iter::once(*default).chain(pairs.iter().map(|x| x.1)).map(jump).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn falls_through(opcode: &Opcode) -> bool {
+    !matches!(
+        opcode,
+        Opcode::Goto(_)
+            | Opcode::GotoW(_)
+            | Opcode::Tableswitch(_, _)
+            | Opcode::Lookupswitch(_, _)
+            | Opcode::Ireturn
+            | Opcode::Lreturn
+            | Opcode::Freturn
+            | Opcode::Dreturn
+            | Opcode::Areturn
+            | Opcode::Return
+            | Opcode::Athrow
+    )
+}
+
+// computes kinds of local variables and operand stack on entry of each block, verifying the code is something we can compile.
+// compiled code can be resumed on these blocks
+fn analyze(code: &AttributeInfoCode, entry: State) -> Result<BTreeMap<u32, State>, String> {
+    fn merge(states: &mut BTreeMap<u32, State>, work: &mut Vec<u32>, offset: u32, state: &State) -> Result<(), String> {
+        match states.get_mut(&offset) {
+            Some(x) => {
+                if x.merge(state)? {
+                    work.push(offset);
+                }
+            }
+            None => {
+                states.insert(offset, state.clone());
+                work.push(offset);
+            }
+        }
+
+        Ok(())
+    }
+
+    // blocks start on jump targets and after branches and instructions run by interpreter
+    let mut leaders = BTreeSet::from([0]);
+    for (offset, opcode) in code.code.iter() {
+        let targets = branch_targets(opcode, |x| (*offset as i32 + x) as u32);
+        if !targets.is_empty() || !falls_through(opcode) || !is_native(opcode) {
+            leaders.extend(next_offset(code, *offset));
+        }
+        leaders.extend(targets);
+    }
+    if let Some(x) = leaders.iter().find(|x| !code.code.contains_key(x)) {
+        return Err(format!("Invalid jump target {}", x));
+    }
+
+    let mut states = BTreeMap::from([(0, entry)]);
+    let mut work = vec![0];
+    while let Some(start) = work.pop() {
+        let mut state = states[&start].clone();
+
+        let mut ended = false;
+        for (offset, opcode) in code.code.range(start..) {
+            if *offset != start && leaders.contains(offset) {
+                merge(&mut states, &mut work, *offset, &state)?;
+                ended = true;
+                break;
+            }
+
+            simulate(opcode, &mut state)?;
+            if state.stack_size() > code.max_stack as usize {
+                return Err("Stack overflow".into());
+            }
+
+            for target in branch_targets(opcode, |x| (*offset as i32 + x) as u32) {
+                merge(&mut states, &mut work, target, &state)?;
+            }
+
+            if !falls_through(opcode) {
+                ended = true;
+                break;
+            }
+        }
+
+        if !ended {
+            return Err("Code falls off the end".into());
+        }
+    }
+
+    Ok(states)
+}
+
+// applies effect of opcode on kinds
+fn simulate(opcode: &Opcode, state: &mut State) -> Result<(), String> {
+    use Kind::{Int, Long, Reference};
+
+    fn field_kind(descriptor: &str) -> Result<Kind, String> {
+        let r#type = JavaType::parse(descriptor).map_err(|x| format!("{}", x))?;
+
+        Kind::from_type(&r#type).ok_or_else(|| format!("Invalid field type {}", descriptor))
+    }
+
+    // pops are listed from top of the stack
+    let (pops, pushes): (Vec<Kind>, Vec<Kind>) = match opcode {
+        Opcode::Nop | Opcode::Goto(_) | Opcode::GotoW(_) | Opcode::Return => (vec![], vec![]),
+        Opcode::Iinc(x, _) => {
+            state.load(Int, *x)?;
+            (vec![], vec![])
+        }
+        Opcode::AconstNull | Opcode::New(_) => (vec![], vec![Reference]),
+        Opcode::Iconst(_) | Opcode::Bipush(_) | Opcode::Sipush(_) | Opcode::Fconst(_) => (vec![], vec![Int]),
+        Opcode::Lconst(_) | Opcode::Dconst(_) => (vec![], vec![Long]),
+        Opcode::Ldc(x) | Opcode::LdcW(x) | Opcode::Ldc2W(x) => match x {
+            ValueConstant::Integer(_) | ValueConstant::Float(_) => (vec![], vec![Int]),
+            ValueConstant::Long(_) | ValueConstant::Double(_) => (vec![], vec![Long]),
+            ValueConstant::String(_) | ValueConstant::Class(_) => (vec![], vec![Reference]),
+            _ => return Err(format!("Unsupported constant {:?}", x)),
+        },
+        Opcode::Iload(x) | Opcode::Fload(x) => {
+            state.load(Int, *x)?;
+            (vec![], vec![Int])
+        }
+        Opcode::Lload(x) | Opcode::Dload(x) => {
+            state.load(Long, *x)?;
+            (vec![], vec![Long])
+        }
+        Opcode::Aload(x) => {
+            state.load(Reference, *x)?;
+            (vec![], vec![Reference])
+        }
+        Opcode::Istore(x) | Opcode::Fstore(x) | Opcode::Lstore(x) | Opcode::Dstore(x) | Opcode::Astore(x) => {
+            let kind = match opcode {
+                Opcode::Istore(_) | Opcode::Fstore(_) => Int,
+                Opcode::Lstore(_) | Opcode::Dstore(_) => Long,
+                _ => Reference,
+            };
+            state.pop(kind)?;
+            state.store(kind, *x as usize)?;
+            (vec![], vec![])
+        }
+        Opcode::Iadd
+        | Opcode::Isub
+        | Opcode::Imul
+        | Opcode::Idiv
+        | Opcode::Irem
+        | Opcode::Iand
+        | Opcode::Ior
+        | Opcode::Ixor
+        | Opcode::Ishl
+        | Opcode::Ishr
+        | Opcode::Iushr
+        | Opcode::Fadd
+        | Opcode::Fsub
+        | Opcode::Fmul
+        | Opcode::Fdiv
+        | Opcode::Frem
+        | Opcode::Fcmpl
+        | Opcode::Fcmpg => (vec![Int, Int], vec![Int]),
+        Opcode::Ladd
+        | Opcode::Lsub
+        | Opcode::Lmul
+        | Opcode::Ldiv
+        | Opcode::Lrem
+        | Opcode::Land
+        | Opcode::Lor
+        | Opcode::Lxor
+        | Opcode::Dadd
+        | Opcode::Dsub
+        | Opcode::Dmul
+        | Opcode::Ddiv
+        | Opcode::Drem => (vec![Long, Long], vec![Long]),
+        Opcode::Lshl | Opcode::Lshr | Opcode::Lushr => (vec![Int, Long], vec![Long]),
+        Opcode::Lcmp | Opcode::Dcmpl | Opcode::Dcmpg => (vec![Long, Long], vec![Int]),
+        Opcode::Ineg | Opcode::Fneg | Opcode::I2b | Opcode::I2c | Opcode::I2s | Opcode::I2f | Opcode::F2i => (vec![Int], vec![Int]),
+        Opcode::Lneg | Opcode::Dneg | Opcode::L2d | Opcode::D2l => (vec![Long], vec![Long]),
+        Opcode::I2l | Opcode::I2d | Opcode::F2l | Opcode::F2d => (vec![Int], vec![Long]),
+        Opcode::L2i | Opcode::L2f | Opcode::D2i | Opcode::D2f => (vec![Long], vec![Int]),
+        Opcode::Pop | Opcode::Pop2 => {
+            let size = if matches!(opcode, Opcode::Pop) { 1 } else { 2 };
+            let count = items(&state.stack, size).ok_or("Invalid pop")?;
+            state.stack.truncate(state.stack.len() - count);
+            (vec![], vec![])
+        }
+        Opcode::Dup | Opcode::DupX1 | Opcode::DupX2 | Opcode::Dup2 | Opcode::Dup2X1 | Opcode::Dup2X2 | Opcode::Swap => {
+            let (count, depth) = match opcode {
+                Opcode::Dup => (1, 1),
+                Opcode::DupX1 | Opcode::Swap => (1, 2),
+                Opcode::DupX2 => (1, 3),
+                Opcode::Dup2 => (2, 2),
+                Opcode::Dup2X1 => (2, 3),
+                _ => (2, 4),
+            };
+            let count = items(&state.stack, count).ok_or("Invalid dup")?;
+            let depth = items(&state.stack, depth).ok_or("Invalid dup")?;
+
+            let values = state.stack.split_off(state.stack.len() - depth);
+            state.stack.extend_from_slice(&values[depth - count..]);
+            state.stack.extend(values);
+
+            // swap is dup_x1 followed by pop
+            if matches!(opcode, Opcode::Swap) {
+                state.stack.pop();
+            }
+            (vec![], vec![])
+        }
+        Opcode::Ifeq(_)
+        | Opcode::Ifne(_)
+        | Opcode::Iflt(_)
+        | Opcode::Ifge(_)
+        | Opcode::Ifgt(_)
+        | Opcode::Ifle(_)
+        | Opcode::Tableswitch(_, _)
+        | Opcode::Lookupswitch(_, _)
+        | Opcode::Ireturn
+        | Opcode::Freturn => (vec![Int], vec![]),
+        Opcode::IfIcmpeq(_) | Opcode::IfIcmpne(_) | Opcode::IfIcmplt(_) | Opcode::IfIcmpge(_) | Opcode::IfIcmpgt(_) | Opcode::IfIcmple(_) => {
+            (vec![Int, Int], vec![])
+        }
+        Opcode::Ifnull(_) | Opcode::Ifnonnull(_) | Opcode::Areturn | Opcode::Athrow | Opcode::Monitorenter | Opcode::Monitorexit => {
+            (vec![Reference], vec![])
+        }
+        Opcode::IfAcmpeq(_) | Opcode::IfAcmpne(_) => (vec![Reference, Reference], vec![]),
+        Opcode::Lreturn | Opcode::Dreturn => (vec![Long], vec![]),
+        Opcode::Getfield(x) => (vec![Reference], vec![field_kind(&x.descriptor)?]),
+        Opcode::Putfield(x) => (vec![field_kind(&x.descriptor)?, Reference], vec![]),
+        Opcode::Getstatic(x) => (vec![], vec![field_kind(&x.descriptor)?]),
+        Opcode::Putstatic(x) => (vec![field_kind(&x.descriptor)?], vec![]),
+        Opcode::Invokeinterface(x, _, _) | Opcode::Invokespecial(x) | Opcode::Invokestatic(x) | Opcode::Invokevirtual(x) => {
+            let r#type = JavaType::parse(&x.descriptor).map_err(|x| format!("{}", x))?;
+            let (params, return_type) = r#type.as_method();
+
+            let mut pops = params
+                .iter()
+                .rev()
+                .map(Kind::from_type)
+                .collect::<Option<Vec<_>>>()
+                .ok_or("Invalid parameter type")?;
+            if !matches!(opcode, Opcode::Invokestatic(_)) {
+                pops.push(Reference);
+            }
+
+            (pops, Kind::from_type(return_type).into_iter().collect())
+        }
+        Opcode::Newarray(_) | Opcode::Anewarray(_) => (vec![Int], vec![Reference]),
+        Opcode::Multianewarray(_, d) => (vec![Int; *d as usize], vec![Reference]),
+        Opcode::Arraylength | Opcode::Instanceof(_) => (vec![Reference], vec![Int]),
+        Opcode::Checkcast(_) => (vec![Reference], vec![Reference]),
+        Opcode::Iaload | Opcode::Faload | Opcode::Baload | Opcode::Caload | Opcode::Saload => (vec![Int, Reference], vec![Int]),
+        Opcode::Laload | Opcode::Daload => (vec![Int, Reference], vec![Long]),
+        Opcode::Aaload => (vec![Int, Reference], vec![Reference]),
+        Opcode::Iastore | Opcode::Fastore | Opcode::Bastore | Opcode::Castore | Opcode::Sastore => (vec![Int, Int, Reference], vec![]),
+        Opcode::Lastore | Opcode::Dastore => (vec![Long, Int, Reference], vec![]),
+        Opcode::Aastore => (vec![Reference, Int, Reference], vec![]),
+        Opcode::Jsr(_) | Opcode::JsrW(_) | Opcode::Ret(_) | Opcode::Wide | Opcode::Invokedynamic(_) => {
+            return Err(format!("Unsupported opcode {:?}", opcode))
+        }
+    };
+
+    for kind in pops {
+        state.pop(kind)?;
+    }
+    state.stack.extend(pushes);
+
+    Ok(())
+}
+
+impl Default for JitState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,16 +3,20 @@ extern crate alloc;
 
 mod array_class_definition;
 mod array_class_instance;
+mod class_data_cache;
 mod class_definition;
 mod class_instance;
 mod field;
 mod interpreter;
+#[cfg(all(feature = "jit", not(target_arch = "wasm32")))]
+mod jit;
 mod method;
 mod stack_frame;
 
 pub use self::{
     array_class_definition::ArrayClassDefinitionImpl,
-    class_definition::ClassDefinitionImpl,
+    class_data_cache::ClassDataCache,
+    class_definition::{ClassData, ClassDefinitionImpl},
     field::FieldImpl,
     method::{MethodBody, MethodImpl},
 };
@@ -7,6 +7,7 @@ use alloc::{
 use core::{
     fmt::{self, Debug, Formatter},
     ops::{Deref, DerefMut},
+    sync::atomic::AtomicU32,
 };
 
 use classfile::{AttributeInfo, AttributeInfoCode, MethodInfo};
@@ -14,7 +15,9 @@ use java_class_proto::JavaMethodProto;
 use java_constants::MethodAccessFlags;
 use jvm::{JavaError, JavaType, JavaValue, Jvm, JvmCallback, Method, Result};
 
-use crate::interpreter::Interpreter;
+use crate::interpreter::{Interpreter, LinkedReferences};
+#[cfg(all(feature = "jit", not(target_arch = "wasm32")))]
+use crate::jit::JitState;
 
 pub enum MethodBody {
     ByteCode(AttributeInfoCode),
@@ -36,28 +39,99 @@ impl Debug for MethodBody {
     }
 }
 
-#[derive(Debug)]
-struct MethodInner {
+// name, descriptor and access flags of a method, which can be shared with the same method bound to other jvm
+#[derive(Debug, Eq, PartialEq)]
+struct MethodSignature {
     name: String,
     descriptor: String,
-    body: Option<MethodBody>,
     access_flags: MethodAccessFlags,
 }
 
-#[derive(Clone, Debug)]
+struct MethodInner {
+    signature: Arc<MethodSignature>,
+    body: Option<MethodBody>,
+}
+
+impl MethodInner {
+    fn new(name: String, descriptor: String, body: Option<MethodBody>, access_flags: MethodAccessFlags) -> Self {
+        Self {
+            signature: Arc::new(MethodSignature {
+                name,
+                descriptor,
+                access_flags,
+            }),
+            body,
+        }
+    }
+}
+
+// runtime state of a method, kept per class definition as method data can be shared between definitions of different jvms
+struct MethodState {
+    hotness: AtomicU32, // number of invocations and back-edges taken
+    linked: LinkedReferences,
+    #[cfg(all(feature = "jit", not(target_arch = "wasm32")))]
+    jit: JitState,
+}
+
+impl MethodState {
+    fn new(body: Option<&MethodBody>) -> Self {
+        let code_attribute = match body {
+            Some(MethodBody::ByteCode(x)) => Some(x),
+            _ => None,
+        };
+
+        Self {
+            hotness: AtomicU32::new(0),
+            linked: LinkedReferences::new(code_attribute),
+            #[cfg(all(feature = "jit", not(target_arch = "wasm32")))]
+            jit: JitState::new(),
+        }
+    }
+}
+
+impl Debug for MethodInner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MethodInner")
+            .field("name", &self.signature.name)
+            .field("descriptor", &self.signature.descriptor)
+            .field("body", &self.body)
+            .field("access_flags", &self.signature.access_flags)
+            .finish()
+    }
+}
+
+#[derive(Clone)]
 pub struct MethodImpl {
     inner: Arc<MethodInner>,
+    state: Arc<MethodState>,
 }
 
 impl MethodImpl {
     pub fn new(name: &str, descriptor: &str, body: MethodBody, access_flags: MethodAccessFlags) -> Self {
+        Self::from_inner(MethodInner::new(name.to_string(), descriptor.to_string(), Some(body), access_flags))
+    }
+
+    fn from_inner(inner: MethodInner) -> Self {
         Self {
-            inner: Arc::new(MethodInner {
-                name: name.to_string(),
-                descriptor: descriptor.to_string(),
-                body: Some(body),
-                access_flags,
-            }),
+            state: Arc::new(MethodState::new(inner.body.as_ref())),
+            inner: Arc::new(inner),
+        }
+    }
+
+    // same method with fresh runtime state, for another definition sharing the method
+    pub(crate) fn clone_for_definition(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            state: Arc::new(MethodState::new(self.inner.body.as_ref())),
+        }
+    }
+
+    // uses signature of the same method bound to other jvm instead of own copy, before this method is shared
+    pub(crate) fn share_signature(&mut self, other: &MethodImpl) {
+        if let Some(inner) = Arc::get_mut(&mut self.inner) {
+            if inner.signature == other.inner.signature {
+                inner.signature = other.inner.signature.clone();
+            }
         }
     }
 
@@ -97,14 +171,12 @@ impl MethodImpl {
     }
 
     pub fn from_method_info(method_info: MethodInfo) -> Self {
-        Self {
-            inner: Arc::new(MethodInner {
-                name: method_info.name.to_string(),
-                descriptor: method_info.descriptor.to_string(),
-                body: Self::extract_body(method_info.attributes).map(MethodBody::ByteCode),
-                access_flags: method_info.access_flags,
-            }),
-        }
+        Self::from_inner(MethodInner::new(
+            method_info.name.to_string(),
+            method_info.descriptor.to_string(),
+            Self::extract_body(method_info.attributes).map(MethodBody::ByteCode),
+            method_info.access_flags,
+        ))
     }
 
     fn extract_body(attributes: Vec<AttributeInfo>) -> Option<AttributeInfoCode> {
@@ -118,25 +190,44 @@ impl MethodImpl {
     }
 }
 
+impl Debug for MethodImpl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
 #[async_trait::async_trait]
 impl Method for MethodImpl {
     fn name(&self) -> String {
-        self.inner.name.clone()
+        self.inner.signature.name.clone()
     }
 
     fn descriptor(&self) -> String {
-        self.inner.descriptor.clone()
+        self.inner.signature.descriptor.clone()
     }
 
     fn access_flags(&self) -> MethodAccessFlags {
-        self.inner.access_flags
+        self.inner.signature.access_flags
     }
 
     async fn run(&self, jvm: &Jvm, args: Box<[JavaValue]>) -> Result<JavaValue> {
         Ok(match &self.inner.body.as_ref().unwrap() {
             MethodBody::ByteCode(x) => {
-                let r#type = JavaType::parse(&self.inner.descriptor);
-                Interpreter::run(jvm, x, args, r#type.as_method().1).await?
+                let r#type = JavaType::parse(&self.inner.signature.descriptor)?;
+                let return_type = r#type.as_method().1;
+
+                #[cfg(all(feature = "jit", not(target_arch = "wasm32")))]
+                if let Some(compiled) = self.state.jit.compiled(
+                    &self.inner.signature.name,
+                    &self.inner.signature.descriptor,
+                    self.inner.signature.access_flags,
+                    x,
+                    &self.state.hotness,
+                ) {
+                    return compiled.run(jvm, x, args, return_type, &self.state.hotness, &self.state.linked).await;
+                }
+
+                Interpreter::run(jvm, x, args, return_type, &self.state.hotness, &self.state.linked).await?
             }
             MethodBody::Rust(x) => x.call(jvm, args).await?,
         })
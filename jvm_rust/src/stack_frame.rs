@@ -1,18 +1,283 @@
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec, vec::Vec};
 
-use jvm::JavaValue;
+use jvm::{ClassInstance, JavaType, JavaValue, Result};
 
-#[derive(Default)]
+// handle of null reference
+const NULL_HANDLE: u64 = 0;
+
+// local variables and operand stack of a method invocation, in untyped 64-bit slots sized from `max_locals` and `max_stack`.
+// long and double take two slots as in class file, with value in the first one.
+// object references are stored as handles into reference table of the frame, so moving them around doesn't touch reference counts
 pub struct StackFrame {
-    pub local_variables: Vec<JavaValue>,
-    pub operand_stack: Vec<JavaValue>,
+    slots: Box<[u64]>,         // local variables, followed by operand stack
+    is_reference: Box<[bool]>, // whether slot holds a handle, to find live references on compaction
+    max_locals: usize,
+    top: usize,                              // index of next operand stack slot
+    references: Vec<Box<dyn ClassInstance>>, // handle n refers to references[n - 1]
+    compaction_threshold: usize,
 }
 
 impl StackFrame {
-    pub fn new() -> Self {
+    pub fn new(max_locals: usize, max_stack: usize) -> Self {
+        let size = max_locals + max_stack;
+
         Self {
-            local_variables: Vec::new(),
-            operand_stack: Vec::new(),
+            slots: vec![0; size].into_boxed_slice(),
+            is_reference: vec![false; size].into_boxed_slice(),
+            max_locals,
+            top: max_locals,
+            references: Vec::new(),
+            compaction_threshold: Self::min_compaction_threshold(size),
+        }
+    }
+
+    pub fn push_int(&mut self, value: i32) {
+        self.push_slot(value as u32 as u64, false);
+    }
+
+    pub fn pop_int(&mut self) -> i32 {
+        self.pop_slot() as i32
+    }
+
+    pub fn push_long(&mut self, value: i64) {
+        self.push_slot(value as u64, false);
+        self.push_slot(0, false);
+    }
+
+    pub fn pop_long(&mut self) -> i64 {
+        self.pop_slot();
+        self.pop_slot() as i64
+    }
+
+    pub fn push_float(&mut self, value: f32) {
+        self.push_slot(value.to_bits() as u64, false);
+    }
+
+    pub fn pop_float(&mut self) -> f32 {
+        f32::from_bits(self.pop_slot() as u32)
+    }
+
+    pub fn push_double(&mut self, value: f64) {
+        self.push_slot(value.to_bits(), false);
+        self.push_slot(0, false);
+    }
+
+    pub fn pop_double(&mut self) -> f64 {
+        self.pop_slot();
+        f64::from_bits(self.pop_slot())
+    }
+
+    pub fn push_reference(&mut self, value: Option<Box<dyn ClassInstance>>) {
+        let handle = self.add_reference(value);
+        self.push_slot(handle, true);
+    }
+
+    pub fn pop_reference(&mut self) -> Option<Box<dyn ClassInstance>> {
+        let handle = self.pop_slot();
+        self.reference(handle).cloned()
+    }
+
+    // pops reference without taking it out of the frame, returns None if null
+    #[allow(clippy::borrowed_box)]
+    pub fn pop_reference_ref(&mut self) -> Option<&Box<dyn ClassInstance>> {
+        let handle = self.pop_slot();
+        self.reference(handle)
+    }
+
+    pub fn pop_is_null(&mut self) -> bool {
+        self.pop_slot() == NULL_HANDLE
+    }
+
+    // pops two references, and returns true if both are null or the same object
+    pub fn pop_same_references(&mut self) -> Result<bool> {
+        let handle2 = self.pop_slot();
+        let handle1 = self.pop_slot();
+
+        if handle1 == handle2 {
+            return Ok(true);
+        }
+
+        Ok(match (self.reference(handle1), self.reference(handle2)) {
+            (Some(value1), Some(value2)) => value1.equals(&**value2)?,
+            _ => false,
+        })
+    }
+
+    pub fn push_value(&mut self, value: JavaValue) {
+        match value {
+            JavaValue::Void => {}
+            JavaValue::Boolean(x) => self.push_int(x as _),
+            JavaValue::Byte(x) => self.push_int(x as _),
+            JavaValue::Char(x) => self.push_int(x as _),
+            JavaValue::Short(x) => self.push_int(x as _),
+            JavaValue::Int(x) => self.push_int(x),
+            JavaValue::Long(x) => self.push_long(x),
+            JavaValue::Float(x) => self.push_float(x),
+            JavaValue::Double(x) => self.push_double(x),
+            JavaValue::Object(x) => self.push_reference(x),
+        }
+    }
+
+    // pops value of given type, converting int on the stack into smaller integer types
+    pub fn pop_value(&mut self, r#type: &JavaType) -> JavaValue {
+        match r#type {
+            JavaType::Boolean => JavaValue::Boolean(self.pop_int() == 1),
+            JavaType::Byte => JavaValue::Byte(self.pop_int() as _),
+            JavaType::Char => JavaValue::Char(self.pop_int() as _),
+            JavaType::Short => JavaValue::Short(self.pop_int() as _),
+            JavaType::Int => JavaValue::Int(self.pop_int()),
+            JavaType::Long => JavaValue::Long(self.pop_long()),
+            JavaType::Float => JavaValue::Float(self.pop_float()),
+            JavaType::Double => JavaValue::Double(self.pop_double()),
+            JavaType::Class(_) | JavaType::Array(_) => JavaValue::Object(self.pop_reference()),
+            JavaType::Void | JavaType::Method(_, _) => JavaValue::Void,
+        }
+    }
+
+    // copies `size` slots of local variable to operand stack
+    pub fn load(&mut self, index: usize, size: usize) {
+        for i in index..index + size {
+            self.push_slot(self.slots[i], self.is_reference[i]);
+        }
+    }
+
+    // moves `size` slots of operand stack to local variable
+    pub fn store(&mut self, index: usize, size: usize) {
+        self.top -= size;
+        self.slots.copy_within(self.top..self.top + size, index);
+        self.is_reference.copy_within(self.top..self.top + size, index);
+    }
+
+    pub fn local_int(&self, index: usize) -> i32 {
+        self.slots[index] as i32
+    }
+
+    pub fn set_local_int(&mut self, index: usize, value: i32) {
+        self.slots[index] = value as u32 as u64;
+        self.is_reference[index] = false;
+    }
+
+    // stores method arguments to local variables, long and double take two slots
+    pub fn set_arguments(&mut self, args: Box<[JavaValue]>) {
+        let mut index = 0;
+        for arg in args.into_vec() {
+            index += self.set_local(index, arg);
         }
     }
+
+    // stores method argument to local variable, returns number of slots it takes
+    fn set_local(&mut self, index: usize, value: JavaValue) -> usize {
+        let top = self.top;
+
+        self.top = index;
+        self.push_value(value);
+        let size = self.top - index;
+
+        self.top = top;
+
+        size
+    }
+
+    // duplicates top `count` slots, and inserts them below `depth` slots from top.
+    // dup family opcodes only differ in these, as long and double take two slots
+    pub fn dup(&mut self, count: usize, depth: usize) {
+        let top = self.top;
+
+        self.slots.copy_within(top - depth..top, top - depth + count);
+        self.is_reference.copy_within(top - depth..top, top - depth + count);
+        self.slots.copy_within(top..top + count, top - depth);
+        self.is_reference.copy_within(top..top + count, top - depth);
+
+        self.top += count;
+    }
+
+    pub fn pop(&mut self, count: usize) {
+        self.top -= count;
+    }
+
+    pub fn swap(&mut self) {
+        self.slots.swap(self.top - 1, self.top - 2);
+        self.is_reference.swap(self.top - 1, self.top - 2);
+    }
+
+    pub fn clear_operand_stack(&mut self) {
+        self.top = self.max_locals;
+    }
+
+    // slots and reference flags for compiled code, which spills its state into the frame when it exits
+    #[cfg(all(feature = "jit", not(target_arch = "wasm32")))]
+    pub fn raw_parts(&mut self) -> (*mut u64, *mut bool) {
+        (self.slots.as_mut_ptr(), self.is_reference.as_mut_ptr())
+    }
+
+    #[cfg(all(feature = "jit", not(target_arch = "wasm32")))]
+    pub fn set_top(&mut self, top: usize) {
+        self.top = top;
+    }
+
+    fn push_slot(&mut self, value: u64, is_reference: bool) {
+        self.slots[self.top] = value;
+        self.is_reference[self.top] = is_reference;
+        self.top += 1;
+    }
+
+    fn pop_slot(&mut self) -> u64 {
+        self.top -= 1;
+        self.slots[self.top]
+    }
+
+    #[allow(clippy::borrowed_box)]
+    fn reference(&self, handle: u64) -> Option<&Box<dyn ClassInstance>> {
+        match handle {
+            NULL_HANDLE => None,
+            x => Some(&self.references[x as usize - 1]),
+        }
+    }
+
+    fn add_reference(&mut self, value: Option<Box<dyn ClassInstance>>) -> u64 {
+        let Some(value) = value else {
+            return NULL_HANDLE;
+        };
+
+        if self.references.len() >= self.compaction_threshold {
+            self.compact();
+        }
+
+        self.references.push(value);
+        self.references.len() as u64
+    }
+
+    // drops references no slot refers to anymore, and renumbers handles of live ones keeping their order
+    fn compact(&mut self) {
+        let mut live = vec![false; self.references.len()];
+        for i in 0..self.top {
+            if self.is_reference[i] && self.slots[i] != NULL_HANDLE {
+                live[self.slots[i] as usize - 1] = true;
+            }
+        }
+
+        let mut count = 0;
+        let new_handles = live
+            .iter()
+            .map(|x| {
+                count += *x as u64;
+                count
+            })
+            .collect::<Vec<_>>();
+
+        for i in 0..self.top {
+            if self.is_reference[i] && self.slots[i] != NULL_HANDLE {
+                self.slots[i] = new_handles[self.slots[i] as usize - 1];
+            }
+        }
+
+        let mut live = live.into_iter();
+        self.references.retain(|_| live.next().unwrap());
+
+        self.compaction_threshold = Self::min_compaction_threshold(self.slots.len()).max(self.references.len() * 2);
+    }
+
+    fn min_compaction_threshold(size: usize) -> usize {
+        (size * 2).max(16)
+    }
 }
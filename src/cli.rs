@@ -0,0 +1,198 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+use clap::{error::ErrorKind, CommandFactory, Parser};
+
+use java_runtime::runtime_stub_jar;
+use jvm::{JavaError, Result};
+
+use crate::{run, RunOptions, RustJarRegistry, StartType};
+
+#[derive(Parser)]
+#[command(override_usage = "rust_java [OPTIONS] <MAINCLASS> [ARGS]...\n       rust_java [OPTIONS] -jar <JARFILE> [ARGS]...")]
+struct Opts {
+    /// Directories and jar files to search for classes, separated by ':' (';' on windows). `dir/*` adds every jar in dir
+    #[arg(long, value_name = "PATH")]
+    class_path: Option<String>,
+
+    /// Registered rustjars to load with bootstrap class loader, separated by ':' (';' on windows)
+    #[arg(long, value_name = "PATH")]
+    boot_class_path: Option<String>,
+
+    /// Run main class of the jar file, ignoring class path
+    #[arg(long, value_name = "JARFILE")]
+    jar: Option<PathBuf>,
+
+    /// Dump all threads to stderr if main doesn't return in given seconds, failing if threads are deadlocked
+    #[arg(long, value_name = "SECONDS")]
+    dump_on_hang: Option<u64>,
+
+    /// Run all threads on a single scheduler seeded with given value, making thread interleaving reproducible
+    #[arg(long, value_name = "SEED")]
+    scheduler_seed: Option<u64>,
+
+    /// Make running threads yield to others after given milliseconds, 0 disables preemption
+    #[arg(long, value_name = "MILLIS")]
+    time_slice: Option<u64>,
+
+    /// Redefine loaded classes when their class files in class path directories change, keeping fields and method signatures
+    #[arg(long)]
+    hot_reload: bool,
+
+    /// Write stub classes of rt.rustjar to given jar file and exit, to compile java code with `javac -bootclasspath` against classes we provide
+    #[arg(long, value_name = "JARFILE")]
+    stub_jar: Option<PathBuf>,
+
+    /// Main class name (or class file path) followed by its arguments. with -jar, arguments to the main class
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+// launcher options taking a value, so we don't mistake the value for main class
+const OPTIONS_WITH_VALUE: [&str; 6] = [
+    "--class-path",
+    "--boot-class-path",
+    "--dump-on-hang",
+    "--scheduler-seed",
+    "--time-slice",
+    "--stub-jar",
+];
+
+// runs java launcher with command line arguments of the process.
+// hosts with their own rust classes can call this from their binary, to put their rustjars on class path
+pub fn main(rustjars: RustJarRegistry) -> Result<()> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    // wasm32-unknown-unknown has no clock for tokio timers, so jvm runs on seeded scheduler's virtual clock there. see `run`
+    #[cfg(target_arch = "wasm32")]
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+    let status = match runtime.block_on(async_main(rustjars)) {
+        Ok(x) => x,
+        // launcher errors are reported like java does
+        Err(JavaError::FatalError(x)) => {
+            eprintln!("{}", x);
+
+            1
+        }
+        Err(x) => return Err(x),
+    };
+
+    // don't wait for remaining daemon threads
+    runtime.shutdown_background();
+
+    process::exit(status)
+}
+
+async fn async_main(rustjars: RustJarRegistry) -> Result<i32> {
+    let opts = Opts::parse_from(launcher_args(env::args()));
+
+    if let Some(stub_jar) = &opts.stub_jar {
+        fs::write(stub_jar, runtime_stub_jar())
+            .map_err(|x| JavaError::FatalError(format!("Error: Unable to write {}: {}", stub_jar.display(), x)))?;
+
+        return Ok(0);
+    }
+
+    let mut args = opts.args.as_slice();
+    let class_path_str = opts.class_path.or_else(|| env::var("CLASSPATH").ok());
+    let mut class_path = class_path_str
+        .as_deref()
+        .map(|x| env::split_paths(x).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let start_type = if let Some(jar) = &opts.jar {
+        class_path.clear();
+
+        StartType::Jar(jar)
+    } else if let Some((main_class, rest)) = args.split_first() {
+        args = rest;
+
+        if main_class.ends_with(".class") && Path::new(main_class).is_file() {
+            // class file path, search the class next to it unless class path is given
+            let path = Path::new(main_class);
+            if class_path_str.is_none() {
+                class_path.push(path.parent().filter(|x| !x.as_os_str().is_empty()).unwrap_or(Path::new(".")).into());
+            }
+
+            StartType::Class(path)
+        } else {
+            if class_path_str.is_none() {
+                class_path.push(".".into());
+            }
+
+            StartType::Main(main_class)
+        }
+    } else {
+        Opts::command()
+            .error(ErrorKind::MissingRequiredArgument, "main class or -jar <JARFILE> is required")
+            .exit()
+    };
+
+    let mut options = RunOptions {
+        hang_timeout: opts.dump_on_hang.map(Duration::from_secs),
+        scheduler_seed: opts.scheduler_seed,
+        hot_reload: opts.hot_reload,
+        rustjars,
+        boot_class_path: opts
+            .boot_class_path
+            .map(|x| env::split_paths(&x).map(|x| x.to_string_lossy().into_owned()).collect())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+    if let Some(time_slice) = opts.time_slice {
+        options.time_slice = (time_slice != 0).then(|| Duration::from_millis(time_slice));
+    }
+
+    let class_path = class_path.iter().map(|x| x.as_path()).collect::<Vec<_>>();
+
+    run(io::stdout(), start_type, args, &class_path, options).await
+}
+
+// converts java style single dash options into ours. arguments after main class or jar file are passed as is
+fn launcher_args<I>(args: I) -> Vec<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let mut result = args.next().into_iter().collect::<Vec<_>>();
+
+    while let Some(arg) = args.next() {
+        if let Some(x) = arg.strip_prefix("-Xbootclasspath/a:") {
+            result.push("--boot-class-path".into());
+            result.push(x.into());
+
+            continue;
+        }
+
+        let arg = match arg.as_str() {
+            "-cp" | "-classpath" => "--class-path".into(),
+            "-jar" => "--jar".into(),
+            _ => arg,
+        };
+
+        if OPTIONS_WITH_VALUE.contains(&arg.as_str()) {
+            result.push(arg);
+            result.extend(args.next());
+        } else if arg == "--jar" {
+            result.push(arg);
+            result.extend(args.next());
+            result.push("--".into());
+            result.extend(args);
+            break;
+        } else if !arg.starts_with('-') {
+            result.push("--".into());
+            result.push(arg);
+            result.extend(args);
+            break;
+        } else {
+            result.push(arg);
+        }
+    }
+
+    result
+}
@@ -1,44 +1,163 @@
 extern crate alloc;
 
+pub mod cli;
 mod runtime;
+mod rustjar;
+mod scheduler;
 
-use std::{io::Write, path::Path};
+use std::{
+    env,
+    ffi::OsStr,
+    fs,
+    io::Write,
+    path::Path,
+    pin::pin,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
 
-use java_runtime::{get_bootstrap_class_loader, Runtime, RT_RUSTJAR};
-use jvm::{runtime::JavaLangString, JavaValue, Jvm, Result};
+use java_runtime::{get_bootstrap_class_loader, start_hot_reload, Runtime, TimeSliceHook, RT_RUSTJAR};
+use jvm::{runtime::JavaLangString, ClassInstance, JavaError, JavaValue, Jvm, Result};
+use jvm_rust::ClassDataCache;
 
 use runtime::RuntimeImpl;
+use scheduler::DeterministicScheduler;
+
+pub use rustjar::{RustJar, RustJarError, RustJarRegistry};
+
+// separator of class path entries on this platform, as used by `std::env::join_paths`
+#[cfg(windows)]
+const PATH_SEPARATOR: &str = ";";
+#[cfg(not(windows))]
+const PATH_SEPARATOR: &str = ":";
+
+// parsed classes are shared between all jvm instances in this process
+static CLASS_DATA_CACHE: LazyLock<Arc<ClassDataCache>> = LazyLock::new(|| Arc::new(ClassDataCache::new()));
 
 pub enum StartType<'a> {
     Jar(&'a Path),
+    // path to class file, class is searched in class path by its file name
     Class(&'a Path),
+    // binary name of main class, e.g. com.example.Main
+    Main(&'a str),
+}
+
+pub const DEFAULT_TIME_SLICE: Duration = Duration::from_millis(10);
+
+pub struct RunOptions {
+    // if main and non-daemon threads don't finish in this time, dumps all threads to stderr and fails if threads are deadlocked
+    pub hang_timeout: Option<Duration>,
+    // runs all java threads on a single scheduler seeded with this value, using virtual clock.
+    // thread interleaving is then reproducible from the seed. required on wasm32, which has no real time clock
+    pub scheduler_seed: Option<u64>,
+    // running java code yields to the host executor after this time. None disables preemption.
+    // ignored if `scheduler_seed` is set, as seeded scheduler switches threads on every scheduling point
+    pub time_slice: Option<Duration>,
+    // watches class path directories and redefines loaded classes whose class files change, like HotSwap.
+    // ignored if `scheduler_seed` is set, as polling on virtual clock would never let the scheduler idle
+    pub hot_reload: bool,
+    // rustjars which can be put on class path by their name, e.g. `acme.rustjar`
+    pub rustjars: RustJarRegistry,
+    // names of registered rustjars to be loaded by bootstrap class loader after rt.rustjar
+    pub boot_class_path: Vec<String>,
 }
 
-pub async fn run<'a, T, S>(stdout: T, start_type: StartType<'a>, args: &[S], class_path: &[&Path]) -> Result<()>
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            hang_timeout: None,
+            scheduler_seed: None,
+            time_slice: Some(DEFAULT_TIME_SLICE),
+            hot_reload: false,
+            rustjars: RustJarRegistry::new(),
+            boot_class_path: Vec::new(),
+        }
+    }
+}
+
+// runs until main and all non-daemon threads finish, or System.exit is called. returns exit status.
+pub async fn run<'a, T, S>(stdout: T, start_type: StartType<'a>, args: &[S], class_path: &[&Path], options: RunOptions) -> Result<i32>
 where
     T: Sync + Send + Write + 'static,
     S: AsRef<str>,
 {
-    let runtime = Box::new(RuntimeImpl::new(stdout)) as Box<dyn Runtime>;
+    // std has no clock or timers on wasm32-unknown-unknown, which real time sleep, time slices and hang timeout need
+    #[cfg(target_arch = "wasm32")]
+    if options.scheduler_seed.is_none() || options.hang_timeout.is_some() {
+        return Err(JavaError::FatalError(
+            "Error: Real time clock is unsupported on wasm32, run with scheduler seed and without hang timeout".into(),
+        ));
+    }
 
-    let bootstrap_class_loader = get_bootstrap_class_loader(runtime.clone());
+    match options.scheduler_seed {
+        Some(seed) => {
+            let scheduler = DeterministicScheduler::new(seed);
 
-    let mut class_path_str = class_path.iter().map(|x| x.to_str().unwrap()).collect::<Vec<_>>().join(":");
+            scheduler
+                .run(run_jvm(stdout, start_type, args, class_path, &options, Some(scheduler.clone())))
+                .await
+        }
+        None => run_jvm(stdout, start_type, args, class_path, &options, None).await,
+    }
+}
+
+async fn run_jvm<T, S>(
+    stdout: T,
+    start_type: StartType<'_>,
+    args: &[S],
+    class_path: &[&Path],
+    options: &RunOptions,
+    scheduler: Option<DeterministicScheduler>,
+) -> Result<i32>
+where
+    T: Sync + Send + Write + 'static,
+    S: AsRef<str>,
+{
+    let runtime = Box::new(RuntimeImpl::new(
+        stdout,
+        CLASS_DATA_CACHE.clone(),
+        scheduler.clone(),
+        options.rustjars.clone(),
+    )) as Box<dyn Runtime>;
+
+    let bootstrap_class_loader = get_bootstrap_class_loader(runtime.clone(), options.boot_class_path.clone());
+
+    let class_path = expand_class_path(class_path);
+
+    // rt.rustjar first, then the jar file we run
+    let mut class_path_entries = vec![RT_RUSTJAR];
     if let StartType::Jar(x) = start_type {
-        class_path_str = format!("{}:{}", x.to_str().unwrap(), class_path_str);
+        if !x.is_file() {
+            return Err(JavaError::FatalError(format!("Error: Unable to access jarfile {}", x.display())));
+        }
+        class_path_entries.push(x.to_str().unwrap());
     }
+    class_path_entries.extend(class_path.iter().map(|x| x.as_str()));
 
-    // add rt.rustjar
-    // TODO do we need boot class path?
-    let class_path_str = format!("{}:{}", RT_RUSTJAR, class_path_str);
-    let properties = [("java.class.path", class_path_str.as_str())].into_iter().collect();
+    let class_path_str = env::join_paths(class_path_entries).map_err(|x| JavaError::FatalError(format!("Error: Invalid class path: {}", x)))?;
+    let properties = [("java.class.path", class_path_str.to_str().unwrap()), ("path.separator", PATH_SEPARATOR)]
+        .into_iter()
+        .collect();
 
-    let jvm = Jvm::new(bootstrap_class_loader, move || runtime.current_task_id(), properties).await?;
+    let task_runtime = runtime.clone();
+    let jvm = Jvm::new(bootstrap_class_loader, move || task_runtime.current_task_id(), properties).await?;
+    if options.hot_reload && scheduler.is_none() {
+        let directories = class_path.into_iter().filter(|x| x.ends_with('/')).collect();
+        start_hot_reload(&jvm, runtime.clone(), directories);
+    }
+    if let Some(scheduler) = scheduler {
+        jvm.set_scheduling_hook(Box::new(scheduler)).await;
+    } else if let Some(time_slice) = options.time_slice {
+        jvm.set_scheduling_hook(Box::new(TimeSliceHook::new(runtime, time_slice))).await;
+    }
 
     let main_class_name = match start_type {
         StartType::Jar(x) => &get_jar_main_class(&jvm, x).await?,
         StartType::Class(x) => x.file_stem().unwrap().to_str().unwrap(),
+        StartType::Main(x) => x,
     };
+    let normalized_name = main_class_name.replace('.', "/");
+    load_main_class(&jvm, main_class_name, &normalized_name).await?;
 
     let mut java_args = Vec::with_capacity(args.len());
     for arg in args {
@@ -47,10 +166,121 @@ where
     let mut array = jvm.instantiate_array("Ljava/lang/String;", args.len()).await?;
     jvm.store_array(&mut array, 0, java_args).await.unwrap();
 
-    let normalized_name = main_class_name.replace('.', "/");
-    let _: () = jvm
-        .invoke_static(&normalized_name, "main", "([Ljava/lang/String;)V", [JavaValue::Object(Some(array))])
-        .await?;
+    let mut threads = pin!(async {
+        let _: () = jvm
+            .invoke_static(&normalized_name, "main", "([Ljava/lang/String;)V", [JavaValue::Object(Some(array))])
+            .await?;
+
+        jvm.wait_for_non_daemon_threads().await;
+
+        Ok::<_, JavaError>(())
+    });
+
+    let lifetime = async {
+        match options.hang_timeout {
+            Some(hang_timeout) => match tokio::time::timeout(hang_timeout, &mut threads).await {
+                Ok(x) => x,
+                Err(_) => {
+                    dump_threads(&jvm).await?;
+
+                    threads.await
+                }
+            },
+            None => threads.await,
+        }?;
+
+        let _: () = jvm.invoke_static("java/lang/Runtime", "runShutdownHooks", "()V", ()).await?;
+
+        Ok(0)
+    };
+
+    tokio::select! {
+        result = lifetime => result,
+        status = jvm.wait_for_exit() => Ok(status),
+    }
+}
+
+async fn dump_threads(jvm: &Jvm) -> Result<()> {
+    eprintln!("Full thread dump:");
+    for thread in jvm.thread_dump().await {
+        eprintln!("{}", thread);
+    }
+
+    let deadlocks = jvm.find_deadlocks().await;
+    if !deadlocks.is_empty() {
+        for threads in &deadlocks {
+            eprintln!("Found deadlock between threads {:?}", threads);
+        }
+
+        return Err(JavaError::FatalError("Deadlock detected".into()));
+    }
+
+    Ok(())
+}
+
+// directories need trailing slash to be distinguished from jars, and `dir/*` expands to every jar in dir like java launcher does
+fn expand_class_path(class_path: &[&Path]) -> Vec<String> {
+    let mut result = Vec::new();
+    for path in class_path {
+        let path_str = path.to_str().unwrap();
+
+        if path.file_name() == Some(OsStr::new("*")) || path_str == "*" {
+            let dir = path.parent().filter(|x| !x.as_os_str().is_empty()).unwrap_or(Path::new("."));
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+
+            let mut jars = entries
+                .filter_map(|x| x.ok())
+                .map(|x| x.path())
+                .filter(|x| x.extension().is_some_and(|x| x.eq_ignore_ascii_case("jar")))
+                .map(|x| x.to_str().unwrap().to_owned())
+                .collect::<Vec<_>>();
+            jars.sort();
+
+            result.extend(jars);
+        } else if path.is_dir() && !path_str.ends_with('/') {
+            result.push(format!("{}/", path_str));
+        } else {
+            result.push(path_str.to_owned());
+        }
+    }
+
+    result
+}
+
+// reports missing class or main method as java launcher does
+async fn load_main_class(jvm: &Jvm, main_class_name: &str, normalized_name: &str) -> Result<()> {
+    let class = match jvm.resolve_class(normalized_name).await {
+        Ok(x) => x,
+        Err(JavaError::JavaException(x)) => {
+            let not_found =
+                jvm.is_instance(&*x, "java/lang/ClassNotFoundException").await? || jvm.is_instance(&*x, "java/lang/NoClassDefFoundError").await?;
+            if not_found {
+                return Err(JavaError::FatalError(format!(
+                    "Error: Could not find or load main class {}\nCaused by: java.lang.ClassNotFoundException: {}",
+                    main_class_name, main_class_name
+                )));
+            }
+
+            // other errors like ClassFormatError are reported with its cause
+            let cause = jvm.invoke_virtual(&x, "toString", "()Ljava/lang/String;", ()).await?;
+            let cause = JavaLangString::to_rust_string(jvm, &cause).await?;
+
+            return Err(JavaError::FatalError(format!(
+                "Error: LinkageError occurred while loading main class {}\n\t{}",
+                main_class_name, cause
+            )));
+        }
+        Err(x) => return Err(x),
+    };
+
+    if class.definition.method("main", "([Ljava/lang/String;)V", true).is_none() {
+        return Err(JavaError::FatalError(format!(
+            "Error: Main method not found in class {}, please define the main method as:\n   public static void main(String[] args)\nor a JavaFX application class must extend javafx.application.Application",
+            main_class_name
+        )));
+    }
 
     Ok(())
 }
@@ -60,12 +290,15 @@ async fn get_jar_main_class(jvm: &Jvm, jar_path: &Path) -> Result<String> {
     let file = jvm.new_class("java/io/File", "(Ljava/lang/String;)V", (filename,)).await?;
     let jar_file = jvm.new_class("java/util/jar/JarFile", "(Ljava/io/File;)V", (file,)).await?;
 
-    let manifest = jvm.invoke_virtual(&jar_file, "getManifest", "()Ljava/util/jar/Manifest;", ()).await?;
+    let manifest: Option<Box<dyn ClassInstance>> = jvm.invoke_virtual(&jar_file, "getManifest", "()Ljava/util/jar/Manifest;", ()).await?;
+    let Some(manifest) = manifest else {
+        return Err(JavaError::FatalError(format!("no main manifest attribute, in {}", jar_path.display())));
+    };
     let attributes = jvm
         .invoke_virtual(&manifest, "getMainAttributes", "()Ljava/util/jar/Attributes;", ())
         .await?;
 
-    let main_class = jvm
+    let main_class: Option<Box<dyn ClassInstance>> = jvm
         .invoke_virtual(
             &attributes,
             "getValue",
@@ -74,5 +307,8 @@ async fn get_jar_main_class(jvm: &Jvm, jar_path: &Path) -> Result<String> {
         )
         .await?;
 
-    JavaLangString::to_rust_string(jvm, &main_class).await
+    match main_class {
+        Some(x) => JavaLangString::to_rust_string(jvm, &x).await,
+        None => Err(JavaError::FatalError(format!("no main manifest attribute, in {}", jar_path.display()))),
+    }
 }
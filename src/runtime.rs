@@ -6,14 +6,21 @@ use core::{
     time::Duration,
 };
 use std::{
+    collections::HashMap,
     fs,
     io::{stderr, stdin, Write},
+    path::{Path, PathBuf},
     sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use java_runtime::{get_runtime_class_proto, File, FileStat, FileType, IOError, IOResult, Runtime, SpawnCallback, RT_RUSTJAR};
+use java_runtime::{
+    get_runtime_class_proto_factory, File, FileChangeCallback, FileStat, FileType, IOError, IOResult, Runtime, SpawnCallback, RT_RUSTJAR,
+};
 use jvm::{ClassDefinition, Jvm};
-use jvm_rust::{ArrayClassDefinitionImpl, ClassDefinitionImpl};
+use jvm_rust::{ArrayClassDefinitionImpl, ClassDataCache};
+
+use crate::{rustjar::RustJarRegistry, scheduler::DeterministicScheduler};
 
 use self::io::{FileImpl, InputStreamFile, WriteStreamFile};
 
@@ -23,6 +30,8 @@ tokio::task_local! {
 
 static LAST_TASK_ID: AtomicU64 = AtomicU64::new(1);
 
+const WATCH_INTERVAL: Duration = Duration::from_millis(200);
+
 struct WriteWrapper<T>
 where
     T: Sync + Send + Write + 'static,
@@ -57,17 +66,23 @@ where
     T: Sync + Send + Write + 'static,
 {
     stdout: WriteWrapper<T>,
+    class_data_cache: Arc<ClassDataCache>,
+    scheduler: Option<DeterministicScheduler>,
+    rustjars: RustJarRegistry,
 }
 
 impl<T> RuntimeImpl<T>
 where
     T: Sync + Send + Write + 'static,
 {
-    pub fn new(stdout: T) -> Self {
+    pub fn new(stdout: T, class_data_cache: Arc<ClassDataCache>, scheduler: Option<DeterministicScheduler>, rustjars: RustJarRegistry) -> Self {
         Self {
             stdout: WriteWrapper {
                 write: Arc::new(Mutex::new(stdout)),
             },
+            class_data_cache,
+            scheduler,
+            rustjars,
         }
     }
 }
@@ -77,15 +92,30 @@ impl<T> Runtime for RuntimeImpl<T>
 where
     T: Sync + Send + Write + 'static,
 {
-    async fn sleep(&self, _duration: Duration) {
-        todo!()
+    async fn sleep(&self, duration: Duration) {
+        if let Some(scheduler) = &self.scheduler {
+            return scheduler.sleep(duration).await;
+        }
+
+        // not reached on wasm32, where `run` requires seeded scheduler
+        tokio::time::sleep(duration).await;
     }
 
     async fn r#yield(&self) {
-        todo!()
+        if let Some(scheduler) = &self.scheduler {
+            return scheduler.r#yield().await;
+        }
+
+        tokio::task::yield_now().await;
     }
 
     fn spawn(&self, _jvm: &Jvm, callback: Box<dyn SpawnCallback>) {
+        if let Some(scheduler) = &self.scheduler {
+            return scheduler.spawn(async move {
+                callback.call().await.unwrap();
+            });
+        }
+
         let task_id = LAST_TASK_ID.fetch_add(1, Ordering::SeqCst) + 1;
         tokio::spawn(async move {
             TASK_ID
@@ -97,10 +127,19 @@ where
     }
 
     fn now(&self) -> u64 {
-        todo!()
+        if let Some(scheduler) = &self.scheduler {
+            return scheduler.now();
+        }
+
+        // not reached on wasm32, where `run` requires seeded scheduler
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
     }
 
     fn current_task_id(&self) -> u64 {
+        if let Some(scheduler) = &self.scheduler {
+            return scheduler.current_task_id();
+        }
+
         TASK_ID.try_with(|x| *x).unwrap_or(0)
     }
 
@@ -138,22 +177,52 @@ where
         }
     }
 
+    async fn watch_files(&self, directories: Vec<String>, callback: Box<dyn FileChangeCallback>) -> jvm::Result<()> {
+        // polls modification time and size, as we don't want platform specific file notification here
+        let mut files = HashMap::new();
+        for directory in &directories {
+            scan_files(Path::new(directory), &mut files);
+        }
+
+        loop {
+            self.sleep(WATCH_INTERVAL).await;
+
+            let mut new_files = HashMap::new();
+            for directory in &directories {
+                scan_files(Path::new(directory), &mut new_files);
+            }
+
+            for (path, stamp) in &new_files {
+                if files.get(path) != Some(stamp) {
+                    callback.call(path.to_str().unwrap()).await?;
+                }
+            }
+            files = new_files;
+        }
+    }
+
     async fn find_rustjar_class(&self, _jvm: &Jvm, classpath: &str, class: &str) -> jvm::Result<Option<Box<dyn ClassDefinition>>> {
         if classpath == RT_RUSTJAR {
-            let proto = get_runtime_class_proto(class);
-            if let Some(proto) = proto {
-                return Ok(Some(Box::new(ClassDefinitionImpl::from_class_proto(
-                    proto,
-                    Box::new(self.clone()) as Box<_>,
-                ))));
+            if let Some(proto) = get_runtime_class_proto_factory(class) {
+                let definition = self
+                    .class_data_cache
+                    .define_class_proto(class, proto, Box::new(self.clone()) as Box<_>)
+                    .await;
+
+                return Ok(Some(Box::new(definition)));
             }
+
+            return Ok(None);
         }
 
-        Ok(None)
+        Ok(self.rustjars.find_class(classpath, class))
     }
 
-    async fn define_class(&self, _jvm: &Jvm, data: &[u8]) -> jvm::Result<Box<dyn ClassDefinition>> {
-        ClassDefinitionImpl::from_classfile(data).map(|x| Box::new(x) as Box<_>)
+    async fn define_class(&self, jvm: &Jvm, data: &[u8]) -> jvm::Result<Box<dyn ClassDefinition>> {
+        match self.class_data_cache.define_class(data).await {
+            Ok(x) => Ok(Box::new(x) as Box<_>),
+            Err(x) => Err(jvm.exception("java/lang/ClassFormatError", &x.to_string()).await),
+        }
     }
 
     async fn define_array_class(&self, _jvm: &Jvm, element_type_name: &str) -> jvm::Result<Box<dyn ClassDefinition>> {
@@ -161,11 +230,35 @@ where
     }
 }
 
+// collects modification time and size of all files under path
+fn scan_files(path: &Path, result: &mut HashMap<PathBuf, (SystemTime, u64)>) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|x| x.ok()) {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            scan_files(&entry.path(), result);
+        } else if let Ok(modified) = metadata.modified() {
+            result.insert(entry.path(), (modified, metadata.len()));
+        }
+    }
+}
+
 impl<T> Clone for RuntimeImpl<T>
 where
     T: Sync + Send + Write + 'static,
 {
     fn clone(&self) -> Self {
-        Self { stdout: self.stdout.clone() }
+        Self {
+            stdout: self.stdout.clone(),
+            class_data_cache: self.class_data_cache.clone(),
+            scheduler: self.scheduler.clone(),
+            rustjars: self.rustjars.clone(),
+        }
     }
 }
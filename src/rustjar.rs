@@ -0,0 +1,91 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use java_class_proto::JavaClassProto;
+use jvm::ClassDefinition;
+use jvm_rust::{ClassData, ClassDefinitionImpl};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RustJarError {
+    InvalidName(String),
+    DuplicateClass(String),
+}
+
+impl Display for RustJarError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName(x) => write!(f, "Rustjar name should end with .rustjar: {}", x),
+            Self::DuplicateClass(x) => write!(f, "Duplicate class in rustjar: {}", x),
+        }
+    }
+}
+
+impl Error for RustJarError {}
+
+// collection of rust classes, which can be put on class path or boot class path by its name like a jar
+pub struct RustJar {
+    name: String,
+    classes: BTreeMap<String, Arc<ClassData>>,
+}
+
+impl RustJar {
+    // name should end with `.rustjar`, as class loaders recognize rustjars on class path by it.
+    // each proto is built once, and jvms define their own classes sharing its data
+    pub fn new<C, Context>(name: &str, protos: Vec<fn() -> JavaClassProto<C>>, context: Context) -> Result<Self, RustJarError>
+    where
+        C: ?Sized + 'static + Send,
+        Context: Sync + Send + DerefMut + Deref<Target = C> + Clone + 'static,
+    {
+        if !name.ends_with(".rustjar") {
+            return Err(RustJarError::InvalidName(name.to_owned()));
+        }
+
+        let mut classes = BTreeMap::new();
+        for proto in protos {
+            let class_data = Arc::new(ClassData::from_class_proto(proto(), context.clone()));
+
+            let class_name = class_data.name().to_owned();
+            if classes.contains_key(&class_name) {
+                return Err(RustJarError::DuplicateClass(class_name));
+            }
+            classes.insert(class_name, class_data);
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            classes,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// rustjars available to jvms by their name. rt.rustjar is provided by java_runtime and doesn't have to be registered
+#[derive(Clone, Default)]
+pub struct RustJarRegistry {
+    rustjars: BTreeMap<String, Arc<RustJar>>,
+}
+
+impl RustJarRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // replaces rustjar with the same name
+    pub fn register(&mut self, rustjar: RustJar) {
+        self.rustjars.insert(rustjar.name.clone(), Arc::new(rustjar));
+    }
+
+    pub(crate) fn find_class(&self, rustjar_name: &str, class_name: &str) -> Option<Box<dyn ClassDefinition>> {
+        let class_data = self.rustjars.get(rustjar_name)?.classes.get(class_name)?;
+
+        Some(Box::new(ClassDefinitionImpl::from_class_data(class_data.clone())))
+    }
+}
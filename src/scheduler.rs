@@ -0,0 +1,283 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    time::Duration,
+};
+
+use jvm::{SchedulePoint, SchedulingHook};
+
+type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+const ROOT_TASK_ID: u64 = 0;
+// virtual clock starts at fixed time, so that runs don't depend on wall clock
+const START_TIME: u64 = 1_000_000_000_000;
+// polls before giving control back to the host executor
+const POLL_BUDGET: usize = 1024;
+
+struct State {
+    rng: u64,
+    current_task_id: u64,
+    last_task_id: u64,
+    tasks: BTreeMap<u64, Task>,
+    runnable: BTreeSet<u64>,
+    now: u64,
+    timers: BTreeMap<(u64, u64), Waker>, // (deadline, timer id)
+    last_timer_id: u64,
+    host_waker: Option<Waker>,
+}
+
+impl State {
+    // splitmix64
+    fn next_random(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+// runs all java threads on a single future, picking next thread to run with seeded rng on every scheduling point.
+// sleeps use virtual clock which advances only when every thread is blocked, so a run is reproducible from the seed.
+#[derive(Clone)]
+pub struct DeterministicScheduler {
+    state: Arc<Mutex<State>>,
+}
+
+impl DeterministicScheduler {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                rng: seed,
+                current_task_id: ROOT_TASK_ID,
+                last_task_id: ROOT_TASK_ID,
+                tasks: BTreeMap::new(),
+                runnable: BTreeSet::new(),
+                now: START_TIME,
+                timers: BTreeMap::new(),
+                last_timer_id: 0,
+                host_waker: None,
+            })),
+        }
+    }
+
+    // drives `future` and every task spawned while it runs
+    pub async fn run<F>(&self, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        self.state.lock().unwrap().runnable.insert(ROOT_TASK_ID);
+
+        Run {
+            scheduler: self.clone(),
+            root: Box::pin(future),
+        }
+        .await
+    }
+
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut state = self.state.lock().unwrap();
+
+        state.last_task_id += 1;
+        let task_id = state.last_task_id;
+
+        state.tasks.insert(task_id, Box::pin(future));
+        state.runnable.insert(task_id);
+    }
+
+    pub fn current_task_id(&self) -> u64 {
+        self.state.lock().unwrap().current_task_id
+    }
+
+    pub fn now(&self) -> u64 {
+        self.state.lock().unwrap().now
+    }
+
+    pub async fn sleep(&self, duration: Duration) {
+        let deadline = self.now() + duration.as_millis() as u64;
+
+        Sleep {
+            scheduler: self.clone(),
+            deadline,
+            timer: None,
+        }
+        .await
+    }
+
+    pub async fn r#yield(&self) {
+        YieldNow { yielded: false }.await
+    }
+
+    fn waker(&self, task_id: u64) -> Waker {
+        Waker::from(Arc::new(TaskWaker {
+            task_id,
+            state: self.state.clone(),
+        }))
+    }
+
+    // picks next task to run, advancing virtual clock if every task is blocked on sleep
+    fn next_task(&self) -> Option<u64> {
+        loop {
+            let mut state = self.state.lock().unwrap();
+
+            if state.runnable.is_empty() {
+                let &(deadline, _) = state.timers.keys().next()?;
+                state.now = state.now.max(deadline);
+
+                let now = state.now;
+                let pending = state.timers.split_off(&(now + 1, 0));
+                let expired = mem::replace(&mut state.timers, pending);
+                drop(state);
+
+                expired.into_values().for_each(Waker::wake);
+                continue;
+            }
+
+            let index = (state.next_random() % state.runnable.len() as u64) as usize;
+            let task_id = *state.runnable.iter().nth(index).unwrap();
+            state.runnable.remove(&task_id);
+            state.current_task_id = task_id;
+
+            return Some(task_id);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SchedulingHook for DeterministicScheduler {
+    async fn schedule(&self, _point: SchedulePoint) {
+        self.r#yield().await
+    }
+}
+
+struct TaskWaker {
+    task_id: u64,
+    state: Arc<Mutex<State>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let mut state = self.state.lock().unwrap();
+        state.runnable.insert(self.task_id);
+        let host_waker = state.host_waker.clone();
+        drop(state);
+
+        // tasks may be woken from outside, e.g. by host timers
+        if let Some(x) = host_waker {
+            x.wake();
+        }
+    }
+}
+
+struct Run<'a, T> {
+    scheduler: DeterministicScheduler,
+    root: Pin<Box<dyn Future<Output = T> + 'a>>,
+}
+
+impl<T> Future for Run<'_, T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        self.scheduler.state.lock().unwrap().host_waker = Some(cx.waker().clone());
+
+        for _ in 0..POLL_BUDGET {
+            let Some(task_id) = self.scheduler.next_task() else {
+                // every task is blocked on something other than sleep
+                return Poll::Pending;
+            };
+
+            let waker = self.scheduler.waker(task_id);
+            let mut context = Context::from_waker(&waker);
+
+            if task_id == ROOT_TASK_ID {
+                if let Poll::Ready(x) = self.root.as_mut().poll(&mut context) {
+                    return Poll::Ready(x);
+                }
+            } else {
+                // task may be woken again after it completed
+                let Some(mut task) = self.scheduler.state.lock().unwrap().tasks.remove(&task_id) else {
+                    continue;
+                };
+
+                if task.as_mut().poll(&mut context).is_pending() {
+                    self.scheduler.state.lock().unwrap().tasks.insert(task_id, task);
+                }
+            }
+        }
+
+        cx.waker().wake_by_ref();
+
+        Poll::Pending
+    }
+}
+
+struct Sleep {
+    scheduler: DeterministicScheduler,
+    deadline: u64,
+    timer: Option<(u64, u64)>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.scheduler.state.lock().unwrap();
+        if state.now >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        let timer = match self.timer {
+            Some(x) => x,
+            None => {
+                state.last_timer_id += 1;
+                (self.deadline, state.last_timer_id)
+            }
+        };
+        state.timers.insert(timer, cx.waker().clone());
+        drop(state);
+
+        self.timer = Some(timer);
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        // cancelled sleep shouldn't advance the clock
+        if let Some(x) = self.timer {
+            self.scheduler.state.lock().unwrap().timers.remove(&x);
+        }
+    }
+}
+
+struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+
+        Poll::Pending
+    }
+}
@@ -0,0 +1,87 @@
+class Access {
+    private int privateField;
+    private final int finalField;
+
+    Access() {
+        this.finalField = 5678;
+    }
+
+    private int privateMethod() {
+        return this.privateField + this.finalField;
+    }
+
+    class Inner {
+        private int innerPrivateField = 2;
+
+        int getOuterPrivateField() {
+            return Access.this.privateMethod();
+        }
+    }
+
+    Inner createInner() {
+        return new Inner();
+    }
+
+    static class Derived extends access.Target {
+        int readProtected(Derived other) {
+            return other.protectedField + other.protectedMethod();
+        }
+    }
+
+    static class Peer extends access.Target {
+        static int readProtected(access.Target other) {
+            return other.protectedField + other.protectedMethod();
+        }
+    }
+
+    public static void main(String[] args) {
+        Access instance = new Access();
+        instance.privateField = 1234;
+
+        Inner inner = instance.createInner();
+        System.out.println(inner.getOuterPrivateField());
+        System.out.println(inner.innerPrivateField);
+
+        access.Target target = new access.Target();
+
+        try {
+            System.out.println(access.Target.privateStaticField);
+        } catch (IllegalAccessError e) {
+            System.out.println("IllegalAccessError");
+        }
+
+        try {
+            System.out.println(target.packageField);
+        } catch (IllegalAccessError e) {
+            System.out.println("IllegalAccessError");
+        }
+
+        try {
+            System.out.println(target.protectedField);
+        } catch (IllegalAccessError e) {
+            System.out.println("IllegalAccessError");
+        }
+
+        try {
+            target.finalField = 1;
+        } catch (IllegalAccessError e) {
+            System.out.println("IllegalAccessError");
+        }
+
+        try {
+            access.Target.privateMethod();
+        } catch (IllegalAccessError e) {
+            System.out.println("IllegalAccessError");
+        }
+
+        // protected instance members are accessible only through instances of the accessing class.
+        // hotspot rejects Peer on verification, so we catch LinkageError instead of IllegalAccessError
+        System.out.println(new Derived().readProtected(new Derived()));
+
+        try {
+            System.out.println(Peer.readProtected(target));
+        } catch (LinkageError e) {
+            System.out.println("LinkageError");
+        }
+    }
+}
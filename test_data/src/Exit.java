@@ -0,0 +1,25 @@
+public class Exit {
+    static class Hook implements Runnable {
+        public void run() {
+            System.out.println("shutdown hook");
+        }
+    }
+
+    static class Exiter implements Runnable {
+        public void run() {
+            System.out.println("exiting");
+            System.exit(3);
+            System.out.println("not reached");
+        }
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        Runtime.getRuntime().addShutdownHook(new Thread(new Hook()));
+
+        Thread exiter = new Thread(new Exiter());
+        exiter.start();
+        exiter.join();
+
+        System.out.println("not reached");
+    }
+}
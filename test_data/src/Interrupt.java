@@ -0,0 +1,62 @@
+public class Interrupt {
+    static class Sleeper implements Runnable {
+        public void run() {
+            try {
+                Thread.sleep(100000);
+                System.out.println("sleep finished");
+            } catch (InterruptedException e) {
+                System.out.println("sleep interrupted");
+            }
+        }
+    }
+
+    static class Waiter implements Runnable {
+        private Object lock;
+
+        Waiter(Object lock) {
+            this.lock = lock;
+        }
+
+        public void run() {
+            synchronized (lock) {
+                try {
+                    lock.wait();
+                    System.out.println("wait finished");
+                } catch (InterruptedException e) {
+                    System.out.println("wait interrupted");
+                }
+            }
+        }
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        Thread sleeper = new Thread(new Sleeper());
+        sleeper.start();
+        Thread.sleep(10);
+        sleeper.interrupt();
+        sleeper.join();
+
+        Thread waiter = new Thread(new Waiter(new Object()));
+        waiter.start();
+        Thread.sleep(10);
+        waiter.interrupt();
+        waiter.join();
+
+        Thread current = Thread.currentThread();
+        System.out.println(current == Thread.currentThread());
+        System.out.println(Thread.interrupted());
+        current.interrupt();
+        System.out.println(current.isInterrupted());
+        System.out.println(Thread.interrupted());
+        System.out.println(current.isInterrupted());
+
+        current.interrupt();
+        try {
+            Thread.sleep(100000);
+            System.out.println("sleep finished");
+        } catch (InterruptedException e) {
+            System.out.println("already interrupted");
+        }
+        System.out.println(current.isInterrupted());
+    }
+}
@@ -0,0 +1,143 @@
+class Jit {
+    int counter;
+    double total;
+
+    static int hash(int seed, int value) {
+        int result = seed;
+        for (int i = 0; i < 4; i++) {
+            result = result * 31 + (value >>> (i * 8) & 0xff);
+            result ^= result << 13;
+            result ^= result >> 17;
+        }
+        return result;
+    }
+
+    static long mix(long value, int shift) {
+        long result = value * 0x9E3779B97F4A7C15L;
+        result ^= result >>> shift;
+        if (result < 0) {
+            result = -result;
+        }
+        return result % 1000003L;
+    }
+
+    static int classify(int value) {
+        switch (value % 5) {
+            case 0:
+                return 10;
+            case 1:
+                return 20;
+            case -3:
+                return 30;
+            default:
+                return value / 3;
+        }
+    }
+
+    static int divide(int a, int b) {
+        return a / b + a % b;
+    }
+
+    int accumulate(int[] values, int index) {
+        synchronized (this) {
+            counter += values[index % values.length];
+        }
+        total += index * 0.5f + 0.25;
+        return counter;
+    }
+
+    static String describe(Jit jit, int value) {
+        StringBuffer buffer = new StringBuffer("value ");
+        buffer.append(value);
+        if (value % 2 == 0) {
+            buffer.append(" even ");
+        }
+        buffer.append(jit.counter);
+        return buffer.toString();
+    }
+
+    static int safeCheck(int value) {
+        try {
+            return check(value);
+        } catch (IllegalArgumentException e) {
+            return -1;
+        }
+    }
+
+    static int check(int value) {
+        if (value % 1000 == 999) {
+            throw new IllegalArgumentException("invalid");
+        }
+        return value;
+    }
+
+    static long safeDivide(long a, int b) {
+        try {
+            return a / b + a % b + 100 / b;
+        } catch (ArithmeticException e) {
+            return -1;
+        }
+    }
+
+    static long spin(int count) {
+        long result = 0;
+        for (int i = 0; i < count; i++) {
+            result += i ^ (result >>> 3);
+        }
+        return result;
+    }
+
+    public static void main(String[] args) {
+        Jit jit = new Jit();
+        int[] values = new int[] { 3, 1, 4, 1, 5, 9, 2, 6 };
+        int accumulated = 0;
+        String description = null;
+        int safe = 0;
+        int checked = 0;
+        int thrown = 0;
+        long quotient = 0;
+        int divisionErrors = 0;
+        for (int i = 0; i < 3000; i++) {
+            quotient += safeDivide(i * 7L, i % 7);
+            try {
+                quotient += divide(i, i % 5);
+            } catch (ArithmeticException e) {
+                divisionErrors++;
+            }
+            accumulated = jit.accumulate(values, i);
+            description = describe(jit, i);
+            safe += safeCheck(i);
+            try {
+                checked += check(i);
+            } catch (IllegalArgumentException e) {
+                thrown++;
+            }
+        }
+        System.out.println(accumulated);
+        System.out.println((long) jit.total);
+        System.out.println(description);
+        System.out.println(safe);
+        System.out.println(checked);
+        System.out.println(thrown);
+        System.out.println(quotient);
+        System.out.println(divisionErrors);
+        System.out.println(spin(200000));
+
+        int hash = 17;
+        long mixed = 0;
+        int classified = 0;
+        for (int i = 0; i < 3000; i++) {
+            hash = hash(hash, i);
+            mixed += mix(i, i % 64);
+            classified += classify(i - 1500);
+        }
+
+        System.out.println(hash);
+        System.out.println(mixed);
+        System.out.println(classified);
+        System.out.println(divide(Integer.MIN_VALUE, -1));
+        System.out.println(divide(-7, 2));
+        System.out.println((byte) hash);
+        System.out.println((short) (hash >> 8));
+    }
+}
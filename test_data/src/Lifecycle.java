@@ -0,0 +1,45 @@
+public class Lifecycle {
+    static class Worker implements Runnable {
+        public void run() {
+            try {
+                Thread.sleep(50);
+            } catch (InterruptedException e) {
+                return;
+            }
+            System.out.println("worker done");
+        }
+    }
+
+    static class Sleeper implements Runnable {
+        public void run() {
+            try {
+                Thread.sleep(100000);
+            } catch (InterruptedException e) {
+                return;
+            }
+            System.out.println("daemon done");
+        }
+    }
+
+    static class Hook implements Runnable {
+        public void run() {
+            System.out.println("shutdown hook");
+        }
+    }
+
+    public static void main(String[] args) {
+        Thread daemon = new Thread(new Sleeper());
+        daemon.setDaemon(true);
+        System.out.println(daemon.isDaemon());
+        daemon.start();
+
+        new Thread(new Worker()).start();
+
+        Thread removed = new Thread(new Hook());
+        Runtime.getRuntime().addShutdownHook(new Thread(new Hook()));
+        Runtime.getRuntime().addShutdownHook(removed);
+        System.out.println(Runtime.getRuntime().removeShutdownHook(removed));
+
+        System.out.println("main done");
+    }
+}
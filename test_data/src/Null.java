@@ -0,0 +1,44 @@
+class Null {
+    int field;
+
+    int method() {
+        return field;
+    }
+
+    public static void main(String[] args) {
+        Null instance = null;
+        Object object = null;
+
+        try {
+            System.out.println(instance.field);
+        } catch (NullPointerException e) {
+            System.out.println("getfield NullPointerException");
+        }
+
+        try {
+            instance.field = 1;
+        } catch (NullPointerException e) {
+            System.out.println("putfield NullPointerException");
+        }
+
+        try {
+            System.out.println(instance.method());
+        } catch (NullPointerException e) {
+            System.out.println("invokevirtual NullPointerException");
+        }
+
+        try {
+            RuntimeException exception = null;
+            throw exception;
+        } catch (NullPointerException e) {
+            System.out.println("athrow NullPointerException");
+        }
+
+        System.out.println(object instanceof String);
+
+        String string = (String) object;
+        System.out.println(string == null);
+        System.out.println(object != instance);
+        System.out.println(new Null() != null);
+    }
+}
@@ -0,0 +1,24 @@
+public class Preemption {
+    static volatile boolean stop = false;
+    static volatile long count = 0;
+
+    static class Busy implements Runnable {
+        public void run() {
+            while (!stop) {
+                count++;
+            }
+        }
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        Thread busy = new Thread(new Busy());
+        busy.start();
+
+        // busy thread must give way, or main never wakes up on single threaded executor
+        Thread.sleep(50);
+        stop = true;
+        busy.join();
+
+        System.out.println(count > 0);
+    }
+}
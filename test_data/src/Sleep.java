@@ -0,0 +1,19 @@
+class Sleep {
+    public static void main(String[] args) throws InterruptedException {
+        long start = System.currentTimeMillis();
+
+        Thread.sleep(20);
+        Thread.yield();
+
+        long elapsed = System.currentTimeMillis() - start;
+
+        System.out.println(start > 0);
+        System.out.println(elapsed >= 20);
+
+        try {
+            Thread.sleep(-1);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+    }
+}
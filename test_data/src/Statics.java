@@ -0,0 +1,13 @@
+class Statics {
+    static int counter;
+
+    static {
+        counter = 10;
+    }
+
+    public static void main(String[] args) {
+        counter += 1;
+
+        System.out.println(counter);
+    }
+}
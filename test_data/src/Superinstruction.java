@@ -0,0 +1,50 @@
+class Superinstruction {
+    int size = 3;
+
+    // aload_0; getfield; iload_2; if_icmpgt
+    static String fieldCompare(Superinstruction s, int unused, int k) {
+        if (s.size <= k) {
+            return "less or equal";
+        }
+        return "greater";
+    }
+
+    // iload_0; iload_1; ifeq, value of a stays on the stack
+    static int zeroCompare(int a, int b) {
+        return a + (b != 0 ? 10 : 20);
+    }
+
+    // iload_0; iload_1; if_icmpge and iload_0; bipush; if_icmple
+    static String compare(int a, int b) {
+        if (a < b) {
+            return "less";
+        }
+        if (a > 100) {
+            return "large";
+        }
+        return "other";
+    }
+
+    // iload_0; ifne
+    static String isZero(int a) {
+        if (a == 0) {
+            return "zero";
+        }
+        return "nonzero";
+    }
+
+    public static void main(String[] args) {
+        Superinstruction s = new Superinstruction();
+
+        System.out.println(fieldCompare(s, 0, 5));
+        System.out.println(fieldCompare(s, 0, 2));
+        System.out.println(zeroCompare(5, 5));
+        System.out.println(zeroCompare(5, 0));
+        System.out.println(zeroCompare(0, 7));
+        System.out.println(compare(1, 2));
+        System.out.println(compare(200, 2));
+        System.out.println(compare(50, 2));
+        System.out.println(isZero(0));
+        System.out.println(isZero(-1));
+    }
+}
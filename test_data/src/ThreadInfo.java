@@ -0,0 +1,44 @@
+public class ThreadInfo {
+    static class Worker implements Runnable {
+        public void run() {
+            Thread current = Thread.currentThread();
+            System.out.println(current.getName());
+            System.out.println(current.getPriority());
+            System.out.println(current.getThreadGroup().getName());
+        }
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        Thread main = Thread.currentThread();
+        System.out.println(main.getName());
+        System.out.println(main.getPriority());
+        System.out.println(main.isDaemon());
+        System.out.println(main.getThreadGroup().getName());
+        System.out.println(main.getThreadGroup().getParent().getName());
+        System.out.println(main);
+        System.out.println(Thread.activeCount());
+
+        Thread unnamed = new Thread();
+        System.out.println(unnamed.getName());
+        unnamed.setName("renamed");
+        System.out.println(unnamed.getName());
+
+        try {
+            unnamed.setPriority(11);
+        } catch (IllegalArgumentException e) {
+            System.out.println("IllegalArgumentException");
+        }
+
+        ThreadGroup group = new ThreadGroup("workers");
+        System.out.println(group.getParent().getName());
+        Thread worker = new Thread(group, new Worker(), "worker");
+        worker.setPriority(Thread.MAX_PRIORITY);
+        worker.start();
+        worker.join();
+
+        System.out.println(worker.isAlive());
+        System.out.println(worker.getThreadGroup() == null);
+        System.out.println(group.activeCount());
+        System.out.println(Thread.activeCount());
+    }
+}
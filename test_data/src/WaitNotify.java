@@ -0,0 +1,96 @@
+public class WaitNotify {
+    static int counter = 0;
+
+    static class Queue {
+        private int value = -1;
+
+        synchronized void put(int value) throws InterruptedException {
+            while (this.value != -1) {
+                wait();
+            }
+            this.value = value;
+            notifyAll();
+        }
+
+        synchronized int take() throws InterruptedException {
+            while (value == -1) {
+                wait();
+            }
+            int result = value;
+            value = -1;
+            notifyAll();
+            return result;
+        }
+    }
+
+    static class Producer implements Runnable {
+        private Queue queue;
+
+        Producer(Queue queue) {
+            this.queue = queue;
+        }
+
+        public void run() {
+            try {
+                for (int i = 0; i < 5; i++) {
+                    queue.put(i);
+                }
+            } catch (InterruptedException e) {
+                System.out.println("interrupted");
+            }
+        }
+    }
+
+    static class Incrementer implements Runnable {
+        public void run() {
+            for (int i = 0; i < 100; i++) {
+                increment();
+            }
+        }
+    }
+
+    static synchronized void increment() {
+        int value = counter;
+        Thread.yield();
+        counter = value + 1;
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        Queue queue = new Queue();
+        Thread producer = new Thread(new Producer(queue));
+        producer.start();
+        for (int i = 0; i < 5; i++) {
+            System.out.println(queue.take());
+        }
+        producer.join();
+
+        Object lock = new Object();
+        long start = System.currentTimeMillis();
+        synchronized (lock) {
+            lock.wait(50);
+        }
+        System.out.println(System.currentTimeMillis() - start >= 50);
+
+        try {
+            lock.notify();
+            System.out.println("no exception");
+        } catch (IllegalMonitorStateException e) {
+            System.out.println("IllegalMonitorStateException");
+        }
+
+        synchronized (lock) {
+            synchronized (lock) {
+                lock.notifyAll();
+            }
+            lock.notify();
+        }
+
+        Thread first = new Thread(new Incrementer());
+        Thread second = new Thread(new Incrementer());
+        first.start();
+        second.start();
+        first.join();
+        second.join();
+        System.out.println(counter);
+    }
+}
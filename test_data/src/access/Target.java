@@ -0,0 +1,16 @@
+package access;
+
+// Access.java is compiled against a version of this class where all members are public
+public class Target {
+    private static int privateStaticField = 1;
+    int packageField = 2;
+    protected int protectedField = 3;
+    public final int finalField = 4;
+
+    private static void privateMethod() {
+    }
+
+    protected int protectedMethod() {
+        return 5;
+    }
+}
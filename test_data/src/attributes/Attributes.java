@@ -0,0 +1,37 @@
+import java.io.IOException;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.util.List;
+import java.util.function.Supplier;
+
+// compiled with -parameters, to cover attributes javac doesn't emit by default
+public class Attributes<T> {
+    @Retention(RetentionPolicy.RUNTIME)
+    public @interface Marker {
+        String value() default "default";
+
+        int[] numbers() default {};
+
+        RetentionPolicy policy() default RetentionPolicy.CLASS;
+    }
+
+    public class Inner {
+    }
+
+    @Deprecated
+    @Marker(value = "method", numbers = {1, 2}, policy = RetentionPolicy.RUNTIME)
+    public List<T> generic(@Marker final int first, String second) throws IOException {
+        return null;
+    }
+
+    public Supplier<String> lambda() {
+        return () -> "lambda";
+    }
+
+    public Runnable anonymous() {
+        return new Runnable() {
+            public void run() {
+            }
+        };
+    }
+}
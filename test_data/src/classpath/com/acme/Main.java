@@ -0,0 +1,12 @@
+package com.acme;
+
+import com.acme.util.Greeter;
+
+public class Main {
+    public static void main(String[] args) {
+        System.out.println(Greeter.greeting());
+        for (int i = 0; i < args.length; i++) {
+            System.out.println(args[i]);
+        }
+    }
+}
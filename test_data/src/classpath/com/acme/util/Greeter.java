@@ -0,0 +1,7 @@
+package com.acme.util;
+
+public class Greeter {
+    public static String greeting() {
+        return "Hello from jar";
+    }
+}
@@ -0,0 +1,36 @@
+public class Deadlock {
+    static class Locker implements Runnable {
+        private Object first;
+        private Object second;
+
+        Locker(Object first, Object second) {
+            this.first = first;
+            this.second = second;
+        }
+
+        public void run() {
+            synchronized (first) {
+                try {
+                    Thread.sleep(50);
+                } catch (InterruptedException e) {
+                    return;
+                }
+                synchronized (second) {
+                    System.out.println("locked both");
+                }
+            }
+        }
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        Object a = new Object();
+        Object b = new Object();
+
+        Thread first = new Thread(new Locker(a, b));
+        Thread second = new Thread(new Locker(b, a));
+        first.start();
+        second.start();
+        first.join();
+        second.join();
+    }
+}
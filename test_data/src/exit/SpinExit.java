@@ -0,0 +1,20 @@
+public class SpinExit {
+    static class Spinner implements Runnable {
+        public void run() {
+            long count = 0;
+            while (true) {
+                count++;
+                if (count % 10000 == 0) {
+                    System.out.println(count);
+                }
+            }
+        }
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        new Thread(new Spinner()).start();
+
+        Thread.sleep(50);
+        System.exit(0);
+    }
+}
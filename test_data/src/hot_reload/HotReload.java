@@ -0,0 +1,27 @@
+import java.net.URL;
+import java.net.URLClassLoader;
+
+public class HotReload {
+    public static void main(String[] args) throws Exception {
+        // Greeting loaded by another class loader from elsewhere shouldn't be redefined
+        ClassLoader other = new URLClassLoader(new URL[] { new URL("file:test_data/hot_reload/other/") }, null);
+        Class.forName("Greeting", true, other);
+
+        String last = null;
+        for (int i = 0; i < 100; i++) {
+            String greeting = Greeting.get();
+            if (!greeting.equals(last)) {
+                System.out.println(greeting);
+                last = greeting;
+            }
+            if (greeting.equals("v2")) {
+                Class.forName("Probe", true, other);
+                return;
+            }
+
+            Thread.sleep(100);
+        }
+
+        System.out.println("timeout");
+    }
+}
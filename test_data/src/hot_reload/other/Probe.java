@@ -0,0 +1,6 @@
+// prints greeting of its own class loader when initialized
+public class Probe {
+    static {
+        System.out.println("other ".concat(Greeting.get()));
+    }
+}
@@ -0,0 +1,5 @@
+public class Greeting {
+    public static String get() {
+        return "v" + (1 + 1);
+    }
+}
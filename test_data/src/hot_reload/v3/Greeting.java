@@ -0,0 +1,10 @@
+// adds a method, which can't be redefined
+public class Greeting {
+    public static String get() {
+        return "v3";
+    }
+
+    public static String other() {
+        return "other";
+    }
+}
@@ -0,0 +1,10 @@
+package app;
+
+import first.First;
+
+public class App {
+    public static void main(String[] args) {
+        System.out.println("App");
+        First.run();
+    }
+}
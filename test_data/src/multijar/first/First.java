@@ -0,0 +1,10 @@
+package first;
+
+import second.Second;
+
+public class First {
+    public static void run() {
+        System.out.println("First");
+        Second.run();
+    }
+}
@@ -0,0 +1,7 @@
+package second;
+
+public class Second {
+    public static void run() {
+        System.out.println("Second");
+    }
+}
@@ -0,0 +1,7 @@
+package com.acme;
+
+public class Util {
+    public static String name() {
+        return "plugin a";
+    }
+}
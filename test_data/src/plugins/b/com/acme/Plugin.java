@@ -0,0 +1,7 @@
+package com.acme;
+
+public class Plugin {
+    static {
+        System.out.println(Util.name());
+    }
+}
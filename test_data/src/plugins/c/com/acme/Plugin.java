@@ -0,0 +1,9 @@
+package com.acme;
+
+import host.Bridge;
+
+public class Plugin {
+    static {
+        Bridge.take(new Util());
+    }
+}
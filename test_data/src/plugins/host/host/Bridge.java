@@ -0,0 +1,9 @@
+package host;
+
+import com.acme.Util;
+
+public class Bridge {
+    public static void take(Util util) {
+        System.out.println(util != null);
+    }
+}
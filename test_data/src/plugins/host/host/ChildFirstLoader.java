@@ -0,0 +1,24 @@
+package host;
+
+import java.net.URL;
+import java.net.URLClassLoader;
+
+// loads com.acme classes from its own jar before asking parent
+public class ChildFirstLoader extends URLClassLoader {
+    public ChildFirstLoader(URL url) {
+        super(new URL[] { url });
+    }
+
+    public Class loadClass(String name) throws ClassNotFoundException {
+        if (!name.startsWith("com.acme.")) {
+            return super.loadClass(name);
+        }
+
+        Class loaded = findLoadedClass(name);
+        if (loaded != null) {
+            return loaded;
+        }
+
+        return findClass(name);
+    }
+}
@@ -0,0 +1,28 @@
+package host;
+
+import java.net.URL;
+
+public class Plugins {
+    public static void main(String[] args) throws Exception {
+        System.out.println(com.acme.Util.name());
+
+        ClassLoader a = new ChildFirstLoader(new URL("file:test_data/plugins/a.jar"));
+        ClassLoader b = new ChildFirstLoader(new URL("file:test_data/plugins/b.jar"));
+
+        Class pluginA = Class.forName("com.acme.Plugin", true, a);
+        Class pluginB = Class.forName("com.acme.Plugin", true, b);
+        System.out.println(pluginA == pluginB);
+        System.out.println(pluginA.getClassLoader() == a);
+        System.out.println(a.loadClass("com.acme.Util") == b.loadClass("com.acme.Util"));
+        System.out.println(a.loadClass("com.acme.Util") == com.acme.Util.class);
+        System.out.println(a.loadClass("java.lang.String") == String.class);
+        System.out.println(a.loadClass("host.Bridge") == Bridge.class);
+
+        // plugin c passes its own Util to Bridge, which sees the host's Util
+        try {
+            Class.forName("com.acme.Plugin", true, new ChildFirstLoader(new URL("file:test_data/plugins/c.jar")));
+        } catch (LinkageError e) {
+            System.out.println("LinkageError");
+        }
+    }
+}
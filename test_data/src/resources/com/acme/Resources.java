@@ -0,0 +1,46 @@
+package com.acme;
+
+import java.io.BufferedReader;
+import java.io.InputStream;
+import java.io.InputStreamReader;
+import java.net.URL;
+import java.util.Enumeration;
+
+public class Resources {
+    static class Plugin {
+        static {
+            System.out.println("Plugin initialized");
+        }
+    }
+
+    public static void main(String[] args) throws Exception {
+        Class plugin = Class.forName("com.acme.Resources$Plugin");
+        System.out.println(plugin == Plugin.class);
+
+        Class string = Class.forName("java.lang.String", true, Resources.class.getClassLoader());
+        System.out.println(string == String.class);
+
+        try {
+            Class.forName("com.acme.Missing");
+        } catch (ClassNotFoundException e) {
+            System.out.println(e.getMessage());
+        }
+
+        printFirstLine(Resources.class.getResourceAsStream("config.properties"));
+        printFirstLine(Resources.class.getResourceAsStream("/app.properties"));
+        printFirstLine(Resources.class.getResource("/com/acme/config.properties").openStream());
+        System.out.println(Resources.class.getResource("missing.properties") == null);
+        printFirstLine(ClassLoader.getSystemResource("app.properties").openStream());
+
+        Enumeration resources = Resources.class.getClassLoader().getResources("app.properties");
+        while (resources.hasMoreElements()) {
+            URL url = (URL) resources.nextElement();
+            printFirstLine(url.openStream());
+        }
+    }
+
+    static void printFirstLine(InputStream stream) throws Exception {
+        BufferedReader reader = new BufferedReader(new InputStreamReader(stream));
+        System.out.println(reader.readLine());
+    }
+}
@@ -0,0 +1,8 @@
+import com.acme.Greeter;
+
+public class Greet {
+    public static void main(String[] args) {
+        System.out.println(Greeter.greet("world"));
+        System.out.println(Greeter.class.getClassLoader() == null);
+    }
+}
@@ -0,0 +1,8 @@
+package com.acme;
+
+// only for compiling Greet, the class is implemented in rust by tests/test_rustjar.rs
+public class Greeter {
+    public static String greet(String name) {
+        return null;
+    }
+}
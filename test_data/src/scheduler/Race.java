@@ -0,0 +1,36 @@
+public class Race {
+    static int counter = 0;
+
+    static class Worker implements Runnable {
+        private String name;
+
+        Worker(String name) {
+            this.name = name;
+        }
+
+        public void run() {
+            for (int i = 0; i < 5; i++) {
+                int value = counter;
+                System.out.println(name);
+                counter = value + 1;
+            }
+        }
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        Thread first = new Thread(new Worker("a"));
+        Thread second = new Thread(new Worker("b"));
+        Thread third = new Thread(new Worker("c"));
+        first.start();
+        second.start();
+        third.start();
+        first.join();
+        second.join();
+        third.join();
+        System.out.println(counter);
+
+        long start = System.currentTimeMillis();
+        Thread.sleep(1000);
+        System.out.println(System.currentTimeMillis() - start);
+    }
+}
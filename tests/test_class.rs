@@ -1,10 +1,16 @@
 mod test_helper;
 
-use std::{fs, path::Path};
+use std::{
+    fs, io,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use jvm::Result;
+use jvm::{JavaError, Result};
+use rust_java::{run, RunOptions, StartType};
 
-use test_helper::{run_class, run_jar};
+use test_helper::{run_class, run_class_with_options, run_jar, run_main, Output};
 
 // TODO parameterized tests..
 #[tokio::test]
@@ -47,3 +53,218 @@ async fn test_class() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_class_data_shared_between_jvms() -> Result<()> {
+    // parsed class data is shared, but each jvm should have its own static fields
+    for _ in 0..2 {
+        let result = run_class(Path::new("test_data/Statics.class"), &[Path::new("./test_data/")], &[]).await?;
+
+        assert_eq!(result, "11\n");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deadlock_detection() -> Result<()> {
+    let result = run(
+        io::sink(),
+        StartType::Class(Path::new("test_data/deadlock/Deadlock.class")),
+        &[] as &[String],
+        &[Path::new("./test_data/deadlock/")],
+        RunOptions {
+            hang_timeout: Some(Duration::from_millis(500)),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(JavaError::FatalError(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_exit_status() -> Result<()> {
+    let status = run(
+        io::sink(),
+        StartType::Class(Path::new("test_data/Exit.class")),
+        &[] as &[String],
+        &[Path::new("./test_data/")],
+        RunOptions::default(),
+    )
+    .await?;
+
+    assert_eq!(status, 3);
+
+    Ok(())
+}
+
+// spinning thread occupies a worker, so main needs another one to wake up from sleep
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_exit_stops_threads() -> Result<()> {
+    let output = Arc::new(Mutex::new(Vec::new()));
+
+    // non-daemon thread spinning forever would keep printing if exit didn't stop it
+    let status = run(
+        Output { output: output.clone() },
+        StartType::Class(Path::new("test_data/exit/SpinExit.class")),
+        &[] as &[String],
+        &[Path::new("./test_data/exit/")],
+        RunOptions::default(),
+    )
+    .await?;
+    assert_eq!(status, 0);
+
+    // let a print already in flight on exit finish before measuring
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let length = output.lock().unwrap().len();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(output.lock().unwrap().len(), length);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deterministic_scheduler() -> Result<()> {
+    let path = Path::new("test_data/scheduler/Race.class");
+    let class_path = [Path::new("./test_data/scheduler/")];
+
+    let mut results = Vec::new();
+    for seed in 0..8 {
+        let options = |seed| RunOptions {
+            scheduler_seed: Some(seed),
+            ..Default::default()
+        };
+
+        let result = run_class_with_options(path, &class_path, &[], options(seed)).await?;
+        let rerun = run_class_with_options(path, &class_path, &[], options(seed)).await?;
+        assert_eq!(result, rerun, "seed {} is not reproducible", seed);
+
+        results.push(result);
+    }
+
+    // interleaving should depend on seed
+    results.sort();
+    results.dedup();
+    assert!(results.len() > 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_class_path() -> Result<()> {
+    // directory without trailing slash, and wildcard expanding to jars in lib
+    let result = run_main(
+        "com.acme.Main",
+        &[Path::new("test_data/classpath/classes"), Path::new("test_data/classpath/lib/*")],
+        &["a".into(), "-b".into()],
+    )
+    .await?;
+
+    assert_eq!(result, "Hello from jar\na\n-b\n");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_absolute_class_path() -> Result<()> {
+    let base_path = std::env::current_dir().unwrap().join("test_data");
+
+    let result = run_main("Hello", &[&base_path], &[]).await?;
+    assert_eq!(result, "Hello, world!\n");
+
+    let result = run_main(
+        "com.acme.Main",
+        &[&base_path.join("classpath/lib/*"), &base_path.join("classpath/classes")],
+        &[],
+    )
+    .await?;
+    assert_eq!(result, "Hello from jar\n");
+
+    let result = run_jar(&base_path.join("multijar/app.jar"), &[]).await?;
+    assert_eq!(result, "App\nFirst\nSecond\n");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_manifest_class_path() -> Result<()> {
+    // app.jar references lib/first.jar, which references lib/second.jar, which references lib/first.jar back
+    let result = run_jar(Path::new("test_data/multijar/app.jar"), &[]).await?;
+
+    assert_eq!(result, "App\nFirst\nSecond\n");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resources() -> Result<()> {
+    let result = run_main(
+        "com.acme.Resources",
+        &[Path::new("test_data/resources/classes"), Path::new("test_data/resources/lib.jar")],
+        &[],
+    )
+    .await?;
+
+    assert_eq!(
+        result,
+        "Plugin initialized\ntrue\ntrue\ncom.acme.Missing\nname=config\nname=app from classes\nname=config\ntrue\nname=app from classes\nname=app from classes\nname=app from jar\n"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_class_loader_namespaces() -> Result<()> {
+    // each plugin jar has its own com.acme.Util, loaded by its own child first class loader
+    let result = run_main("host.Plugins", &[Path::new("test_data/plugins/classes")], &[]).await?;
+
+    assert_eq!(result, "host\nplugin a\nplugin b\nfalse\ntrue\nfalse\nfalse\ntrue\ntrue\nLinkageError\n");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_main_class_not_found() -> Result<()> {
+    let result = run(
+        io::sink(),
+        StartType::Main("com.acme.Missing"),
+        &[] as &[String],
+        &[Path::new("test_data/classpath/classes")],
+        RunOptions::default(),
+    )
+    .await;
+
+    let Err(JavaError::FatalError(message)) = result else {
+        panic!("Expected fatal error");
+    };
+    assert_eq!(
+        message,
+        "Error: Could not find or load main class com.acme.Missing\nCaused by: java.lang.ClassNotFoundException: com.acme.Missing"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_main_class_format_error() -> Result<()> {
+    // truncated classfile should be reported with its cause, not as missing class
+    let result = run(
+        io::sink(),
+        StartType::Main("Broken"),
+        &[] as &[String],
+        &[Path::new("test_data/broken")],
+        RunOptions::default(),
+    )
+    .await;
+
+    let Err(JavaError::FatalError(message)) = result else {
+        panic!("Expected fatal error");
+    };
+    assert!(message.starts_with("Error: LinkageError occurred while loading main class Broken\n\t"));
+    assert!(message.contains("ClassFormatError"));
+
+    Ok(())
+}
@@ -8,10 +8,10 @@ use std::{
 };
 
 use jvm::Result;
-use rust_java::{run, StartType};
+use rust_java::{run, RunOptions, StartType};
 
-struct Output {
-    output: Arc<Mutex<Vec<u8>>>,
+pub struct Output {
+    pub output: Arc<Mutex<Vec<u8>>>,
 }
 
 impl io::Write for Output {
@@ -25,9 +25,27 @@ impl io::Write for Output {
 }
 
 pub async fn run_class(path: &Path, class_path: &[&Path], args: &[String]) -> Result<String> {
+    run_class_with_options(path, class_path, args, RunOptions::default()).await
+}
+
+pub async fn run_class_with_options(path: &Path, class_path: &[&Path], args: &[String], options: RunOptions) -> Result<String> {
+    let output = Arc::new(Mutex::new(Vec::new()));
+
+    run(Output { output: output.clone() }, StartType::Class(path), args, class_path, options).await?;
+
+    let result = str::from_utf8(&output.lock().unwrap()).unwrap().to_string();
+
+    Ok(result)
+}
+
+pub async fn run_main(main_class: &str, class_path: &[&Path], args: &[String]) -> Result<String> {
+    run_main_with_options(main_class, class_path, args, RunOptions::default()).await
+}
+
+pub async fn run_main_with_options(main_class: &str, class_path: &[&Path], args: &[String], options: RunOptions) -> Result<String> {
     let output = Arc::new(Mutex::new(Vec::new()));
 
-    run(Output { output: output.clone() }, StartType::Class(path), args, class_path).await?;
+    run(Output { output: output.clone() }, StartType::Main(main_class), args, class_path, options).await?;
 
     let result = str::from_utf8(&output.lock().unwrap()).unwrap().to_string();
 
@@ -37,7 +55,14 @@ pub async fn run_class(path: &Path, class_path: &[&Path], args: &[String]) -> Re
 pub async fn run_jar(jar_path: &Path, args: &[String]) -> Result<String> {
     let output = Arc::new(Mutex::new(Vec::new()));
 
-    run(Output { output: output.clone() }, StartType::Jar(jar_path), args, &[]).await?;
+    run(
+        Output { output: output.clone() },
+        StartType::Jar(jar_path),
+        args,
+        &[],
+        RunOptions::default(),
+    )
+    .await?;
 
     let result = str::from_utf8(&output.lock().unwrap()).unwrap().to_string();
 
@@ -0,0 +1,40 @@
+mod test_helper;
+
+use std::{fs, path::Path, time::Duration};
+
+use jvm::Result;
+use rust_java::RunOptions;
+
+use test_helper::run_main_with_options;
+
+#[tokio::test]
+async fn test_hot_reload() -> Result<()> {
+    // class files are replaced while running, so run from a copy.
+    // HotReload also loads Greeting from test_data/hot_reload/other by another class loader, which should stay v1
+    let dir = Path::new("target/test_hot_reload");
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).unwrap();
+    fs::copy("test_data/hot_reload/HotReload.class", dir.join("HotReload.class")).unwrap();
+    fs::copy("test_data/hot_reload/v1/Greeting.class", dir.join("Greeting.class")).unwrap();
+
+    let options = RunOptions {
+        hot_reload: true,
+        ..Default::default()
+    };
+
+    let update = async {
+        // v3 adds a method, so it should be rejected and v1 should keep running
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+        fs::copy("test_data/hot_reload/v3/Greeting.class", dir.join("Greeting.class")).unwrap();
+
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+        fs::copy("test_data/hot_reload/v2/Greeting.class", dir.join("Greeting.class")).unwrap();
+    };
+
+    let class_path = [dir];
+    let (result, _) = tokio::join!(run_main_with_options("HotReload", &class_path, &[], options), update);
+
+    assert_eq!(result?, "v1\nv2\nother v1\n");
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+mod test_helper;
+
+use std::path::Path;
+
+use java_class_proto::{JavaClassProto, JavaMethodProto};
+use java_constants::MethodAccessFlags;
+use java_runtime::classes::java::lang::String;
+use jvm::{runtime::JavaLangString, ClassInstanceRef, Jvm, Result};
+use rust_java::{RunOptions, RustJar, RustJarError, RustJarRegistry};
+
+use test_helper::run_main_with_options;
+
+#[derive(Clone)]
+struct GreeterContext {
+    greeting: &'static str,
+}
+
+// class com.acme.Greeter
+struct Greeter;
+
+impl Greeter {
+    fn as_proto() -> JavaClassProto<GreeterContext> {
+        JavaClassProto {
+            name: "com/acme/Greeter",
+            parent_class: Some("java/lang/Object"),
+            is_interface: false,
+            interfaces: vec![],
+            methods: vec![JavaMethodProto::new(
+                "greet",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                Self::greet,
+                MethodAccessFlags::PUBLIC | MethodAccessFlags::STATIC,
+            )],
+            fields: vec![],
+        }
+    }
+
+    async fn greet(jvm: &Jvm, context: &mut GreeterContext, name: ClassInstanceRef<String>) -> Result<ClassInstanceRef<String>> {
+        let name = JavaLangString::to_rust_string(jvm, &name).await?;
+        let result = JavaLangString::from_rust_string(jvm, &format!("{}, {}", context.greeting, name)).await?;
+
+        Ok(result.into())
+    }
+}
+
+fn rustjars() -> RustJarRegistry {
+    let mut rustjars = RustJarRegistry::new();
+    rustjars.register(RustJar::new("greeter.rustjar", vec![Greeter::as_proto], Box::new(GreeterContext { greeting: "hello" })).unwrap());
+
+    rustjars
+}
+
+#[tokio::test]
+async fn test_rustjar_on_class_path() -> Result<()> {
+    let options = RunOptions {
+        rustjars: rustjars(),
+        ..Default::default()
+    };
+    let result = run_main_with_options("Greet", &[Path::new("test_data/rustjar"), Path::new("greeter.rustjar")], &[], options).await?;
+
+    assert_eq!(result, "hello, world\nfalse\n");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rustjar_on_boot_class_path() -> Result<()> {
+    let options = RunOptions {
+        rustjars: rustjars(),
+        boot_class_path: vec!["greeter.rustjar".into()],
+        ..Default::default()
+    };
+    let result = run_main_with_options("Greet", &[Path::new("test_data/rustjar")], &[], options).await?;
+
+    assert_eq!(result, "hello, world\ntrue\n");
+
+    Ok(())
+}
+
+#[test]
+fn test_rustjar_errors() {
+    let context = Box::new(GreeterContext { greeting: "hello" });
+
+    let result = RustJar::new("greeter.jar", vec![Greeter::as_proto], context.clone());
+    assert_eq!(result.err(), Some(RustJarError::InvalidName("greeter.jar".into())));
+
+    let result = RustJar::new("greeter.rustjar", vec![Greeter::as_proto, Greeter::as_proto], context);
+    assert_eq!(result.err(), Some(RustJarError::DuplicateClass("com/acme/Greeter".into())));
+}